@@ -0,0 +1,277 @@
+//! Discovery of the implementations annotated with `#[symmetric]` in a crate.
+//!
+//! This is the library part of `cargo symm`, a cargo subcommand reporting
+//! which pairs of types are covered by mirrored implementations, which
+//! direction is written by hand, and where the annotation is located. It also
+//! finds hand-written mirrors which could be replaced by `#[symmetric]`.
+
+mod lint;
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use symm_impl_core::{render, MirrorOptions};
+use syn::{visit::Visit, Attribute, ItemImpl, ItemMod, PathArguments};
+
+/// Directories of a crate searched for annotated implementations.
+pub const SOURCE_DIRS: &[&str] = &["src", "tests", "examples", "benches"];
+
+/// An implementation of a symmetric trait, either annotated with
+/// `#[symmetric]` or written by hand as the mirror of another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymmetricImpl {
+    /// File containing the implementation.
+    pub file: PathBuf,
+    /// Line of the `#[symmetric]` attribute, or of the `impl` keyword for a
+    /// hand-written mirror.
+    pub line: usize,
+    /// The trait, without generic arguments, e.g. `Distance`.
+    pub trait_name: String,
+    /// The implementing type of the hand-written direction, e.g. `Point2D`.
+    pub self_type: String,
+    /// The type for the symmetry of the hand-written direction, e.g. `Disk`.
+    pub other_type: String,
+    /// Module the mirror is emitted in, if not next to the implementation.
+    pub module: Option<String>,
+}
+
+/// An implementation annotated with `#[symmetric]` which cannot be mirrored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// File containing the implementation.
+    pub file: PathBuf,
+    /// Line of the `#[symmetric]` attribute.
+    pub line: usize,
+    /// The error reported by the attribute.
+    pub message: String,
+}
+
+/// Implementations found in a crate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Implementations annotated with `#[symmetric]`, whose mirror is
+    /// generated by the attribute.
+    pub symmetric: Vec<SymmetricImpl>,
+    /// Implementations annotated with `#[symmetric]` which cannot be mirrored.
+    pub failed: Vec<Failure>,
+    /// Hand-written implementations which only delegate to the reversed
+    /// implementation, whether it is in the crate or not.
+    delegating: Vec<SymmetricImpl>,
+    /// Other hand-written implementations of a trait with a type argument.
+    written: Vec<SymmetricImpl>,
+}
+
+impl Report {
+    /// Merge the implementations found elsewhere into this report.
+    pub fn extend(&mut self, other: Report) {
+        self.symmetric.extend(other.symmetric);
+        self.failed.extend(other.failed);
+        self.delegating.extend(other.delegating);
+        self.written.extend(other.written);
+    }
+
+    /// Hand-written implementations which only delegate to the reversed
+    /// implementation written in the crate, and could be generated by
+    /// `#[symmetric]` instead.
+    ///
+    /// Both implementations must have been scanned, the mirrors are matched
+    /// by the spelling of the trait and of the operand types.
+    pub fn handwritten_mirrors(&self) -> Vec<&SymmetricImpl> {
+        self.delegating
+            .iter()
+            .filter(|mirror| {
+                self.written.iter().any(|original| {
+                    original.trait_name == mirror.trait_name
+                        && original.self_type == mirror.other_type
+                        && original.other_type == mirror.self_type
+                })
+            })
+            .collect()
+    }
+}
+
+/// Whether an attribute path names the `#[symmetric]` attribute or its
+/// `#[symm]` alias, possibly qualified, e.g. `#[symm_impl::symmetric]`.
+fn is_symmetric_attr(path: &syn::Path) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "symmetric" || segment.ident == "symm")
+}
+
+/// Whether an attribute path names the `#[symmetric_options]` attribute.
+fn is_options_attr(path: &syn::Path) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "symmetric_options")
+}
+
+/// The module with the options of its `#[symmetric_options]` handed down to
+/// the nested implementations, as the attribute does when the crate is
+/// compiled, unless the options are invalid.
+fn hand_down(item: &ItemMod) -> Option<ItemMod> {
+    let position = item
+        .attrs
+        .iter()
+        .position(|attr| is_options_attr(&attr.path))?;
+    let mut module = item.clone();
+    let attr = module.attrs.remove(position);
+    let expanded =
+        symm_impl_core::expand_symmetric_options(options(&attr), module.into_token_stream());
+    syn::parse2(expanded).ok()
+}
+
+struct Collector<'a> {
+    file: &'a Path,
+    /// The crate-wide defaults of the options.
+    defaults: &'a MirrorOptions,
+    report: Report,
+}
+
+/// The options of an attribute, i.e. the tokens within its parentheses.
+fn options(attr: &Attribute) -> TokenStream {
+    match attr.tokens.clone().into_iter().next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream()
+        }
+        _ => TokenStream::new(),
+    }
+}
+
+impl<'a> Collector<'a> {
+    /// Describe a trait implementation along with its mirror, as generated by
+    /// `#[symmetric]` with the given options, unless the mirror is written by
+    /// hand or instantiated by downstream crates.
+    fn describe(
+        &self,
+        item: &ItemImpl,
+        options: TokenStream,
+        line: usize,
+    ) -> syn::Result<Option<SymmetricImpl>> {
+        let mirrored = symm_impl_core::mirrored(self.defaults.clone(), options, item.clone())?;
+        if !mirrored.emitted {
+            return Ok(None);
+        }
+        // the mirror of a trait implementation has a trait
+        let (_, trait_path, _) = mirrored.original.trait_.as_ref().unwrap();
+        let mut trait_name = trait_path.clone();
+        trait_name.segments.last_mut().unwrap().arguments = PathArguments::None;
+        Ok(Some(SymmetricImpl {
+            file: self.file.to_path_buf(),
+            line,
+            trait_name: render(&trait_name),
+            self_type: render(&mirrored.original.self_ty),
+            other_type: render(&mirrored.mirror.self_ty),
+            module: mirrored.module.map(|module| module.to_string()),
+        }))
+    }
+}
+
+impl<'ast, 'a> Visit<'ast> for Collector<'a> {
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let mut annotated = false;
+        // every stacked attribute generates its own mirror
+        for attr in item
+            .attrs
+            .iter()
+            .filter(|attr| is_symmetric_attr(&attr.path))
+        {
+            annotated = true;
+            let line = attr.pound_token.spans[0].start().line;
+            match self.describe(item, options(attr), line) {
+                Ok(Some(found)) => self.report.symmetric.push(found),
+                Ok(None) => (),
+                Err(error) => self.report.failed.push(Failure {
+                    file: self.file.to_path_buf(),
+                    line,
+                    message: error.to_string(),
+                }),
+            }
+        }
+        if !annotated {
+            let line = item.impl_token.span.start().line;
+            // implementations which are not symmetric are not reported
+            if let Ok(Some(found)) = self.describe(item, TokenStream::new(), line) {
+                if lint::is_handwritten_mirror(item) {
+                    self.report.delegating.push(found);
+                } else {
+                    self.report.written.push(found);
+                }
+            }
+        }
+        syn::visit::visit_item_impl(self, item);
+    }
+
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        match hand_down(item) {
+            Some(module) => syn::visit::visit_item_mod(self, &module),
+            None => syn::visit::visit_item_mod(self, item),
+        }
+    }
+}
+
+/// Find the symmetric implementations in a source file, mirrored on top of
+/// the crate-wide `defaults`, see [`MirrorOptions::from_config_in`], and of
+/// the options of the enclosing `#[symmetric_options]` modules.
+pub fn scan_source(file: &Path, source: &str, defaults: &MirrorOptions) -> syn::Result<Report> {
+    let ast = syn::parse_file(source)?;
+    let mut collector = Collector {
+        file,
+        defaults,
+        report: Report::default(),
+    };
+    collector.visit_file(&ast);
+    Ok(collector.report)
+}
+
+/// Find the symmetric implementations in every Rust source file under a
+/// directory.
+///
+/// Files that fail to parse are reported to `on_error` and skipped.
+pub fn scan_dir(
+    dir: &Path,
+    defaults: &MirrorOptions,
+    on_error: &mut dyn FnMut(&Path, &dyn fmt::Display),
+) -> io::Result<Report> {
+    let mut report = Report::default();
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            report.extend(scan_dir(&path, defaults, on_error)?);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            let source = fs::read_to_string(&path)?;
+            match scan_source(&path, &source, defaults) {
+                Ok(found) => report.extend(found),
+                Err(error) => on_error(&path, &error),
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Find the symmetric implementations in the source directories of a crate,
+/// mirrored on top of the crate-wide defaults of its `symm_impl.toml`, as the
+/// attribute does when the crate is compiled.
+///
+/// Files that fail to parse are reported to `on_error` and skipped.
+pub fn scan_crate(
+    crate_dir: &Path,
+    on_error: &mut dyn FnMut(&Path, &dyn fmt::Display),
+) -> io::Result<Report> {
+    let defaults = MirrorOptions::from_config_in(crate_dir)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    let mut report = Report::default();
+    for source_dir in SOURCE_DIRS {
+        let dir = crate_dir.join(source_dir);
+        if dir.is_dir() {
+            report.extend(scan_dir(&dir, &defaults, on_error)?);
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,126 @@
+//! Detection of hand-written mirror implementations, i.e. implementations
+//! whose every method only delegates to the reversed implementation. The
+//! reversed implementation is looked up among the implementations of the
+//! crate once every file is scanned, see `Report::handwritten_mirrors`.
+//!
+//! The detection is syntactic, on the sources as written: the delegating call
+//! must name the trait by the same path as the implementation, and
+//! implementations generated by macros are not seen.
+
+use syn::{
+    punctuated::Punctuated, Expr, ExprPath, FnArg, ImplItem, ImplItemMethod, ItemImpl, Pat, Path,
+    PathSegment, Stmt, Token,
+};
+
+/// The identifier bound by a plain `ident` pattern.
+fn pat_ident(pat: &Pat) -> Option<&syn::Ident> {
+    match pat {
+        Pat::Ident(pat) if pat.subpat.is_none() => Some(&pat.ident),
+        _ => None,
+    }
+}
+
+/// Whether an expression is the plain path `ident`, possibly behind a
+/// reference or a deref.
+fn is_ident(expr: &Expr, ident: &str) -> bool {
+    match expr {
+        Expr::Path(path) => path.qself.is_none() && path.path.is_ident(ident),
+        Expr::Reference(reference) => is_ident(&reference.expr, ident),
+        Expr::Unary(unary) => is_ident(&unary.expr, ident),
+        Expr::Paren(paren) => is_ident(&paren.expr, ident),
+        _ => false,
+    }
+}
+
+/// Whether two paths name the same items, whatever their generic arguments.
+fn same_path<'a>(
+    path: impl ExactSizeIterator<Item = &'a PathSegment>,
+    other: &Punctuated<PathSegment, Token![::]>,
+) -> bool {
+    path.len() == other.len()
+        && path
+            .zip(other)
+            .all(|(segment, other)| segment.ident == other.ident)
+}
+
+/// Whether a call names the method of the implemented trait by the same path
+/// as the implementation, e.g. `<B as path::to::Trait<A>>::method` or
+/// `path::to::Trait::method`.
+fn calls_trait(func: &ExprPath, trait_path: &Path, method: &syn::Ident) -> bool {
+    let segments = &func.path.segments;
+    match segments.last() {
+        Some(segment) if segment.ident == *method => (),
+        _ => return false,
+    }
+    // the trait is the path before the method, e.g. in `<B as Trait<A>>::method`
+    let trait_len = match &func.qself {
+        Some(qself) if qself.position + 1 == segments.len() => qself.position,
+        Some(_) => return false,
+        None => segments.len() - 1,
+    };
+    func.path.leading_colon.is_some() == trait_path.leading_colon.is_some()
+        && same_path(segments.iter().take(trait_len), &trait_path.segments)
+}
+
+/// Whether a method body is exactly `other.method(self)` or
+/// `Trait::method(other, self)`, in any qualified form.
+fn is_delegating_method(method: &ImplItemMethod, trait_path: &Path) -> bool {
+    let mut inputs = method.sig.inputs.iter();
+    match (inputs.next(), inputs.next(), inputs.next()) {
+        (Some(FnArg::Receiver(_)), Some(FnArg::Typed(other)), None) => {
+            let other = match pat_ident(&other.pat) {
+                Some(other) => other.to_string(),
+                None => return false,
+            };
+            let expr = match method.block.stmts.as_slice() {
+                [Stmt::Expr(expr)] => expr,
+                _ => return false,
+            };
+            let name = &method.sig.ident;
+            match expr {
+                Expr::MethodCall(call) => {
+                    call.method == *name
+                        && is_ident(&call.receiver, &other)
+                        && call.args.len() == 1
+                        && is_ident(&call.args[0], "self")
+                }
+                Expr::Call(call) => {
+                    let func_matches = match call.func.as_ref() {
+                        Expr::Path(func) => calls_trait(func, trait_path, name),
+                        _ => false,
+                    };
+                    func_matches
+                        && call.args.len() == 2
+                        && is_ident(&call.args[0], &other)
+                        && is_ident(&call.args[1], "self")
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Whether a trait implementation consists of delegating methods only, which
+/// is exactly what `#[symmetric]` generates for the reversed implementation.
+pub(crate) fn is_handwritten_mirror(item: &ItemImpl) -> bool {
+    let trait_path = match &item.trait_ {
+        Some((_, path, _)) => path,
+        None => return false,
+    };
+    let mut has_method = false;
+    for item in &item.items {
+        match item {
+            ImplItem::Method(method) => {
+                if !is_delegating_method(method, trait_path) {
+                    return false;
+                }
+                has_method = true;
+            }
+            // associated types are delegated by the mirror as well
+            ImplItem::Type(_) => (),
+            _ => return false,
+        }
+    }
+    has_method
+}
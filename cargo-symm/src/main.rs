@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, env, path::PathBuf, process};
+
+use cargo_symm::{scan_crate, Report, SymmetricImpl};
+
+const USAGE: &str = "\
+Report the implementations mirrored by #[symmetric] in a crate
+
+USAGE:
+    cargo symm [report] [CRATE_DIR]
+    cargo symm lint [CRATE_DIR]
+
+COMMANDS:
+    report    List the pairs covered by #[symmetric], grouped by trait,
+              exiting with an error if an implementation cannot be mirrored
+    lint      Find hand-written mirrors which #[symmetric] could generate,
+              exiting with an error if any is found
+
+CRATE_DIR defaults to the current directory.
+
+Both commands read the sources of src, tests, examples and benches rather
+than going through the compiler. Implementations generated by macros are not
+seen, nor the files outside of these directories, e.g. modules loaded through
+#[path] or include!. The lint only reports a mirror when the reversed
+implementation is found, with the trait spelled by the same path in both
+implementations and in the delegating calls.";
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    // invoked as `cargo symm`, cargo passes the subcommand name first
+    if args.peek().map(String::as_str) == Some("symm") {
+        args.next();
+    }
+    // `report` is the default command
+    let lint = match args.peek().cloned().as_deref() {
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            return;
+        }
+        Some("lint") => {
+            args.next();
+            true
+        }
+        Some("report") => {
+            args.next();
+            false
+        }
+        _ => false,
+    };
+    let crate_dir = args
+        .next()
+        .map_or_else(|| PathBuf::from("."), PathBuf::from);
+
+    let report = scan(&crate_dir);
+    if lint {
+        if !print_lints(&report) {
+            process::exit(1);
+        }
+    } else if !print_report(&report) {
+        process::exit(1);
+    }
+}
+
+/// Scan all the source directories of a crate.
+fn scan(crate_dir: &std::path::Path) -> Report {
+    let report = scan_crate(crate_dir, &mut |path, error| {
+        eprintln!("warning: skipping {}: {}", path.display(), error)
+    });
+    match report {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("error: failed to scan {}: {}", crate_dir.display(), error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Print the pairs covered by every trait, and an error for every
+/// implementation which cannot be mirrored, returning whether there is none.
+fn print_report(report: &Report) -> bool {
+    for failed in &report.failed {
+        eprintln!("error: {}", failed.message);
+        eprintln!("  --> {}:{}", failed.file.display(), failed.line);
+        eprintln!();
+    }
+    if report.symmetric.is_empty() {
+        println!("no #[symmetric] implementation found");
+        return report.failed.is_empty();
+    }
+
+    // group the pairs covered by each trait
+    let mut by_trait: BTreeMap<&str, Vec<&SymmetricImpl>> = BTreeMap::new();
+    for found in &report.symmetric {
+        by_trait.entry(&found.trait_name).or_default().push(found);
+    }
+    for (trait_name, found) in by_trait {
+        println!("{} ({} pairs)", trait_name, found.len());
+        for found in found {
+            let module = match &found.module {
+                Some(module) => format!(" in mod {}", module),
+                None => String::new(),
+            };
+            println!(
+                "    {} <-> {}    written for {}, mirrored for {}{}    at {}:{}",
+                found.self_type,
+                found.other_type,
+                found.self_type,
+                found.other_type,
+                module,
+                found.file.display(),
+                found.line
+            );
+        }
+    }
+    report.failed.is_empty()
+}
+
+/// Print a warning for every hand-written mirror, returning whether there is
+/// none.
+fn print_lints(report: &Report) -> bool {
+    let mirrors = report.handwritten_mirrors();
+    for found in &mirrors {
+        println!(
+            "warning: hand-written mirror `impl {}<{}> for {}`",
+            found.trait_name, found.other_type, found.self_type
+        );
+        println!("  --> {}:{}", found.file.display(), found.line);
+        println!(
+            "  = help: annotate `impl {}<{}> for {}` with #[symmetric] and remove this implementation",
+            found.trait_name, found.self_type, found.other_type
+        );
+        println!();
+    }
+    mirrors.is_empty()
+}
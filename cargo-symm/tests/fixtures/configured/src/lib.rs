@@ -0,0 +1,15 @@
+use symm_impl::symmetric;
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric(in_mod = disks)]
+impl Distance<Disk> for Segment {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
@@ -0,0 +1,349 @@
+use std::path::Path;
+
+use cargo_symm::{scan_crate, scan_source};
+use symm_impl_core::MirrorOptions;
+
+const SOURCE: &str = r#"
+use symm_impl::symmetric;
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        0.0
+    }
+}
+
+mod inner {
+    #[symm_impl::symmetric(doc_hidden)]
+    impl<'a> shapes::Intersect<'a, &'a [Disk]> for Segment<'a> {
+        fn intersect(&'a self, other: &'a &'a [Disk]) -> bool {
+            false
+        }
+    }
+}
+"#;
+
+const HANDWRITTEN: &str = r#"
+impl Distance<Point2D> for Disk {
+    type Output = f64;
+    fn distance(&self, other: &Point2D) -> f64 {
+        other.distance(self)
+    }
+}
+
+impl Intersect<Point2D> for Disk {
+    fn intersect(self, p: Point2D) -> bool {
+        <Point2D as Intersect<Disk>>::intersect(p, self)
+    }
+}
+
+impl Intersect<Segment> for Disk {
+    fn intersect(self, s: Segment) -> bool {
+        s.intersect(self) && true
+    }
+}
+
+impl Distance<Disk> for Point2D {
+    type Output = f64;
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+impl Intersect<Disk> for Point2D {
+    fn intersect(self, disk: Disk) -> bool {
+        true
+    }
+}
+
+// delegating, but not to the implementation of the trait for `Wall`
+impl Distance<Wall> for Disk {
+    type Output = f64;
+    fn distance(&self, other: &Wall) -> f64 {
+        other.distance(self)
+    }
+}
+
+impl Intersect<Wall> for Disk {
+    fn intersect(self, wall: Wall) -> bool {
+        <Wall as Overlap<Disk>>::intersect(wall, self)
+    }
+}
+
+impl Intersect<Disk> for Wall {
+    fn intersect(self, disk: Disk) -> bool {
+        false
+    }
+}
+"#;
+
+#[test]
+fn test_scan_source() {
+    let found = scan_source(Path::new("lib.rs"), SOURCE, &MirrorOptions::default())
+        .unwrap()
+        .symmetric;
+    assert_eq!(found.len(), 2);
+
+    assert_eq!(found[0].line, 4);
+    assert_eq!(found[0].trait_name, "Distance");
+    assert_eq!(found[0].self_type, "Point2D");
+    assert_eq!(found[0].other_type, "Disk");
+
+    assert_eq!(found[1].line, 18);
+    assert_eq!(found[1].trait_name, "shapes::Intersect");
+    assert_eq!(found[1].self_type, "Segment<'a>");
+    assert_eq!(found[1].other_type, "&'a [Disk]");
+}
+
+#[test]
+fn test_scan_alias() {
+    let source = "#[symm_impl::symm]\nimpl Distance<Disk> for Point2D {}\n";
+    let found = scan_source(Path::new("lib.rs"), source, &MirrorOptions::default())
+        .unwrap()
+        .symmetric;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].line, 1);
+    assert_eq!(found[0].other_type, "Disk");
+}
+
+#[test]
+fn test_handwritten_mirrors() {
+    let report = scan_source(Path::new("lib.rs"), HANDWRITTEN, &MirrorOptions::default()).unwrap();
+    assert!(report.symmetric.is_empty());
+    let found = report.handwritten_mirrors();
+    assert_eq!(found.len(), 2);
+
+    assert_eq!(found[0].line, 2);
+    assert_eq!(found[0].trait_name, "Distance");
+    assert_eq!(found[0].self_type, "Disk");
+    assert_eq!(found[0].other_type, "Point2D");
+
+    assert_eq!(found[1].line, 9);
+    assert_eq!(found[1].trait_name, "Intersect");
+}
+
+#[test]
+fn test_scan_options() {
+    let source = r#"
+#[symmetric(other = "Line")]
+#[symmetric(in_mod = mirrors)]
+impl Touch<Disk, Line> for Point2D {
+    fn touch(&self, other: &Disk) -> bool {
+        false
+    }
+    fn touch_line(&self, other: &Line) -> bool {
+        false
+    }
+}
+
+#[symmetric(remote = LocalPoint)]
+impl Distance<Disk> for geo::Point {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+"#;
+    let found = scan_source(Path::new("lib.rs"), source, &MirrorOptions::default())
+        .unwrap()
+        .symmetric;
+    assert_eq!(found.len(), 3);
+
+    // every stacked attribute generates its own mirror
+    assert_eq!(found[0].line, 2);
+    assert_eq!(found[0].other_type, "Line");
+    assert_eq!(found[0].module, None);
+    assert_eq!(found[1].line, 3);
+    assert_eq!(found[1].other_type, "Disk");
+    assert_eq!(found[1].module.as_deref(), Some("mirrors"));
+
+    // the implementation is written for the local wrapper
+    assert_eq!(found[2].self_type, "LocalPoint");
+    assert_eq!(found[2].other_type, "Disk");
+}
+
+#[test]
+fn test_handwritten_mirrors_across_files() {
+    let mirror = r#"
+impl Distance<Point2D> for Disk {
+    fn distance(&self, other: &Point2D) -> f64 {
+        other.distance(self)
+    }
+}
+"#;
+    let original = r#"
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+"#;
+    let mut report = scan_source(Path::new("disk.rs"), mirror, &MirrorOptions::default()).unwrap();
+    // the reversed implementation is not known yet
+    assert!(report.handwritten_mirrors().is_empty());
+
+    report.extend(scan_source(Path::new("point.rs"), original, &MirrorOptions::default()).unwrap());
+    let found = report.handwritten_mirrors();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].file, Path::new("disk.rs"));
+}
+
+#[test]
+fn test_scan_crate_config() {
+    let found = scan_crate(
+        Path::new("tests/fixtures/configured"),
+        &mut |path, error| panic!("failed to parse {}: {}", path.display(), error),
+    )
+    .unwrap()
+    .symmetric;
+    assert_eq!(found.len(), 2);
+
+    // the mirrors are reported where the crate-wide defaults emit them
+    assert_eq!(found[0].self_type, "Point2D");
+    assert_eq!(found[0].module.as_deref(), Some("mirrors"));
+    assert_eq!(found[1].self_type, "Segment");
+    assert_eq!(found[1].module.as_deref(), Some("disks"));
+}
+
+#[test]
+fn test_scan_module_options() {
+    let source = r#"
+#[symmetric_options(in_mod = mirrors)]
+mod shapes {
+    #[symmetric]
+    impl Distance<Disk> for Point2D {
+        fn distance(&self, other: &Disk) -> f64 {
+            0.0
+        }
+    }
+
+    #[symm_impl::symmetric_options(in_mod = nested)]
+    mod nested {
+        #[symmetric(other = "Wall")]
+        impl Touch<Disk, Wall> for Segment {
+            fn touch(&self, other: &Wall) -> bool {
+                false
+            }
+        }
+    }
+}
+"#;
+    let found = scan_source(Path::new("lib.rs"), source, &MirrorOptions::default())
+        .unwrap()
+        .symmetric;
+    assert_eq!(found.len(), 2);
+
+    // the mirrors are reported where the options of the modules emit them
+    assert_eq!(found[0].line, 4);
+    assert_eq!(found[0].module.as_deref(), Some("mirrors"));
+    assert_eq!(found[1].line, 13);
+    assert_eq!(found[1].other_type, "Wall");
+    assert_eq!(found[1].module.as_deref(), Some("nested"));
+}
+
+#[test]
+fn test_scan_not_generated() {
+    let source = r#"
+#[symmetric(check_only)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric(export = distance_to)]
+impl<T> Distance<T> for Point2D {
+    fn distance(&self, other: &T) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric_options(check_only)]
+mod written {
+    #[symmetric]
+    impl Distance<Disk> for Segment {
+        fn distance(&self, other: &Disk) -> f64 {
+            0.0
+        }
+    }
+}
+"#;
+    let report = scan_source(Path::new("lib.rs"), source, &MirrorOptions::default()).unwrap();
+    // the mirrors are written by hand, or instantiated by downstream crates
+    assert!(report.symmetric.is_empty());
+    assert!(report.failed.is_empty());
+}
+
+#[test]
+fn test_scan_failed() {
+    let source = r#"
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric(unknown_option)]
+impl Distance<Disk> for Segment {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for Line {
+    fn distance(&self, other: Disk) -> f64 {
+        0.0
+    }
+}
+"#;
+    let report = scan_source(Path::new("lib.rs"), source, &MirrorOptions::default()).unwrap();
+    assert_eq!(report.symmetric.len(), 1);
+    assert_eq!(report.failed.len(), 2);
+
+    assert_eq!(report.failed[0].line, 9);
+    assert_eq!(report.failed[0].message, "unknown option `unknown_option`");
+    assert_eq!(report.failed[1].line, 16);
+    assert!(report.failed[1].message.starts_with("expected a reference"));
+}
+
+#[test]
+fn test_handwritten_mirrors_trait_path() {
+    let source = r#"
+impl shapes::Intersect<Point2D> for Disk {
+    fn intersect(self, p: Point2D) -> bool {
+        <Point2D as shapes::Intersect<Disk>>::intersect(p, self)
+    }
+}
+
+impl shapes::Intersect<Disk> for Point2D {
+    fn intersect(self, disk: Disk) -> bool {
+        true
+    }
+}
+
+// delegating to the method of another trait of the same name
+impl Distance<Point2D> for Disk {
+    fn distance(&self, other: &Point2D) -> f64 {
+        metric::Distance::distance(other, self)
+    }
+}
+
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        0.0
+    }
+}
+"#;
+    let report = scan_source(Path::new("lib.rs"), source, &MirrorOptions::default()).unwrap();
+    let found = report.handwritten_mirrors();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].line, 2);
+    assert_eq!(found[0].trait_name, "shapes::Intersect");
+}
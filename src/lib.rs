@@ -158,6 +158,12 @@
 //!       fn operator(&self, other: &Other) -> MoreType;
 //!   }
 //!   ```
+//!   Bindings of associated constants, e.g. `Grid<Disk, RESOLUTION = 3>` with
+//!   the nightly `associated_const_equality` feature, are not candidates for
+//!   the type for the symmetry, and are kept as written in the mirror.
+//!   The type for the symmetry may be a projection, e.g.
+//!   `Distance<<Geometry as Config>::Shape>`, which the mirror is implemented
+//!   for as written, e.g. `impl Distance<Point2D> for <Geometry as Config>::Shape`.
 //! * All the methods in the trait must take exactly 2 arguments, where the
 //!   first argument is `self` and the other argument is of the type for the
 //!   symmetry. The two arguments must have the same family in the sense that
@@ -182,6 +188,14 @@
 //!       fn operator_4(&self, other: &Other, more_other: &Other) -> SomeType;
 //!   }
 //!   ```
+//!
+//! An implementation breaking these constraints is reported once, on the
+//! implementation. A stub mirror whose methods are `unimplemented!()` is
+//! generated in its place, such that uses of the mirror are not reported too.
+//! The same goes for invalid options, with the stub following the valid ones,
+//! unless those deciding whether and where the mirror goes, `check_only`,
+//! `export`, `other` and `trait_path`, are invalid themselves.
+//!
 //! Associated types in a trait are allowed, and they will be transformed as:
 //! ```no_run
 //! # struct A {}
@@ -197,6 +211,404 @@
 //!     type SomeType = <A as TraitWithType<B>>::SomeType;
 //! }
 //! ```
+//!
+//! The generics and bounds of the implementation are kept in the mirror, which
+//! delegates to the implementation under the same conditions. This includes
+//! bounds mentioning the operands, whether inline or in the where-clause, e.g.
+//! `impl<T: Into<Disk>> Distance<Disk> for Point2D<T>` is mirrored as
+//! `impl<T: Into<Disk>> Distance<Point2D<T>> for Disk`. `Self` in the bounds,
+//! e.g. `where Self: Clone + 'a` or `impl<U: PartialEq<Self>>`, is spelled as
+//! the self type of the implementation, such that the bounds stay on the same
+//! type.
+//!
+//! Trait objects may be the self type, e.g. `impl Distance<Disk> for dyn Shape`
+//! is mirrored as `impl Distance<dyn Shape> for Disk`, whose methods take the
+//! other operand as `&(dyn Shape + 'static)`, the lifetime of the trait object
+//! in the implementation. The type parameter of the trait must then be
+//! `?Sized`, and the methods take the operands by reference.
+//!
+//! Traits without type arguments implemented for a pair of operands, e.g.
+//! `impl Collide for (Ball, Wall)`, are mirrored for the swapped pair,
+//! `impl Collide for (Wall, Ball)`, whose methods call the original ones with
+//! the components swapped back and their other arguments passed along. Methods
+//! must take the pair as `self`, or as `&self` when the components are `Copy`,
+//! e.g. references in `impl<'a> Collide for (&'a Ball, &'a Wall)`.
+//!
+//! The mirrors of `PartialEq` and `PartialOrd` also delegate their provided
+//! methods to the implementation, whether or not it overrides them: `ne` to
+//! `ne`, and the comparison operators to their converse, e.g. `disk < point`
+//! to `point > disk`. The ordering given by `partial_cmp` is reversed.
+//!
+//! Conditional compilation attributes (`#[cfg]` and `#[cfg_attr]`) on the
+//! implementation and on its items are copied to the mirrored counterparts.
+//! So are doc comments, `#[must_use]`, `#[track_caller]` and `#[deprecated]`,
+//! such that both directions present the same API contract.
+//!
+//! `#[target_feature]` is copied too, e.g. for SIMD kernels, so the mirror is
+//! compiled with the same features and the original method can be inlined
+//! into it. Such methods are `unsafe`, and the mirror calls the original one
+//! in an `unsafe` block, under the same safety contract.
+//!
+//! The mirror, along with any helper item generated for it, is emitted inside
+//! an anonymous `const _: () = { ... };` block, so generated names never
+//! collide with user items. Generated code never relies on the prelude and
+//! only refers to `core` by fully qualified paths, so it works in `#![no_std]`
+//! crates and under `#![no_implicit_prelude]`.
+//!
+//! # Stacking with other attributes
+//! Attribute macros expand from top to bottom, so `#[symmetric]` sees the
+//! implementation as produced by the attribute macros above it, and as written
+//! by the ones below it:
+//! * an attribute macro above `#[symmetric]` expands first, and if it keeps
+//!   `#[symmetric]` on the implementations it emits, each of them is mirrored;
+//! * an attribute macro below `#[symmetric]` only expands on the original
+//!   implementation, unless it is copied to the mirror, either with
+//!   `copy_attrs` or like `#[async_trait]` below;
+//! * `#[cfg_attr(predicate, attrs)]` is copied to the mirror as a whole, so the
+//!   attributes it enables, including attribute macros, apply to both
+//!   directions under the same predicate;
+//! * `#[cfg_attr(predicate, symmetric)]` only mirrors the implementation when
+//!   the predicate holds.
+//!
+//! With `#[symmetric(defer)]`, the attribute moves itself after the attribute
+//! macros below it, such that it mirrors their output instead. This is needed
+//! for macros emitting several implementations, which are then all mirrored.
+//! ```ignore
+//! #[symmetric(defer)]
+//! #[duplicate_for(Square)] // emits `impl Distance<Square> for Point2D` too
+//! impl Distance<Disk> for Point2D {
+//!     // ...
+//! }
+//! ```
+//!
+//! Traits using `#[async_trait]` are supported as long as `#[symmetric]` is
+//! placed above `#[async_trait]`, such that it sees the `async fn` as written.
+//! `#[async_trait]` is then copied to the mirror, whose methods await the
+//! original ones.
+//! ```ignore
+//! #[symmetric]
+//! #[async_trait]
+//! impl Distance<Disk> for Point2D {
+//!     async fn distance(&self, other: &Disk) -> f64 {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! # Options
+//! The behavior of the attribute can be customized with options, e.g.
+//! `#[symmetric(copy_attrs(allow), skip_attrs(doc))]`. Options are written
+//! either `key = value`, `key(...)` or as a bare flag. A flag may also be
+//! written `flag = false`, e.g. `doc_hidden = false`, to unset it when it is
+//! inherited from [`macro@symmetric_options`] or the crate-wide defaults.
+//! Those taking a list accumulate when repeated, the others may only be given
+//! once, and unknown or malformed options are errors.
+//! * `copy_attrs(...)`: additional attributes to copy to the mirror. Copied
+//!   attributes take the place of the ones the mirror would get otherwise,
+//!   e.g. a copied `#[inline(always)]` replaces the default `#[inline]`, and
+//!   lints already allowed by a copied `#[allow(...)]` are not allowed again.
+//! * `skip_attrs(...)`: attributes never copied to the mirror. Conditional
+//!   compilation attributes are always copied and cannot be skipped.
+//! * `automatically_derived = false`: by default the mirror is marked
+//!   `#[automatically_derived]` so that lints and tools treat it like the
+//!   output of `#[derive]`. Set to `false` to have it linted normally.
+//! * `track_caller = false`: by default the mirrored methods are marked
+//!   `#[track_caller]`, such that panics of `#[track_caller]` methods in the
+//!   original implementation report the call site of the mirror instead of
+//!   the generated code. Set to `false` to leave the attribute out, unless
+//!   written on the original method, which is then forwarded as usual. It is
+//!   never put on `async fn`.
+//! * `doc_hidden`: mark the mirror `#[doc(hidden)]`, for crates which consider
+//!   the mirror an implementation detail.
+//! * `clippy_allow(...)`: clippy lints allowed on the mirrored methods, such
+//!   that the delegation does not trip lints enabled by the user. Defaults to
+//!   the lints the generated code itself may cause,
+//!   `clippy_allow(mut_mut, needless_borrow, needless_lifetimes, use_self)`,
+//!   while code forwarded from the original implementation is linted as
+//!   usual. Use `clippy_allow()` to lint mirrored methods like any other code.
+//! * `coverage_off`: exclude the mirrored methods from code coverage, which
+//!   only delegate to the original methods, with
+//!   `#[cfg_attr(coverage_nightly, coverage(off))]`. `#[coverage]` is unstable,
+//!   so the crate must enable it when coverage is collected on nightly, e.g. by
+//!   cargo-llvm-cov setting `cfg(coverage_nightly)`:
+//!   `#![cfg_attr(coverage_nightly, feature(coverage_attribute))]`. Crates
+//!   checking their cfgs also declare `coverage_nightly` as expected.
+//! * `cfg(...)`: only compile the mirror if the cfg predicate holds, e.g.
+//!   `cfg(feature = "mirror-impls")`. The original implementation is not
+//!   affected. The mirror is also marked
+//!   `#[cfg_attr(docsrs, doc(cfg(...)))]`, such that documentation built with
+//!   `--cfg docsrs` and the `doc_cfg` feature shows the condition.
+//! * `in_mod = name`: emit the mirror inside a new private module `name`
+//!   instead of next to the original implementation. The module glob-imports
+//!   its parent so the mirror sees the same names, and `self::` and `super::`
+//!   paths are adjusted to resolve to the same items as in the original
+//!   implementation. Since a module cannot be reopened, each use of `in_mod`
+//!   must name a different module.
+//! * `inline = "always" | "never" | "hint" | "none"`: the inlining attribute
+//!   put on mirrored methods, `#[inline(always)]`, `#[inline(never)]`,
+//!   `#[inline]` or nothing respectively. Defaults to `"hint"`. Methods with
+//!   `#[target_feature]` cannot be `#[inline(always)]`, and get `#[inline]`
+//!   instead.
+//! * `call = "qualified" | "method"`: how the mirror delegates to the original
+//!   implementation, `<A as Trait<B>>::method(other, self)` or
+//!   `other.method(self)` respectively. Method-call syntax allows autoref and
+//!   deref coercions to apply. Defaults to `"qualified"`.
+//! * `rewrite_body`: copy the body of every original method into its mirror
+//!   instead of delegating to it, e.g. for hot paths where the delegation
+//!   gets in the way of inlining. `self` and the other operand swap names in
+//!   the expressions and bindings of the body, macro arguments included, and
+//!   `Self` names the self type of the implementation. Fields, methods and
+//!   path segments are left alone, including the fields initialized by the
+//!   shorthand `Foo { other }`, and the format strings of `format!`,
+//!   `write!` and the other formatting macros of `std` capturing the other
+//!   operand, e.g. `"{other:?}"`, are given it as an argument instead. A body
+//!   binding a local named after the other operand, e.g. `let other: f64 =
+//!   other.radius`, refers to the operand through an alias, while the
+//!   locals of nested items are left alone. A destructured other operand,
+//!   e.g. `Disk(center, radius): &Disk`, is destructured from `self` at the
+//!   start of the body. The other operand keeps its name unless `other_ident`
+//!   is given.
+//! * `delegate_via = path::to::helper`: the mirrored method calls
+//!   `helper(other, self)` instead of the original method, e.g. when the
+//!   original method is a thin wrapper over a free function, so both methods
+//!   share the same inlined core. The implementation must have a single
+//!   mirrored method, other methods can be left out with `only`.
+//! * `pairs(a = b, ...)`: methods whose names encode the direction of the
+//!   relation, e.g. `pairs(casts_shadow_on = shadowed_by)`. The mirror of each
+//!   method delegates to its counterpart instead of the method of the same
+//!   name, in both directions, such that `disk.shadowed_by(&point)` calls
+//!   `point.casts_shadow_on(&disk)`. At least one method of each pair must be
+//!   in the implementation, and a method may only be paired once. May be given
+//!   multiple times.
+//! * `other_ident = name`: name of the non-receiver parameter in the mirrored
+//!   signatures, as shown in rustdoc and IDE hints. Defaults to `other`.
+//! * `other = "Type"`: the type argument of the trait taken as the other
+//!   operand, for traits with several of them, e.g. `other = "Line"` on
+//!   `impl Touch<Disk, Line> for Point2D` mirrors it as
+//!   `impl Touch<Disk, Point2D> for Line`. Defaults to the first type
+//!   argument. Only the methods whose other operand is of this type are
+//!   mirrored, the others are left to the defaults of the trait. Stacking
+//!   `#[symmetric]` with a different `other` on the same implementation
+//!   generates one mirror per attribute. Options generating items besides the
+//!   mirror, e.g. `by_ref` or `proptest`, cannot be combined with `other`.
+//! * `bound = "..."`: where-clause predicates added to the mirror only, e.g.
+//!   `bound = "Disk: Clone"`. May be given multiple times.
+//! * `remove_bound = "..."`: predicates removed from the bounds of the mirror,
+//!   either from its where-clause or from inline bounds on its generic
+//!   parameters. `T: A + B` removes the bounds `A` and `B` on `T` while keeping
+//!   other bounds on `T`. May be given multiple times.
+//! * `trait_path = path::to::Trait`: how the trait is spelled in the mirror and
+//!   in the delegating call, e.g. when it is re-exported through a facade. The
+//!   generic arguments are taken from the implementation and must be omitted.
+//! * `same_type(A = B, ...)`: spellings of the type for the symmetry known to
+//!   name the same type. When the argument of a method is not spelled like the
+//!   type in the trait of the implementation, e.g. through a type alias, the
+//!   mirror has the compiler check that they are the same type. The check is
+//!   skipped for the listed pairs, for spellings which only make sense in the
+//!   original implementation, such as `<Self as Trait>::Assoc`.
+//! * `defer`: expand after the attribute macros below `#[symmetric]`, see
+//!   [Stacking with other attributes](#stacking-with-other-attributes).
+//! * `strict`: require the implementation to write every method of the trait,
+//!   instead of relying on default methods, such that the mirror delegates
+//!   every method to the original implementation. The trait definition must
+//!   be annotated with [`macro@symmetric_trait`], which records its methods.
+//! * `check_only`: check that the implementation can be mirrored, with the
+//!   same diagnostics as without the option, but emit no mirror, e.g. for
+//!   mirrors kept hand-written. No stub mirror is emitted along with errors
+//!   either, since it would conflict with the hand-written one.
+//! * `antisymmetric`: for relations where `F(b, a) = -F(a, b)`, such as
+//!   `impl Sub<Offset> for Point`. The mirrored methods negate the result of
+//!   the original ones with `core::ops::Neg`, and the mirror requires the
+//!   return types to implement `Neg` onto themselves.
+//! * `swap_output`: for results referring to the operands in order, e.g. the
+//!   closest points of two shapes or a contact normal, the mirrored methods
+//!   flip the result of the original ones with the `SwapOperands` trait of the
+//!   `symm_impl_runtime` crate, which the return types must implement.
+//! * `guarded`: add the original implementation to the where-clause of the
+//!   mirror, e.g. `where Point2D: Distance<Disk>`, such that the mirror only
+//!   exists where the original implementation does. This keeps the mirror
+//!   valid when its bounds are loosened with `remove_bound`, or when the
+//!   original implementation is generic over more than the annotated one.
+//!   Without generic parameters the bound must still hold, use `cfg(...)` for
+//!   original implementations compiled conditionally.
+//! * `only(...)`: methods to mirror, e.g. `only(distance, intersects)`, for
+//!   implementations where only some of the methods are symmetric. The other
+//!   methods are left out of the mirror, which then uses their default in the
+//!   trait, and do not need to fit the shape required above. Listing a method
+//!   missing from the implementation is an error. A mirror left without any
+//!   method or associated type, e.g. by `only()`, is most likely a mistake and
+//!   raises a `deprecated` warning, unless the implementation is empty too,
+//!   e.g. for a marker trait.
+//! * `by_ref`: also implement both directions for a reference to the type for
+//!   the symmetry, e.g. `impl<'a> Distance<&'a Disk> for Point2D` and
+//!   `impl<'a> Distance<&'a Point2D> for Disk`, delegating through a
+//!   dereference. Methods taking their operands by value clone the borrowed
+//!   operand, which must then implement `Clone`. A trait with a method taking
+//!   `&mut self` is implemented for a mutable reference instead, e.g.
+//!   `impl<'a> Absorb<&'a mut Disk> for Point2D`, which the other methods
+//!   reborrow.
+//! * `fan_out(&, &mut, Box)`: also implement the trait for pointers to either
+//!   operand, delegating to the operand behind the pointer, along with their
+//!   mirrors, e.g. `impl<'a> Distance<Disk> for &'a Point2D` and
+//!   `impl<'a> Distance<&'a Point2D> for Disk`, such that generic code holding
+//!   references or boxes of the operands can use the trait. Other smart
+//!   pointers, e.g. `Rc`, are given by path. Methods taking `&mut self` are
+//!   not reachable through `&`, and methods taking `self` only through `Box`.
+//!   `fan_out(&)` includes `by_ref`.
+//! * `lift(Container, reduce = min | max | sum | any | all)`: also implement
+//!   the trait between the self type and a container of the other operand, in
+//!   both directions, e.g. `impl Distance<Vec<Disk>> for Point2D` and
+//!   `impl Distance<Point2D> for Vec<Disk>`. The results of the elements are
+//!   reduced into one, e.g. with `min` for the distance to the nearest disk,
+//!   the same way for every method.
+//!   `min` and `max` compare with `PartialOrd` and panic on an empty
+//!   container, `sum` adds from `Default::default()`. `empty = expr` gives the
+//!   result for an empty container instead, e.g. `empty = f64::INFINITY`. The
+//!   container must be iterable by reference, and the methods must take their
+//!   operands by reference. May be given multiple times.
+//! * `wrap(Wrapper, unwrap = path)`: also implement the trait between the
+//!   wrappers of both operands, in both directions, e.g.
+//!   `impl Distance<Transformed<Disk>> for Transformed<Point2D>` and
+//!   `impl Distance<Transformed<Point2D>> for Transformed<Disk>`. The methods
+//!   call `path` on both wrappers and delegate with the results, which may be
+//!   references to the operands or new operands, e.g. shapes with their
+//!   transform applied, as long as they implement `Borrow` of the operands.
+//!   `path` defaults to `Deref::deref`. The methods must take their operands
+//!   by shared reference. May be given multiple times.
+//! * `optional = "none" | "default" | "panic"`: also implement the trait
+//!   between the self type and an optional other operand, in both directions,
+//!   e.g. `impl Distance<Option<Disk>> for Point2D` and
+//!   `impl Distance<Point2D> for Option<Disk>`. When the operand is `None`,
+//!   the methods return `None`, `Default::default()` or panic respectively.
+//!   `"none"` requires the methods to return an associated type of the trait,
+//!   e.g. `Self::Output`, which is then wrapped in `Option`.
+//! * `operator(Operator = method, ...)`: also implement binary operators of
+//!   `core::ops` calling methods of the trait, in both directions, e.g.
+//!   `operator(BitAnd = intersects)` implements `BitAnd<Disk>` for `Point2D`
+//!   and `BitAnd<Point2D>` for `Disk`, such that `point & disk` calls
+//!   `point.intersects(&disk)`. Operators take their operands by value, which
+//!   are borrowed as the method expects.
+//! * `remote = Wrapper`: work around the orphan rules when both operands and
+//!   the trait are foreign. A `#[repr(transparent)]` newtype `Wrapper` of the
+//!   self type is declared, with `From`, `AsRef`, `AsMut` and `Deref`
+//!   conversions, and the implementation is rewritten for it, the mirror then
+//!   taking the wrapper as the other operand. The bodies see `self` through
+//!   `Deref`. A visibility may precede the name, e.g. `remote = pub Seconds`.
+//!   Generic implementations are not supported.
+//! * `ffi(self_name, other_name)`: also export `#[no_mangle] extern "C"`
+//!   functions calling every mirrored method in both argument orders, e.g.
+//!   `ffi(point, disk)` exports `point_disk_distance` and
+//!   `disk_point_distance`. Operands taken by reference are passed as
+//!   pointers, and by value otherwise. The functions are `unsafe`, and the
+//!   types are expected to be FFI-safe, e.g. `#[repr(C)]`. Generic
+//!   implementations are not supported.
+//! * `wasm_bindgen(self_name, other_name)`: also export `#[wasm_bindgen]`
+//!   functions calling every mirrored method in both argument orders, named
+//!   as with `ffi`, so that the JavaScript API does not depend on the order
+//!   of the operands. The operands are taken as the methods take them, and
+//!   async methods are exported as async functions. The crate depends on
+//!   `wasm-bindgen`, and the types are exported with `#[wasm_bindgen]`.
+//!   Generic implementations are not supported.
+//! * `pymethods`: also expose every mirrored method on both Python classes
+//!   with `#[pymethods]` of [PyO3](https://pyo3.rs), e.g. `Point.distance(disk)`
+//!   and `Disk.distance(point)`. The crate enables the `multiple-pymethods`
+//!   feature of PyO3, as the classes get another `#[pymethods]` block, and the
+//!   methods take the operands by reference. Generic implementations and
+//!   async methods are not supported.
+//! * `export = name`: for an implementation generic over the other operand,
+//!   e.g. `impl<T: Shape> Distance<T> for Point2D`, whose mirror for any `T` is
+//!   rejected by the orphan rules unless the trait is local, emit an exported
+//!   `macro_rules! name` instead of the mirror. Downstream crates invoke it
+//!   with their types, e.g. `mirror_distance!(Square, Triangle)`, to implement
+//!   the mirror for them. Paths starting with `crate::` in the implementation
+//!   resolve to the crate of the implementation, other paths must resolve
+//!   where the macro is invoked.
+//! * `instrument`: wrap the calls of the mirror to the original implementation
+//!   in a `tracing::trace_span!` named `symmetric`, recording the operation,
+//!   the self type and the other operand of the mirror, and the direction,
+//!   e.g. to tell which direction of an operation ran in a pipeline. Requires
+//!   the `tracing` feature of this crate, and the crate using the option to
+//!   depend on `tracing`.
+//! * `kani`: generate a [Kani](https://github.com/model-checking/kani) proof
+//!   harness for every mirrored method, asserting that `F(a, b) == F(b, a)`
+//!   for any operands, or `F(a, b) == -F(b, a)` with `antisymmetric`. The
+//!   harnesses are only compiled by `cargo kani`, which sets `cfg(kani)`, and
+//!   require both types to implement `kani::Arbitrary` and the results
+//!   `PartialEq`. Operands taken by value must implement `Clone`. Generic
+//!   implementations and methods taking `&mut self` are not supported.
+//! * `proptest` or `proptest(strategy(Type = strategy, ...))`: generate a
+//!   [proptest](https://crates.io/crates/proptest) property test for every
+//!   mirrored method, asserting the same as the `kani` harnesses. The tests
+//!   are compiled with `cfg(test)` in a private module next to the
+//!   implementation, and require the crate to depend on `proptest`. The
+//!   operands are generated by `any::<T>()` unless a strategy is given for
+//!   their type, e.g.
+//!   `proptest(strategy(Point2D = finite_point(), Disk = non_degenerate_disk()))`
+//!   for types without an `Arbitrary` implementation or with constrained
+//!   values. The same restrictions as `kani` apply.
+//! * `markers`: implement the `SymmetricOp` marker trait of the
+//!   `symm_impl_runtime` crate for both directions, e.g.
+//!   `Point2D: SymmetricOp<Disk, dyn Distance<Disk>>`, such that generic code
+//!   can require the mirror to exist. The trait must be dyn compatible, and
+//!   the option cannot be combined with `antisymmetric` or `only`.
+//! * `seal = path::to::Seal`: implement the sealing trait `Seal<Other>` along
+//!   with the implementation, its mirror and every other implementation of
+//!   the trait generated for it, e.g. by `by_ref`. With the trait declared as
+//!   `pub trait Distance<Other>: private::DistanceSeal<Other>`, where
+//!   `private` is a private module, the trait can only be implemented with
+//!   this option in the defining crate, so downstream crates cannot write a
+//!   reverse implementation diverging from the mirror. Each sealed trait needs
+//!   its own sealing trait, declared as `pub trait DistanceSeal<Other: ?Sized> {}`.
+//! * `runtime = path::to::symm_impl_runtime`: path to the `symm_impl_runtime`
+//!   crate used by `markers` and `swap_output`. Defaults to
+//!   `::symm_impl_runtime`.
+//! * `crate = path::to::symm_impl`: path to this crate used by generated code
+//!   referring to its support items, e.g. by `strict`, for crates re-exporting
+//!   the attribute to their users. Defaults to `::symm_impl`.
+//!
+//! # Crate-wide defaults
+//! Options given to every `#[symmetric]` of a crate can be set once in
+//! `symm_impl.toml`, next to its `Cargo.toml`:
+//! ```toml
+//! options = 'doc_hidden, inline = "never", skip_attrs(must_use)'
+//! ```
+//! The value holds options written as in the attribute, which override them
+//! or, for options given several times such as `skip_attrs`, add to them.
+//! Flags set by the file are unset by an attribute with `flag = false`. The
+//! environment variable `SYMM_IMPL_CONFIG` names another file, relative to
+//! the same directory. Only the `options` key is read, and cargo does not
+//! track the file, so a clean build may be needed after editing it.
+//!
+//! Defaults of the implementations of a module are set by
+//! [`macro@symmetric_options`] on the module, on top of the ones of the crate.
+//!
+//! # Reviewing generated code
+//! Setting the environment variable `SYMM_IMPL_DUMP` to a directory while
+//! compiling makes every expansion write the generated code into a file in that
+//! directory, named after the trait and the types of the mirror, e.g.
+//! `Distance_Point2D_for_Disk.rs` for `impl Distance<Disk> for Point2D`, so
+//! that stacked attributes are dumped into their own files. Since cargo does not
+//! track this variable, a clean build may be needed for the dump to happen.
+//! The files are not formatted, run `rustfmt` over them for easier reading.
+//!
+//! # Inventory
+//! Setting the environment variable `SYMM_IMPL_INVENTORY` makes every
+//! expansion record the mirror it generates into `symm_impl.json` in the
+//! `OUT_DIR` of the crate, for documentation generators and other tooling. The
+//! crate opts in from its build script, which also gives it an `OUT_DIR`:
+//! ```no_run
+//! println!("cargo:rustc-env=SYMM_IMPL_INVENTORY=1");
+//! ```
+//! The file holds an array with an object per mirror, giving the implemented
+//! trait, the self type, the other operand, the names of the methods and the
+//! original implementation:
+//! ```json
+//! [
+//!   {"trait": "Distance<Point2D>", "self_type": "Disk", "other_type": "Point2D", "methods": ["distance"], "original": "impl Distance<Disk> for Point2D"}
+//! ]
+//! ```
+//! The other operand is `null` for implementations for a pair. Mirrors of
+//! `export` are instantiated by other crates, and are not recorded.
 //!  
 //! # Example
 //! ```
@@ -232,9 +644,17 @@
 //!     }
 //! }
 //! /* Expands to
+//! /// Symmetric mirror of [`impl Distance<Disk> for Point2D`](trait@Distance).
+//! #[automatically_derived]
 //! impl Distance<Point2D> for Disk {
-//!     #[allow(unused_mut)]
+//!     #[track_caller]
 //!     #[inline]
+//!     #[allow(
+//!         clippy::mut_mut,
+//!         clippy::needless_borrow,
+//!         clippy::needless_lifetimes,
+//!         clippy::use_self
+//!     )]
 //!     fn distance(&self, other: &Point2D) -> f64 {
 //!         <Point2D as Distance<Disk>>::distance(other, self)
 //!     }
@@ -249,254 +669,634 @@
 //! assert_eq!(p.distance(&c), c.distance(&p));
 //! ```
 
-use std::{iter::FromIterator, mem};
-
-use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
-use quote::quote;
-use syn::{
-    parse::Parser, parse_macro_input, parse_quote, spanned::Spanned, Attribute, Block, FnArg,
-    GenericArgument, ImplItem, ItemImpl, Pat, PatIdent, PathArguments, Type,
-};
-
 /// See module-level documentation
 #[proc_macro_attribute]
 pub fn symmetric(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let ast = parse_macro_input!(item as ItemImpl);
-
-    let mirrored_ast = mirror(ast.clone());
-
-    let expanded = quote! {
-        #ast
-
-        #mirrored_ast
-    };
-
-    proc_macro::TokenStream::from(expanded)
+    proc_macro::TokenStream::from(symm_impl_core::expand(attr.into(), item.into()))
 }
 
-/// Take a syntax tree of impl and generate the mirror implementation for a
-/// symmetric trait.
-fn mirror(mut ast: ItemImpl) -> TokenStream {
-    if ast.trait_.is_none() {
-        // not a trait implementation
-        return to_compile_error(
-            "#[symmetric] can only be used on trait implementation".to_string(),
-            Span::call_site(),
-        );
-    }
-    let trait_ = ast.trait_.as_mut().unwrap();
-    if let Some(bang) = trait_.0 {
-        // negative marker trait
-        return to_compile_error(
-            "#[symmetric] cannot be used on negative trait bound".to_string(),
-            bang.span.clone(),
-        );
-    }
-    // it is guaranteed that trait_.1 is a non-empty path sequence since this is a trait impl
-    let original_trait = trait_.1.clone();
-    let last_segment = trait_.1.segments.last_mut().unwrap();
-    let trait_generics = match &mut last_segment.arguments {
-        PathArguments::AngleBracketed(generics) => generics,
-        _ => {
-            // no generics arguments
-            return to_compile_error("expected a generic trait".to_string(), trait_.1.span());
-        }
-    };
-
-    // deduce the "other" type for this trait
-    let other_type = trait_generics.args.iter_mut().find_map(|arg| {
-        if let GenericArgument::Type(type_arg) = arg {
-            Some(type_arg)
-        } else {
-            None
-        }
-    });
-    if other_type.is_none() {
-        // no type arguments
-        return to_compile_error(
-            "symmetric trait must contain at least 1 type argument".to_string(),
-            trait_generics.span(),
-        );
-    }
-    let other_type = other_type.unwrap();
+/// Shorthand for [`macro@symmetric`], taking the same options.
+///
+/// ```
+/// use symm_impl::symm;
+///
+/// trait Distance<Other> {
+///     fn distance(&self, other: &Other) -> f64;
+/// }
+///
+/// struct Point2D(f64);
+/// struct Disk(f64, f64);
+///
+/// #[symm(doc_hidden)]
+/// impl Distance<Disk> for Point2D {
+///     fn distance(&self, other: &Disk) -> f64 {
+///         (self.0 - other.0).abs() - other.1
+///     }
+/// }
+///
+/// assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+/// ```
+#[proc_macro_attribute]
+pub fn symm(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    symmetric(attr, item)
+}
 
-    // deduce the "self" type for this trait
-    let self_type = ast.self_ty.as_mut();
+/// Record the methods of a trait, for `#[symmetric(strict)]` on its
+/// implementations.
+///
+/// The attribute goes on the trait definition. It defines a hidden macro named
+/// after the trait, which is imported along with the trait, so the trait can
+/// only be checked by implementations in the same crate.
+#[proc_macro_attribute]
+pub fn symmetric_trait(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_trait(attr.into(), item.into()))
+}
 
-    // go through items inside the block
-    // 1. For every associated type, make it
-    //    type SomeType = <other_type as Trait>::SomeType
-    // 2. For every method, make sure it is of one of the following
-    //     * f(&self, other: &other_type)
-    //     * f(&mut self, other: &mut other_type)
-    //     * f(self, other: other_type)
-    //     * f(mut self, mut other: other_type)
-    //    If there are lifetime decorations, they must be the same.
-    //    replace other_type with self_type
-    //    replace the body with:
-    //    Trait::f(other, self)
-    // 3. Leave everything else intact
-    for item in ast
-        .items
-        .iter_mut()
-        .filter(|item| matches!(item, ImplItem::Method(_) | ImplItem::Type(_)))
-    {
-        match item {
-            ImplItem::Method(method) => {
-                if let Some(variadic) = &method.sig.variadic {
-                    // variadic method
-                    return to_compile_error(
-                        "method in a symmetric trait cannot be variadic".to_string(),
-                        variadic.span(),
-                    );
-                }
+#[doc(hidden)]
+#[proc_macro]
+pub fn __check_strict(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::check_strict(input.into()))
+}
 
-                // verify the input arguments of the method
+/// Implement the approximate equality traits of the `approx` crate
+/// symmetrically.
+///
+/// `symmetric_approx!(A, B)` implements `AbsDiffEq<A>`, `RelativeEq<A>` and
+/// `UlpsEq<A>` for `B` by delegating to the implementations of `AbsDiffEq<B>`,
+/// `RelativeEq<B>` and `UlpsEq<B>` for `A`, including the default tolerances.
+/// The mirrors use the same `Epsilon` as the original implementations.
+///
+/// Generic parameters are introduced with `impl`, and the traits to mirror can
+/// be restricted after a `;`, e.g.
+/// `symmetric_approx!(impl<T: Float> Point<T>, Disk<T>; AbsDiffEq, RelativeEq)`.
+///
+/// Since `AbsDiffEq<A>` requires `PartialEq<A>`, `B` must implement it too,
+/// e.g. with `#[symmetric]` on `impl PartialEq<B> for A`. The crate using the
+/// macro must depend on `approx`.
+///
+/// ```
+/// use symm_impl::{symmetric, symmetric_approx};
+/// use approx::{assert_relative_eq, AbsDiffEq, RelativeEq};
+///
+/// #[derive(Debug)]
+/// struct Meters(f64);
+/// #[derive(Debug)]
+/// struct Feet(f64);
+///
+/// #[symmetric]
+/// impl PartialEq<Feet> for Meters {
+///     fn eq(&self, other: &Feet) -> bool {
+///         self.0 == other.0 * 0.3048
+///     }
+/// }
+///
+/// impl AbsDiffEq<Feet> for Meters {
+///     type Epsilon = f64;
+///     fn default_epsilon() -> f64 {
+///         f64::default_epsilon()
+///     }
+///     fn abs_diff_eq(&self, other: &Feet, epsilon: f64) -> bool {
+///         self.0.abs_diff_eq(&(other.0 * 0.3048), epsilon)
+///     }
+/// }
+///
+/// impl RelativeEq<Feet> for Meters {
+///     fn default_max_relative() -> f64 {
+///         f64::default_max_relative()
+///     }
+///     fn relative_eq(&self, other: &Feet, epsilon: f64, max_relative: f64) -> bool {
+///         self.0.relative_eq(&(other.0 * 0.3048), epsilon, max_relative)
+///     }
+/// }
+///
+/// symmetric_approx!(Meters, Feet; AbsDiffEq, RelativeEq);
+///
+/// assert_relative_eq!(Feet(10.0), Meters(3.048));
+/// ```
+#[proc_macro]
+pub fn symmetric_approx(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_approx(input.into()))
+}
 
-                if method.sig.inputs.len() != 2 {
-                    // wrong number of arguments
-                    return to_compile_error(
-                        "expected 2 arguments".to_string(),
-                        method.sig.inputs.span(),
-                    );
-                }
+/// Implement symmetric traits for an enum by dispatching over the variants of
+/// both operands.
+///
+/// Each variant must wrap a single type, and the trait must be implemented
+/// between every pair of wrapped types, in both directions. The traits to
+/// implement are listed in `#[symm(impl ...)]` attributes with the signatures
+/// of their items, since a derive macro cannot see the trait definition.
+/// Associated types and constants are given their value.
+///
+/// For an enum without generic parameters, the pairs of variants whose types
+/// do not implement the trait are reported together in a single error, e.g.
+/// ``"`Distance` is not implemented for the pairs of variants of `Shape`:
+/// (Disk, Segment), (Segment, Disk)"``. The pairs of a generic enum are
+/// required by bounds of the implementation instead.
+///
+/// A variant marked `#[symm(nested)]` wraps another enum deriving
+/// `SymmetricDispatch` with the same traits, e.g. `Curve(CurveKind)`. Paired
+/// with another variant, it is dispatched over the variants of the nested enum,
+/// such that the trait need not be implemented between the nested enum and the
+/// other wrapped types. The nested enum must be non-generic and declared where
+/// its path resolves from the outer enum.
+///
+/// A variant marked `#[symm(skip)]`, e.g. a marker or a sentinel, takes no part
+/// in the dispatch and need not wrap a type. The pairs with it result in the
+/// body given to the signature of each method, e.g.
+/// `fn distance(&self, other: &Shape) -> f64 { f64::INFINITY }`. A variant
+/// marked `#[symm(with = path)]` is skipped the same way, its pairs calling the
+/// function of the module `path` named after the method with both operands,
+/// e.g. `path::distance(self, other)`. The first operand takes precedence if
+/// both are skipped. An enum with skipped variants cannot be nested.
+///
+/// `#[symm(fallback = path)]` on the enum routes the pairs not implemented, and
+/// the skipped pairs without a result, to the functions of the module `path`
+/// instead, e.g. `path::distance(self, other)`, which allows adding the
+/// pairwise implementations progressively. The pairs of a generic enum are
+/// still required by bounds.
+///
+/// ```
+/// use symm_impl::{symmetric, SymmetricDispatch};
+///
+/// trait Distance<Other> {
+///     fn distance(&self, other: &Other) -> f64;
+/// }
+/// # struct Point2D(f64);
+/// # struct Disk(f64, f64);
+/// # impl Distance<Point2D> for Point2D {
+/// #     fn distance(&self, other: &Point2D) -> f64 { (self.0 - other.0).abs() }
+/// # }
+/// # impl Distance<Disk> for Disk {
+/// #     fn distance(&self, other: &Disk) -> f64 { (self.0 - other.0).abs() - self.1 - other.1 }
+/// # }
+/// # #[symmetric]
+/// # impl Distance<Disk> for Point2D {
+/// #     fn distance(&self, other: &Disk) -> f64 { (self.0 - other.0).abs() - other.1 }
+/// # }
+///
+/// #[derive(SymmetricDispatch)]
+/// #[symm(impl Distance<Shape> {
+///     fn distance(&self, other: &Shape) -> f64;
+/// })]
+/// enum Shape {
+///     Point(Point2D),
+///     Disk(Disk),
+/// }
+///
+/// let (p, d) = (Shape::Point(Point2D(5.0)), Shape::Disk(Disk(1.0, 2.0)));
+/// assert_eq!(p.distance(&d), 2.0);
+/// assert_eq!(d.distance(&p), 2.0);
+/// ```
+#[proc_macro_derive(SymmetricDispatch, attributes(symm))]
+pub fn symmetric_dispatch(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_dispatch_derive(input.into()))
+}
 
-                let mut iter = method.sig.inputs.iter_mut();
-                let self_arg = iter.next().unwrap();
-                let other_arg = iter.next().unwrap();
+/// Define an enum over operand types, along with `From` conversions from each
+/// of them and the implementations of symmetric traits dispatching over its
+/// variants.
+///
+/// This is [`SymmetricDispatch`](derive@SymmetricDispatch) with the
+/// conversions, the traits to implement following the enum as `impl` blocks of
+/// signatures.
+///
+/// ```
+/// use symm_impl::{symmetric, symmetrize_enum};
+///
+/// trait Distance<Other> {
+///     fn distance(&self, other: &Other) -> f64;
+/// }
+/// # struct Point2D(f64);
+/// # struct Disk(f64, f64);
+/// # impl Distance<Point2D> for Point2D {
+/// #     fn distance(&self, other: &Point2D) -> f64 { (self.0 - other.0).abs() }
+/// # }
+/// # impl Distance<Disk> for Disk {
+/// #     fn distance(&self, other: &Disk) -> f64 { (self.0 - other.0).abs() - self.1 - other.1 }
+/// # }
+/// # #[symmetric]
+/// # impl Distance<Disk> for Point2D {
+/// #     fn distance(&self, other: &Disk) -> f64 { (self.0 - other.0).abs() - other.1 }
+/// # }
+///
+/// symmetrize_enum! {
+///     enum Shape {
+///         Point(Point2D),
+///         Disk(Disk),
+///     }
+///
+///     impl Distance<Shape> {
+///         fn distance(&self, other: &Shape) -> f64;
+///     }
+/// }
+///
+/// let (p, d): (Shape, Shape) = (Point2D(5.0).into(), Disk(1.0, 2.0).into());
+/// assert_eq!(p.distance(&d), d.distance(&p));
+/// ```
+#[proc_macro]
+pub fn symmetrize_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_symmetrize_enum(input.into()))
+}
 
-                // self_arg must be one of the 4 form
-                let self_arg = match self_arg {
-                    FnArg::Receiver(receiver) => receiver,
-                    _ => {
-                        return to_compile_error(
-                            "expected a receiver".to_string(),
-                            self_arg.span(),
-                        );
-                    }
-                };
+/// Define an object-safe trait dispatching symmetric traits over a closed set
+/// of operand types through the visitor pattern, for dispatch over trait
+/// objects rather than an enum.
+///
+/// The trait is followed by the operand types, and its body names the visitor
+/// trait then lists the methods, each with the symmetric trait it calls. `_`
+/// stands for the other operand when the trait takes several generic
+/// arguments. The methods take `&self` and the other operand as a trait
+/// object. The visitor trait has one `visit_` method per operand type, named
+/// after the snake case of the type, and the implementations between every
+/// pair of operand types are required.
+///
+/// ```
+/// use symm_impl::{symmetric, symmetric_visitor};
+///
+/// trait Distance<Other> {
+///     fn distance(&self, other: &Other) -> f64;
+/// }
+/// # struct Point2D(f64);
+/// # struct Disk(f64, f64);
+/// # impl Distance<Point2D> for Point2D {
+/// #     fn distance(&self, other: &Point2D) -> f64 { (self.0 - other.0).abs() }
+/// # }
+/// # impl Distance<Disk> for Disk {
+/// #     fn distance(&self, other: &Disk) -> f64 { (self.0 - other.0).abs() - self.1 - other.1 }
+/// # }
+/// # #[symmetric]
+/// # impl Distance<Disk> for Point2D {
+/// #     fn distance(&self, other: &Disk) -> f64 { (self.0 - other.0).abs() - other.1 }
+/// # }
+///
+/// symmetric_visitor! {
+///     trait Shape for Point2D, Disk {
+///         visitor ShapeVisitor;
+///
+///         fn distance(&self, other: &dyn Shape) -> f64 = Distance;
+///     }
+/// }
+///
+/// let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Point2D(5.0)), Box::new(Disk(1.0, 2.0))];
+/// assert_eq!(shapes[0].distance(&*shapes[1]), 2.0);
+/// assert_eq!(shapes[1].distance(&*shapes[0]), 2.0);
+/// ```
+#[proc_macro]
+pub fn symmetric_visitor(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_visitor(input.into()))
+}
 
-                let other_arg = match other_arg {
-                    FnArg::Typed(typed_arg) => typed_arg,
-                    FnArg::Receiver(_) => unreachable!(),
-                };
+/// Generate [criterion](https://crates.io/crates/criterion) benchmarks of a
+/// symmetric operation with the operands in both orders.
+///
+/// Each entry `fn name = Trait::method(lhs, rhs);` generates a function `name`
+/// taking a `&mut criterion::Criterion`, to be registered with
+/// `criterion_group!`. It benchmarks `Trait::method(lhs, rhs)` as `forward` and
+/// `Trait::method(rhs, lhs)` as `reversed` in a group named after the method,
+/// such that a mirror slower than the original implementation stands out. The
+/// method must be named without the other operand, so that both calls resolve.
+///
+/// The sample operands are evaluated once. They are passed the way they are
+/// written, `&sample`, `&mut sample` or `sample`, and cloned for every
+/// iteration unless passed by shared reference.
+///
+/// ```ignore
+/// use criterion::{criterion_group, criterion_main};
+/// use symm_impl::symmetric_bench;
+///
+/// symmetric_bench! {
+///     fn bench_distance = Distance::distance(&Point2D::new(1.0, 2.0), &Disk::unit());
+/// }
+///
+/// criterion_group!(benches, bench_distance);
+/// criterion_main!(benches);
+/// ```
+#[proc_macro]
+pub fn symmetric_bench(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_bench(input.into()))
+}
 
-                let other_ident = if let Some((_, lifetime)) = &self_arg.reference {
-                    // both should be reference with the same lifetime
-                    match other_arg.ty.as_mut() {
-                        Type::Reference(reference) => {
-                            if self_arg.mutability != reference.mutability {
-                                return to_compile_error(
-                                    "mismatched mutability".to_string(),
-                                    other_arg.span(),
-                                );
-                            }
-                            if lifetime != &reference.lifetime {
-                                return to_compile_error(
-                                    "mismatched lifetime".to_string(),
-                                    other_arg.span(),
-                                );
-                            }
-                            // replace the underlying type for other_arg
-                            reference.elem = Box::new(self_type.clone());
+/// Check a hand-written reverse implementation against the implementation it
+/// reverses.
+///
+/// When the reverse of an implementation is written by hand, e.g. for speed,
+/// instead of being generated by [`macro@symmetric`], the two can drift apart.
+/// On an implementation `impl Trait<Other> for Self`, every method
+/// `fn method(&self, other: &Other) -> T` asserts in debug builds that its
+/// result equals the one of `<Other as Trait<Self>>::method(other, self)`. The
+/// assertion is compiled under `#[cfg(debug_assertions)]` only, so release
+/// builds run the hand-written code alone.
+///
+/// The results are compared with `PartialEq::eq` unless a function taking both
+/// by reference is given by `eq = path`, e.g. to compare floating-point
+/// results within a tolerance. Methods with other receivers, other arguments
+/// or async methods are rejected, as well as `?` in methods returning neither
+/// `Result` nor `Option`.
+///
+/// Both directions may be checked against each other: the direction called by
+/// a check does not check back, which would never end. The guard is an atomic
+/// flag of each method, so checked implementations only require `core`. The
+/// flag is global to the process: it is shared by all threads, and by all the
+/// instantiations of a generic implementation, so a method called while
+/// another call of it is being checked, on another thread or for other type
+/// arguments, skips its check. The flag is reset if the other direction
+/// panics.
+///
+/// ```
+/// use symm_impl::check_symmetric;
+///
+/// trait Distance<Other> {
+///     fn distance(&self, other: &Other) -> f64;
+/// }
+///
+/// struct Point2D(f64);
+/// struct Disk(f64, f64);
+///
+/// impl Distance<Disk> for Point2D {
+///     fn distance(&self, other: &Disk) -> f64 {
+///         (self.0 - other.0).abs() - other.1
+///     }
+/// }
+///
+/// fn close(lhs: &f64, rhs: &f64) -> bool {
+///     (lhs - rhs).abs() < 1e-9
+/// }
+///
+/// #[check_symmetric(eq = close)]
+/// impl Distance<Point2D> for Disk {
+///     fn distance(&self, other: &Point2D) -> f64 {
+///         (other.0 - self.0).abs() - self.1
+///     }
+/// }
+///
+/// assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+/// ```
+#[proc_macro_attribute]
+pub fn check_symmetric(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_check(attr.into(), item.into()))
+}
 
-                            let pat_ident = PatIdent {
-                                attrs: Vec::new(),
-                                by_ref: None,
-                                mutability: None,
-                                ident: Ident::new("other", other_arg.span()),
-                                subpat: None,
-                            };
-                            other_arg.pat = Box::new(Pat::Ident(pat_ident));
-                            match other_arg.pat.as_ref() {
-                                Pat::Ident(ident) => &ident.ident,
-                                _ => unreachable!(),
-                            }
-                        }
-                        _ => {
-                            return to_compile_error(
-                                "expected a reference".to_string(),
-                                other_arg.span(),
-                            );
-                        }
-                    }
-                } else {
-                    // replace other_arg by plain pattern
-                    let pat_ident = PatIdent {
-                        attrs: Vec::new(),
-                        by_ref: None,
-                        mutability: None,
-                        ident: Ident::new("other", other_arg.span()),
-                        subpat: None,
-                    };
-                    other_arg.pat = Box::new(Pat::Ident(pat_ident));
-                    // replace the type of other_arg
-                    other_arg.ty = Box::new(self_type.clone());
-                    match other_arg.pat.as_ref() {
-                        Pat::Ident(ident) => &ident.ident,
-                        _ => unreachable!(),
-                    }
-                };
+/// Implement the complementary predicate of a predicate implementation, for
+/// both orders of the operands.
+///
+/// `#[complement(Disjoint)]` on `impl Intersects<Disk> for Point2D` implements
+/// `Disjoint<Disk>` for `Point2D` and `Disjoint<Point2D>` for `Disk`, whose
+/// methods negate the ones of the implementation. The methods must return
+/// `bool` and have the shape required by [`macro@symmetric`]. The
+/// complementary methods have the same names unless renamed, e.g.
+/// `#[complement(Disjoint, intersects = disjoint)]`. The complementary trait
+/// takes the generic arguments of the implemented one unless they are given.
+///
+/// ```
+/// use symm_impl::{complement, symmetric};
+///
+/// trait Intersects<Other> {
+///     fn intersects(&self, other: &Other) -> bool;
+/// }
+///
+/// trait Disjoint<Other> {
+///     fn disjoint(&self, other: &Other) -> bool;
+/// }
+///
+/// struct Point2D(f64);
+/// struct Disk(f64, f64);
+///
+/// #[complement(Disjoint, intersects = disjoint)]
+/// #[symmetric]
+/// impl Intersects<Disk> for Point2D {
+///     fn intersects(&self, other: &Disk) -> bool {
+///         (self.0 - other.0).abs() <= other.1
+///     }
+/// }
+///
+/// assert!(Disk(1.0, 2.0).intersects(&Point2D(2.0)));
+/// assert!(Point2D(5.0).disjoint(&Disk(1.0, 2.0)));
+/// assert!(Disk(1.0, 2.0).disjoint(&Point2D(5.0)));
+/// ```
+#[proc_macro_attribute]
+pub fn complement(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_complement(attr.into(), item.into()))
+}
 
-                // replace method body
-                let method_name = &method.sig.ident;
-                let new_block: Block = parse_quote! {
-                    {
-                        <#self_type as #original_trait>::#method_name(#other_ident, self)
-                    }
-                };
-                method.block = new_block;
-                method.attrs.append(
-                    &mut Attribute::parse_outer
-                        .parse_str("#[allow(unused_mut)]")
-                        .unwrap(),
-                );
-                method
-                    .attrs
-                    .append(&mut Attribute::parse_outer.parse_str("#[inline]").unwrap());
-            }
-            ImplItem::Type(associated_type) => {
-                // replace associated type
-                let type_ident = &associated_type.ident;
-                let delegated_type: Type = parse_quote! {
-                    <#self_type as #original_trait>::#type_ident
-                };
-                associated_type.ty = delegated_type;
-            }
-            // keep as-is
-            _ => (),
-        }
-    }
+/// Generate helpers of a commutative and associative operation of a type with
+/// itself.
+///
+/// `#[commutative(fold = combine_all)]` on `impl Merge<Bounds> for Bounds`
+/// generates `fn combine_all<I: IntoIterator<Item = Bounds>>(iter: I) -> Option<Bounds>`,
+/// combining all the items with `merge`, or giving `None` if there is no item.
+/// The function is declared next to the implementation, with the visibility
+/// written before its name, e.g. `fold = pub combine_all`. The operation is
+/// the method of the implementation, or the one given by `method = name`. It
+/// must combine two operands by value or by reference into a new one.
+///
+/// Since the operation is commutative, the result does not depend on the order
+/// of the items, so unordered collections such as `HashSet` can be folded too.
+///
+/// With an identity element of the operation given by `identity = expr`, the
+/// folding function starts from it and returns `Bounds` rather than
+/// `Option<Bounds>`. The `default` flag additionally implements `Default` for
+/// the type with the identity element.
+///
+/// `proptest` or `proptest(strategy(Type = strategy))` generates
+/// [proptest](https://crates.io/crates/proptest) property tests of the laws of
+/// the operation: commutativity, associativity and, if given, identity. They
+/// are compiled with `cfg(test)` in a private module next to the
+/// implementation, like the ones of `#[symmetric(proptest)]`, with the same
+/// requirements, and the type must implement `PartialEq`.
+///
+/// ```
+/// use symm_impl::commutative;
+///
+/// trait Merge<Other> {
+///     fn merge(&self, other: &Other) -> Self;
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Bounds(i32, i32);
+///
+/// #[commutative(fold = combine_all)]
+/// impl Merge<Bounds> for Bounds {
+///     fn merge(&self, other: &Bounds) -> Bounds {
+///         Bounds(self.0.min(other.0), self.1.max(other.1))
+///     }
+/// }
+///
+/// let bounds = vec![Bounds(1, 2), Bounds(-1, 0), Bounds(3, 4)];
+/// assert_eq!(combine_all(bounds), Some(Bounds(-1, 4)));
+/// assert_eq!(combine_all(Vec::new()), None);
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Count(u32);
+///
+/// #[commutative(fold = total, identity = Count(0), default)]
+/// impl Merge<Count> for Count {
+///     fn merge(&self, other: &Count) -> Count {
+///         Count(self.0 + other.0)
+///     }
+/// }
+///
+/// assert_eq!(total(vec![Count(1), Count(2)]), Count(3));
+/// assert_eq!(total(Vec::new()), Count::default());
+/// ```
+#[proc_macro_attribute]
+pub fn commutative(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_commutative(attr.into(), item.into()))
+}
 
-    // perform swapping of the types on impl
-    mem::swap(other_type, self_type);
+/// Declare a relation of a type with itself transitive, i.e. `R(a, b)` and
+/// `R(b, c)` imply `R(a, c)`.
+///
+/// The relation is the method of the implementation, or the one given by
+/// `method = name`, and must return `bool`. `proptest` or
+/// `proptest(strategy(Type = strategy))` generates a
+/// [proptest](https://crates.io/crates/proptest) property test of the law,
+/// with the same requirements as the ones of `#[symmetric(proptest)]`.
+///
+/// `reflexive` declares the relation reflexive as well, i.e. `R(a, a)`, which
+/// the property tests then check. `markers` implements
+/// `symm_impl_runtime::TransitiveOp`, and `ReflexiveOp` for a reflexive
+/// relation, for the operation named by a trait object, e.g.
+/// `dyn Reaches<Level>`, as `#[symmetric(markers)]` does. `runtime = path`
+/// gives the path to `symm_impl_runtime` if it is not a dependency of the
+/// crate.
+///
+/// ```
+/// use symm_impl::transitive;
+///
+/// trait Reaches<Other> {
+///     fn reaches(&self, other: &Other) -> bool;
+/// }
+///
+/// struct Level(u8);
+///
+/// #[transitive]
+/// impl Reaches<Level> for Level {
+///     fn reaches(&self, other: &Level) -> bool {
+///         self.0 <= other.0
+///     }
+/// }
+///
+/// assert!(Level(1).reaches(&Level(2)));
+/// ```
+#[proc_macro_attribute]
+pub fn transitive(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_transitive(attr.into(), item.into()))
+}
 
-    quote! {
-        #ast
-    }
+/// Define the converse of a trait, implemented for every type whose other
+/// operand implements the trait.
+///
+/// `#[with_reverse(Within)]` on `trait Contains<Other>` defines
+/// `trait Within<Other>`, with the visibility and generics of the trait, and
+/// implements `Within<A>` for `B` whenever `A: Contains<B>`, so `b.within(&a)`
+/// is `a.contains(&b)`. The other operand is the first type parameter of the
+/// trait. The converse methods have the same names unless renamed, e.g.
+/// `#[with_reverse(Within, contains = within)]`, and take the receiver the
+/// way the methods take the other operand, and conversely. Associated types
+/// and constants are those of the implementation of the trait.
+///
+/// Only the converse is implemented in terms of the trait: implementing the
+/// trait in terms of the converse too would conflict with the implementations
+/// of the trait. Implementing the trait for `A` and `B` thus gives both
+/// `a.contains(&b)` and `b.within(&a)`.
+///
+/// ```
+/// use symm_impl::with_reverse;
+///
+/// #[with_reverse(Within, contains = within)]
+/// trait Contains<Other> {
+///     fn contains(&self, other: &Other) -> bool;
+/// }
+///
+/// struct Point2D(f64);
+/// struct Disk(f64, f64);
+///
+/// impl Contains<Point2D> for Disk {
+///     fn contains(&self, other: &Point2D) -> bool {
+///         (self.0 - other.0).abs() <= self.1
+///     }
+/// }
+///
+/// assert!(Point2D(2.0).within(&Disk(1.0, 2.0)));
+/// assert!(!Point2D(5.0).within(&Disk(1.0, 2.0)));
+/// ```
+#[proc_macro_attribute]
+pub fn with_reverse(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_reverse(attr.into(), item.into()))
 }
 
-fn to_compile_error(message: String, span: Span) -> TokenStream {
-    TokenStream::from_iter(vec![
-        TokenTree::Ident(Ident::new("compile_error", span)),
-        TokenTree::Punct({
-            let mut punct = Punct::new('!', Spacing::Alone);
-            punct.set_span(span);
-            punct
-        }),
-        TokenTree::Group({
-            let mut group = Group::new(Delimiter::Brace, {
-                TokenStream::from_iter(vec![TokenTree::Literal({
-                    let mut string = Literal::string(&message);
-                    string.set_span(span);
-                    string
-                })])
-            });
-            group.set_span(span);
-            group
-        }),
-    ])
+/// Set default options of the `#[symmetric]` implementations of an inline
+/// module.
+///
+/// Every `#[symmetric]` in the module, including in nested modules, takes the
+/// options of `#[symmetric_options(...)]` as if written first in its own
+/// options, which override them, or add to them for the options given
+/// several times such as `skip_attrs`. A nested module with its own
+/// `#[symmetric_options]` overrides the ones of the enclosing module the same
+/// way. The options sit between the defaults of the crate, see
+/// [Crate-wide defaults](crate#crate-wide-defaults), and the ones of the
+/// attributes.
+///
+/// Procedural macros cannot be inner attributes, nor apply to modules in
+/// their own file, so the attribute goes on an inline module.
+///
+/// ```
+/// use symm_impl::symmetric_options;
+///
+/// #[symmetric_options(by_ref)]
+/// mod shapes {
+///     use symm_impl::symmetric;
+///
+///     pub trait Distance<Other> {
+///         fn distance(&self, other: &Other) -> f64;
+///     }
+///
+///     pub struct Point2D(pub f64);
+///     pub struct Disk(pub f64, pub f64);
+///
+///     #[symmetric]
+///     impl Distance<Disk> for Point2D {
+///         fn distance(&self, other: &Disk) -> f64 {
+///             (self.0 - other.0).abs() - other.1
+///         }
+///     }
+/// }
+///
+/// use shapes::{Disk, Distance, Point2D};
+///
+/// assert_eq!(Disk(1.0, 2.0).distance(&&Point2D(5.0)), 2.0);
+/// ```
+#[proc_macro_attribute]
+pub fn symmetric_options(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(symm_impl_core::expand_symmetric_options(
+        attr.into(),
+        item.into(),
+    ))
 }
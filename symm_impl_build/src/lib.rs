@@ -0,0 +1,215 @@
+//! Generation of the mirrors of `#[symmetric]` from a build script.
+//!
+//! Instead of annotating implementations with `#[symmetric]`, the pairs to
+//! mirror are listed in a manifest, and the mirrors are written into a file
+//! which the crate includes. The generated code can then be reviewed like any
+//! other file, e.g. where proc-macro expansion is not acceptable.
+//!
+//! # Manifest
+//! Each `[[impl]]` entry describes a hand-written implementation, by its header
+//! and the signatures of its items written as in the trait definition.
+//! `options` takes the same options as `#[symmetric(...)]`.
+//! ```toml
+//! [[impl]]
+//! header = "impl Distance<Disk> for Point2D"
+//! items = ["fn distance(&self, other: &Disk) -> f64;"]
+//!
+//! [[impl]]
+//! header = "impl<'a> Overlap<Segment<'a>> for Point2D"
+//! items = [
+//!     "type Output = bool;",
+//!     "fn overlap(self, other: Segment<'a>) -> bool;",
+//! ]
+//! options = "call = \"method\", doc_hidden"
+//! ```
+//!
+//! # Usage
+//! In the `main` function of `build.rs`:
+//! ```no_run
+//! let out_dir = std::env::var_os("OUT_DIR").unwrap();
+//! symm_impl_build::generate("symmetry.toml", out_dir).unwrap();
+//! ```
+//! The mirrors are written into `symmetry.rs`, named after the manifest, and
+//! included next to the hand-written implementations:
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/symmetry.rs"));
+//! ```
+//! The generated file is not formatted, run `rustfmt` over it for easier
+//! reading.
+
+use std::{
+    error, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Deserialize;
+use syn::{parse_quote, Block, ImplItem, ImplItemMethod, ItemImpl, TraitItem, Visibility};
+
+/// Error generating the mirrors.
+#[derive(Debug)]
+pub enum Error {
+    /// The manifest could not be read or the mirrors could not be written.
+    Io(io::Error),
+    /// The manifest is not a valid TOML document of `[[impl]]` entries.
+    Manifest(toml::de::Error),
+    /// An entry of the manifest cannot be mirrored.
+    Entry {
+        /// Header of the entry, e.g. `impl Distance<Disk> for Point2D`.
+        header: String,
+        /// Cause of the failure.
+        error: syn::Error,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{}", error),
+            Error::Manifest(error) => write!(f, "invalid manifest: {}", error),
+            Error::Entry { header, error } => write!(f, "cannot mirror `{}`: {}", header, error),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Manifest(error) => Some(error),
+            Error::Entry { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    #[serde(default, rename = "impl")]
+    impls: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    header: String,
+    #[serde(default)]
+    items: Vec<String>,
+    #[serde(default)]
+    options: String,
+}
+
+impl Entry {
+    /// Generate the mirror of the implementation described by this entry.
+    fn generate(&self) -> syn::Result<TokenStream> {
+        let header: TokenStream = syn::parse_str(&self.header)?;
+        let items = self
+            .items
+            .iter()
+            .map(|item| stub(syn::parse_str(item)?))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let ast: ItemImpl = syn::parse2(quote! {
+            #header {
+                #(#items)*
+            }
+        })?;
+        let options: TokenStream = syn::parse_str(&self.options)?;
+        symm_impl_core::generate(options, &ast)
+    }
+}
+
+/// Turn the signature of a trait item into an implementation item with the
+/// same signature. Only the signature matters to the mirror, so method bodies
+/// are left empty.
+fn stub(item: TraitItem) -> syn::Result<ImplItem> {
+    match item {
+        TraitItem::Method(method) => Ok(ImplItem::Method(ImplItemMethod {
+            attrs: method.attrs,
+            vis: Visibility::Inherited,
+            defaultness: None,
+            sig: method.sig,
+            block: Block {
+                brace_token: Default::default(),
+                stmts: Vec::new(),
+            },
+        })),
+        TraitItem::Type(associated_type) => {
+            let attrs = &associated_type.attrs;
+            let ident = &associated_type.ident;
+            let generics = &associated_type.generics;
+            let where_clause = &generics.where_clause;
+            // the mirror refers to the type of the original implementation,
+            // so the value is irrelevant
+            Ok(parse_quote! {
+                #(#attrs)*
+                type #ident #generics = () #where_clause;
+            })
+        }
+        TraitItem::Const(constant) => match &constant.default {
+            Some((_, value)) => {
+                let attrs = &constant.attrs;
+                let ident = &constant.ident;
+                let ty = &constant.ty;
+                Ok(parse_quote! {
+                    #(#attrs)*
+                    const #ident: #ty = #value;
+                })
+            }
+            None => Err(syn::Error::new_spanned(
+                &constant.ident,
+                format!("associated constant `{}` needs a value", constant.ident),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            item,
+            "expected an associated type, constant or method",
+        )),
+    }
+}
+
+/// Generate the mirrors described by a manifest, returning the code to
+/// include.
+pub fn generate_str(manifest: &str) -> Result<String, Error> {
+    let manifest: Manifest = toml::from_str(manifest).map_err(Error::Manifest)?;
+    let mut generated = String::from("// Generated by symm_impl_build, do not edit.\n");
+    for entry in &manifest.impls {
+        let mirror = entry.generate().map_err(|error| Error::Entry {
+            header: entry.header.clone(),
+            error,
+        })?;
+        generated.push_str(&format!(
+            "\n// Mirror of `{}`\n{}\n",
+            entry.header,
+            symm_impl_core::render(&mirror)
+        ));
+    }
+    Ok(generated)
+}
+
+/// Generate the mirrors described by the manifest at `manifest` into
+/// `out_dir`, returning the path of the generated file.
+///
+/// The file is named after the manifest, e.g. `symmetry.rs` for
+/// `symmetry.toml`. Cargo is told to rerun the build script when the manifest
+/// changes.
+pub fn generate(manifest: impl AsRef<Path>, out_dir: impl AsRef<Path>) -> Result<PathBuf, Error> {
+    let manifest = manifest.as_ref();
+    println!("cargo:rerun-if-changed={}", manifest.display());
+
+    let generated = generate_str(&fs::read_to_string(manifest)?)?;
+    let file_name = match manifest.file_stem() {
+        Some(stem) => PathBuf::from(stem).with_extension("rs"),
+        None => PathBuf::from("symmetry.rs"),
+    };
+    let path = out_dir.as_ref().join(file_name);
+    fs::create_dir_all(out_dir)?;
+    fs::write(&path, generated)?;
+    Ok(path)
+}
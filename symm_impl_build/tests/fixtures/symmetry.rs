@@ -0,0 +1,7 @@
+// Generated by symm_impl_build, do not edit.
+
+// Mirror of `impl Distance<Disk> for Point2D`
+const _: () = { #[doc = "Symmetric mirror of [`impl Distance<Disk> for Point2D`](trait@Distance)."] #[automatically_derived] impl Distance<Point2D> for Disk { #[track_caller] #[inline] #[allow(clippy::mut_mut, clippy::needless_borrow, clippy::needless_lifetimes, clippy::use_self)] fn distance(&self, other: &Point2D) -> f64 { <Point2D as Distance<Disk>>::distance(other, self) } } };
+
+// Mirror of `impl Merge<Disk> for Point2D`
+const _: () = { #[doc = "Symmetric mirror of [`impl Merge<Disk> for Point2D`](trait@Merge)."] #[automatically_derived] impl Merge<Point2D> for Disk { type Output = <Point2D as Merge<Disk>>::Output; #[track_caller] #[allow(clippy::mut_mut, clippy::needless_borrow, clippy::needless_lifetimes, clippy::use_self)] fn merge(self, other: Point2D) -> Disk { other.merge(self) } } };
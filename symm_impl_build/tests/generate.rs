@@ -0,0 +1,94 @@
+use std::{fs, path::Path};
+
+use symm_impl_build::{generate, generate_str, Error};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Merge<Other> {
+    type Output;
+    fn merge(self, other: Other) -> Self::Output;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let p_diff = self.distance(&other.center);
+        if p_diff.le(&other.radius) {
+            0.0_f64
+        } else {
+            p_diff - other.radius
+        }
+    }
+}
+
+impl Merge<Disk> for Point2D {
+    type Output = Disk;
+    fn merge(self, other: Disk) -> Disk {
+        Disk {
+            center: other.center,
+            radius: other.radius.max(self.distance(&other.center)),
+        }
+    }
+}
+
+include!("fixtures/symmetry.rs");
+
+#[test]
+fn test_generate() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("generate");
+    let generated = generate("tests/fixtures/symmetry.toml", &out_dir).unwrap();
+    assert_eq!(generated, out_dir.join("symmetry.rs"));
+    assert_eq!(
+        fs::read_to_string(generated).unwrap(),
+        fs::read_to_string("tests/fixtures/symmetry.rs").unwrap()
+    );
+}
+
+#[test]
+fn test_generated_mirrors() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), c.distance(&p));
+    assert_eq!(p.merge(c), c.merge(p));
+}
+
+#[test]
+fn test_invalid_entry() {
+    let error = generate_str(
+        r#"
+        [[impl]]
+        header = "impl Limits<Disk> for Point2D"
+        items = ["const MAX: u32;"]
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::Entry { .. }));
+    assert_eq!(
+        error.to_string(),
+        "cannot mirror `impl Limits<Disk> for Point2D`: associated constant `MAX` needs a value"
+    );
+}
@@ -0,0 +1,172 @@
+//! Preset mirroring the approximate equality traits of the `approx` crate.
+//!
+//! The methods of these traits take tolerances in addition to the two operands,
+//! and come with receiver-less functions returning the default tolerances, so
+//! they do not fit the shape `#[symmetric]` expects. Since their signatures are
+//! known, the mirrors are generated from the two types alone.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    Error, Generics, Ident, Result, Token, Type, WhereClause,
+};
+
+/// Traits of the family, in the order they are generated.
+const TRAITS: &[&str] = &["AbsDiffEq", "RelativeEq", "UlpsEq"];
+
+/// Input of `symmetric_approx!`, e.g.
+/// `impl<T: Float> Point<T>, Disk<T>; AbsDiffEq, RelativeEq`.
+struct Input {
+    /// Generic parameters of the implementations, with the where-clause.
+    generics: Generics,
+    /// Type implementing the traits by hand.
+    self_type: Type,
+    /// Type for the symmetry of the hand-written implementations.
+    other_type: Type,
+    /// Traits to mirror.
+    traits: Vec<Ident>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut generics = Generics::default();
+        if input.peek(Token![impl]) {
+            input.parse::<Token![impl]>()?;
+            generics = input.parse()?;
+        }
+        let self_type = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let other_type = input.parse()?;
+        if input.peek(Token![where]) {
+            generics.where_clause = Some(input.parse()?);
+        }
+
+        let traits = if input.is_empty() {
+            TRAITS
+                .iter()
+                .map(|name| Ident::new(name, Span::call_site()))
+                .collect()
+        } else {
+            input.parse::<Token![;]>()?;
+            let traits = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
+            for name in &traits {
+                if !TRAITS.iter().any(|known| name == known) {
+                    return Err(Error::new(
+                        name.span(),
+                        "expected one of `AbsDiffEq`, `RelativeEq` or `UlpsEq`",
+                    ));
+                }
+            }
+            traits.into_iter().collect()
+        };
+
+        Ok(Input {
+            generics,
+            self_type,
+            other_type,
+            traits,
+        })
+    }
+}
+
+/// Expand `symmetric_approx!`, generating the mirror of each listed trait of
+/// the family.
+pub fn expand(input: TokenStream) -> TokenStream {
+    match syn::parse2::<Input>(input) {
+        Ok(input) => generate(&input),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+fn generate(input: &Input) -> TokenStream {
+    let self_type = &input.self_type;
+    let other_type = &input.other_type;
+    let abs_diff_eq = quote! { <#self_type as ::approx::AbsDiffEq<#other_type>> };
+    let epsilon = quote! { #abs_diff_eq::Epsilon };
+
+    let impls = input.traits.iter().map(|name| {
+        let original = quote! { <#self_type as ::approx::#name<#other_type>> };
+        let items = match name.to_string().as_str() {
+            "AbsDiffEq" => quote! {
+                type Epsilon = #epsilon;
+                #[inline]
+                fn default_epsilon() -> #epsilon {
+                    #original::default_epsilon()
+                }
+                #[inline]
+                fn abs_diff_eq(&self, other: &#self_type, epsilon: #epsilon) -> bool {
+                    #original::abs_diff_eq(other, self, epsilon)
+                }
+                #[inline]
+                fn abs_diff_ne(&self, other: &#self_type, epsilon: #epsilon) -> bool {
+                    #original::abs_diff_ne(other, self, epsilon)
+                }
+            },
+            "RelativeEq" => quote! {
+                #[inline]
+                fn default_max_relative() -> #epsilon {
+                    #original::default_max_relative()
+                }
+                #[inline]
+                fn relative_eq(
+                    &self,
+                    other: &#self_type,
+                    epsilon: #epsilon,
+                    max_relative: #epsilon,
+                ) -> bool {
+                    #original::relative_eq(other, self, epsilon, max_relative)
+                }
+                #[inline]
+                fn relative_ne(
+                    &self,
+                    other: &#self_type,
+                    epsilon: #epsilon,
+                    max_relative: #epsilon,
+                ) -> bool {
+                    #original::relative_ne(other, self, epsilon, max_relative)
+                }
+            },
+            "UlpsEq" => quote! {
+                #[inline]
+                fn default_max_ulps() -> u32 {
+                    #original::default_max_ulps()
+                }
+                #[inline]
+                fn ulps_eq(&self, other: &#self_type, epsilon: #epsilon, max_ulps: u32) -> bool {
+                    #original::ulps_eq(other, self, epsilon, max_ulps)
+                }
+                #[inline]
+                fn ulps_ne(&self, other: &#self_type, epsilon: #epsilon, max_ulps: u32) -> bool {
+                    #original::ulps_ne(other, self, epsilon, max_ulps)
+                }
+            },
+            _ => unreachable!(),
+        };
+
+        let mut generics = input.generics.clone();
+        if !generics.params.is_empty() {
+            // the hand-written implementation may not exist for every
+            // instantiation
+            let where_clause: &mut WhereClause = generics.make_where_clause();
+            where_clause
+                .predicates
+                .push(parse_quote!(#self_type: ::approx::#name<#other_type>));
+        }
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::approx::#name<#self_type> for #other_type #where_clause {
+                #items
+            }
+        }
+    });
+
+    quote! {
+        const _: () = {
+            #(#impls)*
+        };
+    }
+}
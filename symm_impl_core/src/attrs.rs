@@ -0,0 +1,215 @@
+//! Selection of the attributes carried over from the original implementation
+//! to the mirrored one.
+
+use syn::{parse_quote, Attribute, ImplItemMethod, Meta, NestedMeta, Path, Signature};
+
+use crate::{
+    options::{Inline, MirrorOptions},
+    render::render,
+};
+
+/// Whether an attribute affects conditional compilation.
+///
+/// Such attributes must be present on both the original item and its mirror,
+/// otherwise the mirror may refer to an item which is compiled out.
+pub(crate) fn is_cfg_like(attr: &Attribute) -> bool {
+    attr.path.is_ident("cfg") || attr.path.is_ident("cfg_attr")
+}
+
+/// Whether an attribute is part of the API contract of an item.
+///
+/// The mirror should present the same contract as the original item, so
+/// callers see the same documentation and diagnostics in both directions.
+fn is_api_contract(attr: &Attribute) -> bool {
+    ["doc", "must_use", "track_caller", "deprecated"]
+        .iter()
+        .any(|name| attr.path.is_ident(name))
+}
+
+/// Whether an attribute affects the code generated for an item.
+///
+/// The mirror of a method compiled with extra target features must be
+/// compiled with them too, for the original method to be inlined into it.
+fn is_codegen(attr: &Attribute) -> bool {
+    attr.path.is_ident("target_feature")
+}
+
+/// Whether an attribute is known not to be an attribute macro, i.e. it is a
+/// built-in attribute or a tool attribute.
+pub(crate) fn is_inert(attr: &Attribute) -> bool {
+    // `cfg_attr` is missing since it may expand to an attribute macro
+    const BUILTIN: &[&str] = &[
+        "allow",
+        "automatically_derived",
+        "cfg",
+        "cold",
+        "deny",
+        "deprecated",
+        "doc",
+        "expect",
+        "forbid",
+        "inline",
+        "must_use",
+        "target_feature",
+        "track_caller",
+        "warn",
+    ];
+    const TOOLS: &[&str] = &["clippy", "diagnostic", "rustfmt"];
+    if attr.path.segments.len() > 1 {
+        TOOLS.iter().any(|tool| attr.path.segments[0].ident == tool)
+    } else {
+        BUILTIN.iter().any(|name| attr.path.is_ident(name))
+    }
+}
+
+/// Whether an attribute is `#[async_trait]`, possibly qualified.
+///
+/// It rewrites the signatures of the methods, so the mirror only matches the
+/// trait if it goes through the same rewriting.
+fn is_async_trait(attr: &Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "async_trait")
+}
+
+/// Compare two paths segment by segment, ignoring spans.
+fn same_path(lhs: &Path, rhs: &Path) -> bool {
+    lhs.leading_colon.is_some() == rhs.leading_colon.is_some()
+        && lhs.segments.len() == rhs.segments.len()
+        && lhs
+            .segments
+            .iter()
+            .zip(rhs.segments.iter())
+            .all(|(lhs, rhs)| lhs.ident == rhs.ident)
+}
+
+/// Whether an attribute with the given name is among the attributes.
+fn has(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path.is_ident(name))
+}
+
+/// Compare two attributes token by token, ignoring spans.
+fn same_attr(lhs: &Attribute, rhs: &Attribute) -> bool {
+    render(lhs) == render(rhs)
+}
+
+/// The lints allowed by `#[allow(...)]` among the attributes, rendered, e.g.
+/// `clippy::all`.
+fn allowed_lints(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("allow"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(lint)) => Some(render(&lint)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collect the attributes of an original item that should also be put on the
+/// corresponding mirrored item.
+pub(crate) fn forwarded(attrs: &[Attribute], options: &MirrorOptions) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            let listed = |paths: &[Path]| paths.iter().any(|path| same_path(&attr.path, path));
+            is_cfg_like(attr)
+                || ((is_api_contract(attr)
+                    || is_codegen(attr)
+                    || is_async_trait(attr)
+                    || listed(&options.copy_attrs))
+                    && !listed(&options.skip_attrs))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether `#[track_caller]` should be added to a mirrored method, such that a
+/// panic in the original method reports the call site of the mirror rather
+/// than the generated code.
+///
+/// The attribute is not allowed on `async fn` nor on non-Rust ABIs, and is not
+/// added when already forwarded or when listed in `skip_attrs`.
+fn wants_track_caller(sig: &Signature, forwarded: &[Attribute], options: &MirrorOptions) -> bool {
+    options.track_caller
+        && sig.asyncness.is_none()
+        && sig.abi.is_none()
+        && !forwarded
+            .iter()
+            .any(|attr| attr.path.is_ident("track_caller"))
+        && !options
+            .skip_attrs
+            .iter()
+            .any(|path| path.is_ident("track_caller"))
+}
+
+/// Attributes of a generated implementation, starting with the `summary` doc
+/// line, followed by those forwarded from the original implementation and the
+/// ones requested by the options.
+pub(crate) fn generated_impl(
+    summary: &str,
+    original: &[Attribute],
+    options: &MirrorOptions,
+) -> Vec<Attribute> {
+    let mut attrs = vec![parse_quote!(#[doc = #summary])];
+    let forwarded_attrs = forwarded(original, options);
+    if forwarded_attrs.iter().any(|attr| attr.path.is_ident("doc")) {
+        // keep the summary in its own paragraph
+        attrs.push(parse_quote!(#[doc = ""]));
+    }
+    attrs.extend(forwarded_attrs);
+    if let Some(predicate) = &options.cfg {
+        attrs.push(parse_quote!(#[cfg(#predicate)]));
+        // have docs.rs show when the mirror is available
+        attrs.push(parse_quote!(#[cfg_attr(docsrs, doc(cfg(#predicate)))]));
+    }
+    if options.automatically_derived && !has(&attrs, "automatically_derived") {
+        attrs.push(parse_quote!(#[automatically_derived]));
+    }
+    let hidden: Attribute = parse_quote!(#[doc(hidden)]);
+    if options.doc_hidden && !attrs.iter().any(|attr| same_attr(attr, &hidden)) {
+        attrs.push(hidden);
+    }
+    attrs
+}
+
+/// Attributes of a generated method delegating to `method`, those forwarded
+/// from it followed by the ones requested by the options.
+pub(crate) fn generated_method(method: &ImplItemMethod, options: &MirrorOptions) -> Vec<Attribute> {
+    let mut attrs = forwarded(&method.attrs, options);
+    if wants_track_caller(&method.sig, &attrs, options) {
+        attrs.push(parse_quote!(#[track_caller]));
+    }
+    if !has(&attrs, "inline") {
+        // an inlining hint forwarded from the original method takes precedence
+        match options.inline {
+            // not allowed along with extra target features
+            Inline::Always if has(&attrs, "target_feature") => attrs.push(parse_quote!(#[inline])),
+            Inline::Always => attrs.push(parse_quote!(#[inline(always)])),
+            Inline::Never => attrs.push(parse_quote!(#[inline(never)])),
+            Inline::Hint => attrs.push(parse_quote!(#[inline])),
+            Inline::None => (),
+        }
+    }
+    let allowed = allowed_lints(&attrs);
+    let lints: Vec<_> = options
+        .clippy_allow
+        .iter()
+        .filter(|lint| !allowed.contains(&format!("clippy::{}", lint)))
+        .collect();
+    if !lints.is_empty() {
+        attrs.push(parse_quote!(#[allow(#(clippy::#lints),*)]));
+    }
+    if options.coverage_off {
+        // `#[coverage]` is unstable, `cfg(coverage_nightly)` is set by
+        // cargo-llvm-cov on nightly toolchains
+        attrs.push(parse_quote!(#[cfg_attr(coverage_nightly, coverage(off))]));
+    }
+    attrs
+}
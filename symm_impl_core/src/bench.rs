@@ -0,0 +1,151 @@
+//! Criterion benchmarks of a symmetric operation in both argument orders,
+//! generated by `symmetric_bench!`.
+//!
+//! Measuring both orders side by side catches a mirror slower than the
+//! original implementation, e.g. when the delegation is not inlined.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Error, Expr, ExprCall, ExprReference, Ident, Result, Token, Visibility,
+};
+
+/// Benchmark function, e.g.
+/// `fn bench_distance = Distance::distance(&Point2D::new(0.0, 0.0), &Disk::unit());`.
+struct Bench {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    call: ExprCall,
+}
+
+impl Parse for Bench {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![fn]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let call = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Bench {
+            attrs,
+            vis,
+            ident,
+            call,
+        })
+    }
+}
+
+/// Input of `symmetric_bench!`, any number of benchmark functions.
+struct BenchInput {
+    benches: Vec<Bench>,
+}
+
+impl Parse for BenchInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut benches = Vec::new();
+        while !input.is_empty() {
+            benches.push(input.parse()?);
+        }
+        Ok(BenchInput { benches })
+    }
+}
+
+/// How the sample operands are passed to the operation.
+#[derive(PartialEq)]
+enum Passing {
+    Shared,
+    Exclusive,
+    Value,
+}
+
+/// Split an operand into the sample value and how it is passed.
+fn sample(operand: &Expr) -> (&Expr, Passing) {
+    match operand {
+        Expr::Reference(ExprReference {
+            mutability: Some(_),
+            expr,
+            ..
+        }) => (expr, Passing::Exclusive),
+        Expr::Reference(ExprReference { expr, .. }) => (expr, Passing::Shared),
+        _ => (operand, Passing::Value),
+    }
+}
+
+/// Expand `symmetric_bench!`, generating a function benchmarking the operation
+/// with the sample operands in both orders for every entry.
+pub fn expand(input: TokenStream) -> TokenStream {
+    match syn::parse2::<BenchInput>(input).and_then(|input| {
+        input
+            .benches
+            .iter()
+            .map(generate)
+            .collect::<Result<TokenStream>>()
+    }) {
+        Ok(generated) => generated,
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// Generate the benchmark function of one entry.
+fn generate(bench: &Bench) -> Result<TokenStream> {
+    let Bench {
+        attrs,
+        vis,
+        ident,
+        call,
+    } = bench;
+    if call.args.len() != 2 {
+        return Err(Error::new_spanned(
+            &call.args,
+            "expected the two operands of the operation",
+        ));
+    }
+    let (lhs, lhs_passing) = sample(&call.args[0]);
+    let (rhs, rhs_passing) = sample(&call.args[1]);
+    if lhs_passing != rhs_passing {
+        return Err(Error::new_spanned(
+            &call.args,
+            "expected both operands to be passed the same way",
+        ));
+    }
+    let func = &call.func;
+    let routine = |first: TokenStream, second: TokenStream| match lhs_passing {
+        Passing::Shared => quote! {
+            |bencher| bencher.iter(|| {
+                #func(::criterion::black_box(&#first), ::criterion::black_box(&#second))
+            })
+        },
+        // every iteration gets fresh operands
+        Passing::Exclusive => quote! {
+            |bencher| bencher.iter_batched(
+                || (::core::clone::Clone::clone(&#first), ::core::clone::Clone::clone(&#second)),
+                |(mut first, mut second)| #func(&mut first, &mut second),
+                ::criterion::BatchSize::SmallInput,
+            )
+        },
+        Passing::Value => quote! {
+            |bencher| bencher.iter_batched(
+                || (::core::clone::Clone::clone(&#first), ::core::clone::Clone::clone(&#second)),
+                |(first, second)| #func(first, second),
+                ::criterion::BatchSize::SmallInput,
+            )
+        },
+    };
+    let forward = routine(quote!(lhs), quote!(rhs));
+    let reversed = routine(quote!(rhs), quote!(lhs));
+    let group = crate::render(func.as_ref());
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #ident(criterion: &mut ::criterion::Criterion) {
+            let lhs = #lhs;
+            let rhs = #rhs;
+            let mut group = criterion.benchmark_group(#group);
+            group.bench_function("forward", #forward);
+            group.bench_function("reversed", #reversed);
+            group.finish();
+        }
+    })
+}
@@ -0,0 +1,167 @@
+//! Counterparts of the implementations taking the type for the symmetry by
+//! reference, requested by `#[symmetric(by_ref)]`.
+
+use quote::quote;
+use syn::{
+    parse_quote, Block, Expr, FnArg, GenericArgument, ImplItem, ImplItemMethod, ItemImpl, Lifetime,
+    PathArguments, Type,
+};
+
+use crate::{attrs, options::MirrorOptions, render};
+
+/// Generate the implementation of the trait for a reference to the type for
+/// the symmetry, delegating to `item` through a dereference.
+///
+/// `item` is either the original implementation `ast` or its mirror, which were
+/// both checked to have the symmetric shape.
+pub(crate) fn borrowed(item: &ItemImpl, ast: &ItemImpl, options: &MirrorOptions) -> ItemImpl {
+    let original = std::ptr::eq(item, ast);
+    let lifetime: Lifetime = parse_quote!('__symm);
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = &item.self_ty;
+
+    let mut borrowed_trait = trait_path.clone();
+    let other_type = match &mut borrowed_trait.segments.last_mut().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => generics
+            .args
+            .iter_mut()
+            .find_map(|arg| match arg {
+                GenericArgument::Type(other_type) => Some(other_type),
+                _ => None,
+            })
+            .unwrap(),
+        _ => unreachable!(),
+    };
+    let owned_type = other_type.clone();
+    // a method mutating both operands needs a mutable reference, which the
+    // other methods reborrow
+    let mutable = item.items.iter().any(|impl_item| match impl_item {
+        ImplItem::Method(method) if !original || crate::is_mirrored(method, options) => matches!(
+            &method.sig.inputs[0],
+            FnArg::Receiver(receiver) if receiver.reference.is_some() && receiver.mutability.is_some()
+        ),
+        _ => false,
+    });
+    *other_type = if mutable {
+        parse_quote!(&#lifetime mut #owned_type)
+    } else {
+        parse_quote!(&#lifetime #owned_type)
+    };
+
+    let mut items = Vec::with_capacity(item.items.len());
+    for impl_item in &item.items {
+        match impl_item {
+            ImplItem::Method(method) if !original || crate::is_mirrored(method, options) => {
+                items.push(ImplItem::Method(borrowed_method(
+                    method,
+                    self_type,
+                    trait_path,
+                    &owned_type,
+                    &lifetime,
+                    mutable,
+                    options,
+                )));
+            }
+            ImplItem::Type(associated_type) => {
+                let mut associated_type = associated_type.clone();
+                let type_ident = &associated_type.ident;
+                associated_type.attrs = attrs::forwarded(&associated_type.attrs, options);
+                associated_type.ty = parse_quote!(<#self_type as #trait_path>::#type_ident);
+                items.push(ImplItem::Type(associated_type));
+            }
+            ImplItem::Const(constant) => {
+                let mut constant = constant.clone();
+                let const_ident = &constant.ident;
+                constant.attrs = attrs::forwarded(&constant.attrs, options);
+                constant.expr = parse_quote!(<#self_type as #trait_path>::#const_ident);
+                items.push(ImplItem::Const(constant));
+            }
+            _ => (),
+        }
+    }
+
+    let summary = format!(
+        "Borrowed counterpart of [`{}`]({}).",
+        render::render(&quote! { impl #trait_path for #self_type }),
+        render::doc_link(trait_path),
+    );
+    let attrs = attrs::generated_impl(&summary, &ast.attrs, options);
+    let mut generics = item.generics.clone();
+    generics.params.insert(0, parse_quote!(#lifetime));
+
+    ItemImpl {
+        attrs,
+        defaultness: None,
+        unsafety: item.unsafety,
+        impl_token: item.impl_token,
+        generics,
+        trait_: Some((None, borrowed_trait, item.trait_.as_ref().unwrap().2)),
+        self_ty: item.self_ty.clone(),
+        brace_token: item.brace_token,
+        items,
+    }
+}
+
+/// Generate the counterpart of a method taking the other operand by
+/// reference, which is a mutable reference if `mutable`.
+fn borrowed_method(
+    method: &ImplItemMethod,
+    self_type: &Type,
+    trait_path: &syn::Path,
+    owned_type: &Type,
+    lifetime: &Lifetime,
+    mutable: bool,
+    options: &MirrorOptions,
+) -> ImplItemMethod {
+    let mut sig = method.sig.clone();
+    let (by_reference, mutable_self) = match &sig.inputs[0] {
+        FnArg::Receiver(receiver) => (receiver.reference.is_some(), receiver.mutability.is_some()),
+        FnArg::Typed(_) => (false, false),
+    };
+    let borrowed_type: Type = if mutable {
+        parse_quote!(&#lifetime mut #owned_type)
+    } else {
+        parse_quote!(&#lifetime #owned_type)
+    };
+    if let FnArg::Receiver(receiver) = &mut sig.inputs[0] {
+        if receiver.reference.is_none() {
+            // `self` is moved without being mutated
+            receiver.mutability = None;
+        }
+    }
+    if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+        *other_arg.pat = parse_quote!(other);
+        match other_arg.ty.as_mut() {
+            Type::Reference(reference) if by_reference => *reference.elem = borrowed_type,
+            ty => *ty = borrowed_type,
+        }
+    }
+
+    let method_name = &method.sig.ident;
+    let other: Expr = match (by_reference, mutable) {
+        (true, true) if mutable_self => parse_quote!(&mut **other),
+        (true, true) => parse_quote!(&**other),
+        (true, false) => parse_quote!(*other),
+        // an owned operand can only be obtained from a reference by cloning
+        (false, true) => parse_quote!(::core::clone::Clone::clone(&*other)),
+        (false, false) => parse_quote!(::core::clone::Clone::clone(other)),
+    };
+    let call: Expr = parse_quote!(<#self_type as #trait_path>::#method_name(self, #other));
+    let call: Expr = match method.sig.asyncness {
+        Some(_) => parse_quote!(#call.await),
+        None => call,
+    };
+    let block: Block = parse_quote! {
+        {
+            #call
+        }
+    };
+
+    ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: None,
+        sig,
+        block,
+    }
+}
@@ -0,0 +1,219 @@
+//! Adjustment of the bounds of the mirrored implementation.
+
+use syn::{
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Error, GenericParam, Generics, ImplItem, ItemImpl, Path, Result, ReturnType, Type,
+    TypeParamBound, TypePath, WherePredicate,
+};
+
+use crate::{options::MirrorOptions, render::render};
+
+/// Apply the `bound` and `remove_bound` options to the generics of the mirror.
+pub(crate) fn adjust(generics: &mut Generics, options: &MirrorOptions) -> Result<()> {
+    for predicate in &options.remove_bound {
+        if !remove(generics, predicate) {
+            return Err(Error::new_spanned(
+                predicate,
+                format!(
+                    "bound `{}` not found on the implementation",
+                    render(predicate)
+                ),
+            ));
+        }
+    }
+    if let Some(where_clause) = &mut generics.where_clause {
+        if where_clause.predicates.is_empty() {
+            generics.where_clause = None;
+        }
+    }
+
+    if !options.bound.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(options.bound.iter().cloned());
+    }
+
+    Ok(())
+}
+
+/// Remove every bound in `bounds` from `existing`, returning whether any was
+/// removed.
+fn remove_bounds<P: Default>(
+    existing: &mut Punctuated<TypeParamBound, P>,
+    bounds: &Punctuated<TypeParamBound, syn::Token![+]>,
+) -> bool {
+    let bounds: Vec<_> = bounds.iter().map(render).collect();
+    let before = existing.len();
+    *existing = existing
+        .iter()
+        .filter(|bound| !bounds.contains(&render(*bound)))
+        .cloned()
+        .collect();
+    existing.len() != before
+}
+
+/// Remove a predicate from the generics, either from the where-clause or from
+/// the inline bounds of a type parameter, returning whether it was found.
+///
+/// A predicate `T: A + B` removes the bounds `A` and `B` on `T`, leaving other
+/// bounds on `T` in place.
+fn remove(generics: &mut Generics, predicate: &WherePredicate) -> bool {
+    let mut found = false;
+
+    if let WherePredicate::Type(removed) = predicate {
+        let bounded_ty = render(&removed.bounded_ty);
+        for param in generics.params.iter_mut() {
+            if let GenericParam::Type(param) = param {
+                if param.ident == bounded_ty {
+                    found |= remove_bounds(&mut param.bounds, &removed.bounds);
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                }
+            }
+        }
+        if let Some(where_clause) = &mut generics.where_clause {
+            for existing in where_clause.predicates.iter_mut() {
+                if let WherePredicate::Type(existing) = existing {
+                    if render(&existing.bounded_ty) == bounded_ty {
+                        found |= remove_bounds(&mut existing.bounds, &removed.bounds);
+                    }
+                }
+            }
+            where_clause.predicates = where_clause
+                .predicates
+                .iter()
+                .filter(|existing| {
+                    !matches!(existing, WherePredicate::Type(existing) if existing.bounds.is_empty())
+                })
+                .cloned()
+                .collect();
+        }
+    } else if let Some(where_clause) = &mut generics.where_clause {
+        let removed = render(predicate);
+        let before = where_clause.predicates.len();
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|existing| render(*existing) != removed)
+            .cloned()
+            .collect();
+        found = where_clause.predicates.len() != before;
+    }
+
+    found
+}
+
+/// Require the original implementation to hold for the mirror, rather than
+/// assuming it holds whenever the mirror does.
+pub(crate) fn require_original(generics: &mut Generics, self_type: &Type, original_trait: &Path) {
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#self_type: #original_trait));
+}
+
+/// Require the return type of every method to be negatable onto itself, as
+/// antisymmetric mirrors negate the result of the original methods.
+pub(crate) fn require_neg(
+    generics: &mut Generics,
+    ast: &ItemImpl,
+    self_type: &Type,
+    original_trait: &Path,
+) {
+    require_outputs(
+        generics,
+        ast,
+        self_type,
+        original_trait,
+        |output| parse_quote!(#output: ::core::ops::Neg<Output = #output>),
+    );
+}
+
+/// Require the return type of every method to implement `SwapOperands`, as
+/// mirrors with `swap_output` flip the result of the original methods.
+pub(crate) fn require_swap(
+    generics: &mut Generics,
+    ast: &ItemImpl,
+    self_type: &Type,
+    original_trait: &Path,
+    runtime_path: &Path,
+) {
+    require_outputs(
+        generics,
+        ast,
+        self_type,
+        original_trait,
+        |output| parse_quote!(#output: #runtime_path::SwapOperands),
+    );
+}
+
+/// Add the predicate on the return type of every method, once per type.
+fn require_outputs(
+    generics: &mut Generics,
+    ast: &ItemImpl,
+    self_type: &Type,
+    original_trait: &Path,
+    predicate: impl Fn(&Type) -> WherePredicate,
+) {
+    let mut required = Vec::new();
+    for item in &ast.items {
+        if let ImplItem::Method(method) = item {
+            if let ReturnType::Type(_, output) = &method.sig.output {
+                let output = resolve_self(output, self_type, original_trait);
+                let rendered = render(&output);
+                if !required.contains(&rendered) {
+                    generics
+                        .make_where_clause()
+                        .predicates
+                        .push(predicate(&output));
+                    required.push(rendered);
+                }
+            }
+        }
+    }
+}
+
+/// Spell `Self` in the bounds of the original implementation such that they
+/// keep bounding the same type in the mirror, e.g. `where Self: 'a`.
+pub(crate) fn resolve_self_in(generics: &mut Generics, self_type: &Type, original_trait: &Path) {
+    ResolveSelf {
+        self_type,
+        original_trait,
+    }
+    .visit_generics_mut(generics);
+}
+
+struct ResolveSelf<'a> {
+    self_type: &'a Type,
+    original_trait: &'a Path,
+}
+
+impl VisitMut for ResolveSelf<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.segments[0].ident == "Self" => {
+                *ty = resolve_self(ty, self.self_type, self.original_trait);
+            }
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+}
+
+/// Spell `Self` and `Self::Assoc` of the original implementation such that
+/// they keep their meaning in the mirror.
+pub(crate) fn resolve_self(ty: &Type, self_type: &Type, original_trait: &Path) -> Type {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => self_type.clone(),
+        Type::Path(TypePath { qself: None, path })
+            if path.segments.len() == 2 && path.segments[0].ident == "Self" =>
+        {
+            let associated = &path.segments[1];
+            parse_quote!(<#self_type as #original_trait>::#associated)
+        }
+        _ => ty.clone(),
+    }
+}
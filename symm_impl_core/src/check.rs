@@ -0,0 +1,307 @@
+//! Debug assertions comparing a hand-written reverse implementation with the
+//! one it mirrors, requested by `#[check_symmetric]`.
+//!
+//! Hand-optimized reverse implementations, e.g. of `Distance<Point2D>` for
+//! `Disk`, can drift from the implementation they are meant to mirror. Every
+//! method is checked against the result of the other direction in debug
+//! builds.
+//!
+//! Both directions may be checked against each other, in which case the
+//! direction called by a check is not checked in turn. The guard only uses
+//! `core`: it is a static of the method, so it is shared by all threads and,
+//! for generic implementations, by all instantiations, and it is reset even
+//! if the other direction panics.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Error, Expr, ExprAsync, ExprClosure, FnArg, GenericArgument, Ident, ImplItem, ImplItemMethod,
+    Item, ItemImpl, Path, PathArguments, Result, ReturnType, Token, Type,
+};
+
+use crate::render::render;
+
+/// Options of `#[check_symmetric(...)]`.
+#[derive(Default)]
+struct CheckOptions {
+    /// Function comparing the results by reference, `PartialEq::eq` if not
+    /// given.
+    eq: Option<Path>,
+}
+
+impl Parse for CheckOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = CheckOptions::default();
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            match name.to_string().as_str() {
+                "eq" if options.eq.is_none() => {
+                    input.parse::<Token![=]>()?;
+                    options.eq = Some(input.parse()?);
+                }
+                "eq" => {
+                    return Err(Error::new(name.span(), "duplicate option `eq`"));
+                }
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}`", name),
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(options)
+    }
+}
+
+/// Expand `#[check_symmetric]`, emitting the implementation with its methods
+/// checked against the other direction.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let checked = syn::parse2::<CheckOptions>(attr).and_then(|options| {
+        let ast = syn::parse2::<ItemImpl>(item.clone())?;
+        checked(ast, &options)
+    });
+    match checked {
+        Ok(checked) => quote!(#checked),
+        Err(error) => {
+            // the implementation is kept, such that its uses do not fail too
+            let mut expanded = item;
+            expanded.extend(error.to_compile_error());
+            expanded
+        }
+    }
+}
+
+/// Rewrite the methods of the implementation to assert that their results
+/// agree with the other direction.
+fn checked(mut ast: ItemImpl, options: &CheckOptions) -> Result<ItemImpl> {
+    let trait_path = match &ast.trait_ {
+        Some((None, trait_path, _)) => trait_path.clone(),
+        _ => {
+            return Err(Error::new_spanned(
+                &ast.self_ty,
+                "#[check_symmetric] can only be used on trait implementation",
+            ));
+        }
+    };
+    let has_type_argument = match &trait_path.segments.last().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => generics
+            .args
+            .iter()
+            .any(|arg| matches!(arg, GenericArgument::Type(_))),
+        _ => false,
+    };
+    if !has_type_argument {
+        return Err(Error::new_spanned(
+            &trait_path,
+            "symmetric trait must contain at least 1 type argument",
+        ));
+    }
+    let self_type = ast.self_ty.as_ref().clone();
+    let mut other_trait = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_trait, self_type.clone());
+    let header = render(&quote!(impl #trait_path for #self_type));
+    let other_header = render(&quote!(impl #other_trait for #other_type));
+
+    for item in &mut ast.items {
+        if let ImplItem::Method(method) = item {
+            check_method(
+                method,
+                &other_type,
+                &other_trait,
+                &header,
+                &other_header,
+                options,
+            )?;
+        }
+    }
+    Ok(ast)
+}
+
+/// Rewrite a method to compare its result with the one of the other direction.
+fn check_method(
+    method: &mut ImplItemMethod,
+    other_type: &Type,
+    other_trait: &Path,
+    header: &str,
+    other_header: &str,
+    options: &CheckOptions,
+) -> Result<()> {
+    let shared = method.sig.inputs.len() == 2
+        && matches!(
+            &method.sig.inputs[0],
+            FnArg::Receiver(receiver) if receiver.reference.is_some() && receiver.mutability.is_none()
+        )
+        && matches!(
+            &method.sig.inputs[1],
+            FnArg::Typed(other_arg) if matches!(
+                other_arg.ty.as_ref(),
+                Type::Reference(reference) if reference.mutability.is_none()
+            )
+        );
+    if !shared {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[check_symmetric] requires methods taking `&self` and the other operand by shared reference",
+        ));
+    }
+    if method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[check_symmetric] does not support async methods",
+        ));
+    }
+    let other_arg = match &mut method.sig.inputs[1] {
+        FnArg::Typed(other_arg) => other_arg,
+        FnArg::Receiver(_) => unreachable!(),
+    };
+    // the argument is bound to a name the assertion can refer to
+    let other_pat = std::mem::replace(other_arg.pat.as_mut(), parse_quote!(__symm_other));
+
+    let method_name = &method.sig.ident;
+    let eq = match &options.eq {
+        Some(eq) => quote!(#eq),
+        None => quote!(::core::cmp::PartialEq::eq),
+    };
+    let message = format!(
+        "`{}` of `{}` disagrees with `{}`",
+        method_name, header, other_header
+    );
+    let output = match &method.sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, output) => output.as_ref().clone(),
+    };
+    // a given comparison stays used in release builds
+    let unchecked = options.eq.as_ref().map(|eq| {
+        quote! {
+            #[cfg(not(debug_assertions))]
+            let _ = |result: &#output, other_result: &#output| #eq(result, other_result);
+        }
+    });
+    // the result is checked before leaving the method, so early returns leave
+    // the body instead
+    let mut exits = Exits {
+        output: &output,
+        found: false,
+        error: None,
+    };
+    let mut block = method.block.clone();
+    exits.visit_block_mut(&mut block);
+    if let Some(error) = exits.error {
+        return Err(error);
+    }
+    let body = if exits.found {
+        quote!('__symm_body: #block)
+    } else {
+        quote!(#block)
+    };
+    method.block = parse_quote! {
+        {
+            let #other_pat = __symm_other;
+            let result: #output = #body;
+            #[cfg(debug_assertions)]
+            {
+                // the other direction may be checked against this one, it is
+                // not checked again while it is called by the check
+                static CHECKING: ::core::sync::atomic::AtomicBool =
+                    ::core::sync::atomic::AtomicBool::new(false);
+                if !CHECKING.swap(true, ::core::sync::atomic::Ordering::Acquire) {
+                    // the flag is reset even if the other direction panics
+                    struct Reset;
+                    impl ::core::ops::Drop for Reset {
+                        fn drop(&mut self) {
+                            CHECKING.store(false, ::core::sync::atomic::Ordering::Release);
+                        }
+                    }
+                    let reset = Reset;
+                    let other_result = <#other_type as #other_trait>::#method_name(__symm_other, self);
+                    ::core::mem::drop(reset);
+                    ::core::assert!(#eq(&result, &other_result), #message);
+                }
+            }
+            #unchecked
+            result
+        }
+    };
+    Ok(())
+}
+
+/// Rewrite of the early exits of a body, `return` and `?`, into breaks out of
+/// the body labeled `'__symm_body`.
+struct Exits<'a> {
+    /// The return type of the method, which tells how `?` exits.
+    output: &'a Type,
+    /// Whether the body exits early.
+    found: bool,
+    error: Option<Error>,
+}
+
+impl VisitMut for Exits<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+        match expr {
+            Expr::Return(early) => {
+                self.found = true;
+                let value = early.expr.take();
+                *expr = parse_quote!(break '__symm_body #value);
+            }
+            Expr::Try(early) => {
+                self.found = true;
+                let tried = &early.expr;
+                let kind = match self.output {
+                    Type::Path(output) if output.qself.is_none() => {
+                        output.path.segments.last().map(|segment| &segment.ident)
+                    }
+                    _ => None,
+                };
+                *expr = match kind {
+                    Some(kind) if kind == "Result" => parse_quote! {
+                        match #tried {
+                            ::core::result::Result::Ok(value) => value,
+                            ::core::result::Result::Err(error) => {
+                                break '__symm_body ::core::result::Result::Err(
+                                    ::core::convert::From::from(error),
+                                );
+                            }
+                        }
+                    },
+                    Some(kind) if kind == "Option" => parse_quote! {
+                        match #tried {
+                            ::core::option::Option::Some(value) => value,
+                            ::core::option::Option::None => {
+                                break '__symm_body ::core::option::Option::None;
+                            }
+                        }
+                    },
+                    _ => {
+                        let error = Error::new(
+                            early.question_token.span(),
+                            "#[check_symmetric] supports `?` only in methods returning `Result` or `Option`",
+                        );
+                        match &mut self.error {
+                            Some(errors) => errors.combine(error),
+                            None => self.error = Some(error),
+                        }
+                        return;
+                    }
+                };
+            }
+            _ => (),
+        }
+    }
+
+    // closures, async blocks and items have exits of their own
+    fn visit_expr_closure_mut(&mut self, _: &mut ExprClosure) {}
+
+    fn visit_expr_async_mut(&mut self, _: &mut ExprAsync) {}
+
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
@@ -0,0 +1,229 @@
+//! Complementary predicates, requested by `#[complement(...)]`, e.g.
+//! `impl Disjoint<Disk> for Point2D` and `impl Disjoint<Point2D> for Disk`
+//! negating `impl Intersects<Disk> for Point2D`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    Error, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl, Path, PathArguments, Result,
+    ReturnType, Token, Type,
+};
+
+use crate::{attrs, options::MirrorOptions, render};
+
+/// Arguments of `#[complement(Trait, method = complement, ...)]`.
+struct ComplementArgs {
+    /// The complementary trait, taking the generic arguments of the
+    /// implemented one unless given.
+    trait_path: Path,
+    /// Names of the complementary methods, if different from the original
+    /// ones.
+    renames: Vec<(Ident, Ident)>,
+}
+
+/// A `method = renamed` pair.
+struct Rename {
+    method: Ident,
+    renamed: Ident,
+}
+
+impl Parse for Rename {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let method = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let renamed = input.parse()?;
+        Ok(Rename { method, renamed })
+    }
+}
+
+/// Parse the rest of the arguments as `method = renamed` pairs, naming each
+/// method once.
+pub(crate) fn renames(input: ParseStream) -> Result<Vec<(Ident, Ident)>> {
+    let mut renames: Vec<(Ident, Ident)> = Vec::new();
+    let pairs: Punctuated<Rename, Token![,]> = input.parse_terminated(Rename::parse)?;
+    for Rename { method, renamed } in pairs {
+        if renames.iter().any(|(listed, _)| *listed == method) {
+            return Err(Error::new(
+                method.span(),
+                format!("duplicate method `{}`", method),
+            ));
+        }
+        renames.push((method, renamed));
+    }
+    Ok(renames)
+}
+
+impl Parse for ComplementArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let trait_path = input.parse()?;
+        let mut renames = Vec::new();
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            renames = self::renames(input)?;
+        }
+        Ok(ComplementArgs {
+            trait_path,
+            renames,
+        })
+    }
+}
+
+/// Expand `#[complement]`, emitting the implementation along with the
+/// complementary ones.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut expanded = item.clone();
+    let generated = syn::parse2::<ComplementArgs>(attr).and_then(|args| {
+        let ast = syn::parse2::<ItemImpl>(item)?;
+        complements(&ast, &args)
+    });
+    match generated {
+        Ok(generated) => expanded.extend(generated),
+        Err(error) => expanded.extend(error.to_compile_error()),
+    }
+    expanded
+}
+
+/// Generate the complementary implementation and its mirror, in their own
+/// scope.
+fn complements(ast: &ItemImpl, args: &ComplementArgs) -> Result<TokenStream> {
+    let original_trait = match &ast.trait_ {
+        Some((None, trait_path, _)) => trait_path,
+        _ => {
+            return Err(Error::new_spanned(
+                &ast.self_ty,
+                "#[complement] can only be used on trait implementation",
+            ));
+        }
+    };
+    let original_arguments = &original_trait.segments.last().unwrap().arguments;
+    if !matches!(original_arguments, PathArguments::AngleBracketed(_)) {
+        return Err(Error::new_spanned(
+            original_trait,
+            "symmetric trait must contain at least 1 type argument",
+        ));
+    }
+    let mut complement_trait = args.trait_path.clone();
+    let last_segment = complement_trait.segments.last_mut().unwrap();
+    if let PathArguments::None = last_segment.arguments {
+        last_segment.arguments = original_arguments.clone();
+    }
+    for (method, _) in &args.renames {
+        let written = ast.items.iter().any(|item| match item {
+            ImplItem::Method(written) => written.sig.ident == *method,
+            _ => false,
+        });
+        if !written {
+            return Err(Error::new(
+                method.span(),
+                format!("method `{}` is not in the implementation", method),
+            ));
+        }
+    }
+
+    let options = MirrorOptions::default();
+    let self_type = ast.self_ty.as_ref();
+    let mut items = Vec::with_capacity(ast.items.len());
+    for item in &ast.items {
+        if let ImplItem::Method(method) = item {
+            items.push(ImplItem::Method(complement_method(
+                method,
+                self_type,
+                original_trait,
+                args,
+                &options,
+            )?));
+        }
+    }
+    let summary = format!(
+        "Complement of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #self_type }),
+        render::doc_link(original_trait),
+    );
+    let complement = ItemImpl {
+        attrs: attrs::generated_impl(&summary, &ast.attrs, &options),
+        defaultness: None,
+        unsafety: ast.unsafety,
+        impl_token: ast.impl_token,
+        generics: ast.generics.clone(),
+        trait_: Some((None, complement_trait, ast.trait_.as_ref().unwrap().2)),
+        self_ty: ast.self_ty.clone(),
+        brace_token: ast.brace_token,
+        items,
+    };
+
+    let mut generated = vec![complement];
+    let mut other_path = original_trait.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, self_type.clone());
+    if render::render(&other_type) != render::render(self_type) {
+        // the complementary implementation for the other order, unless it is
+        // the same implementation
+        let mirrored = crate::mirror_impl(&generated[0], &options)?;
+        generated.push(mirrored);
+    }
+    Ok(crate::scope(generated, &options))
+}
+
+/// Generate the method negating `method`.
+fn complement_method(
+    method: &ImplItemMethod,
+    self_type: &Type,
+    original_trait: &Path,
+    args: &ComplementArgs,
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    let returns_bool = match &method.sig.output {
+        ReturnType::Type(_, output) => {
+            matches!(output.as_ref(), Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+        }
+        ReturnType::Default => false,
+    };
+    if !returns_bool {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[complement] requires methods returning `bool`",
+        ));
+    }
+    if method.sig.inputs.len() != 2 {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[complement] requires methods taking `self` and the other operand",
+        ));
+    }
+    let method_name = &method.sig.ident;
+    let mut sig = method.sig.clone();
+    if let Some((_, complement)) = args
+        .renames
+        .iter()
+        .find(|(listed, _)| listed == method_name)
+    {
+        sig.ident = complement.clone();
+    }
+    if let FnArg::Receiver(receiver) = &mut sig.inputs[0] {
+        if receiver.reference.is_none() {
+            // `self` is moved without being mutated
+            receiver.mutability = None;
+        }
+    }
+    if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+        *other_arg.pat = parse_quote!(other);
+    }
+    let call = quote!(<#self_type as #original_trait>::#method_name(self, other));
+    let call = match method.sig.asyncness {
+        Some(_) => quote!(#call.await),
+        None => call,
+    };
+    Ok(ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: None,
+        sig,
+        block: parse_quote! {
+            {
+                !#call
+            }
+        },
+    })
+}
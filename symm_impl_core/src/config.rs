@@ -0,0 +1,155 @@
+//! Crate-wide default options, read from `symm_impl.toml` next to the
+//! `Cargo.toml` of the crate, or from the file named by the `SYMM_IMPL_CONFIG`
+//! environment variable, relative to the same directory.
+//!
+//! The file sets the `options` key to options written as in the attribute,
+//! e.g. `options = 'doc_hidden, inline = "never"'`. Only this subset of TOML is
+//! understood, which keeps the macro free of a TOML parser.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::Span;
+use syn::{
+    parse::{ParseStream, Parser},
+    Error, Result,
+};
+
+use crate::options::MirrorOptions;
+
+/// Name of the environment variable holding the path of the configuration.
+const CONFIG_VAR: &str = "SYMM_IMPL_CONFIG";
+
+/// Name of the configuration file looked up next to `Cargo.toml`.
+const CONFIG_FILE: &str = "symm_impl.toml";
+
+/// The default options of the crate being compiled, which are the defaults of
+/// `MirrorOptions` without a configuration file.
+pub(crate) fn defaults() -> Result<MirrorOptions> {
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => defaults_in(Path::new(&dir)),
+        None => Ok(MirrorOptions::default()),
+    }
+}
+
+/// The default options of the crate in `dir`, the directory of its
+/// `Cargo.toml`.
+pub(crate) fn defaults_in(dir: &Path) -> Result<MirrorOptions> {
+    let (path, named) = path(dir);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) if !named => {
+            // the configuration file is optional unless named
+            return Ok(MirrorOptions::default());
+        }
+        Err(error) => {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("failed to read `{}`: {}", path.display(), error),
+            ));
+        }
+    };
+    let invalid = |message: String| {
+        Error::new(
+            Span::call_site(),
+            format!("invalid configuration `{}`: {}", path.display(), message),
+        )
+    };
+    let options = match options(&contents).map_err(invalid)? {
+        Some(options) => options,
+        None => return Ok(MirrorOptions::default()),
+    };
+    let parser = |input: ParseStream| MirrorOptions::parse_onto(MirrorOptions::default(), input);
+    parser
+        .parse_str(&options)
+        .map_err(|error| invalid(error.to_string()))
+}
+
+/// Path of the configuration file of the crate in `dir`, and whether it is
+/// named by the environment variable.
+fn path(dir: &Path) -> (PathBuf, bool) {
+    match env::var_os(CONFIG_VAR) {
+        Some(path) if !path.is_empty() => (dir.join(path), true),
+        _ => (dir.join(CONFIG_FILE), false),
+    }
+}
+
+/// The value of the `options` key of a configuration, if set.
+fn options(contents: &str) -> std::result::Result<Option<String>, String> {
+    let mut options = None;
+    let mut rest = contents;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(options);
+        }
+        if rest.starts_with('#') {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+            continue;
+        }
+        let key_end = rest
+            .find(|ch: char| !(ch.is_alphanumeric() || ch == '_' || ch == '-'))
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        if key != "options" {
+            return Err(format!("unknown key `{}`, expected `options`", key));
+        }
+        if options.is_some() {
+            return Err(String::from("duplicate key `options`"));
+        }
+        rest = rest[key_end..].trim_start_matches([' ', '\t']);
+        rest = rest
+            .strip_prefix('=')
+            .ok_or_else(|| String::from("expected `=` after `options`"))?
+            .trim_start_matches([' ', '\t']);
+        let (value, after) = string(rest)?;
+        options = Some(value);
+        rest = after.trim_start_matches([' ', '\t']);
+        if !(rest.is_empty() || rest.starts_with(['\n', '\r', '#'])) {
+            return Err(String::from(
+                "expected a new line after the value of `options`",
+            ));
+        }
+    }
+}
+
+/// Parse a TOML string at the start of `input`, returning its value and the
+/// rest of the input.
+fn string(input: &str) -> std::result::Result<(String, &str), String> {
+    for (delimiter, literal) in [("'''", true), ("\"\"\"", false), ("'", true), ("\"", false)] {
+        let content = match input.strip_prefix(delimiter) {
+            Some(content) => content,
+            None => continue,
+        };
+        // a new line right after the opening delimiter is trimmed
+        let content = match delimiter.len() {
+            3 => content
+                .strip_prefix("\r\n")
+                .or_else(|| content.strip_prefix('\n'))
+                .unwrap_or(content),
+            _ => content,
+        };
+        let mut value = String::new();
+        let mut chars = content.char_indices();
+        while let Some((i, ch)) = chars.next() {
+            if content[i..].starts_with(delimiter) {
+                return Ok((value, &content[i + delimiter.len()..]));
+            }
+            match ch {
+                '\n' if delimiter.len() == 1 => break,
+                '\\' if !literal => match chars.next() {
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, '\\')) => value.push('\\'),
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    _ => return Err(String::from("unsupported escape sequence")),
+                },
+                ch => value.push(ch),
+            }
+        }
+        return Err(String::from("unterminated string"));
+    }
+    Err(String::from("expected a string"))
+}
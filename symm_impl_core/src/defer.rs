@@ -0,0 +1,34 @@
+//! Deferral of the expansion until the attribute macros following
+//! `#[symmetric(defer)]` have expanded.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::{parse_quote, ItemImpl};
+
+use crate::{attrs, options::MirrorOptions};
+
+/// Whether the expansion should be deferred, i.e. `defer` is requested and
+/// some attribute on the implementation may be an attribute macro.
+pub(crate) fn should_defer(ast: &ItemImpl, options: &MirrorOptions) -> bool {
+    options.defer && ast.attrs.iter().any(|attr| !attrs::is_inert(attr))
+}
+
+/// Emit the implementation with `#[symmetric]` moved after its other
+/// attributes, without `defer` such that the next expansion mirrors it.
+pub(crate) fn reemit(attr: TokenStream, mut ast: ItemImpl, options: &MirrorOptions) -> TokenStream {
+    // split the options at top-level commas, nested ones are inside groups
+    let mut chunks = vec![TokenStream::new()];
+    for token in attr {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => chunks.push(TokenStream::new()),
+            _ => chunks.last_mut().unwrap().extend(Some(token)),
+        }
+    }
+    let remaining = chunks
+        .into_iter()
+        .filter(|chunk| !chunk.is_empty() && chunk.to_string() != "defer");
+    let crate_path = &options.crate_path;
+    ast.attrs
+        .push(parse_quote!(#[#crate_path::symmetric(#(#remaining),*)]));
+    quote!(#ast)
+}
@@ -0,0 +1,964 @@
+//! Dispatch of symmetric traits over the variants of an enum.
+//!
+//! An enum whose variants each wrap one operand type implements a trait with
+//! itself by matching on both operands and delegating to the implementation
+//! between the wrapped types, which `#[symmetric]` provides in both directions.
+//! A procedural macro only sees the item it is applied to, so the signatures of
+//! the trait are written in `impl` blocks along with the enum.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_quote, Attribute, Error, Fields, FnArg, GenericArgument, Ident, ImplItem, ItemEnum, Path,
+    PathArguments, Result, ReturnType, Token, TraitItem, TraitItemMethod, TraitItemType, Type,
+    TypePath, Variant,
+};
+
+/// Trait to implement for the enum, e.g.
+/// `impl Distance<Shape> { fn distance(&self, other: &Shape) -> f64; }`.
+struct DispatchImpl {
+    trait_path: Path,
+    items: Vec<TraitItem>,
+}
+
+impl Parse for DispatchImpl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![impl]>()?;
+        let trait_path = input.parse()?;
+        let content;
+        braced!(content in input);
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            items.push(content.parse()?);
+        }
+        Ok(DispatchImpl { trait_path, items })
+    }
+}
+
+/// Input of `symmetrize_enum!`, an enum followed by the traits to implement.
+struct EnumInput {
+    item: ItemEnum,
+    impls: Vec<DispatchImpl>,
+}
+
+impl Parse for EnumInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let item = input.parse()?;
+        let mut impls = Vec::new();
+        while !input.is_empty() {
+            impls.push(input.parse()?);
+        }
+        Ok(EnumInput { item, impls })
+    }
+}
+
+/// Options of the enum, given in `#[symm(...)]` attributes.
+#[derive(Default)]
+struct EnumOptions {
+    /// Traits to implement, `#[symm(impl Trait<Enum> { ... })]`.
+    impls: Vec<DispatchImpl>,
+    /// Module whose functions named after the methods are called for the pairs
+    /// not implemented or skipped, `#[symm(fallback = path)]`.
+    fallback: Option<Path>,
+}
+
+/// Expand `symmetrize_enum!`, generating the enum, the conversions from the
+/// wrapped types, and the dispatching implementations.
+pub fn expand_enum(input: TokenStream) -> TokenStream {
+    let EnumInput { item, impls } = match syn::parse2::<EnumInput>(input) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error(),
+    };
+    let mut declaration = item.clone();
+    declaration.attrs.retain(|attr| !attr.path.is_ident("symm"));
+    for variant in &mut declaration.variants {
+        variant.attrs.retain(|attr| !attr.path.is_ident("symm"));
+    }
+    let mut expanded = quote!(#declaration);
+    match conversions(&item).and_then(|conversions| {
+        let mut options = enum_options(&item.attrs)?;
+        options.impls.extend(impls);
+        let impls = generate(&item, &options)?;
+        Ok(quote!(#conversions #impls))
+    }) {
+        Ok(generated) => expanded.extend(generated),
+        Err(error) => expanded.extend(error.to_compile_error()),
+    }
+    expanded
+}
+
+/// Expand `#[derive(SymmetricDispatch)]`, generating the implementations
+/// listed in the `#[symm(impl ...)]` attributes of the enum.
+pub fn expand_derive(input: TokenStream) -> TokenStream {
+    let item = match syn::parse2::<ItemEnum>(input) {
+        Ok(item) => item,
+        Err(error) => return error.to_compile_error(),
+    };
+    let generated = enum_options(&item.attrs).and_then(|options| {
+        if options.impls.is_empty() {
+            return Err(Error::new(
+                item.ident.span(),
+                "expected the traits to implement in `#[symm(impl Trait<Enum> { ... })]`",
+            ));
+        }
+        generate(&item, &options)
+    });
+    match generated {
+        Ok(generated) => generated,
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// Parse the options given in the `#[symm(...)]` attributes of the enum.
+fn enum_options(attrs: &[Attribute]) -> Result<EnumOptions> {
+    let mut options = EnumOptions::default();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("symm")) {
+        attr.parse_args_with(|input: ParseStream| {
+            if input.peek(Token![impl]) {
+                options.impls.push(input.parse()?);
+                return Ok(());
+            }
+            let option = input.parse::<Ident>()?;
+            if option != "fallback" {
+                return Err(Error::new(
+                    option.span(),
+                    format!("unknown option `{}` of an enum", option),
+                ));
+            }
+            if options.fallback.is_some() {
+                return Err(Error::new(option.span(), "duplicate option `fallback`"));
+            }
+            input.parse::<Token![=]>()?;
+            options.fallback = Some(input.parse()?);
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+/// Type wrapped by a variant.
+fn wrapped(variant: &Variant) -> Result<&Type> {
+    match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(&fields.unnamed[0].ty),
+        _ => Err(Error::new(
+            variant.ident.span(),
+            "expected a variant wrapping a single type, e.g. `Disk(Disk)`",
+        )),
+    }
+}
+
+/// Generate `From` implementations from the wrapped types.
+fn conversions(item: &ItemEnum) -> Result<TokenStream> {
+    let ident = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let mut seen = Vec::new();
+    let mut conversions = TokenStream::new();
+    for variant in &item.variants {
+        let ty = match (wrapped(variant), variant_role(variant)?) {
+            (Ok(ty), _) => ty,
+            // excluded variants such as markers need not wrap a type
+            (Err(_), Role::Excluded { .. }) => continue,
+            (Err(error), Role::Operand { .. }) => return Err(error),
+        };
+        let rendered = crate::render::render(ty);
+        if seen.contains(&rendered) {
+            return Err(Error::new(
+                variant.ident.span(),
+                format!("`{}` is wrapped by several variants", rendered),
+            ));
+        }
+        seen.push(rendered);
+        let variant_ident = &variant.ident;
+        conversions.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#ty> for #ident #ty_generics #where_clause {
+                #[inline]
+                fn from(value: #ty) -> Self {
+                    #ident::#variant_ident(value)
+                }
+            }
+        });
+    }
+    Ok(conversions)
+}
+
+/// Operand type wrapped by a variant.
+struct Operand<'a> {
+    variant: &'a Ident,
+    ty: &'a Type,
+    /// Whether the type is itself a dispatch enum, marked `#[symm(nested)]`,
+    /// whose variants are dispatched over when paired with the other operands.
+    nested: bool,
+}
+
+/// Variant excluded from the dispatch with `#[symm(skip)]` or
+/// `#[symm(with = path)]`.
+struct Excluded<'a> {
+    variant: &'a Ident,
+    /// Module whose functions named after the methods are called with the
+    /// operands of the pairs with the variant, instead of the result given as
+    /// the body of the signatures.
+    with: Option<Path>,
+}
+
+/// Role of a variant, given in `#[symm(...)]` attributes.
+enum Role {
+    /// Operand of the trait, possibly a nested dispatch enum.
+    Operand { nested: bool },
+    /// Excluded from the dispatch.
+    Excluded { with: Option<Path> },
+}
+
+/// Parse the role of a variant from its `#[symm(...)]` attributes.
+fn variant_role(variant: &Variant) -> Result<Role> {
+    let mut role = None;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("symm"))
+    {
+        attr.parse_args_with(|input: ParseStream| {
+            let option = input.parse::<Ident>()?;
+            let parsed = match option.to_string().as_str() {
+                "nested" => Role::Operand { nested: true },
+                "skip" => Role::Excluded { with: None },
+                "with" => {
+                    input.parse::<Token![=]>()?;
+                    Role::Excluded {
+                        with: Some(input.parse()?),
+                    }
+                }
+                _ => {
+                    return Err(Error::new(
+                        option.span(),
+                        format!("unknown option `{}` of a variant", option),
+                    ));
+                }
+            };
+            if role.is_some() {
+                return Err(Error::new(
+                    option.span(),
+                    "expected at most one of `nested`, `skip` and `with` for a variant",
+                ));
+            }
+            role = Some(parsed);
+            Ok(())
+        })?;
+    }
+    Ok(role.unwrap_or(Role::Operand { nested: false }))
+}
+
+/// Generate the dispatching implementations of the traits for the enum.
+fn generate(item: &ItemEnum, options: &EnumOptions) -> Result<TokenStream> {
+    let mut operands = Vec::with_capacity(item.variants.len());
+    let mut excluded = Vec::new();
+    for variant in &item.variants {
+        match variant_role(variant)? {
+            Role::Operand { nested } => operands.push(Operand {
+                variant: &variant.ident,
+                ty: wrapped(variant)?,
+                nested,
+            }),
+            Role::Excluded { with } => excluded.push(Excluded {
+                variant: &variant.ident,
+                with,
+            }),
+        }
+    }
+    let mut generated = TokenStream::new();
+    for dispatch in &options.impls {
+        generated.extend(generate_impl(
+            item,
+            &operands,
+            &excluded,
+            dispatch,
+            options.fallback.as_ref(),
+        )?);
+        // the helper dispatches over every variant of the enum
+        if item.generics.params.is_empty() && excluded.is_empty() {
+            generated.extend(nested_helper(item, &operands, dispatch)?);
+        }
+    }
+    Ok(generated)
+}
+
+/// Spell the trait of an implementation with another type for the symmetry.
+fn with_other(trait_path: &Path, other_type: &Type) -> Result<Path> {
+    let mut trait_path = trait_path.clone();
+    let last_segment = trait_path.segments.last_mut().unwrap();
+    let other = match &mut last_segment.arguments {
+        PathArguments::AngleBracketed(generics) => {
+            generics.args.iter_mut().find_map(|arg| match arg {
+                GenericArgument::Type(other) => Some(other),
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+    match other {
+        Some(other) => *other = other_type.clone(),
+        None => {
+            return Err(Error::new_spanned(
+                &last_segment.ident,
+                "symmetric trait must contain at least 1 type argument",
+            ));
+        }
+    }
+    Ok(trait_path)
+}
+
+/// Name of the helper trait through which the enum `ident` is dispatched over
+/// when nested in another enum.
+fn helper_ident(ident: &Ident, dispatch: &DispatchImpl) -> Ident {
+    let trait_ident = &dispatch.trait_path.segments.last().unwrap().ident;
+    format_ident!("__SymmNested{}{}", ident, trait_ident)
+}
+
+/// Path of the helper trait of a nested enum, next to the enum.
+fn helper_path(nested: &Type, dispatch: &DispatchImpl, other: &Type) -> Result<Path> {
+    let mut path = match nested {
+        Type::Path(TypePath { qself: None, path }) => path.clone(),
+        _ => {
+            return Err(Error::new_spanned(
+                nested,
+                "expected the path of an enum deriving `SymmetricDispatch`",
+            ));
+        }
+    };
+    let last_segment = path.segments.last_mut().unwrap();
+    last_segment.ident = helper_ident(&last_segment.ident, dispatch);
+    last_segment.arguments = PathArguments::AngleBracketed(parse_quote!(<#other>));
+    Ok(path)
+}
+
+/// Name of a function of the helper trait, with the enum being either operand.
+fn helper_method(method: &TraitItemMethod, enum_first: bool) -> Ident {
+    let side = if enum_first { "lhs" } else { "rhs" };
+    format_ident!("{}_{}", method.sig.ident, side)
+}
+
+/// Operands of the generated functions calling the pairwise implementations,
+/// borrowed the same way as by the receiver of a method.
+fn operand_types(method: &TraitItemMethod, lhs: &Type, rhs: &Type) -> (TokenStream, TokenStream) {
+    match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => (quote!(&mut #lhs), quote!(&mut #rhs)),
+            (Some(_), None) => (quote!(&#lhs), quote!(&#rhs)),
+            (None, _) => (quote!(#lhs), quote!(#rhs)),
+        },
+        FnArg::Typed(_) => unreachable!(),
+    }
+}
+
+/// Spell the return type of a method outside of the implementation for the
+/// enum, with `Self` being the enum and its associated types their value.
+fn resolved_output(method: &TraitItemMethod, enum_type: &Type, dispatch: &DispatchImpl) -> Type {
+    match &method.sig.output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => {
+                enum_type.clone()
+            }
+            Type::Path(TypePath { qself: None, path })
+                if path.segments.len() == 2 && path.segments[0].ident == "Self" =>
+            {
+                let associated = &path.segments[1].ident;
+                dispatch
+                    .items
+                    .iter()
+                    .find_map(|item| match item {
+                        TraitItem::Type(TraitItemType {
+                            ident,
+                            default: Some((_, value)),
+                            ..
+                        }) if ident == associated => Some(value.clone()),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| ty.as_ref().clone())
+            }
+            ty => ty.clone(),
+        },
+        ReturnType::Default => parse_quote!(()),
+    }
+}
+
+/// Spell the trait with the given other operand, binding its associated types
+/// to the ones of the enum, which the methods may return.
+fn bound_with_other(dispatch: &DispatchImpl, other_type: &Type) -> Result<Path> {
+    let mut bound = with_other(&dispatch.trait_path, other_type)?;
+    if let PathArguments::AngleBracketed(generics) =
+        &mut bound.segments.last_mut().unwrap().arguments
+    {
+        for trait_item in &dispatch.items {
+            if let TraitItem::Type(TraitItemType {
+                ident,
+                default: Some((_, ty)),
+                ..
+            }) = trait_item
+            {
+                generics.args.push(parse_quote!(#ident = #ty));
+            }
+        }
+    }
+    Ok(bound)
+}
+
+/// Generate the helper trait through which an enum is dispatched over when
+/// nested in another one, implemented with every type its variants are
+/// implemented with in both directions.
+fn nested_helper(
+    item: &ItemEnum,
+    operands: &[Operand],
+    dispatch: &DispatchImpl,
+) -> Result<TokenStream> {
+    let ident = &item.ident;
+    let vis = &item.vis;
+    let enum_type: Type = parse_quote!(#ident);
+    let other: Type = parse_quote!(__O);
+    let helper = helper_ident(ident, dispatch);
+
+    let mut predicates = Vec::with_capacity(operands.len() * 2);
+    for operand in operands {
+        let ty = operand.ty;
+        if operand.nested {
+            let nested_helper = helper_path(ty, dispatch, &other)?;
+            predicates.push(quote!(#ty: #nested_helper));
+        } else {
+            let lhs_bound = bound_with_other(dispatch, &other)?;
+            let rhs_bound = bound_with_other(dispatch, ty)?;
+            predicates.push(quote!(#ty: #lhs_bound));
+            predicates.push(quote!(__O: #rhs_bound));
+        }
+    }
+
+    let mut signatures = Vec::new();
+    let mut functions = Vec::new();
+    for trait_item in &dispatch.items {
+        let method = match trait_item {
+            TraitItem::Method(method) => method,
+            _ => continue,
+        };
+        let method_name = &method.sig.ident;
+        let output = resolved_output(method, &enum_type, dispatch);
+        for &enum_first in &[true, false] {
+            let name = helper_method(method, enum_first);
+            let (lhs, rhs) = if enum_first {
+                operand_types(method, &parse_quote!(Self), &other)
+            } else {
+                operand_types(method, &other, &parse_quote!(Self))
+            };
+            let arms = operands
+                .iter()
+                .map(|operand| {
+                    let (variant, ty) = (operand.variant, operand.ty);
+                    let call = match (operand.nested, enum_first) {
+                        (true, _) => {
+                            let nested_helper = helper_path(ty, dispatch, &other)?;
+                            quote!(<#ty as #nested_helper>::#name(lhs, rhs))
+                        }
+                        (false, true) => {
+                            let pair_trait = with_other(&dispatch.trait_path, &other)?;
+                            quote!(<#ty as #pair_trait>::#method_name(lhs, rhs))
+                        }
+                        (false, false) => {
+                            let pair_trait = with_other(&dispatch.trait_path, ty)?;
+                            quote!(<__O as #pair_trait>::#method_name(lhs, rhs))
+                        }
+                    };
+                    let binding = if enum_first {
+                        quote!(let lhs = lhs;)
+                    } else {
+                        quote!(let rhs = rhs;)
+                    };
+                    let pattern = if enum_first { quote!(lhs) } else { quote!(rhs) };
+                    Ok(quote! {
+                        #ident::#variant(#pattern) => {
+                            #binding
+                            #call
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let scrutinee = if enum_first { quote!(lhs) } else { quote!(rhs) };
+            signatures.push(quote!(fn #name(lhs: #lhs, rhs: #rhs) -> #output;));
+            functions.push(quote! {
+                #[inline]
+                fn #name(lhs: #lhs, rhs: #rhs) -> #output {
+                    match #scrutinee {
+                        #(#arms)*
+                    }
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        #vis trait #helper<__O>: ::core::marker::Sized {
+            #(#signatures)*
+        }
+
+        #[automatically_derived]
+        impl<__O> #helper<__O> for #ident
+        where
+            #(#predicates,)*
+        {
+            #(#functions)*
+        }
+    })
+}
+
+/// How a pair of operands is dispatched.
+#[derive(Clone, Copy)]
+enum Route {
+    /// Through the implementation between the operands.
+    Direct,
+    /// Over the variants of the first operand, a nested enum.
+    NestedLhs,
+    /// Over the variants of the other operand, a nested enum.
+    NestedRhs,
+}
+
+impl Route {
+    fn of(lhs: &Operand, rhs: &Operand) -> Self {
+        match (lhs.nested, rhs.nested) {
+            (true, false) => Route::NestedLhs,
+            (false, true) => Route::NestedRhs,
+            _ => Route::Direct,
+        }
+    }
+}
+
+/// Generate the compile-time check of the pairwise implementations of a
+/// non-generic enum, reporting every missing pair of variants in a single
+/// error.
+///
+/// `__Probe::<L, R>` has the inherent constant `IMPLEMENTED` and functions
+/// calling the methods only if `L` implements the trait with `R`, which take
+/// precedence over the ones of `__Missing` implemented for every type. The
+/// match arms call the functions of `__Probe`, which diverge for the missing
+/// pairs, such that the only error is the one of the check. `__NestedN::<T>`
+/// does the same for the `N`-th operand, a nested enum, paired with `T`.
+///
+/// With a fallback, the missing pairs are looked up with `__pair` instead of
+/// being reported.
+fn probe(
+    item: &ItemEnum,
+    operands: &[Operand],
+    dispatch: &DispatchImpl,
+    methods: &[&TraitItemMethod],
+    fallback: bool,
+) -> Result<TokenStream> {
+    let ident = &item.ident;
+    let enum_type: Type = parse_quote!(#ident);
+    let (lhs_param, rhs_param): (Type, Type) = (parse_quote!(__L), parse_quote!(__R));
+    let generic_trait = with_other(&dispatch.trait_path, &rhs_param)?;
+    let bound = bound_with_other(dispatch, &rhs_param)?;
+    let mut calls = Vec::with_capacity(methods.len());
+    let mut fallbacks = Vec::with_capacity(methods.len() * 3);
+    for method in methods {
+        let method_name = &method.sig.ident;
+        let (lhs, rhs) = operand_types(method, &lhs_param, &rhs_param);
+        let output = resolved_output(method, &enum_type, dispatch);
+        calls.push(quote! {
+            #[inline]
+            fn #method_name(lhs: #lhs, rhs: #rhs) -> #output {
+                <__L as #generic_trait>::#method_name(lhs, rhs)
+            }
+        });
+        let names = [
+            method_name.clone(),
+            helper_method(method, true),
+            helper_method(method, false),
+        ];
+        fallbacks.push(quote! {
+            #(
+                fn #names<__A, __B>(_: __A, _: __B) -> ! {
+                    ::core::unreachable!()
+                }
+            )*
+        });
+    }
+
+    let mut nested_probes = TokenStream::new();
+    for (index, operand) in operands.iter().enumerate() {
+        if !operand.nested {
+            continue;
+        }
+        let probe_ident = format_ident!("__Nested{}", index);
+        let nested = operand.ty;
+        let other: Type = parse_quote!(__T);
+        let nested_helper = helper_path(nested, dispatch, &other)?;
+        let mut functions = Vec::with_capacity(methods.len() * 2);
+        for method in methods {
+            let output = resolved_output(method, &enum_type, dispatch);
+            for &enum_first in &[true, false] {
+                let name = helper_method(method, enum_first);
+                let (lhs, rhs) = if enum_first {
+                    operand_types(method, nested, &other)
+                } else {
+                    operand_types(method, &other, nested)
+                };
+                functions.push(quote! {
+                    #[inline]
+                    fn #name(lhs: #lhs, rhs: #rhs) -> #output {
+                        <#nested as #nested_helper>::#name(lhs, rhs)
+                    }
+                });
+            }
+        }
+        nested_probes.extend(quote! {
+            struct #probe_ident<__T>(::core::marker::PhantomData<__T>);
+
+            #[allow(dead_code)]
+            impl<__T> #probe_ident<__T>
+            where
+                #nested: #nested_helper,
+            {
+                const IMPLEMENTED: bool = true;
+
+                #(#functions)*
+            }
+        });
+    }
+
+    let trait_name = &dispatch.trait_path.segments.last().unwrap().ident;
+    let prefix = format!(
+        "`{}` is not implemented for the pairs of variants of `{}`: ",
+        trait_name, ident
+    );
+    let mut capacity = prefix.len();
+    let mut pairs = Vec::with_capacity(operands.len() * operands.len());
+    for (lhs_index, lhs) in operands.iter().enumerate() {
+        for (rhs_index, rhs) in operands.iter().enumerate() {
+            let name = format!("({}, {})", lhs.variant, rhs.variant);
+            capacity += name.len() + 2;
+            let (lhs_type, rhs_type) = (lhs.ty, rhs.ty);
+            let implemented = match Route::of(lhs, rhs) {
+                Route::Direct => quote!(__Probe::<#lhs_type, #rhs_type>::IMPLEMENTED),
+                Route::NestedLhs => {
+                    let probe_ident = format_ident!("__Nested{}", lhs_index);
+                    quote!(#probe_ident::<#rhs_type>::IMPLEMENTED)
+                }
+                Route::NestedRhs => {
+                    let probe_ident = format_ident!("__Nested{}", rhs_index);
+                    quote!(#probe_ident::<#lhs_type>::IMPLEMENTED)
+                }
+            };
+            pairs.push(quote!((!#implemented, #name)));
+        }
+    }
+    let count = pairs.len();
+
+    // the error points at the enum rather than at the derive
+    let report = quote_spanned! {ident.span()=>
+        if MESSAGE.1 > #prefix.len() {
+            match ::core::str::from_utf8(MESSAGE.0.split_at(MESSAGE.1).0) {
+                ::core::result::Result::Ok(message) => ::core::panic!("{}", message),
+                ::core::result::Result::Err(_) => ::core::unreachable!(),
+            }
+        }
+    };
+    let check = if fallback {
+        let variants = operands.iter().map(|operand| operand.variant);
+        let indices = 0..operands.len();
+        let len = operands.len();
+        quote! {
+            fn __pair(lhs: &#ident, rhs: &#ident) -> usize {
+                let index = |operand: &#ident| match operand {
+                    #(#ident::#variants { .. } => #indices,)*
+                    #[allow(unreachable_patterns)]
+                    _ => ::core::unreachable!(),
+                };
+                index(lhs) * #len + index(rhs)
+            }
+        }
+    } else {
+        quote! {
+            const MESSAGE: ([u8; #capacity], usize) = {
+                let mut message = [0u8; #capacity];
+                let mut len = 0;
+                let prefix = #prefix.as_bytes();
+                while len < prefix.len() {
+                    message[len] = prefix[len];
+                    len += 1;
+                }
+                let mut i = 0;
+                while i < MISSING.len() {
+                    if MISSING[i].0 {
+                        if len > prefix.len() {
+                            message[len] = b',';
+                            message[len + 1] = b' ';
+                            len += 2;
+                        }
+                        let name = MISSING[i].1.as_bytes();
+                        let mut j = 0;
+                        while j < name.len() {
+                            message[len] = name[j];
+                            len += 1;
+                            j += 1;
+                        }
+                    }
+                    i += 1;
+                }
+                (message, len)
+            };
+            #report
+        }
+    };
+
+    Ok(quote! {
+        struct __Probe<__L, __R>(::core::marker::PhantomData<(__L, __R)>);
+
+        #[allow(dead_code)]
+        impl<__L: #bound, __R> __Probe<__L, __R> {
+            const IMPLEMENTED: bool = true;
+
+            #(#calls)*
+        }
+
+        #nested_probes
+
+        #[allow(dead_code)]
+        trait __Missing {
+            const IMPLEMENTED: bool = false;
+
+            #(#fallbacks)*
+        }
+
+        impl<__T> __Missing for __T {}
+
+        const MISSING: [(bool, &str); #count] = [#(#pairs),*];
+        #check
+    })
+}
+
+/// Generate the call of a method between the operands of a pair.
+fn pair_call(
+    lhs_index: usize,
+    lhs: &Operand,
+    rhs_index: usize,
+    rhs: &Operand,
+    method: &TraitItemMethod,
+    dispatch: &DispatchImpl,
+    probed: bool,
+) -> Result<TokenStream> {
+    let method_name = &method.sig.ident;
+    let (lhs_type, rhs_type) = (lhs.ty, rhs.ty);
+    Ok(match (Route::of(lhs, rhs), probed) {
+        (Route::Direct, true) => {
+            quote!(__Probe::<#lhs_type, #rhs_type>::#method_name(lhs, rhs))
+        }
+        (Route::Direct, false) => {
+            let pair_trait = with_other(&dispatch.trait_path, rhs_type)?;
+            quote!(<#lhs_type as #pair_trait>::#method_name(lhs, rhs))
+        }
+        (Route::NestedLhs, true) => {
+            let probe_ident = format_ident!("__Nested{}", lhs_index);
+            let name = helper_method(method, true);
+            quote!(#probe_ident::<#rhs_type>::#name(lhs, rhs))
+        }
+        (Route::NestedLhs, false) => {
+            let nested_helper = helper_path(lhs_type, dispatch, rhs_type)?;
+            let name = helper_method(method, true);
+            quote!(<#lhs_type as #nested_helper>::#name(lhs, rhs))
+        }
+        (Route::NestedRhs, true) => {
+            let probe_ident = format_ident!("__Nested{}", rhs_index);
+            let name = helper_method(method, false);
+            quote!(#probe_ident::<#lhs_type>::#name(lhs, rhs))
+        }
+        (Route::NestedRhs, false) => {
+            let nested_helper = helper_path(rhs_type, dispatch, lhs_type)?;
+            let name = helper_method(method, false);
+            quote!(<#rhs_type as #nested_helper>::#name(lhs, rhs))
+        }
+    })
+}
+
+/// Generate the result of a method for the pairs with an excluded variant.
+fn skipped_result(
+    skipped: &Excluded,
+    method: &TraitItemMethod,
+    fallback: Option<&Path>,
+) -> Result<TokenStream> {
+    let method_name = &method.sig.ident;
+    match (&skipped.with, &method.default, fallback) {
+        (Some(with), _, _) => Ok(quote!(#with::#method_name(self, other))),
+        (None, Some(result), _) => Ok(quote!(#result)),
+        (None, None, Some(fallback)) => Ok(quote!(#fallback::#method_name(self, other))),
+        (None, None, None) => Err(Error::new(
+            skipped.variant.span(),
+            format!(
+                "expected the result of `{}` for the pairs with a skipped variant as the body of its signature, or `#[symm(fallback = path)]` on the enum",
+                method_name
+            ),
+        )),
+    }
+}
+
+/// Generate the implementation of one trait for the enum.
+fn generate_impl(
+    item: &ItemEnum,
+    operands: &[Operand],
+    excluded: &[Excluded],
+    dispatch: &DispatchImpl,
+    fallback: Option<&Path>,
+) -> Result<TokenStream> {
+    let ident = &item.ident;
+    let trait_path = &dispatch.trait_path;
+    // the pairwise implementations of a generic enum are required by bounds,
+    // those of other enums are checked at compile time
+    let probed = item.generics.params.is_empty();
+
+    let mut generics = item.generics.clone();
+    if !probed {
+        // the pairwise implementations may not exist for every instantiation
+        let where_clause = generics.make_where_clause();
+        for lhs in operands {
+            for rhs in operands {
+                let (lhs_type, rhs_type) = (lhs.ty, rhs.ty);
+                where_clause.predicates.push(match Route::of(lhs, rhs) {
+                    Route::Direct => {
+                        let pair_trait = with_other(trait_path, rhs_type)?;
+                        parse_quote!(#lhs_type: #pair_trait)
+                    }
+                    Route::NestedLhs => {
+                        let nested_helper = helper_path(lhs_type, dispatch, rhs_type)?;
+                        parse_quote!(#lhs_type: #nested_helper)
+                    }
+                    Route::NestedRhs => {
+                        let nested_helper = helper_path(rhs_type, dispatch, lhs_type)?;
+                        parse_quote!(#rhs_type: #nested_helper)
+                    }
+                });
+            }
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut items = Vec::with_capacity(dispatch.items.len());
+    let mut methods = Vec::with_capacity(dispatch.items.len());
+    for trait_item in &dispatch.items {
+        let impl_item: ImplItem = match trait_item {
+            TraitItem::Method(method) => {
+                if method.sig.inputs.len() != 2 {
+                    return Err(Error::new_spanned(
+                        &method.sig.inputs,
+                        "expected 2 arguments",
+                    ));
+                }
+                if !matches!(method.sig.inputs[0], FnArg::Receiver(_)) {
+                    return Err(Error::new_spanned(
+                        &method.sig.inputs[0],
+                        "expected a receiver",
+                    ));
+                }
+                methods.push(method);
+                let mut sig = method.sig.clone();
+                if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+                    *other_arg.pat = parse_quote!(other);
+                }
+                let mut arms = Vec::with_capacity(operands.len() * operands.len());
+                for (lhs_index, lhs) in operands.iter().enumerate() {
+                    for (rhs_index, rhs) in operands.iter().enumerate() {
+                        let call =
+                            pair_call(lhs_index, lhs, rhs_index, rhs, method, dispatch, probed)?;
+                        let (lhs_variant, rhs_variant) = (lhs.variant, rhs.variant);
+                        arms.push(quote! {
+                            (#ident::#lhs_variant(lhs), #ident::#rhs_variant(rhs)) => {
+                                #call
+                            }
+                        });
+                    }
+                }
+                if !excluded.is_empty() {
+                    arms.push(quote!(_ => ::core::unreachable!(),));
+                }
+                // the pairs with an excluded variant are checked first, with
+                // the first operand taking precedence
+                let mut conditions = Vec::with_capacity(excluded.len() * 2);
+                let mut results = Vec::with_capacity(excluded.len() * 2);
+                for operand in &[quote!(self), quote!(other)] {
+                    for skipped in excluded {
+                        let variant = skipped.variant;
+                        conditions
+                            .push(quote!(::core::matches!(#operand, #ident::#variant { .. })));
+                        results.push(skipped_result(skipped, method, fallback)?);
+                    }
+                }
+                if let (true, Some(fallback)) = (probed, fallback) {
+                    let method_name = &method.sig.ident;
+                    // the pairs not implemented are not reported by the probe
+                    conditions.push(quote!(MISSING[__pair(&self, &other)].0));
+                    results.push(quote!(#fallback::#method_name(self, other)));
+                }
+                let attrs = &method.attrs;
+                parse_quote! {
+                    #(#attrs)*
+                    #sig {
+                        #(if #conditions { #results } else)* {
+                            match (self, other) {
+                                #(#arms)*
+                            }
+                        }
+                    }
+                }
+            }
+            TraitItem::Type(associated_type) => match &associated_type.default {
+                Some((_, ty)) => {
+                    let attrs = &associated_type.attrs;
+                    let type_ident = &associated_type.ident;
+                    parse_quote!(#(#attrs)* type #type_ident = #ty;)
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        &associated_type.ident,
+                        format!("associated type `{}` needs a value", associated_type.ident),
+                    ));
+                }
+            },
+            TraitItem::Const(constant) => match &constant.default {
+                Some((_, value)) => {
+                    let attrs = &constant.attrs;
+                    let const_ident = &constant.ident;
+                    let ty = &constant.ty;
+                    parse_quote!(#(#attrs)* const #const_ident: #ty = #value;)
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        &constant.ident,
+                        format!("associated constant `{}` needs a value", constant.ident),
+                    ));
+                }
+            },
+            _ => {
+                return Err(Error::new_spanned(
+                    trait_item,
+                    "expected an associated type, constant or method",
+                ));
+            }
+        };
+        items.push(impl_item);
+    }
+
+    let implementation = quote! {
+        #[automatically_derived]
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            #(#items)*
+        }
+    };
+    if probed {
+        let probe = probe(item, operands, dispatch, &methods, fallback.is_some())?;
+        Ok(quote! {
+            const _: () = {
+                #probe
+                #implementation
+            };
+        })
+    } else {
+        Ok(implementation)
+    }
+}
@@ -0,0 +1,68 @@
+//! Dumping of generated code to files for review, enabled by setting the
+//! `SYMM_IMPL_DUMP` environment variable to a directory.
+
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ItemImpl;
+
+use crate::render::render;
+
+/// Name of the environment variable holding the dump directory.
+const DUMP_VAR: &str = "SYMM_IMPL_DUMP";
+
+/// Write the code generated for an implementation into the dump directory, if
+/// dumping is enabled.
+///
+/// The file is named after the trait and the types of the mirror, e.g.
+/// `Distance_Point2D_for_Disk.rs` for `impl Distance<Disk> for Point2D`, such
+/// that the stacked mirrors of an implementation are dumped into their own
+/// files.
+pub(crate) fn dump(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    generated: &TokenStream,
+) -> Result<(), String> {
+    let dir = match env::var_os(DUMP_VAR) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => return Ok(()),
+    };
+
+    let mut file_name: String = header(mirrored_ast)
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { '_' })
+        .collect();
+    // collapse runs of separators
+    while file_name.contains("__") {
+        file_name = file_name.replace("__", "_");
+    }
+    let file_name = format!("{}.rs", file_name.trim_matches('_'));
+
+    let contents = format!(
+        "// Generated by #[symmetric] for `impl {}`\n{}\n",
+        header(ast),
+        render(generated)
+    );
+    let path = dir.join(file_name);
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&path, contents))
+        .map_err(|error| {
+            format!(
+                "failed to dump expansion to `{}`: {}",
+                path.display(),
+                error
+            )
+        })
+}
+
+/// The header of an implementation, e.g. `Distance<Disk> for Point2D`.
+fn header(ast: &ItemImpl) -> String {
+    match &ast.trait_ {
+        Some((_, trait_path, _)) => {
+            let self_ty = &ast.self_ty;
+            render(&quote! { #trait_path for #self_ty })
+        }
+        None => render(&ast.self_ty),
+    }
+}
@@ -0,0 +1,38 @@
+//! Warning on mirrors delegating nothing to the original implementation.
+//!
+//! Procedural macros cannot emit warnings on stable Rust, so the warning is
+//! the use of a deprecated constant, spanned on the implementation, whose
+//! deprecation note explains the problem.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, ImplItem, ItemImpl};
+
+/// Generate the warning if the mirror of a non-empty implementation has
+/// neither a method nor an associated type, e.g. when `only()` leaves out
+/// every method.
+///
+/// Empty implementations, e.g. of marker traits, are mirrored on purpose.
+pub(crate) fn warn(ast: &ItemImpl, mirrored_ast: &ItemImpl) -> TokenStream {
+    let delegates = mirrored_ast
+        .items
+        .iter()
+        .any(|item| matches!(item, ImplItem::Method(_) | ImplItem::Type(_)));
+    if ast.items.is_empty() || delegates {
+        return TokenStream::new();
+    }
+    let (_, trait_path, _) = mirrored_ast.trait_.as_ref().unwrap();
+    let self_type = &mirrored_ast.self_ty;
+    let note = format!(
+        "the mirror `{}` is empty, no method or associated type of the implementation is mirrored",
+        crate::render(&quote!(impl #trait_path for #self_type)),
+    );
+    quote_spanned! {ast.self_ty.span()=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const symmetric_mirror_is_empty: () = ();
+            symmetric_mirror_is_empty
+        };
+    }
+}
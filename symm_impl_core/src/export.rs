@@ -0,0 +1,142 @@
+//! Exported `macro_rules!` instantiating the mirror of an implementation
+//! generic over the other operand, requested by
+//! `#[symmetric(export = mirror_distance)]`.
+//!
+//! The mirror of `impl<T: Shape> Distance<T> for Point2D` is implemented for
+//! any `T`, which the orphan rules reject unless the trait is local. Downstream
+//! crates owning the other operand instantiate it for their types instead, with
+//! `mirror_distance!(Square, Triangle)`.
+
+use proc_macro2::{Group, Ident, Punct, Spacing, TokenStream, TokenTree};
+use quote::quote;
+use syn::{parse_quote, Error, GenericParam, ItemImpl, Result, Type};
+
+use crate::options::MirrorOptions;
+
+/// Generate the exported macro in place of the mirror, if requested by the
+/// options.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Option<TokenStream>> {
+    let name = match &options.export {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let unsupported = [
+        ("in_mod", options.in_mod.is_some()),
+        ("by_ref", options.by_ref),
+        ("fan_out", !options.fan_out.is_empty()),
+        ("lift", !options.lift.is_empty()),
+        ("wrap", !options.wrap.is_empty()),
+        ("optional", options.optional.is_some()),
+        ("operator", !options.operator.is_empty()),
+        ("ffi", options.ffi.is_some()),
+        ("wasm_bindgen", options.wasm_bindgen.is_some()),
+        ("pymethods", options.pymethods),
+        ("markers", options.markers),
+        ("seal", options.seal.is_some()),
+        ("kani", options.kani),
+        ("proptest", options.proptest.is_some()),
+    ];
+    if let Some((option, _)) = unsupported.iter().find(|(_, given)| *given) {
+        return Err(Error::new(
+            name.span(),
+            format!("`{}` cannot be combined with `export`", option),
+        ));
+    }
+
+    // the other operand must be a type parameter of the implementation
+    let param = match mirrored_ast.self_ty.as_ref() {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident().filter(|ident| {
+            ast.generics
+                .type_params()
+                .any(|param| param.ident == **ident)
+        }),
+        _ => None,
+    };
+    let param = match param {
+        Some(param) => param.clone(),
+        None => {
+            return Err(Error::new_spanned(
+                &mirrored_ast.self_ty,
+                "`export` requires the other operand to be a type parameter of the implementation, e.g. `impl<T: Shape> Distance<T> for Point2D`",
+            ));
+        }
+    };
+
+    // the parameter is replaced by the types given to the macro, its bounds
+    // are kept in the where-clause
+    let mut mirror = mirrored_ast.clone();
+    let mut bounds = None;
+    mirror.generics.params = mirror
+        .generics
+        .params
+        .into_iter()
+        .filter(|generic| match generic {
+            GenericParam::Type(type_param) if type_param.ident == param => {
+                bounds = Some(type_param.bounds.clone());
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    if let Some(bounds) = bounds.filter(|bounds| !bounds.is_empty()) {
+        mirror
+            .generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#param: #bounds));
+    }
+    if mirror.generics.params.is_empty() {
+        mirror.generics.lt_token = None;
+        mirror.generics.gt_token = None;
+    }
+
+    let (_, trait_path, _) = ast.trait_.as_ref().unwrap();
+    let (_, mirrored_trait, _) = mirror.trait_.as_ref().unwrap();
+    let doc = format!(
+        " Implement `{}` for the given types, mirroring `impl {} for {}`.",
+        crate::render(mirrored_trait),
+        crate::render(trait_path),
+        crate::render(&ast.self_ty),
+    );
+    let body = substitute(quote!(const _: () = { #mirror };), &param);
+    Ok(Some(quote! {
+        #[doc = #doc]
+        #[macro_export]
+        macro_rules! #name {
+            ($($other:ty),+ $(,)?) => {
+                $( #body )+
+            };
+        }
+    }))
+}
+
+/// Replace the type parameter by the `$other` fragment of the macro, and the
+/// `crate` root of paths by `$crate`, such that they resolve to this crate
+/// where the macro is invoked.
+fn substitute(tokens: TokenStream, param: &Ident) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| -> Vec<TokenTree> {
+            match token {
+                TokenTree::Group(group) => {
+                    let mut substituted =
+                        Group::new(group.delimiter(), substitute(group.stream(), param));
+                    substituted.set_span(group.span());
+                    vec![substituted.into()]
+                }
+                TokenTree::Ident(ident) if ident == *param => vec![
+                    Punct::new('$', Spacing::Alone).into(),
+                    Ident::new("other", ident.span()).into(),
+                ],
+                TokenTree::Ident(ident) if ident == "crate" => {
+                    vec![Punct::new('$', Spacing::Alone).into(), ident.into()]
+                }
+                token => vec![token],
+            }
+        })
+        .collect()
+}
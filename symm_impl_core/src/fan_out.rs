@@ -0,0 +1,229 @@
+//! Implementations for pointers to the operands, requested by
+//! `#[symmetric(fan_out(&, &mut, Box))]`, e.g.
+//! `impl Distance<Disk> for &Point2D` delegating to
+//! `impl Distance<Disk> for Point2D`, along with their mirrors, e.g.
+//! `impl Distance<&Point2D> for Disk`.
+
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Error, Expr, FnArg, ImplItem, ImplItemMethod, ItemImpl, Lifetime, Path, Result,
+    Token, Type,
+};
+
+use crate::{attrs, bounds, options::MirrorOptions, render};
+
+/// A pointer of the `fan_out(...)` option.
+#[derive(Clone)]
+pub(crate) enum Pointer {
+    /// `&`
+    Shared,
+    /// `&mut`
+    Mutable,
+    /// A smart pointer dereferencing to the operand, e.g. `Box`.
+    Smart(Path),
+}
+
+impl Parse for Pointer {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![&]) {
+            input.parse::<Token![&]>()?;
+            if input.peek(Token![mut]) {
+                input.parse::<Token![mut]>()?;
+                return Ok(Pointer::Mutable);
+            }
+            return Ok(Pointer::Shared);
+        }
+        Ok(Pointer::Smart(input.parse()?))
+    }
+}
+
+impl Pointer {
+    /// How the pointer is written in `fan_out(...)`.
+    fn name(&self) -> String {
+        match self {
+            Pointer::Shared => String::from("&"),
+            Pointer::Mutable => String::from("&mut"),
+            Pointer::Smart(path) => render::render(path),
+        }
+    }
+
+    /// Whether an operand can be moved out of the pointer, which only holds
+    /// for `Box`.
+    fn owns(&self) -> bool {
+        matches!(self, Pointer::Smart(path) if path.segments.last().unwrap().ident == "Box")
+    }
+}
+
+/// Generate the implementations for the pointers listed in the options, for
+/// both the original implementation and its mirror, along with their mirrors.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemImpl>> {
+    if options.by_ref && options.fan_out.iter().any(|p| matches!(p, Pointer::Shared)) {
+        // both implement the trait for a reference to the other operand
+        return Err(Error::new(
+            proc_macro2::Span::call_site(),
+            "`fan_out(&)` cannot be combined with `by_ref`, which it includes",
+        ));
+    }
+    let mut generated = Vec::with_capacity(options.fan_out.len() * 4);
+    for (i, pointer) in options.fan_out.iter().enumerate() {
+        if options.fan_out[..i]
+            .iter()
+            .any(|listed| listed.name() == pointer.name())
+        {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                format!("duplicate pointer `{}` in `fan_out`", pointer.name()),
+            ));
+        }
+        for item in [ast, mirrored_ast] {
+            let pointed = pointed(item, ast, pointer, options)?;
+            // the mirror of the implementation for the pointer is documented
+            // after the original implementation
+            let mut source = pointed.clone();
+            source.attrs = ast.attrs.clone();
+            let mirrored = crate::mirror_impl(&source, options)?;
+            generated.push(pointed);
+            generated.push(mirrored);
+        }
+    }
+    Ok(generated)
+}
+
+/// Generate the implementation for a pointer to the self type of `item`,
+/// either the original implementation or its mirror.
+fn pointed(
+    item: &ItemImpl,
+    original: &ItemImpl,
+    pointer: &Pointer,
+    options: &MirrorOptions,
+) -> Result<ItemImpl> {
+    // the original and mirrored implementations were checked to be generic
+    // trait implementations
+    let (_, trait_path, for_token) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let lifetime: Lifetime = parse_quote!('__symm);
+    let pointer_type: Type = match pointer {
+        Pointer::Shared => parse_quote!(&#lifetime #self_type),
+        Pointer::Mutable => parse_quote!(&#lifetime mut #self_type),
+        Pointer::Smart(path) => parse_quote!(#path<#self_type>),
+    };
+
+    let mut items = Vec::with_capacity(item.items.len());
+    for impl_item in &item.items {
+        match impl_item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                items.push(ImplItem::Method(pointed_method(
+                    method, self_type, trait_path, pointer, options,
+                )?));
+            }
+            ImplItem::Type(associated_type) => {
+                let mut associated_type = associated_type.clone();
+                let type_ident = &associated_type.ident;
+                associated_type.attrs = attrs::forwarded(&associated_type.attrs, options);
+                associated_type.ty = parse_quote!(<#self_type as #trait_path>::#type_ident);
+                items.push(ImplItem::Type(associated_type));
+            }
+            ImplItem::Const(constant) => {
+                let mut constant = constant.clone();
+                let const_ident = &constant.ident;
+                constant.attrs = attrs::forwarded(&constant.attrs, options);
+                constant.expr = parse_quote!(<#self_type as #trait_path>::#const_ident);
+                items.push(ImplItem::Const(constant));
+            }
+            _ => (),
+        }
+    }
+
+    let (_, original_trait, _) = original.trait_.as_ref().unwrap();
+    let original_self = &original.self_ty;
+    let summary = format!(
+        "Counterpart for `{}` of [`{}`]({}).",
+        pointer.name(),
+        render::render(&quote! { impl #original_trait for #original_self }),
+        render::doc_link(original_trait),
+    );
+    let mut generics = item.generics.clone();
+    // `Self` in the bounds is the pointee, not the pointer
+    bounds::resolve_self_in(&mut generics, self_type, trait_path);
+    let mut attrs = attrs::generated_impl(&summary, &original.attrs, options);
+    if let Pointer::Shared | Pointer::Mutable = pointer {
+        generics.params.insert(0, parse_quote!(#lifetime));
+        // the lifetime is named for the associated items, which may not elide it
+        attrs.push(parse_quote!(#[allow(clippy::needless_lifetimes)]));
+    }
+    Ok(ItemImpl {
+        attrs,
+        defaultness: None,
+        unsafety: item.unsafety,
+        impl_token: item.impl_token,
+        generics,
+        trait_: Some((None, trait_path.clone(), *for_token)),
+        self_ty: Box::new(pointer_type),
+        brace_token: item.brace_token,
+        items,
+    })
+}
+
+/// Generate a method calling `method` with the operand behind the pointer.
+fn pointed_method(
+    method: &ImplItemMethod,
+    self_type: &Type,
+    trait_path: &Path,
+    pointer: &Pointer,
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    let mut sig = method.sig.clone();
+    let pointee: Option<Expr> = match &mut sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), None) => Some(parse_quote!(&**self)),
+            (Some(_), Some(_)) if !matches!(pointer, Pointer::Shared) => {
+                Some(parse_quote!(&mut **self))
+            }
+            (None, _) if pointer.owns() => {
+                // `self` is moved without being mutated
+                receiver.mutability = None;
+                Some(parse_quote!(*self))
+            }
+            _ => None,
+        },
+        FnArg::Typed(_) => None,
+    };
+    let pointee = match pointee {
+        Some(pointee) => pointee,
+        None => {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                format!(
+                    "`fan_out({})` cannot reach the operand from the receiver of this method",
+                    pointer.name()
+                ),
+            ));
+        }
+    };
+    if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+        *other_arg.pat = parse_quote!(other);
+    }
+
+    let method_name = &method.sig.ident;
+    let call: Expr = parse_quote!(<#self_type as #trait_path>::#method_name(#pointee, other));
+    let call: Expr = match method.sig.asyncness {
+        Some(_) => parse_quote!(#call.await),
+        None => call,
+    };
+    Ok(ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: None,
+        sig,
+        block: parse_quote! {
+            {
+                #call
+            }
+        },
+    })
+}
@@ -0,0 +1,159 @@
+//! `extern "C"` functions calling the implementations in both argument orders,
+//! requested by `#[symmetric(ffi(point, disk))]`.
+
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Attribute, Error, FnArg, Ident, ImplItem, ImplItemMethod, ItemFn, ItemImpl,
+    Result, ReturnType, Token, Type,
+};
+
+use crate::{attrs, bounds, options::MirrorOptions};
+
+/// The `ffi(self_name, other_name)` option, naming the operands in the names of
+/// the exported functions, also used by `wasm_bindgen(self_name, other_name)`.
+#[derive(Clone)]
+pub(crate) struct Ffi {
+    pub(crate) self_name: Ident,
+    pub(crate) other_name: Ident,
+}
+
+impl Parse for Ffi {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let self_name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let other_name = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Ffi {
+            self_name,
+            other_name,
+        })
+    }
+}
+
+/// Generate the exported functions of every mirrored method, named
+/// `<self_name>_<other_name>_<method>` and `<other_name>_<self_name>_<method>`,
+/// if requested by the options.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemFn>> {
+    let ffi = match &options.ffi {
+        Some(ffi) => ffi,
+        None => return Ok(Vec::new()),
+    };
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`ffi` does not support generic implementations",
+        ));
+    }
+    let mut functions = Vec::new();
+    for item in &ast.items {
+        match item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                functions.push(shim(ast, method, &ffi.self_name, &ffi.other_name, options)?);
+                functions.push(shim(
+                    mirrored_ast,
+                    method,
+                    &ffi.other_name,
+                    &ffi.self_name,
+                    options,
+                )?);
+            }
+            _ => (),
+        }
+    }
+    Ok(functions)
+}
+
+/// Generate the function calling `method` of `item` through pointers to the
+/// operands, or the operands themselves when taken by value.
+fn shim(
+    item: &ItemImpl,
+    method: &ImplItemMethod,
+    lhs_name: &Ident,
+    rhs_name: &Ident,
+    options: &MirrorOptions,
+) -> Result<ItemFn> {
+    if method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "`ffi` does not support async methods",
+        ));
+    }
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, parse_quote!(()));
+    let (lhs_type, rhs_type, lhs, rhs): (Type, Type, _, _) = match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => (
+                parse_quote!(*mut #self_type),
+                parse_quote!(*mut #other_type),
+                quote!(&mut *lhs),
+                quote!(&mut *rhs),
+            ),
+            (Some(_), None) => (
+                parse_quote!(*const #self_type),
+                parse_quote!(*const #other_type),
+                quote!(&*lhs),
+                quote!(&*rhs),
+            ),
+            (None, _) => (
+                self_type.clone(),
+                other_type.clone(),
+                quote!(lhs),
+                quote!(rhs),
+            ),
+        },
+        FnArg::Typed(_) => unreachable!(),
+    };
+    let output = match &method.sig.output {
+        ReturnType::Type(arrow, ty) => {
+            let ty = bounds::resolve_self(ty, self_type, trait_path);
+            quote!(#arrow #ty)
+        }
+        ReturnType::Default => quote!(),
+    };
+
+    let method_name = &method.sig.ident;
+    let name = format_ident!("{}_{}_{}", lhs_name, rhs_name, method_name);
+    let summary = format!(
+        " Call `{}` of `{}` with `{}` as the other operand.",
+        method_name,
+        crate::render(self_type),
+        crate::render(&other_type),
+    );
+    let mut fn_attrs: Vec<Attribute> = method
+        .attrs
+        .iter()
+        .chain(&item.attrs)
+        .filter(|attr| attrs::is_cfg_like(attr))
+        .cloned()
+        .collect();
+    if let Some(predicate) = &options.cfg {
+        fn_attrs.push(parse_quote!(#[cfg(#predicate)]));
+    }
+    let safety = if matches!(lhs_type, Type::Ptr(_)) {
+        quote! {
+            #[doc = ""]
+            #[doc = " # Safety"]
+            #[doc = ""]
+            #[doc = " Both pointers must be valid for the access, aligned, and point to"]
+            #[doc = " initialized values."]
+        }
+    } else {
+        quote!()
+    };
+    Ok(parse_quote! {
+        #[doc = #summary]
+        #safety
+        #(#fn_attrs)*
+        #[no_mangle]
+        pub unsafe extern "C" fn #name(lhs: #lhs_type, rhs: #rhs_type) #output {
+            <#self_type as #trait_path>::#method_name(#lhs, #rhs)
+        }
+    })
+}
@@ -0,0 +1,380 @@
+//! Folding helpers of commutative operations, requested by
+//! `#[commutative(fold = combine_all)]`, e.g.
+//! `fn combine_all<I: IntoIterator<Item = Bounds>>(iter: I) -> Option<Bounds>`
+//! combining items with `impl Merge<Bounds> for Bounds`.
+//!
+//! The operation is declared commutative and associative, so the result does
+//! not depend on the order of the items, and collections without a
+//! meaningful order, e.g. `HashSet`, can be folded as well.
+//!
+//! With an identity element, given by `identity = expr`, the operation is a
+//! commutative monoid: folding no items gives the identity element, which can
+//! also be the `Default` of the type.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_quote, token, Error, Expr, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl, Path,
+    Result, ReturnType, Token, Type, Visibility,
+};
+
+use crate::{attrs, options::MirrorOptions, proptest::Proptest, render::render};
+
+/// Arguments of
+/// `#[commutative(fold = [vis] name, method = name, identity = expr, default, proptest)]`.
+struct CommutativeArgs {
+    /// Visibility and name of the folding function.
+    fold: Option<(Visibility, Ident)>,
+    /// The method combining two operands, needed if the implementation has
+    /// several methods.
+    method: Option<Ident>,
+    /// The identity element of the operation, making it a monoid.
+    identity: Option<Expr>,
+    /// Whether `Default` is implemented with the identity element.
+    default: bool,
+    /// Whether property tests check the laws of the operation.
+    proptest: Option<Proptest>,
+}
+
+impl Parse for CommutativeArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = CommutativeArgs {
+            fold: None,
+            method: None,
+            identity: None,
+            default: false,
+            proptest: None,
+        };
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            match name.to_string().as_str() {
+                "default" if !args.default => args.default = true,
+                "fold" if args.fold.is_none() => {
+                    input.parse::<Token![=]>()?;
+                    args.fold = Some((input.parse()?, input.parse()?));
+                }
+                "method" if args.method.is_none() => {
+                    input.parse::<Token![=]>()?;
+                    args.method = Some(input.parse()?);
+                }
+                "identity" if args.identity.is_none() => {
+                    input.parse::<Token![=]>()?;
+                    args.identity = Some(input.parse()?);
+                }
+                "proptest" if args.proptest.is_none() => {
+                    args.proptest = Some(if input.peek(token::Paren) {
+                        let content;
+                        parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        Proptest::default()
+                    });
+                }
+                "default" | "fold" | "method" | "identity" | "proptest" => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("duplicate option `{}`", name),
+                    ));
+                }
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}`", name),
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if args.default && args.identity.is_none() {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "`default` requires the identity element given by `identity`",
+            ));
+        }
+        Ok(args)
+    }
+}
+
+/// Expand `#[commutative]`, emitting the implementation along with the
+/// helpers of the operation.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut expanded = item.clone();
+    let generated = syn::parse2::<CommutativeArgs>(attr).and_then(|args| {
+        let ast = syn::parse2::<ItemImpl>(item)?;
+        helpers(&ast, &args)
+    });
+    match generated {
+        Ok(generated) => expanded.extend(generated),
+        Err(error) => expanded.extend(error.to_compile_error()),
+    }
+    expanded
+}
+
+/// Generate the helpers requested by the arguments.
+fn helpers(ast: &ItemImpl, args: &CommutativeArgs) -> Result<TokenStream> {
+    let trait_path = homogeneous(ast, "#[commutative]")?;
+    let method = select(ast, args.method.as_ref(), "#[commutative]")?;
+    let returns_self = match &method.sig.output {
+        ReturnType::Type(_, output) => {
+            render(output.as_ref()) == render(ast.self_ty.as_ref())
+                || matches!(output.as_ref(), Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+        }
+        ReturnType::Default => false,
+    };
+    if !returns_self {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[commutative] requires a method combining two operands into a new one",
+        ));
+    }
+    let mut generated = match &args.fold {
+        Some((vis, name)) => fold(ast, trait_path, method, vis, name, args.identity.as_ref())?,
+        None => TokenStream::new(),
+    };
+    if let (true, Some(identity)) = (args.default, &args.identity) {
+        let default = default(ast, trait_path, identity);
+        generated.extend(crate::scope(vec![default], &MirrorOptions::default()));
+    }
+    if let Some(proptest) = &args.proptest {
+        generated.extend(laws(ast, method, args.identity.as_ref(), proptest)?);
+    }
+    Ok(generated)
+}
+
+/// The trait of an implementation of an operation of a type with itself, as
+/// required by the attribute named `attr`.
+pub(crate) fn homogeneous<'a>(ast: &'a ItemImpl, attr: &str) -> Result<&'a Path> {
+    let trait_path = match &ast.trait_ {
+        Some((None, trait_path, _)) => trait_path,
+        _ => {
+            return Err(Error::new_spanned(
+                &ast.self_ty,
+                format!("{} can only be used on trait implementation", attr),
+            ));
+        }
+    };
+    let self_type = ast.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, self_type.clone());
+    if render(&other_type) != render(self_type) {
+        return Err(Error::new_spanned(
+            trait_path,
+            format!(
+                "{} requires an operation of `{}` with itself",
+                attr,
+                render(self_type)
+            ),
+        ));
+    }
+    Ok(trait_path)
+}
+
+/// The method of the implementation taking two operands, either the only
+/// method or the one named by `method`.
+pub(crate) fn select<'a>(
+    ast: &'a ItemImpl,
+    method: Option<&Ident>,
+    attr: &str,
+) -> Result<&'a ImplItemMethod> {
+    let mut methods = ast.items.iter().filter_map(|item| match item {
+        ImplItem::Method(method) => Some(method),
+        _ => None,
+    });
+    let method = match method {
+        Some(name) => methods
+            .find(|method| method.sig.ident == *name)
+            .ok_or_else(|| {
+                Error::new(
+                    name.span(),
+                    format!("method `{}` is not in the implementation", name),
+                )
+            })?,
+        None => match (methods.next(), methods.next()) {
+            (Some(method), None) => method,
+            _ => {
+                return Err(Error::new_spanned(
+                    &ast.self_ty,
+                    format!(
+                        "{} requires a single method, or the operation given by `method`",
+                        attr
+                    ),
+                ));
+            }
+        },
+    };
+    if method.sig.inputs.len() != 2 || method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            format!("{} requires a method taking two operands", attr),
+        ));
+    }
+    Ok(method)
+}
+
+/// Generate the function folding the items of an iterator with the
+/// operation, starting from the identity element if any, or giving `None` for
+/// no items otherwise.
+fn fold(
+    ast: &ItemImpl,
+    trait_path: &Path,
+    method: &ImplItemMethod,
+    vis: &Visibility,
+    name: &Ident,
+    identity: Option<&Expr>,
+) -> Result<TokenStream> {
+    let self_type = ast.self_ty.as_ref();
+    let method_name = &method.sig.ident;
+    let (acc, item) = match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (None, _) => (quote!(acc), quote!(item)),
+            (Some(_), None) => (quote!(&acc), quote!(&item)),
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    method.sig.ident.span(),
+                    "#[commutative] does not support methods taking `&mut self`",
+                ));
+            }
+        },
+        FnArg::Typed(_) => {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                "#[commutative] requires a method taking `self`",
+            ));
+        }
+    };
+    let iter = format_ident!("__SymmIter");
+    let mut generics = ast.generics.clone();
+    generics
+        .params
+        .push(parse_quote!(#iter: ::core::iter::IntoIterator<Item = #self_type>));
+    let (fn_generics, _, where_clause) = generics.split_for_impl();
+    let combine = quote!(|acc, item| <#self_type as #trait_path>::#method_name(#acc, #item));
+    let iter_expr = quote!(::core::iter::IntoIterator::into_iter(iter));
+    let (output, body, empty) = match identity {
+        Some(identity) => (
+            quote!(#self_type),
+            quote!(::core::iter::Iterator::fold(#iter_expr, #identity, #combine)),
+            "the identity element",
+        ),
+        None => (
+            quote!(::core::option::Option<#self_type>),
+            quote!(::core::iter::Iterator::reduce(#iter_expr, #combine)),
+            "`None`",
+        ),
+    };
+    let doc = format!(
+        " Combine all the items of `iter` with `{}` of [`{}`]({}), or {} if there is no item.",
+        method_name,
+        render(trait_path),
+        crate::render::doc_link(trait_path),
+        empty,
+    );
+    let impl_attrs = ast.attrs.iter().filter(|attr| attrs::is_cfg_like(attr));
+    Ok(quote! {
+        #(#impl_attrs)*
+        #[doc = #doc]
+        #vis fn #name #fn_generics(iter: #iter) -> #output #where_clause {
+            #body
+        }
+    })
+}
+
+/// Generate the implementation of `Default` giving the identity element.
+fn default(ast: &ItemImpl, trait_path: &Path, identity: &Expr) -> ItemImpl {
+    let self_type = ast.self_ty.as_ref();
+    let (impl_generics, _, where_clause) = ast.generics.split_for_impl();
+    let summary = format!(
+        "Identity element of [`{}`]({}).",
+        render(&quote! { impl #trait_path for #self_type }),
+        crate::render::doc_link(trait_path),
+    );
+    let impl_attrs = attrs::generated_impl(&summary, &ast.attrs, &MirrorOptions::default());
+    parse_quote! {
+        #(#impl_attrs)*
+        impl #impl_generics ::core::default::Default for #self_type #where_clause {
+            fn default() -> Self {
+                #identity
+            }
+        }
+    }
+}
+
+/// Generate the property tests of the laws of the operation: commutativity,
+/// associativity, and identity if the identity element is given.
+fn laws(
+    ast: &ItemImpl,
+    method: &ImplItemMethod,
+    identity: Option<&Expr>,
+    proptest: &Proptest,
+) -> Result<TokenStream> {
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`proptest` does not support generic implementations",
+        ));
+    }
+    let (_, trait_path, _) = ast.trait_.as_ref().unwrap();
+    let self_type = ast.self_ty.as_ref();
+    let method_name = &method.sig.ident;
+    let operation = quote!(<#self_type as #trait_path>::#method_name);
+    let call = |lhs: TokenStream, rhs: TokenStream| quote!(#operation(#lhs, #rhs));
+    let a = crate::proptest::operand(method, &quote!(a))?;
+    let b = crate::proptest::operand(method, &quote!(b))?;
+    let c = crate::proptest::operand(method, &quote!(c))?;
+    // results of the operation are passed the same way as the operands, but
+    // need not be cloned
+    let by_ref =
+        matches!(&method.sig.inputs[0], FnArg::Receiver(receiver) if receiver.reference.is_some());
+    let temporary = |result: TokenStream| {
+        if by_ref {
+            quote!(&#result)
+        } else {
+            result
+        }
+    };
+    let strategy = proptest.strategy(self_type);
+    let trait_name = render(trait_path);
+
+    let mut tests = Vec::with_capacity(3);
+    let name = format_ident!("{}_is_commutative", method_name);
+    let (lhs, rhs) = (call(a.clone(), b.clone()), call(b.clone(), a.clone()));
+    let message = format!("`{}` of `{}` is not commutative", method_name, trait_name);
+    tests.push(quote! {
+        #[test]
+        fn #name(a in #strategy, b in #strategy) {
+            ::proptest::prop_assert!(#lhs == #rhs, #message,);
+        }
+    });
+    let name = format_ident!("{}_is_associative", method_name);
+    let lhs = call(temporary(call(a.clone(), b.clone())), c.clone());
+    let rhs = call(a.clone(), temporary(call(b, c)));
+    let message = format!("`{}` of `{}` is not associative", method_name, trait_name);
+    tests.push(quote! {
+        #[test]
+        fn #name(a in #strategy, b in #strategy, c in #strategy) {
+            ::proptest::prop_assert!(#lhs == #rhs, #message,);
+        }
+    });
+    if let Some(identity) = identity {
+        let name = format_ident!("{}_has_identity", method_name);
+        let combined = call(a, temporary(quote!((#identity))));
+        let message = format!(
+            "`{}` is not the identity element of `{}` of `{}`",
+            render(identity),
+            method_name,
+            trait_name
+        );
+        tests.push(quote! {
+            #[test]
+            fn #name(a in #strategy) {
+                ::proptest::prop_assert!(#combined == a, #message,);
+            }
+        });
+    }
+    Ok(crate::proptest::module(ast, "commutative", None, &tests))
+}
@@ -0,0 +1,123 @@
+//! Options inherited from a module, requested by
+//! `#[symmetric_options(...)]` on an inline module.
+//!
+//! The options of the module are handed to every nested `#[symmetric]`, as a
+//! hidden `__symm_module_defaults { ... }` group ahead of its own options,
+//! which override them. Nested modules with their own `#[symmetric_options]`
+//! are handed the options of the enclosing module the same way, and pass both
+//! on to their implementations.
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+    braced, ext::IdentExt, parse::ParseStream, Attribute, Error, Ident, Item, ItemMod, Result,
+    Token,
+};
+
+use crate::options::MirrorOptions;
+
+/// Name of the group of options inherited from a module.
+pub(crate) const MODULE_DEFAULTS: &str = "__symm_module_defaults";
+
+/// Expand `#[symmetric_options]`, handing the options to the implementations
+/// of the module.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // the options are checked once, rather than by every implementation
+    if let Err(error) = syn::parse2::<MirrorOptions>(attr.clone()) {
+        let mut expanded = item;
+        expanded.extend(error.to_compile_error());
+        return expanded;
+    }
+    let mut ast = match syn::parse2::<ItemMod>(item) {
+        Ok(ast) => ast,
+        Err(error) => return error.to_compile_error(),
+    };
+    match &mut ast.content {
+        Some((_, items)) => hand_down(items, &attr),
+        None => {
+            return Error::new_spanned(
+                &ast,
+                "#[symmetric_options] can only be used on inline modules",
+            )
+            .to_compile_error();
+        }
+    }
+    quote!(#ast)
+}
+
+/// Hand the options down to the implementations in `items`, and to the nested
+/// modules.
+fn hand_down(items: &mut [Item], options: &TokenStream) {
+    for item in items {
+        match item {
+            Item::Impl(item_impl) => {
+                for attr in &mut item_impl.attrs {
+                    if is_attr(attr, &["symmetric", "symm"]) {
+                        inherit(attr, options);
+                    }
+                }
+            }
+            Item::Mod(item_mod) => {
+                let nested = item_mod
+                    .attrs
+                    .iter_mut()
+                    .find(|attr| is_attr(attr, &["symmetric_options"]));
+                match (nested, &mut item_mod.content) {
+                    // the nested module hands both down itself
+                    (Some(attr), _) => inherit(attr, options),
+                    (None, Some((_, items))) => hand_down(items, options),
+                    (None, None) => (),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Whether the attribute is one of the named attributes of this crate,
+/// possibly through a path, e.g. `symm_impl::symmetric`.
+fn is_attr(attr: &Attribute, names: &[&str]) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| names.iter().any(|name| segment.ident == name))
+}
+
+/// Put the inherited options ahead of the options of the attribute.
+fn inherit(attr: &mut Attribute, options: &TokenStream) {
+    let group = format_ident!("{}", MODULE_DEFAULTS);
+    let own = attr
+        .tokens
+        .clone()
+        .into_iter()
+        .next()
+        .and_then(|tree| match tree {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                Some(group.stream())
+            }
+            _ => None,
+        });
+    attr.tokens = match own {
+        Some(own) if !own.is_empty() => quote!((#group { #options }, #own)),
+        _ => quote!((#group { #options })),
+    };
+}
+
+/// The options inherited from the enclosing modules, if given at the start of
+/// `input`, on top of `defaults`.
+pub(crate) fn inherited(defaults: MirrorOptions, input: ParseStream) -> Result<MirrorOptions> {
+    let fork = input.fork();
+    match fork.call(Ident::parse_any) {
+        Ok(ident) if ident == MODULE_DEFAULTS && fork.peek(syn::token::Brace) => (),
+        _ => return Ok(defaults),
+    }
+    input.call(Ident::parse_any)?;
+    let content;
+    braced!(content in input);
+    let options = inherited(defaults, &content)?;
+    let options = MirrorOptions::parse_onto(options, &content)?;
+    if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+    }
+    Ok(options)
+}
@@ -0,0 +1,44 @@
+//! `tracing` spans around the delegated calls of the mirror, requested by
+//! `#[symmetric(instrument)]`, telling which direction of the operation ran.
+
+use syn::{parse_quote, Expr, ImplItemMethod, Path, Type};
+
+use crate::render::render;
+
+/// Wrap the call of the original method in a span recording the operation, the
+/// operands of the mirror and the direction.
+///
+/// The span is entered around synchronous calls, and instruments the future
+/// of async ones.
+pub(crate) fn instrumented(
+    call: Expr,
+    method: &ImplItemMethod,
+    self_type: &Type,
+    other_type: &Type,
+    original_trait: &Path,
+) -> Expr {
+    let operation = format!(
+        "<{} as {}>::{}",
+        render(self_type),
+        render(original_trait),
+        method.sig.ident
+    );
+    let mirror_self = render(other_type);
+    let mirror_other = render(self_type);
+    let span = quote::quote! {
+        ::tracing::trace_span!(
+            "symmetric",
+            operation = #operation,
+            self_type = #mirror_self,
+            other_type = #mirror_other,
+            direction = "mirrored",
+        )
+    };
+    match method.sig.asyncness {
+        Some(_) => parse_quote!(::tracing::Instrument::instrument(#call, #span)),
+        None => parse_quote! {{
+            let _span = #span.entered();
+            #call
+        }},
+    }
+}
@@ -0,0 +1,126 @@
+//! Machine-readable inventory of the generated mirrors, written into
+//! `$OUT_DIR/symm_impl.json` when the `SYMM_IMPL_INVENTORY` environment
+//! variable is set, e.g. by `cargo:rustc-env=SYMM_IMPL_INVENTORY=1` from the
+//! build script of the crate.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    env, fs,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use quote::quote;
+use syn::{ImplItem, ItemImpl};
+
+use crate::{options::MirrorOptions, render::render};
+
+/// Name of the environment variable enabling the inventory.
+const INVENTORY_VAR: &str = "SYMM_IMPL_INVENTORY";
+
+/// Name of the inventory file in `OUT_DIR`.
+const INVENTORY_FILE: &str = "symm_impl.json";
+
+/// Entries recorded by this compiler process, by inventory file.
+///
+/// Every expansion of a crate happens in the same process, so the file is
+/// rewritten with every entry so far, and entries of implementations removed
+/// since the last compilation do not linger. Entries are sorted, such that the
+/// file does not depend on the order of expansion.
+static ENTRIES: Mutex<Option<HashMap<PathBuf, BTreeSet<String>>>> = Mutex::new(None);
+
+/// Record the mirror of an implementation, as generated by the expansion, into
+/// the inventory, if enabled.
+///
+/// Crates without a build script have no `OUT_DIR`, and are skipped.
+pub(crate) fn record(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<(), String> {
+    match env::var_os(INVENTORY_VAR) {
+        Some(enabled) if !enabled.is_empty() => (),
+        _ => return Ok(()),
+    }
+    let path = match env::var_os("OUT_DIR") {
+        Some(dir) => PathBuf::from(dir).join(INVENTORY_FILE),
+        None => return Ok(()),
+    };
+    if options.export.is_some() {
+        // the mirror is implemented by downstream crates
+        return Ok(());
+    }
+
+    let entry = entry(ast, mirrored_ast);
+    let mut entries = ENTRIES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let recorded = entries
+        .get_or_insert_with(HashMap::new)
+        .entry(path.clone())
+        .or_default();
+    recorded.insert(entry);
+
+    let mut contents = String::from("[\n");
+    for (index, entry) in recorded.iter().enumerate() {
+        if index > 0 {
+            contents.push_str(",\n");
+        }
+        contents.push_str("  ");
+        contents.push_str(entry);
+    }
+    contents.push_str("\n]\n");
+    fs::write(&path, contents).map_err(|error| {
+        format!(
+            "failed to write the inventory to `{}`: {}",
+            path.display(),
+            error
+        )
+    })
+}
+
+/// Describe the mirror as a JSON object.
+fn entry(ast: &ItemImpl, mirrored_ast: &ItemImpl) -> String {
+    let (_, trait_path, _) = ast.trait_.as_ref().unwrap();
+    let (_, mirrored_trait, _) = mirrored_ast.trait_.as_ref().unwrap();
+    let self_type = &ast.self_ty;
+    // implementations for a pair have no other operand
+    let other_type = if crate::tuple::is_tuple_impl(ast, trait_path) {
+        String::from("null")
+    } else {
+        json_string(&render(self_type))
+    };
+    let methods: Vec<_> = mirrored_ast
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Method(method) => Some(json_string(&method.sig.ident.to_string())),
+            _ => None,
+        })
+        .collect();
+    format!(
+        "{{\"trait\": {}, \"self_type\": {}, \"other_type\": {}, \"methods\": [{}], \"original\": {}}}",
+        json_string(&render(mirrored_trait)),
+        json_string(&render(&mirrored_ast.self_ty)),
+        other_type,
+        methods.join(", "),
+        json_string(&render(&quote! { impl #trait_path for #self_type })),
+    )
+}
+
+/// Quote a string as a JSON string literal.
+fn json_string(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for ch in string.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
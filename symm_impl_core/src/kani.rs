@@ -0,0 +1,116 @@
+//! Kani proof harnesses checking that the operation is symmetric, requested by
+//! `#[symmetric(kani)]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Error, FnArg, ImplItem, ImplItemMethod, ItemFn, ItemImpl, Result};
+
+use crate::{attrs, options::MirrorOptions};
+
+/// Generate the harnesses of every mirrored method, only compiled by
+/// `cargo kani`, if requested by the options.
+pub(crate) fn generate(ast: &ItemImpl, options: &MirrorOptions) -> Result<TokenStream> {
+    if !options.kani {
+        return Ok(TokenStream::new());
+    }
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`kani` does not support generic implementations",
+        ));
+    }
+    let mut harnesses = Vec::new();
+    for item in &ast.items {
+        match item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                harnesses.push(harness(ast, method, options)?);
+            }
+            _ => (),
+        }
+    }
+    let cfg = options
+        .cfg
+        .as_ref()
+        .map(|predicate| quote!(#[cfg(#predicate)]));
+    // `kani` is set by `cargo kani`, and unknown to cargo otherwise
+    Ok(quote! {
+        #[allow(unexpected_cfgs)]
+        #cfg
+        const _: () = {
+            #(#harnesses)*
+        };
+    })
+}
+
+/// Generate the harness asserting that `method` gives the same result with the
+/// operands in both orders, or the negated result for antisymmetric relations.
+fn harness(item: &ItemImpl, method: &ImplItemMethod, options: &MirrorOptions) -> Result<ItemFn> {
+    if method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "`kani` does not support async methods",
+        ));
+    }
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, self_type.clone());
+    let method_name = &method.sig.ident;
+    let (lhs, rhs) = match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    method.sig.ident.span(),
+                    "`kani` does not support methods taking `&mut self`",
+                ));
+            }
+            (Some(_), None) => (quote!(&lhs), quote!(&rhs)),
+            // the operands are used twice
+            (None, _) => (
+                quote!(::core::clone::Clone::clone(&lhs)),
+                quote!(::core::clone::Clone::clone(&rhs)),
+            ),
+        },
+        FnArg::Typed(_) => unreachable!(),
+    };
+    // the mirror of a paired method delegates to its counterpart
+    let mirrored_name = options.counterpart(method_name).unwrap_or(method_name);
+    let mirrored_call = quote!(<#other_type as #other_path>::#mirrored_name(#rhs, #lhs));
+    // the mirror flips the results, which are flipped back for the comparison
+    let mirrored_call = if options.swap_output {
+        let runtime_path = &options.runtime_path;
+        quote!(#runtime_path::SwapOperands::swap_operands(#mirrored_call))
+    } else {
+        mirrored_call
+    };
+    let (mirrored, relation) = if options.antisymmetric {
+        (quote!(-#mirrored_call), "antisymmetric")
+    } else {
+        (quote!(#mirrored_call), "symmetric")
+    };
+    let message = format!(
+        "`{}` of `{}` is not {}",
+        method_name,
+        crate::render(trait_path),
+        relation
+    );
+    let name = format_ident!("{}_is_{}", method_name, relation);
+    let fn_attrs = method
+        .attrs
+        .iter()
+        .chain(&item.attrs)
+        .filter(|attr| attrs::is_cfg_like(attr));
+    Ok(parse_quote! {
+        #(#fn_attrs)*
+        #[cfg(kani)]
+        #[kani::proof]
+        fn #name() {
+            let lhs: #self_type = kani::any();
+            let rhs: #other_type = kani::any();
+            ::core::assert!(
+                <#self_type as #trait_path>::#method_name(#lhs, #rhs) == #mirrored,
+                #message,
+            );
+        }
+    })
+}
@@ -0,0 +1,978 @@
+//! Implementation of the `#[symmetric]` attribute of [`symm_impl`], as a
+//! regular library.
+//!
+//! The transform is shared by the attribute macro and by `symm_impl_build`,
+//! which generates mirrors from a build script. It is also meant for authors
+//! of other procedural macros reusing the mirroring of symmetric
+//! implementations, see [`mirror`]. Refer to the documentation of
+//! [`symm_impl`] for the requirements on the implementation and the accepted
+//! options. Tools only checking the shape of an implementation can use
+//! [`validate_symmetric_impl`], and tools reporting the mirrors of a crate
+//! [`mirrored`]. Crates using `#[symmetric]` can keep snapshots of the
+//! generated code with [`assert_expansion_snapshot`], to review how upgrades
+//! change their mirrors.
+//!
+//! # Example
+//! ```
+//! use symm_impl_core::{mirror, render, MirrorOptions};
+//! use syn::{parse_quote, ItemImpl};
+//!
+//! let original: ItemImpl = parse_quote! {
+//!     impl Distance<Disk> for Point2D {
+//!         fn distance(&self, other: &Disk) -> f64 {
+//!             self.distance(&other.center) - other.radius
+//!         }
+//!     }
+//! };
+//! let options: MirrorOptions = syn::parse_str("call = \"method\"").unwrap();
+//! let mirrored = mirror(original, options).unwrap();
+//! assert!(render(&mirrored).contains("impl Distance<Point2D> for Disk"));
+//! ```
+//!
+//! [`symm_impl`]: https://docs.rs/symm_impl
+
+mod approx;
+mod attrs;
+mod bench;
+mod borrow;
+mod bounds;
+mod check;
+mod complement;
+mod config;
+mod defer;
+mod dispatch;
+mod dump;
+mod empty;
+mod export;
+mod fan_out;
+mod ffi;
+mod fold;
+mod inherit;
+mod instrument;
+mod inventory;
+mod kani;
+mod lift;
+mod markers;
+mod operators;
+mod optional;
+mod options;
+mod paths;
+mod proptest;
+mod provided;
+mod pymethods;
+mod remote;
+mod render;
+mod reverse;
+mod rewrite;
+mod seal;
+mod snapshot;
+mod strict;
+mod stub;
+mod transitive;
+mod tuple;
+mod validate;
+mod visitor;
+mod wasm;
+mod wrap;
+
+pub use crate::{
+    approx::expand as expand_approx,
+    bench::expand as expand_bench,
+    check::expand as expand_check,
+    complement::expand as expand_complement,
+    dispatch::{expand_derive as expand_dispatch_derive, expand_enum as expand_symmetrize_enum},
+    fold::expand as expand_commutative,
+    inherit::expand as expand_symmetric_options,
+    options::MirrorOptions,
+    render::render,
+    reverse::expand as expand_reverse,
+    snapshot::assert_expansion_snapshot,
+    strict::{check_strict, expand_trait},
+    transitive::expand as expand_transitive,
+    validate::{validate_symmetric_impl, SymmetryInfo},
+    visitor::expand as expand_visitor,
+};
+
+use std::mem;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{ParseStream, Parser},
+    parse_quote,
+    spanned::Spanned,
+    AngleBracketedGenericArguments, Block, Error, Expr, FnArg, GenericArgument, ImplItem,
+    ImplItemMethod, ImplItemType, ItemImpl, Pat, PatIdent, PathArguments, Receiver, Result,
+    ReturnType, Signature, Type, TypeParamBound,
+};
+
+use crate::options::CallStyle;
+
+/// Expand `#[symmetric]` purely in terms of `proc_macro2`.
+///
+/// Keeping the expansion independent of `proc_macro` allows it to be driven
+/// outside of a compiler invocation, e.g. from a precompiled macro runtime.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // the original implementation is emitted verbatim, token streams are
+    // reference counted so this clone is cheap
+    let mut expanded = item.clone();
+    let ast = match syn::parse2::<ItemImpl>(item) {
+        Ok(ast) => ast,
+        Err(error) => return error.to_compile_error(),
+    };
+    let options = match configured(attr.clone()) {
+        Ok(options) => options,
+        Err(error) => {
+            expanded.extend(error.to_compile_error());
+            // the stub follows the options which could be parsed
+            let options = MirrorOptions::from_config()
+                .ok()
+                .and_then(|defaults| MirrorOptions::recover(defaults, attr));
+            if let Some(stub) = options.and_then(|options| stub(&ast, &options)) {
+                expanded.extend(stub);
+            }
+            return expanded;
+        }
+    };
+
+    if defer::should_defer(&ast, &options) {
+        return defer::reemit(attr, ast, &options);
+    }
+
+    let ast = match &options.remote {
+        // the implementation is rewritten for the local wrapper, since it is
+        // rejected by the orphan rules as written
+        Some(remote) => match remote::localize(ast, remote) {
+            Ok((declaration, ast)) => {
+                expanded = quote!(#declaration #ast);
+                ast
+            }
+            Err(error) => {
+                expanded.extend(error.to_compile_error());
+                return expanded;
+            }
+        },
+        None => ast,
+    };
+
+    expanded.extend(strict::check(&ast, &options));
+    match mirror_scoped(&ast, &options) {
+        // the mirror is written by hand, or not at all
+        Ok(_) if options.check_only => (),
+        Ok((mirrored_ast, generated)) => {
+            let written = dump::dump(&ast, &mirrored_ast, &generated)
+                .and_then(|_| inventory::record(&ast, &mirrored_ast, &options));
+            if let Err(message) = written {
+                expanded.extend(Error::new(Span::call_site(), message).to_compile_error());
+            }
+            expanded.extend(generated);
+        }
+        Err(error) => {
+            expanded.extend(error.to_compile_error());
+            if let Some(stub) = stub(&ast, &options) {
+                expanded.extend(stub);
+            }
+        }
+    }
+
+    expanded
+}
+
+/// The stub standing for the mirror of `ast` which failed, such that code
+/// relying on the mirror is not flooded with errors, unless it would conflict
+/// with a hand-written mirror, or with the ones instantiated by downstream
+/// crates.
+fn stub(ast: &ItemImpl, options: &MirrorOptions) -> Option<TokenStream> {
+    if options.check_only || options.export.is_some() {
+        return None;
+    }
+    stub::generate(ast, options).map(|stub| scope(vec![stub], options))
+}
+
+/// Generate the mirror of an implementation, without the implementation
+/// itself.
+///
+/// This is the output of `#[symmetric(attr)]` on `ast` minus the original
+/// implementation, for callers emitting the mirror somewhere else than next to
+/// the implementation, e.g. into a file included by the crate. The crate-wide
+/// defaults apply as they do to the attribute.
+pub fn generate(attr: TokenStream, ast: &ItemImpl) -> Result<TokenStream> {
+    let options = configured(attr)?;
+    if options.remote.is_some() {
+        return Err(remote::unsupported());
+    }
+    let mut generated = strict::check(ast, &options);
+    let (_, mirror) = mirror_scoped(ast, &options)?;
+    if !options.check_only {
+        generated.extend(mirror);
+    }
+    Ok(generated)
+}
+
+/// Parse the options of `#[symmetric(attr)]` on top of the crate-wide
+/// defaults, which only the entry points of the attribute read.
+fn configured(attr: TokenStream) -> Result<MirrorOptions> {
+    parse_options(MirrorOptions::from_config()?, attr)
+}
+
+/// Parse the options of an attribute on top of the crate-wide `defaults`.
+fn parse_options(defaults: MirrorOptions, attr: TokenStream) -> Result<MirrorOptions> {
+    let parser = |input: ParseStream| MirrorOptions::parse_with(defaults, input);
+    parser.parse2(attr)
+}
+
+/// Generate the mirror implementation of a symmetric trait implementation.
+///
+/// The result is the implementation `#[symmetric]` adds next to `item`, such
+/// that it can be emitted by another procedural macro. Options about where the
+/// mirror is emitted, i.e. `in_mod`, are left to the caller, while options about
+/// the mirror itself are applied. Errors are spanned on the offending part of
+/// `item`, and can be reported with [`syn::Error::to_compile_error`].
+pub fn mirror(item: ItemImpl, options: MirrorOptions) -> Result<ItemImpl> {
+    mirror_impl(&item, &options)
+}
+
+/// The implementations `#[symmetric]` leaves in a crate, as reported by tools
+/// auditing the mirrors of a crate, e.g. `cargo symm`.
+pub struct Mirrored {
+    /// The implementation written by hand, rewritten for the local wrapper
+    /// with `remote`.
+    pub original: ItemImpl,
+    /// The mirror of the implementation.
+    pub mirror: ItemImpl,
+    /// The module the mirror is emitted in, with `in_mod`.
+    pub module: Option<Ident>,
+    /// Whether the mirror is emitted by the attribute, rather than written by
+    /// hand with `check_only`, or instantiated by downstream crates with
+    /// `export`.
+    pub emitted: bool,
+}
+
+/// Generate the mirror of `ast` as `#[symmetric(attr)]` would in a crate
+/// whose crate-wide defaults are `defaults`, e.g. read by
+/// [`MirrorOptions::from_config_in`].
+///
+/// Unlike [`mirror`], every option of the attribute is taken into account,
+/// including those about where the mirror is emitted, see [`Mirrored`].
+pub fn mirrored(defaults: MirrorOptions, attr: TokenStream, ast: ItemImpl) -> Result<Mirrored> {
+    let options = parse_options(defaults, attr)?;
+    let original = match &options.remote {
+        Some(remote) => remote::localize(ast, remote)?.1,
+        None => ast,
+    };
+    let mirror = mirror_impl(&original, &options)?;
+    Ok(Mirrored {
+        original,
+        mirror,
+        emitted: !options.check_only && options.export.is_none(),
+        module: options.in_mod,
+    })
+}
+
+/// Generate the mirror along with the items accompanying it, in their own
+/// scope, returning the mirror as well.
+fn mirror_scoped(ast: &ItemImpl, options: &MirrorOptions) -> Result<(ItemImpl, TokenStream)> {
+    let mirrored_ast = mirror_impl(ast, options)?;
+    if let Some(exported) = export::generate(ast, &mirrored_ast, options)? {
+        // the mirror is instantiated by downstream crates
+        return Ok((mirrored_ast, exported));
+    }
+    let mut items = markers::generate(ast, &mirrored_ast, options)?;
+    if options.by_ref {
+        items.push(borrow::borrowed(ast, ast, options));
+        items.push(borrow::borrowed(&mirrored_ast, ast, options));
+    }
+    items.extend(fan_out::generate(ast, &mirrored_ast, options)?);
+    items.extend(lift::generate(ast, &mirrored_ast, options)?);
+    items.extend(wrap::generate(ast, &mirrored_ast, options)?);
+    items.extend(optional::generate(ast, &mirrored_ast, options)?);
+    items.extend(operators::generate(ast, &mirrored_ast, options)?);
+    // the exported functions are named by the user, and are callable from
+    // Rust as well
+    let mut functions = ffi::generate(ast, &mirrored_ast, options)?;
+    functions.extend(wasm::generate(ast, &mirrored_ast, options)?);
+    let classes = pymethods::generate(ast, &mirrored_ast, options)?;
+    let harnesses = kani::generate(ast, options)?;
+    let tests = proptest::generate(ast, options)?;
+    items.insert(0, mirrored_ast.clone());
+    let seals = seal::generate(ast, &items, options);
+    items.extend(seals);
+    let warning = empty::warn(ast, &items[0]);
+    let mut scoped = scope(items, options);
+    scoped.extend(quote!(#(#functions)* #classes #harnesses #tests #warning));
+    Ok((mirrored_ast, scoped))
+}
+
+/// Put the generated items into their own scope, as requested by the options.
+fn scope(mut items: Vec<ItemImpl>, options: &MirrorOptions) -> TokenStream {
+    if options.in_mod.is_some() {
+        // the items are emitted one module deeper than the original
+        for item in &mut items {
+            paths::reroot(item, 1);
+        }
+    }
+    // everything generated goes into an anonymous const scope, such that
+    // names of helper items never collide with or leak to user items
+    let scoped = quote! {
+        const _: () = {
+            #(#items)*
+        };
+    };
+    match &options.in_mod {
+        Some(module) => quote! {
+            mod #module {
+                use super::*;
+
+                #scoped
+            }
+        },
+        None => scoped,
+    }
+}
+
+/// Take a syntax tree of impl and generate the mirror implementation for a
+/// symmetric trait.
+///
+/// Only the parts of the syntax tree that appear in the mirror are cloned,
+/// method bodies of the original implementation are never copied unless
+/// requested by `rewrite_body`.
+fn mirror_impl(ast: &ItemImpl, options: &MirrorOptions) -> Result<ItemImpl> {
+    let (bang, trait_path, for_token) = match &ast.trait_ {
+        Some(trait_) => trait_,
+        None => {
+            // not a trait implementation
+            return Err(Error::new(
+                Span::call_site(),
+                "#[symmetric] can only be used on trait implementation",
+            ));
+        }
+    };
+    if let Some(bang) = bang {
+        // negative marker trait
+        return Err(Error::new(
+            bang.span,
+            "#[symmetric] cannot be used on negative trait bound",
+        ));
+    }
+    let original_trait = &spelled_trait(trait_path, options);
+    if tuple::is_tuple_impl(ast, original_trait) {
+        // e.g. `impl Collide for (Ball, Wall)`, the operands are the pair
+        return tuple::mirror(ast, original_trait, options);
+    }
+    let mut mirrored_trait = original_trait.clone();
+    let last_segment = mirrored_trait.segments.last_mut().unwrap();
+    let trait_generics = match &mut last_segment.arguments {
+        PathArguments::AngleBracketed(generics) => generics,
+        _ => {
+            // no generics arguments
+            return Err(Error::new(trait_path.span(), "expected a generic trait"));
+        }
+    };
+    // deduce the "other" type for this trait
+    let slot = other_slot(trait_generics, options)?;
+    let other_type = match &mut trait_generics.args[slot] {
+        GenericArgument::Type(other_type) => other_type,
+        _ => unreachable!(),
+    };
+    if options.other.is_some() {
+        check_other(ast, options)?;
+    }
+
+    // deduce the "self" type for this trait
+    let self_type = ast.self_ty.as_ref();
+
+    // go through items inside the block
+    // 1. For every associated type, make it
+    //    type SomeType = <other_type as Trait>::SomeType
+    // 2. For every method, make sure it is of one of the following
+    //     * f(&self, other: &other_type)
+    //     * f(&mut self, other: &mut other_type)
+    //     * f(self, other: other_type)
+    //     * f(mut self, mut other: other_type)
+    //    If there are lifetime decorations, they must be the same.
+    //    replace other_type with self_type
+    //    replace the body with:
+    //    Trait::f(other, self)
+    // 3. Leave everything else intact
+    if let Some(only) = &options.only {
+        for listed in only {
+            let written = ast.items.iter().any(|item| match item {
+                ImplItem::Method(method) => method.sig.ident == *listed,
+                _ => false,
+            });
+            if !written {
+                return Err(Error::new(
+                    listed.span(),
+                    format!(
+                        "method `{}` listed in `only` is not in the implementation",
+                        listed
+                    ),
+                ));
+            }
+        }
+    }
+    for (method, counterpart) in &options.pairs {
+        let written = ast.items.iter().any(|item| match item {
+            ImplItem::Method(written) => {
+                written.sig.ident == *method || written.sig.ident == *counterpart
+            }
+            _ => false,
+        });
+        if !written {
+            return Err(Error::new(
+                method.span(),
+                format!(
+                    "neither `{}` nor `{}` paired in `pairs` is in the implementation",
+                    method, counterpart
+                ),
+            ));
+        }
+    }
+    if let Some(helper) = &options.delegate_via {
+        if options.rewrite_body {
+            return Err(Error::new_spanned(
+                helper,
+                "`delegate_via` cannot be combined with `rewrite_body`",
+            ));
+        }
+        let mut methods = ast.items.iter().filter_map(|item| match item {
+            ImplItem::Method(method) if is_mirrored(method, options) => Some(method),
+            _ => None,
+        });
+        if let (Some(_), Some(second)) = (methods.next(), methods.next()) {
+            return Err(Error::new(
+                second.sig.ident.span(),
+                "`delegate_via` requires a single mirrored method, the others can be left out with `only`",
+            ));
+        }
+    }
+    let associated_types = rewrite::associated_types(ast);
+    let mut items = Vec::with_capacity(ast.items.len());
+    for item in &ast.items {
+        let mirrored_item = match item {
+            ImplItem::Method(method) if !is_mirrored(method, options) => {
+                // left to the default of the trait
+                continue;
+            }
+            ImplItem::Method(method) => ImplItem::Method(mirror_method(
+                method,
+                self_type,
+                other_type,
+                original_trait,
+                &associated_types,
+                options,
+            )?),
+            ImplItem::Type(associated_type) => {
+                // replace associated type
+                let type_ident = &associated_type.ident;
+                ImplItem::Type(ImplItemType {
+                    attrs: attrs::forwarded(&associated_type.attrs, options),
+                    vis: associated_type.vis.clone(),
+                    defaultness: associated_type.defaultness,
+                    type_token: associated_type.type_token,
+                    ident: associated_type.ident.clone(),
+                    generics: associated_type.generics.clone(),
+                    eq_token: associated_type.eq_token,
+                    ty: parse_quote! {
+                        <#self_type as #original_trait>::#type_ident
+                    },
+                    semi_token: associated_type.semi_token,
+                })
+            }
+            // keep as-is
+            _ => item.clone(),
+        };
+        items.push(mirrored_item);
+    }
+    if options.only.is_none() {
+        // provided methods of well-known traits, e.g. `PartialEq::ne`
+        for method in provided::missing(ast, original_trait, other_type) {
+            items.push(ImplItem::Method(mirror_method(
+                &method,
+                self_type,
+                other_type,
+                original_trait,
+                &associated_types,
+                options,
+            )?));
+        }
+    }
+
+    // perform swapping of the types on impl
+    let mirrored_self_type = mem::replace(other_type, self_type.clone());
+
+    let summary = format!(
+        "Symmetric mirror of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #self_type }),
+        render::doc_link(original_trait),
+    );
+    let attrs = attrs::generated_impl(&summary, &ast.attrs, options);
+
+    let mut generics = ast.generics.clone();
+    bounds::resolve_self_in(&mut generics, self_type, original_trait);
+    if options.antisymmetric {
+        bounds::require_neg(&mut generics, ast, self_type, original_trait);
+    }
+    if options.swap_output {
+        bounds::require_swap(
+            &mut generics,
+            ast,
+            self_type,
+            original_trait,
+            &options.runtime_path,
+        );
+    }
+    if options.guarded {
+        bounds::require_original(&mut generics, self_type, original_trait);
+    }
+    bounds::adjust(&mut generics, options)?;
+
+    Ok(ItemImpl {
+        attrs,
+        defaultness: ast.defaultness,
+        unsafety: ast.unsafety,
+        impl_token: ast.impl_token,
+        generics,
+        trait_: Some((None, mirrored_trait, *for_token)),
+        self_ty: Box::new(mirrored_self_type),
+        brace_token: ast.brace_token,
+        items,
+    })
+}
+
+/// Generate the delegating counterpart of a method in the original
+/// implementation.
+fn mirror_method(
+    method: &ImplItemMethod,
+    self_type: &Type,
+    other_type: &Type,
+    original_trait: &syn::Path,
+    associated_types: &[&Ident],
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    if let Some(variadic) = &method.sig.variadic {
+        // variadic method
+        return Err(Error::new(
+            variadic.span(),
+            "method in a symmetric trait cannot be variadic",
+        ));
+    }
+
+    if method
+        .sig
+        .generics
+        .lifetimes()
+        .any(|param| param.lifetime.ident == "async_trait")
+    {
+        // signature already desugared by #[async_trait], the lifetimes of the
+        // arguments no longer match
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[symmetric] must be placed above #[async_trait]",
+        ));
+    }
+
+    // verify the input arguments of the method
+
+    // the signature expected from the receiver, which is guessed to be `&self`
+    // when missing
+    let expected = |message: &str, receiver: Option<&Receiver>| {
+        format!(
+            "{}, the expected signature is `{}`",
+            message,
+            expected_signature(&method.sig, other_type, receiver)
+        )
+    };
+    let written_receiver = match method.sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) => Some(receiver),
+        _ => None,
+    };
+
+    if method.sig.inputs.len() != 2 {
+        // wrong number of arguments
+        return Err(Error::new(
+            method.sig.inputs.span(),
+            expected("expected 2 arguments", written_receiver),
+        ));
+    }
+
+    let mut sig = method.sig.clone();
+    let mut iter = sig.inputs.iter_mut();
+    let self_arg = iter.next().unwrap();
+    let other_arg = iter.next().unwrap();
+
+    // self_arg must be one of the 4 form
+    let self_arg = match self_arg {
+        FnArg::Receiver(receiver) => receiver,
+        _ => {
+            return Err(Error::new(
+                self_arg.span(),
+                expected("expected a receiver", None),
+            ));
+        }
+    };
+
+    let other_arg = match other_arg {
+        FnArg::Typed(typed_arg) => typed_arg,
+        FnArg::Receiver(_) => unreachable!(),
+    };
+
+    let mut mutable_self = false;
+    let written_type = if let Some((_, lifetime)) = &self_arg.reference {
+        // both should be reference with the same lifetime
+        match other_arg.ty.as_mut() {
+            Type::Reference(reference) => {
+                if self_arg.mutability.is_some() != reference.mutability.is_some() {
+                    return Err(Error::new(
+                        other_arg.span(),
+                        expected("mismatched mutability", written_receiver),
+                    ));
+                }
+                if lifetime != &reference.lifetime {
+                    return Err(Error::new(
+                        other_arg.span(),
+                        expected("mismatched lifetime", written_receiver),
+                    ));
+                }
+                // replace the underlying type for other_arg
+                mem::replace(reference.elem.as_mut(), operand_type(self_type))
+            }
+            _ => {
+                return Err(Error::new(
+                    other_arg.span(),
+                    expected("expected a reference", written_receiver),
+                ));
+            }
+        }
+    } else {
+        // the delegation moves `self` without mutating it, so `mut self` is
+        // normalized into `self`
+        mutable_self = self_arg.mutability.take().is_some();
+        // replace the type of other_arg
+        mem::replace(other_arg.ty.as_mut(), operand_type(self_type))
+    };
+
+    // replace method body
+    let (method_name, reversed) = match options.counterpart(&method.sig.ident) {
+        Some(counterpart) => (counterpart.clone(), false),
+        None => provided::delegation(original_trait, &method.sig.ident),
+    };
+    // a method delegating to another one cannot copy its own body
+    let rewrite = options.rewrite_body && method_name == method.sig.ident;
+    let original_pattern = other_arg.pat.as_ref().clone();
+    let original_other = match &original_pattern {
+        Pat::Ident(pat) => Some(pat),
+        _ => None,
+    };
+
+    // replace other_arg by plain pattern, which is never mutable unless the
+    // copied body mutates it
+    let other_ident = match (&options.other_ident, original_other) {
+        (Some(other_ident), _) => other_ident.clone(),
+        (None, Some(pat)) if rewrite => pat.ident.clone(),
+        (None, _) => Ident::new("other", other_arg.span()),
+    };
+    // a body shadowing the other operand binds its alias instead, along with
+    // the mutability
+    let aliased =
+        original_other.is_some_and(|pat| rewrite && rewrite::shadows(&method.block, &pat.ident));
+    if rewrite && !aliased && self_arg.reference.is_none() {
+        self_arg.mutability = original_other.and_then(|pat| pat.mutability);
+    }
+    *other_arg.pat = Pat::Ident(PatIdent {
+        attrs: Vec::new(),
+        by_ref: None,
+        mutability: if rewrite && mutable_self {
+            Some(Default::default())
+        } else {
+            None
+        },
+        ident: other_ident.clone(),
+        subpat: None,
+    });
+
+    let helper = options
+        .delegate_via
+        .as_ref()
+        .filter(|_| method_name == method.sig.ident);
+    let call: Expr = if let Some(helper) = helper {
+        parse_quote!(#helper(#other_ident, self))
+    } else if rewrite {
+        let body = rewrite::Rewrite {
+            original_other: &original_pattern,
+            mirrored_other: &other_ident,
+            self_type,
+            original_trait,
+            associated_types,
+        }
+        .body(&method.block)?;
+        parse_quote!(#body)
+    } else {
+        match options.call {
+            CallStyle::Qualified => parse_quote! {
+                <#self_type as #original_trait>::#method_name(#other_ident, self)
+            },
+            CallStyle::Method => parse_quote! {
+                #other_ident.#method_name(self)
+            },
+        }
+    };
+    let call = if options.instrument {
+        instrument::instrumented(call, method, self_type, other_type, original_trait)
+    } else {
+        call
+    };
+    let call: Expr = match method.sig.asyncness {
+        // the copied body is already awaited within the mirror
+        Some(_) if !rewrite => parse_quote!(#call.await),
+        _ => call,
+    };
+    let call: Expr = if reversed {
+        parse_quote!(::core::option::Option::map(#call, ::core::cmp::Ordering::reverse))
+    } else {
+        call
+    };
+    let call: Expr = if options.antisymmetric {
+        if let ReturnType::Default = method.sig.output {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                "method of an antisymmetric trait must return a value",
+            ));
+        }
+        parse_quote!(::core::ops::Neg::neg(#call))
+    } else {
+        call
+    };
+    let call: Expr = if options.swap_output {
+        if let ReturnType::Default = method.sig.output {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                "method must return a value to be swapped with `swap_output`",
+            ));
+        }
+        let runtime_path = &options.runtime_path;
+        parse_quote!(#runtime_path::SwapOperands::swap_operands(#call))
+    } else {
+        call
+    };
+    let call: Expr = match method.sig.unsafety {
+        // e.g. methods with `#[target_feature]`, the mirror upholds the same
+        // contract as the original method
+        Some(_) if !rewrite => parse_quote!(unsafe { #call }),
+        _ => call,
+    };
+    let block: Block = if same_type(&written_type, other_type, options) {
+        match &call {
+            // the copied body as is
+            Expr::Block(body) => body.block.clone(),
+            _ => parse_quote! {
+                {
+                    #call
+                }
+            },
+        }
+    } else {
+        // the argument may be spelled through a type alias, have the compiler
+        // check that it is the type for the symmetry
+        let assertion = quote_spanned! {written_type.span()=>
+            let _: ::core::marker::PhantomData<#written_type> =
+                ::core::marker::PhantomData::<#other_type>;
+        };
+        parse_quote! {
+            {
+                #assertion
+                #call
+            }
+        }
+    };
+    let attrs = attrs::generated_method(method, options);
+
+    Ok(ImplItemMethod {
+        attrs,
+        vis: method.vis.clone(),
+        defaultness: method.defaultness,
+        sig,
+        block,
+    })
+}
+
+/// The self type as the other operand of a mirrored method.
+///
+/// A trait object without a lifetime bound, e.g. `dyn Shape`, is
+/// `dyn Shape + 'static` as the self type of the implementation, but would
+/// take the lifetime of the reference in a signature, so the lifetime is
+/// spelled out. Trait objects are parenthesized, as `&dyn Shape + 'a` is
+/// ambiguous.
+fn operand_type(self_type: &Type) -> Type {
+    let object = match self_type {
+        Type::TraitObject(object) => object,
+        Type::Paren(paren) => match paren.elem.as_ref() {
+            Type::TraitObject(object) => object,
+            _ => return self_type.clone(),
+        },
+        _ => return self_type.clone(),
+    };
+    let mut object = object.clone();
+    if !object
+        .bounds
+        .iter()
+        .any(|bound| matches!(bound, TypeParamBound::Lifetime(_)))
+    {
+        object.bounds.push(parse_quote!('static));
+    }
+    parse_quote!((#object))
+}
+
+/// Render the signature a method of a symmetric trait is expected to have,
+/// with the other operand taken the same way as the receiver, e.g.
+/// `fn distance(&self, other: &Disk) -> f64`.
+fn expected_signature(sig: &Signature, other_type: &Type, receiver: Option<&Receiver>) -> String {
+    let receiver = match receiver {
+        Some(receiver) => receiver.clone(),
+        None => parse_quote!(&self),
+    };
+    let other: Type = match &receiver.reference {
+        Some((_, lifetime)) => {
+            let mutability = &receiver.mutability;
+            parse_quote!(&#lifetime #mutability #other_type)
+        }
+        None => other_type.clone(),
+    };
+    let Signature {
+        asyncness,
+        unsafety,
+        ident,
+        generics,
+        output,
+        ..
+    } = sig;
+    render::render(&quote! {
+        #asyncness #unsafety fn #ident #generics(#receiver, other: #other) #output
+    })
+}
+
+/// Whether a method of the original implementation is mirrored, as opposed to
+/// left out by `only`.
+fn is_mirrored(method: &ImplItemMethod, options: &MirrorOptions) -> bool {
+    let listed = match &options.only {
+        Some(only) => only.contains(&method.sig.ident),
+        None => true,
+    };
+    listed
+        && options.other.as_ref().is_none_or(|other_type| {
+            // the methods of the other type arguments are mirrored by their
+            // own attributes
+            match method.sig.inputs.iter().nth(1) {
+                Some(FnArg::Typed(other_arg)) => {
+                    let written_type = match other_arg.ty.as_ref() {
+                        Type::Reference(reference) => reference.elem.as_ref(),
+                        ty => ty,
+                    };
+                    same_type(written_type, other_type, options)
+                }
+                _ => false,
+            }
+        })
+}
+
+/// The trait of the implementation as spelled by `trait_path`, if given, with
+/// the generic arguments of the implementation.
+fn spelled_trait(trait_path: &syn::Path, options: &MirrorOptions) -> syn::Path {
+    // it is guaranteed that trait_path is a non-empty path sequence since this is a trait impl
+    match &options.trait_path {
+        Some(spelling) => {
+            // spell the trait differently, but keep the generic arguments
+            let mut respelled = spelling.clone();
+            respelled.segments.last_mut().unwrap().arguments =
+                trait_path.segments.last().unwrap().arguments.clone();
+            respelled
+        }
+        None => trait_path.clone(),
+    }
+}
+
+/// Index of the generic argument of the trait taken as the other operand,
+/// the first type argument unless given by `other`.
+fn other_slot(generics: &AngleBracketedGenericArguments, options: &MirrorOptions) -> Result<usize> {
+    let mut slots = generics
+        .args
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, arg)| match arg {
+            GenericArgument::Type(type_arg) => Some((slot, type_arg)),
+            _ => None,
+        });
+    let other_type = match &options.other {
+        Some(other_type) => other_type,
+        None => {
+            return slots.next().map(|(slot, _)| slot).ok_or_else(|| {
+                // no type arguments
+                Error::new(
+                    generics.span(),
+                    "symmetric trait must contain at least 1 type argument",
+                )
+            });
+        }
+    };
+    let matching: Vec<usize> = slots
+        .filter(|(_, type_arg)| same_type(type_arg, other_type, options))
+        .map(|(slot, _)| slot)
+        .collect();
+    match matching.as_slice() {
+        [slot] => Ok(*slot),
+        [] => Err(Error::new_spanned(
+            generics,
+            format!(
+                "`{}` given by `other` is not a type argument of the trait",
+                render::render(other_type)
+            ),
+        )),
+        _ => Err(Error::new_spanned(
+            generics,
+            format!(
+                "`{}` given by `other` is several type arguments of the trait",
+                render::render(other_type)
+            ),
+        )),
+    }
+}
+
+/// Check that the options generating items other than the mirror are not
+/// given with `other`, as they take the first type argument as the other
+/// operand.
+fn check_other(ast: &ItemImpl, options: &MirrorOptions) -> Result<()> {
+    let unsupported = [
+        ("by_ref", options.by_ref),
+        ("fan_out", !options.fan_out.is_empty()),
+        ("lift", !options.lift.is_empty()),
+        ("wrap", !options.wrap.is_empty()),
+        ("optional", options.optional.is_some()),
+        ("operator", !options.operator.is_empty()),
+        ("ffi", options.ffi.is_some()),
+        ("wasm_bindgen", options.wasm_bindgen.is_some()),
+        ("pymethods", options.pymethods),
+        ("markers", options.markers),
+        ("seal", options.seal.is_some()),
+        ("kani", options.kani),
+        ("proptest", options.proptest.is_some()),
+        ("export", options.export.is_some()),
+    ];
+    match unsupported.iter().find(|(_, given)| *given) {
+        Some((name, _)) => Err(Error::new_spanned(
+            &ast.self_ty,
+            format!("`{}` cannot be combined with `other`", name),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Whether two spellings of a type are known to name the same type, either
+/// because they are written the same or because the options say so.
+fn same_type(lhs: &Type, rhs: &Type, options: &MirrorOptions) -> bool {
+    let (lhs, rhs) = (render::render(lhs), render::render(rhs));
+    lhs == rhs
+        || options.same_type.iter().any(|(alias, aliased)| {
+            let (alias, aliased) = (render::render(alias), render::render(aliased));
+            (alias == lhs && aliased == rhs) || (alias == rhs && aliased == lhs)
+        })
+}
@@ -0,0 +1,314 @@
+//! Implementations between an operand and a container of the other operand,
+//! requested by `#[symmetric(lift(Container, reduce = ...))]`.
+
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Error, Expr, FnArg, GenericArgument, Ident, ImplItem, ImplItemMethod, ItemImpl,
+    Path, PathArguments, Result, Token, Type,
+};
+
+use crate::{attrs, options::MirrorOptions, render};
+
+/// Combination of the results of the elements of a container.
+#[derive(Clone, Copy)]
+pub(crate) enum Reduce {
+    /// Smallest result, by `PartialOrd`.
+    Min,
+    /// Largest result, by `PartialOrd`.
+    Max,
+    /// Sum of the results, starting from `Default::default()`.
+    Sum,
+    /// Whether any result is `true`.
+    Any,
+    /// Whether every result is `true`.
+    All,
+}
+
+/// A `lift(Container, reduce = ..., empty = ...)` option.
+#[derive(Clone)]
+pub(crate) struct Lift {
+    /// Container of the other operand, e.g. `Vec`.
+    container: Path,
+    reduce: Reduce,
+    /// Result for an empty container, if not the default of the reducer.
+    empty: Option<Expr>,
+}
+
+impl Parse for Lift {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let container: Path = input.parse()?;
+        if let Some(segment) = container
+            .segments
+            .iter()
+            .find(|segment| !matches!(segment.arguments, PathArguments::None))
+        {
+            return Err(Error::new_spanned(
+                &segment.arguments,
+                "the element type of the container is the type for the symmetry",
+            ));
+        }
+        let mut reduce = None;
+        let mut empty = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            match name.to_string().as_str() {
+                "reduce" => {
+                    let reducer = input.parse::<Ident>()?;
+                    reduce = Some(match reducer.to_string().as_str() {
+                        "min" => Reduce::Min,
+                        "max" => Reduce::Max,
+                        "sum" => Reduce::Sum,
+                        "any" => Reduce::Any,
+                        "all" => Reduce::All,
+                        _ => {
+                            return Err(Error::new(
+                                reducer.span(),
+                                "expected one of `min`, `max`, `sum`, `any` or `all`",
+                            ));
+                        }
+                    });
+                }
+                "empty" => empty = Some(input.parse()?),
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}` of `lift`", name),
+                    ));
+                }
+            }
+        }
+        match reduce {
+            Some(reduce) => Ok(Lift {
+                container,
+                reduce,
+                empty,
+            }),
+            None => Err(Error::new(
+                Span::call_site(),
+                "`lift` requires `reduce = ...`",
+            )),
+        }
+    }
+}
+
+/// Generate the implementations between the operands of the original
+/// implementation `ast` and the containers of the other operand listed in the
+/// options, in both directions.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemImpl>> {
+    let mut generated = Vec::with_capacity(options.lift.len() * 2);
+    for lift in &options.lift {
+        generated.push(lifted(ast, mirrored_ast, lift, false, options)?);
+        generated.push(lifted(ast, mirrored_ast, lift, true, options)?);
+    }
+    Ok(generated)
+}
+
+/// Replace the type for the symmetry in the path of a trait, returning the
+/// one it replaced.
+pub(crate) fn replace_other(trait_path: &mut Path, other_type: Type) -> Type {
+    match &mut trait_path.segments.last_mut().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => {
+            let slot = generics
+                .args
+                .iter_mut()
+                .find_map(|arg| match arg {
+                    GenericArgument::Type(slot) => Some(slot),
+                    _ => None,
+                })
+                .unwrap();
+            std::mem::replace(slot, other_type)
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Generate the implementation between the self type of `ast` and a container
+/// of the other operand, either with the self type or the container as `Self`.
+fn lifted(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    lift: &Lift,
+    container_first: bool,
+    options: &MirrorOptions,
+) -> Result<ItemImpl> {
+    // the original and mirrored implementations were checked to be generic
+    // trait implementations
+    let (_, original_trait, for_token) = ast.trait_.as_ref().unwrap();
+    let (_, mirrored_trait, _) = mirrored_ast.trait_.as_ref().unwrap();
+    let self_type = ast.self_ty.as_ref();
+    let mut lifted_trait = original_trait.clone();
+    let element_type = replace_other(&mut lifted_trait, self_type.clone());
+    let container = &lift.container;
+    let container_type: Type = parse_quote!(#container<#element_type>);
+    // the implementation delegated to for every element
+    let (delegate_self, delegate_trait) = if container_first {
+        (&element_type, mirrored_trait)
+    } else {
+        replace_other(&mut lifted_trait, container_type.clone());
+        (self_type, original_trait)
+    };
+    let lifted_self = if container_first {
+        &container_type
+    } else {
+        self_type
+    };
+
+    let mut items = Vec::with_capacity(ast.items.len());
+    let delegate_items = if container_first {
+        &mirrored_ast.items
+    } else {
+        &ast.items
+    };
+    for item in delegate_items {
+        match item {
+            ImplItem::Method(method) if container_first || crate::is_mirrored(method, options) => {
+                items.push(ImplItem::Method(lifted_method(
+                    method,
+                    delegate_self,
+                    delegate_trait,
+                    &container_type,
+                    container_first,
+                    lift,
+                    options,
+                )?));
+            }
+            ImplItem::Type(associated_type) => {
+                let mut associated_type = associated_type.clone();
+                let type_ident = &associated_type.ident;
+                associated_type.attrs = attrs::forwarded(&associated_type.attrs, options);
+                associated_type.ty = parse_quote!(<#delegate_self as #delegate_trait>::#type_ident);
+                items.push(ImplItem::Type(associated_type));
+            }
+            ImplItem::Const(constant) => {
+                let mut constant = constant.clone();
+                let const_ident = &constant.ident;
+                constant.attrs = attrs::forwarded(&constant.attrs, options);
+                constant.expr = parse_quote!(<#delegate_self as #delegate_trait>::#const_ident);
+                items.push(ImplItem::Const(constant));
+            }
+            _ => (),
+        }
+    }
+
+    let summary = format!(
+        "Lifted counterpart of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #self_type }),
+        render::doc_link(original_trait),
+    );
+    Ok(ItemImpl {
+        attrs: attrs::generated_impl(&summary, &ast.attrs, options),
+        defaultness: None,
+        unsafety: ast.unsafety,
+        impl_token: ast.impl_token,
+        generics: if container_first {
+            mirrored_ast.generics.clone()
+        } else {
+            ast.generics.clone()
+        },
+        trait_: Some((None, lifted_trait, *for_token)),
+        self_ty: Box::new(lifted_self.clone()),
+        brace_token: ast.brace_token,
+        items,
+    })
+}
+
+/// Generate a method applying `method` to every element of the container and
+/// reducing the results.
+fn lifted_method(
+    method: &ImplItemMethod,
+    delegate_self: &Type,
+    delegate_trait: &Path,
+    container_type: &Type,
+    container_first: bool,
+    lift: &Lift,
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    let mut sig = method.sig.clone();
+    match &sig.inputs[0] {
+        FnArg::Receiver(receiver) if receiver.reference.is_some() => (),
+        _ => {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                "`lift` requires methods taking their operands by reference",
+            ));
+        }
+    }
+    if sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "`lift` does not support async methods",
+        ));
+    }
+    if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+        *other_arg.pat = parse_quote!(other);
+        if !container_first {
+            if let Type::Reference(reference) = other_arg.ty.as_mut() {
+                *reference.elem = container_type.clone();
+            }
+        }
+    }
+
+    let method_name = &method.sig.ident;
+    let (container, call) = if container_first {
+        (
+            quote!(self),
+            quote!(<#delegate_self as #delegate_trait>::#method_name(element, other)),
+        )
+    } else {
+        (
+            quote!(other),
+            quote!(<#delegate_self as #delegate_trait>::#method_name(self, element)),
+        )
+    };
+    let combine = match lift.reduce {
+        Reduce::Min => quote!(if value < reduced { value } else { reduced }),
+        Reduce::Max => quote!(if value > reduced { value } else { reduced }),
+        Reduce::Sum => quote!(reduced + value),
+        Reduce::Any => quote!(reduced || value),
+        Reduce::All => quote!(reduced && value),
+    };
+    let empty = match (&lift.empty, lift.reduce) {
+        (Some(empty), _) => quote!(#empty),
+        (None, Reduce::Min) | (None, Reduce::Max) => {
+            let message = format!("`{}` of an empty container has no value", method_name);
+            quote!(::core::panic!(#message))
+        }
+        (None, Reduce::Sum) => quote!(::core::default::Default::default()),
+        (None, Reduce::Any) => quote!(false),
+        (None, Reduce::All) => quote!(true),
+    };
+
+    Ok(ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: None,
+        sig,
+        block: parse_quote! {
+            {
+                let mut reduced = ::core::option::Option::None;
+                for element in ::core::iter::IntoIterator::into_iter(#container) {
+                    let value = #call;
+                    reduced = ::core::option::Option::Some(match reduced {
+                        ::core::option::Option::Some(reduced) => #combine,
+                        ::core::option::Option::None => value,
+                    });
+                }
+                match reduced {
+                    ::core::option::Option::Some(reduced) => reduced,
+                    ::core::option::Option::None => #empty,
+                }
+            }
+        },
+    })
+}
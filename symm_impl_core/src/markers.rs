@@ -0,0 +1,97 @@
+//! Implementations of the marker traits of `symm_impl_runtime` requested by
+//! `#[symmetric(markers)]` and `#[transitive(markers)]`.
+
+use proc_macro2::Span;
+use syn::{
+    parse_quote, Error, GenericArgument, Ident, ImplItem, ItemImpl, Path, PathArguments, Result,
+    TypeParamBound,
+};
+
+use crate::options::MirrorOptions;
+
+/// Generate the `SymmetricOp` implementations of both the original
+/// implementation and its mirror, if requested by the options.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemImpl>> {
+    if !options.markers {
+        return Ok(Vec::new());
+    }
+    if options.antisymmetric || options.only.is_some() {
+        // the mirror does not give the same result in both directions
+        return Err(Error::new(
+            Span::call_site(),
+            "`markers` cannot be combined with `antisymmetric` or `only`",
+        ));
+    }
+    Ok(vec![
+        symmetric_op(ast, options),
+        symmetric_op(mirrored_ast, options),
+    ])
+}
+
+/// Generate the `SymmetricOp` implementation matching a trait implementation.
+fn symmetric_op(item: &ItemImpl, options: &MirrorOptions) -> ItemImpl {
+    // both implementations were checked to be generic trait implementations
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = &item.self_ty;
+    let other_type = match &trait_path.segments.last().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => generics
+            .args
+            .iter()
+            .find_map(|arg| match arg {
+                GenericArgument::Type(other_type) => Some(other_type),
+                _ => None,
+            })
+            .unwrap(),
+        _ => unreachable!(),
+    };
+    let bound = operation(item);
+    let runtime_path = &options.runtime_path;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    parse_quote! {
+        #[automatically_derived]
+        impl #impl_generics #runtime_path::SymmetricOp<#other_type, dyn #bound>
+            for #self_type #where_clause
+        {
+        }
+    }
+}
+
+/// Generate the implementation of a marker trait of a property of a relation
+/// of a type with itself, e.g. `TransitiveOp`.
+pub(crate) fn property(item: &ItemImpl, marker: &str, runtime_path: &Path) -> ItemImpl {
+    let marker = Ident::new(marker, Span::call_site());
+    let self_type = &item.self_ty;
+    let bound = operation(item);
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    parse_quote! {
+        #[automatically_derived]
+        impl #impl_generics #runtime_path::#marker<dyn #bound> for #self_type #where_clause {}
+    }
+}
+
+/// The bound naming the operation of a trait implementation in a trait
+/// object, e.g. `Merge<Disk, Output = <Point2D as Merge<Disk>>::Output>`.
+fn operation(item: &ItemImpl) -> TypeParamBound {
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = &item.self_ty;
+    // associated types must be bound in the trait object naming the operation
+    let mut bound: TypeParamBound = parse_quote!(#trait_path);
+    if let TypeParamBound::Trait(bound) = &mut bound {
+        let last_segment = bound.path.segments.last_mut().unwrap();
+        if let PathArguments::AngleBracketed(generics) = &mut last_segment.arguments {
+            for impl_item in &item.items {
+                if let ImplItem::Type(associated_type) = impl_item {
+                    let ident = &associated_type.ident;
+                    generics.args.push(parse_quote! {
+                        #ident = <#self_type as #trait_path>::#ident
+                    });
+                }
+            }
+        }
+    }
+    bound
+}
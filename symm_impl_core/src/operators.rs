@@ -0,0 +1,145 @@
+//! Operator implementations calling methods of the symmetric trait, requested
+//! by `#[symmetric(operator(BitAnd = intersects))]`.
+
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Error, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl, Path, Result, ReturnType,
+    Token, Type,
+};
+
+use crate::{attrs, bounds, lift::replace_other, options::MirrorOptions, render};
+
+/// Binary operators of `core::ops`, with the name of their method.
+const OPERATORS: &[(&str, &str)] = &[
+    ("Add", "add"),
+    ("Sub", "sub"),
+    ("Mul", "mul"),
+    ("Div", "div"),
+    ("Rem", "rem"),
+    ("BitAnd", "bitand"),
+    ("BitOr", "bitor"),
+    ("BitXor", "bitxor"),
+    ("Shl", "shl"),
+    ("Shr", "shr"),
+];
+
+/// A `Operator = method` pair of the `operator(...)` option.
+#[derive(Clone)]
+pub(crate) struct Operator {
+    operator: Ident,
+    method: Ident,
+}
+
+impl Parse for Operator {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let operator: Ident = input.parse()?;
+        if !OPERATORS.iter().any(|(name, _)| operator == name) {
+            return Err(Error::new(
+                operator.span(),
+                "expected a binary operator of `core::ops`, e.g. `Add` or `BitAnd`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let method = input.parse()?;
+        Ok(Operator { operator, method })
+    }
+}
+
+/// Generate the operator implementations listed in the options, for both the
+/// original implementation `ast` and its mirror.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemImpl>> {
+    let mut generated = Vec::with_capacity(options.operator.len() * 2);
+    for operator in &options.operator {
+        let method = ast.items.iter().find_map(|item| match item {
+            ImplItem::Method(method) if method.sig.ident == operator.method => Some(method),
+            _ => None,
+        });
+        let method = match method {
+            Some(method) if crate::is_mirrored(method, options) => method,
+            _ => {
+                return Err(Error::new(
+                    operator.method.span(),
+                    format!(
+                        "method `{}` mapped to `{}` is not mirrored",
+                        operator.method, operator.operator
+                    ),
+                ));
+            }
+        };
+        generated.push(bridge(ast, method, operator, options)?);
+        generated.push(bridge(mirrored_ast, method, operator, options)?);
+    }
+    Ok(generated)
+}
+
+/// Generate the operator implementation of `item` calling the method `method`
+/// of its trait.
+fn bridge(
+    item: &ItemImpl,
+    method: &ImplItemMethod,
+    operator: &Operator,
+    options: &MirrorOptions,
+) -> Result<ItemImpl> {
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let mut other_path: Path = trait_path.clone();
+    let other_type = replace_other(&mut other_path, parse_quote!(()));
+
+    let output: Type = match &method.sig.output {
+        ReturnType::Type(_, output) => bounds::resolve_self(output, self_type, trait_path),
+        ReturnType::Default => parse_quote!(()),
+    };
+    if method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "an async method cannot be called by an operator",
+        ));
+    }
+    let (lhs, rhs) = match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => (quote!(&mut self), quote!(&mut rhs)),
+            (Some(_), None) => (quote!(&self), quote!(&rhs)),
+            (None, _) => (quote!(self), quote!(rhs)),
+        },
+        FnArg::Typed(_) => unreachable!(),
+    };
+    let mutability = match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) if receiver.reference.is_some() => {
+            receiver.mutability.map(|_| quote!(mut))
+        }
+        _ => None,
+    };
+
+    let operator_ident = &operator.operator;
+    let operator_method = OPERATORS
+        .iter()
+        .find(|(name, _)| operator_ident == name)
+        .map(|(_, method)| Ident::new(method, operator_ident.span()))
+        .unwrap();
+    let method_name = &method.sig.ident;
+    let summary = format!(
+        "`{}` operator calling [`{}`]({}).",
+        operator_ident,
+        render::render(&quote! { impl #trait_path for #self_type }),
+        render::doc_link(trait_path),
+    );
+    let impl_attrs = attrs::generated_impl(&summary, &[], options);
+    let method_attrs = attrs::generated_method(method, options);
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    Ok(parse_quote! {
+        #(#impl_attrs)*
+        impl #impl_generics ::core::ops::#operator_ident<#other_type> for #self_type #where_clause {
+            type Output = #output;
+
+            #(#method_attrs)*
+            fn #operator_method(#mutability self, #mutability rhs: #other_type) -> #output {
+                <#self_type as #trait_path>::#method_name(#lhs, #rhs)
+            }
+        }
+    })
+}
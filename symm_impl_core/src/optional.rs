@@ -0,0 +1,236 @@
+//! Implementations between an operand and an optional other operand, requested
+//! by `#[symmetric(optional = "...")]`.
+
+use quote::quote;
+use syn::{
+    parse_quote, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl, Path, Result, ReturnType, Type,
+    TypePath,
+};
+
+use crate::{attrs, lift::replace_other, options::MirrorOptions, render};
+
+/// Result of a method when the optional operand is `None`.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum NonePolicy {
+    /// `None`, the associated types returned by methods being wrapped in
+    /// `Option`.
+    None,
+    /// `Default::default()`.
+    Default,
+    /// Panic.
+    Panic,
+}
+
+/// Generate the implementations between the operands of the original
+/// implementation `ast` and the optional other operand, in both directions,
+/// if requested by the options.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemImpl>> {
+    match options.optional {
+        Some(policy) => Ok(vec![
+            optional(ast, mirrored_ast, policy, false, options)?,
+            optional(ast, mirrored_ast, policy, true, options)?,
+        ]),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Name of the associated type returned by a method, e.g. `Output` for
+/// `Self::Output`.
+fn returned_associated_type(method: &ImplItemMethod) -> Option<&syn::Ident> {
+    match &method.sig.output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(TypePath { qself: None, path })
+                if path.segments.len() == 2 && path.segments[0].ident == "Self" =>
+            {
+                Some(&path.segments[1].ident)
+            }
+            _ => None,
+        },
+        ReturnType::Default => None,
+    }
+}
+
+/// Generate the implementation between the self type of `ast` and an optional
+/// other operand, either with the self type or the option as `Self`.
+fn optional(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    policy: NonePolicy,
+    option_first: bool,
+    options: &MirrorOptions,
+) -> Result<ItemImpl> {
+    let (_, original_trait, for_token) = ast.trait_.as_ref().unwrap();
+    let (_, mirrored_trait, _) = mirrored_ast.trait_.as_ref().unwrap();
+    let self_type = ast.self_ty.as_ref();
+    let mut lifted_trait = original_trait.clone();
+    let element_type = replace_other(&mut lifted_trait, self_type.clone());
+    let option_type: Type = parse_quote!(::core::option::Option<#element_type>);
+    let (delegate_self, delegate_trait, delegate) = if option_first {
+        (&element_type, mirrored_trait, mirrored_ast)
+    } else {
+        replace_other(&mut lifted_trait, option_type.clone());
+        (self_type, original_trait, ast)
+    };
+
+    let mut methods = Vec::new();
+    let mut wrapped = Vec::new();
+    for item in &delegate.items {
+        if let ImplItem::Method(method) = item {
+            if option_first || crate::is_mirrored(method, options) {
+                if policy == NonePolicy::None {
+                    match returned_associated_type(method) {
+                        Some(associated) => wrapped.push(associated.clone()),
+                        None => {
+                            return Err(Error::new(
+                                method.sig.ident.span(),
+                                "`optional = \"none\"` requires methods returning an associated \
+                                 type, e.g. `Self::Output`",
+                            ));
+                        }
+                    }
+                }
+                methods.push(ImplItem::Method(optional_method(
+                    method,
+                    delegate_self,
+                    delegate_trait,
+                    &option_type,
+                    option_first,
+                    policy,
+                    options,
+                )?));
+            }
+        }
+    }
+
+    let mut items = Vec::with_capacity(delegate.items.len());
+    for item in &delegate.items {
+        match item {
+            ImplItem::Type(associated_type) => {
+                let mut associated_type = associated_type.clone();
+                let type_ident = &associated_type.ident;
+                associated_type.attrs = attrs::forwarded(&associated_type.attrs, options);
+                associated_type.ty = parse_quote!(<#delegate_self as #delegate_trait>::#type_ident);
+                if wrapped.contains(type_ident) {
+                    let ty = &associated_type.ty;
+                    associated_type.ty = parse_quote!(::core::option::Option<#ty>);
+                }
+                items.push(ImplItem::Type(associated_type));
+            }
+            ImplItem::Const(constant) => {
+                let mut constant = constant.clone();
+                let const_ident = &constant.ident;
+                constant.attrs = attrs::forwarded(&constant.attrs, options);
+                constant.expr = parse_quote!(<#delegate_self as #delegate_trait>::#const_ident);
+                items.push(ImplItem::Const(constant));
+            }
+            _ => (),
+        }
+    }
+    items.extend(methods);
+
+    let summary = format!(
+        "Optional counterpart of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #self_type }),
+        render::doc_link(original_trait),
+    );
+    Ok(ItemImpl {
+        attrs: attrs::generated_impl(&summary, &ast.attrs, options),
+        defaultness: None,
+        unsafety: ast.unsafety,
+        impl_token: ast.impl_token,
+        generics: delegate.generics.clone(),
+        trait_: Some((None, lifted_trait, *for_token)),
+        self_ty: Box::new(if option_first {
+            option_type
+        } else {
+            self_type.clone()
+        }),
+        brace_token: ast.brace_token,
+        items,
+    })
+}
+
+/// Generate a method delegating to `method` if the optional operand is
+/// present.
+fn optional_method(
+    method: &ImplItemMethod,
+    delegate_self: &Type,
+    delegate_trait: &Path,
+    option_type: &Type,
+    option_first: bool,
+    policy: NonePolicy,
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    if method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "`optional` does not support async methods",
+        ));
+    }
+    let mut sig = method.sig.clone();
+    // the option is borrowed the same way as the operands
+    let unwrap = match &mut sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => quote!(::core::option::Option::as_mut),
+            (Some(_), None) => quote!(::core::option::Option::as_ref),
+            (None, _) => {
+                // `self` is moved without being mutated
+                receiver.mutability = None;
+                quote!(::core::convert::identity)
+            }
+        },
+        FnArg::Typed(_) => unreachable!(),
+    };
+    if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+        *other_arg.pat = parse_quote!(other);
+        if !option_first {
+            match other_arg.ty.as_mut() {
+                Type::Reference(reference) => *reference.elem = option_type.clone(),
+                ty => *ty = option_type.clone(),
+            }
+        }
+    }
+
+    let method_name = &method.sig.ident;
+    let (option, call) = if option_first {
+        (
+            quote!(self),
+            quote!(<#delegate_self as #delegate_trait>::#method_name(element, other)),
+        )
+    } else {
+        (
+            quote!(other),
+            quote!(<#delegate_self as #delegate_trait>::#method_name(self, element)),
+        )
+    };
+    let (some, none) = match policy {
+        NonePolicy::None => (
+            quote!(::core::option::Option::Some(#call)),
+            quote!(::core::option::Option::None),
+        ),
+        NonePolicy::Default => (call, quote!(::core::default::Default::default())),
+        NonePolicy::Panic => {
+            let message = format!("`{}` of `None`", method_name);
+            (call, quote!(::core::panic!(#message)))
+        }
+    };
+
+    Ok(ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: None,
+        sig,
+        block: parse_quote! {
+            {
+                match #unwrap(#option) {
+                    ::core::option::Option::Some(element) => #some,
+                    ::core::option::Option::None => #none,
+                }
+            }
+        },
+    })
+}
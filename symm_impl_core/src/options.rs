@@ -0,0 +1,587 @@
+//! Options accepted by `#[symmetric(...)]`.
+
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    token, Error, LitBool, LitStr, NestedMeta, Path, PathArguments, Result, Token, Type,
+    WherePredicate,
+};
+
+use crate::{
+    config, fan_out::Pointer, ffi::Ffi, inherit, lift::Lift, operators::Operator,
+    optional::NonePolicy, proptest::Proptest, remote::Remote, wrap::Wrap,
+};
+
+/// Inlining hint put on mirrored methods.
+#[derive(Clone, Copy)]
+pub(crate) enum Inline {
+    /// `#[inline(always)]`
+    Always,
+    /// `#[inline(never)]`
+    Never,
+    /// `#[inline]`
+    Hint,
+    /// No inlining attribute at all.
+    None,
+}
+
+/// Syntax of the call delegating to the original implementation.
+#[derive(Clone, Copy)]
+pub(crate) enum CallStyle {
+    /// `<A as Trait<B>>::method(other, self)`
+    Qualified,
+    /// `other.method(self)`
+    Method,
+}
+
+/// Options controlling how the mirror is generated.
+///
+/// The options are parsed from the same syntax as the arguments of
+/// `#[symmetric(...)]`, e.g.
+/// `syn::parse_str::<MirrorOptions>("call = \"method\", doc_hidden")`.
+/// [`MirrorOptions::default`] corresponds to a bare `#[symmetric]`.
+#[derive(Clone)]
+pub struct MirrorOptions {
+    /// Attributes copied to the mirror in addition to the default ones.
+    pub(crate) copy_attrs: Vec<Path>,
+    /// Attributes never copied to the mirror.
+    pub(crate) skip_attrs: Vec<Path>,
+    /// Whether the mirror is marked `#[automatically_derived]`.
+    pub(crate) automatically_derived: bool,
+    /// Whether the mirrored methods are marked `#[track_caller]`.
+    pub(crate) track_caller: bool,
+    /// Whether the mirror is hidden from the documentation.
+    pub(crate) doc_hidden: bool,
+    /// Clippy lints allowed on the mirrored methods.
+    pub(crate) clippy_allow: Vec<Ident>,
+    /// Whether the mirrored methods are excluded from code coverage.
+    pub(crate) coverage_off: bool,
+    /// Predicate of the `#[cfg]` applied to the mirror only.
+    pub(crate) cfg: Option<NestedMeta>,
+    /// Module in which the mirror is emitted.
+    pub(crate) in_mod: Option<Ident>,
+    /// Inlining hint on the mirrored methods.
+    pub(crate) inline: Inline,
+    /// Syntax of the delegating call.
+    pub(crate) call: CallStyle,
+    /// Whether the mirrored methods copy the bodies of the original ones
+    /// instead of delegating to them.
+    pub(crate) rewrite_body: bool,
+    /// Function the mirrored methods call instead of the original ones.
+    pub(crate) delegate_via: Option<Path>,
+    /// Pairs of methods whose mirrors delegate to each other, e.g. for
+    /// direction-specific names.
+    pub(crate) pairs: Vec<(Ident, Ident)>,
+    /// Name of the non-receiver parameter in mirrored signatures.
+    pub(crate) other_ident: Option<Ident>,
+    /// Type argument of the trait taken as the other operand, instead of the
+    /// first one.
+    pub(crate) other: Option<Type>,
+    /// Predicates added to the where-clause of the mirror only.
+    pub(crate) bound: Vec<WherePredicate>,
+    /// Predicates removed from the bounds of the mirror.
+    pub(crate) remove_bound: Vec<WherePredicate>,
+    /// Spelling of the trait in the mirror.
+    pub(crate) trait_path: Option<Path>,
+    /// Pairs of spellings asserted to name the same type.
+    pub(crate) same_type: Vec<(Type, Type)>,
+    /// Whether the expansion waits for the attribute macros after it.
+    pub(crate) defer: bool,
+    /// Whether every method of the trait must be written in the
+    /// implementation.
+    pub(crate) strict: bool,
+    /// Whether the implementation is only checked, without emitting the
+    /// mirror.
+    pub(crate) check_only: bool,
+    /// Whether the mirror negates the result of the original implementation.
+    pub(crate) antisymmetric: bool,
+    /// Whether the mirror requires the original implementation in its
+    /// where-clause.
+    pub(crate) guarded: bool,
+    /// Whether both directions are also implemented for a reference to the
+    /// type for the symmetry.
+    pub(crate) by_ref: bool,
+    /// Pointers to the operands to implement the trait for.
+    pub(crate) fan_out: Vec<Pointer>,
+    /// Containers of the other operand to implement the trait with.
+    pub(crate) lift: Vec<Lift>,
+    /// Wrappers of both operands to implement the trait with.
+    pub(crate) wrap: Vec<Wrap>,
+    /// Result for a missing operand, if the trait is also implemented with an
+    /// optional other operand.
+    pub(crate) optional: Option<NonePolicy>,
+    /// Operators implemented by calling methods of the trait.
+    pub(crate) operator: Vec<Operator>,
+    /// Local wrapper of the self type the trait is implemented for instead.
+    pub(crate) remote: Option<Remote>,
+    /// Names of the operands in the exported `extern "C"` functions.
+    pub(crate) ffi: Option<Ffi>,
+    /// Names of the operands in the exported `#[wasm_bindgen]` functions.
+    pub(crate) wasm_bindgen: Option<Ffi>,
+    /// Whether the methods are exposed on both Python classes with
+    /// `#[pymethods]`.
+    pub(crate) pymethods: bool,
+    /// Whether the results of the original methods are flipped with
+    /// `SwapOperands`.
+    pub(crate) swap_output: bool,
+    /// Name of the exported macro instantiating the mirror, emitted instead of
+    /// the mirror.
+    pub(crate) export: Option<Ident>,
+    /// Whether Kani proof harnesses of the symmetry are generated.
+    pub(crate) kani: bool,
+    /// Strategies of the property tests of the symmetry, if generated.
+    pub(crate) proptest: Option<Proptest>,
+    /// Whether the delegated calls are wrapped in `tracing` spans.
+    pub(crate) instrument: bool,
+    /// Whether the marker traits of the properties of the relation are
+    /// implemented.
+    pub(crate) markers: bool,
+    /// Sealing trait implemented along with every implementation of the
+    /// trait.
+    pub(crate) seal: Option<Path>,
+    /// Methods mirrored, if not all of them.
+    pub(crate) only: Option<Vec<Ident>>,
+    /// Path to this crate in generated code.
+    pub(crate) crate_path: Path,
+    /// Path to `symm_impl_runtime` in generated code.
+    pub(crate) runtime_path: Path,
+}
+
+impl Default for MirrorOptions {
+    fn default() -> Self {
+        MirrorOptions {
+            copy_attrs: Vec::new(),
+            skip_attrs: Vec::new(),
+            automatically_derived: true,
+            track_caller: true,
+            doc_hidden: false,
+            clippy_allow: [
+                "mut_mut",
+                "needless_borrow",
+                "needless_lifetimes",
+                "use_self",
+            ]
+            .iter()
+            .map(|lint| Ident::new(lint, Span::call_site()))
+            .collect(),
+            coverage_off: false,
+            cfg: None,
+            in_mod: None,
+            inline: Inline::Hint,
+            call: CallStyle::Qualified,
+            rewrite_body: false,
+            delegate_via: None,
+            pairs: Vec::new(),
+            other_ident: None,
+            other: None,
+            bound: Vec::new(),
+            remove_bound: Vec::new(),
+            trait_path: None,
+            same_type: Vec::new(),
+            defer: false,
+            strict: false,
+            check_only: false,
+            antisymmetric: false,
+            guarded: false,
+            by_ref: false,
+            fan_out: Vec::new(),
+            lift: Vec::new(),
+            wrap: Vec::new(),
+            optional: None,
+            operator: Vec::new(),
+            remote: None,
+            ffi: None,
+            wasm_bindgen: None,
+            pymethods: false,
+            swap_output: false,
+            export: None,
+            kani: false,
+            proptest: None,
+            instrument: false,
+            markers: false,
+            seal: None,
+            only: None,
+            crate_path: syn::parse_quote!(::symm_impl),
+            runtime_path: syn::parse_quote!(::symm_impl_runtime),
+        }
+    }
+}
+
+/// Options which accumulate when given several times, the others may only be
+/// given once.
+const REPEATABLE: &[&str] = &[
+    "copy_attrs",
+    "skip_attrs",
+    "bound",
+    "remove_bound",
+    "same_type",
+    "pairs",
+    "lift",
+    "wrap",
+    "operator",
+    "only",
+];
+
+/// Options deciding whether and where a mirror is generated, as well as the
+/// options inherited from the modules.
+const DECISIVE: &[&str] = &[
+    "check_only",
+    "export",
+    "other",
+    "trait_path",
+    inherit::MODULE_DEFAULTS,
+];
+
+impl Parse for MirrorOptions {
+    /// Parse the options of an attribute, on top of the options inherited from
+    /// the enclosing modules.
+    fn parse(input: ParseStream) -> Result<Self> {
+        MirrorOptions::parse_with(MirrorOptions::default(), input)
+    }
+}
+
+impl MirrorOptions {
+    /// The crate-wide defaults of the crate being compiled, read from the
+    /// `symm_impl.toml` next to its `Cargo.toml`, or from the file named by the
+    /// `SYMM_IMPL_CONFIG` environment variable.
+    ///
+    /// The file is only read by the entry points of the attribute, parsing
+    /// options does not depend on the environment.
+    pub fn from_config() -> Result<Self> {
+        config::defaults()
+    }
+
+    /// The crate-wide defaults of the crate in `dir`, the directory of its
+    /// `Cargo.toml`, as [`MirrorOptions::from_config`] reads them when the
+    /// crate is compiled. For tools inspecting a crate, e.g. `cargo symm`.
+    pub fn from_config_in(dir: &std::path::Path) -> Result<Self> {
+        config::defaults_in(dir)
+    }
+
+    /// Parse the options of an attribute on top of `defaults`, e.g.
+    /// [`MirrorOptions::from_config`], and of the options inherited from the
+    /// enclosing modules.
+    pub fn parse_with(defaults: MirrorOptions, input: ParseStream) -> Result<Self> {
+        let options = inherit::inherited(defaults, input)?;
+        MirrorOptions::parse_onto(options, input)
+    }
+
+    /// The options of an attribute which failed to parse, as far as they can
+    /// be told, for the stub emitted along with the error. Each option is
+    /// parsed on its own and dropped if invalid, unless it decides where the
+    /// mirror goes, in which case there is no telling.
+    pub(crate) fn recover(mut options: MirrorOptions, attr: TokenStream) -> Option<Self> {
+        let mut option = Vec::new();
+        let end = TokenTree::from(Punct::new(',', Spacing::Alone));
+        for token in attr.into_iter().chain(Some(end)) {
+            if !matches!(&token, TokenTree::Punct(comma) if comma.as_char() == ',') {
+                option.push(token);
+                continue;
+            }
+            let decisive = matches!(
+                option.first(),
+                Some(TokenTree::Ident(name)) if DECISIVE.iter().any(|decisive| name == decisive)
+            );
+            let parser = |input: ParseStream| MirrorOptions::parse_with(options.clone(), input);
+            match parser.parse2(option.drain(..).collect()) {
+                Ok(parsed) => options = parsed,
+                Err(_) if decisive => return None,
+                Err(_) => (),
+            }
+        }
+        Some(options)
+    }
+
+    /// Parse options overriding `options`, or adding to them for the
+    /// repeatable ones.
+    pub(crate) fn parse_onto(mut options: MirrorOptions, input: ParseStream) -> Result<Self> {
+        let mut seen = Vec::new();
+        while !input.is_empty() {
+            let name = input.call(Ident::parse_any)?;
+            let key = name.to_string();
+            if seen.contains(&key) && !REPEATABLE.contains(&key.as_str()) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("duplicate option `{}`", name),
+                ));
+            }
+            match key.as_str() {
+                "copy_attrs" => options.copy_attrs.extend(parse_list(input)?),
+                "skip_attrs" => {
+                    for path in parse_list::<Path>(input)? {
+                        if path.is_ident("cfg") || path.is_ident("cfg_attr") {
+                            return Err(Error::new_spanned(
+                                path,
+                                "conditional compilation attributes are always copied",
+                            ));
+                        }
+                        options.skip_attrs.push(path);
+                    }
+                }
+                "automatically_derived" => {
+                    options.automatically_derived = parse_value::<LitBool>(input)?.value;
+                }
+                "track_caller" => {
+                    options.track_caller = parse_value::<LitBool>(input)?.value;
+                }
+                "doc_hidden" => options.doc_hidden = parse_flag(input, &name)?,
+                "coverage_off" => options.coverage_off = parse_flag(input, &name)?,
+                "clippy_allow" => {
+                    options.clippy_allow = parse_list::<Ident>(input)?.into_iter().collect();
+                }
+                "cfg" => {
+                    let content;
+                    parenthesized!(content in input);
+                    options.cfg = Some(content.parse()?);
+                    if !content.is_empty() {
+                        return Err(content.error("expected a single cfg predicate"));
+                    }
+                }
+                "in_mod" => options.in_mod = Some(parse_value(input)?),
+                "inline" => {
+                    let level = parse_value::<LitStr>(input)?;
+                    options.inline = match level.value().as_str() {
+                        "always" => Inline::Always,
+                        "never" => Inline::Never,
+                        "hint" => Inline::Hint,
+                        "none" => Inline::None,
+                        _ => {
+                            return Err(Error::new(
+                                level.span(),
+                                "expected one of \"always\", \"never\", \"hint\" or \"none\"",
+                            ));
+                        }
+                    };
+                }
+                "rewrite_body" => options.rewrite_body = parse_flag(input, &name)?,
+                "delegate_via" => options.delegate_via = Some(parse_value(input)?),
+                "pairs" => {
+                    for pair in parse_list::<MethodPair>(input)? {
+                        for method in [&pair.method, &pair.counterpart] {
+                            if options.counterpart(method).is_some() {
+                                return Err(Error::new(
+                                    method.span(),
+                                    format!("method `{}` is already paired", method),
+                                ));
+                            }
+                        }
+                        if pair.method == pair.counterpart {
+                            return Err(Error::new(
+                                pair.counterpart.span(),
+                                format!("method `{}` is paired with itself", pair.method),
+                            ));
+                        }
+                        options.pairs.push((pair.method, pair.counterpart));
+                    }
+                }
+                "call" => {
+                    let style = parse_value::<LitStr>(input)?;
+                    options.call = match style.value().as_str() {
+                        "qualified" => CallStyle::Qualified,
+                        "method" => CallStyle::Method,
+                        _ => {
+                            return Err(Error::new(
+                                style.span(),
+                                "expected one of \"qualified\" or \"method\"",
+                            ));
+                        }
+                    };
+                }
+                "other_ident" => options.other_ident = Some(parse_value(input)?),
+                "other" => options.other = Some(parse_value::<LitStr>(input)?.parse()?),
+                "bound" => options.bound.extend(parse_predicates(input)?),
+                "remove_bound" => options.remove_bound.extend(parse_predicates(input)?),
+                "trait_path" => {
+                    let path = parse_value::<Path>(input)?;
+                    let last_segment = path.segments.last().unwrap();
+                    if !matches!(last_segment.arguments, PathArguments::None) {
+                        return Err(Error::new_spanned(
+                            &last_segment.arguments,
+                            "generic arguments of the trait are taken from the implementation",
+                        ));
+                    }
+                    options.trait_path = Some(path);
+                }
+                "same_type" => {
+                    for pair in parse_list::<TypePair>(input)? {
+                        options.same_type.push((pair.alias, pair.aliased));
+                    }
+                }
+                "defer" => options.defer = parse_flag(input, &name)?,
+                "strict" => options.strict = parse_flag(input, &name)?,
+                "check_only" => options.check_only = parse_flag(input, &name)?,
+                "antisymmetric" => options.antisymmetric = parse_flag(input, &name)?,
+                "guarded" => options.guarded = parse_flag(input, &name)?,
+                "by_ref" => options.by_ref = parse_flag(input, &name)?,
+                "fan_out" => options.fan_out = parse_list(input)?.into_iter().collect(),
+                "lift" => {
+                    let content;
+                    parenthesized!(content in input);
+                    options.lift.push(content.parse()?);
+                }
+                "wrap" => {
+                    let content;
+                    parenthesized!(content in input);
+                    options.wrap.push(content.parse()?);
+                }
+                "optional" => {
+                    let policy = parse_value::<LitStr>(input)?;
+                    options.optional = Some(match policy.value().as_str() {
+                        "none" => NonePolicy::None,
+                        "default" => NonePolicy::Default,
+                        "panic" => NonePolicy::Panic,
+                        _ => {
+                            return Err(Error::new(
+                                policy.span(),
+                                "expected one of \"none\", \"default\" or \"panic\"",
+                            ));
+                        }
+                    });
+                }
+                "operator" => options.operator.extend(parse_list::<Operator>(input)?),
+                "remote" => options.remote = Some(parse_value(input)?),
+                "ffi" => {
+                    let content;
+                    parenthesized!(content in input);
+                    options.ffi = Some(content.parse()?);
+                }
+                "wasm_bindgen" => {
+                    let content;
+                    parenthesized!(content in input);
+                    options.wasm_bindgen = Some(content.parse()?);
+                }
+                "pymethods" => options.pymethods = parse_flag(input, &name)?,
+                "swap_output" => options.swap_output = parse_flag(input, &name)?,
+                "export" => options.export = Some(parse_value(input)?),
+                "kani" => options.kani = parse_flag(input, &name)?,
+                "proptest" => {
+                    options.proptest = Some(if input.peek(token::Paren) {
+                        let content;
+                        parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        Proptest::default()
+                    });
+                }
+                "instrument" => {
+                    if !cfg!(feature = "tracing") {
+                        return Err(Error::new(
+                            name.span(),
+                            "`instrument` requires the `tracing` feature of symm_impl",
+                        ));
+                    }
+                    options.instrument = parse_flag(input, &name)?;
+                }
+                "markers" => options.markers = parse_flag(input, &name)?,
+                "seal" => options.seal = Some(parse_value(input)?),
+                "only" => {
+                    let methods = options.only.get_or_insert_with(Vec::new);
+                    methods.extend(parse_list::<Ident>(input)?);
+                }
+                "crate" => options.crate_path = parse_value(input)?,
+                "runtime" => options.runtime_path = parse_value(input)?,
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}`", name),
+                    ));
+                }
+            }
+
+            seen.push(key);
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(options)
+    }
+
+    /// The method paired with `method` by `pairs(...)`, which the mirror of
+    /// `method` delegates to, in either direction.
+    pub(crate) fn counterpart(&self, method: &Ident) -> Option<&Ident> {
+        self.pairs.iter().find_map(|(first, second)| {
+            if first == method {
+                Some(second)
+            } else if second == method {
+                Some(first)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// A `A = B` pair of the `same_type(...)` option.
+struct TypePair {
+    alias: Type,
+    aliased: Type,
+}
+
+impl Parse for TypePair {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let alias = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let aliased = input.parse()?;
+        Ok(TypePair { alias, aliased })
+    }
+}
+
+/// A `a = b` pair of the `pairs(...)` option.
+struct MethodPair {
+    method: Ident,
+    counterpart: Ident,
+}
+
+impl Parse for MethodPair {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let method = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let counterpart = input.parse()?;
+        Ok(MethodPair {
+            method,
+            counterpart,
+        })
+    }
+}
+
+/// Parse the `(a, b, ...)` part of a `key(a, b, ...)` option.
+fn parse_list<T: Parse>(input: ParseStream) -> Result<Punctuated<T, Token![,]>> {
+    let content;
+    parenthesized!(content in input);
+    content.parse_terminated(T::parse)
+}
+
+/// Parse a flag option, which is set unless given `= false`, e.g. to unset a
+/// flag inherited from `#[symmetric_options]` or the crate-wide defaults.
+fn parse_flag(input: ParseStream, name: &Ident) -> Result<bool> {
+    let message = || format!("`{}` is a flag and only takes `true` or `false`", name);
+    if input.peek(token::Paren) {
+        return Err(input.error(message()));
+    }
+    if !input.peek(Token![=]) {
+        return Ok(true);
+    }
+    input.parse::<Token![=]>()?;
+    match input.parse::<LitBool>() {
+        Ok(value) => Ok(value.value),
+        Err(error) => Err(Error::new(error.span(), message())),
+    }
+}
+
+/// Parse the `= value` part of a `key = value` option.
+fn parse_value<T: Parse>(input: ParseStream) -> Result<T> {
+    input.parse::<Token![=]>()?;
+    input.parse()
+}
+
+/// Parse the `= "A: Trait, B: 'a"` part of an option listing where-clause
+/// predicates.
+fn parse_predicates(input: ParseStream) -> Result<Punctuated<WherePredicate, Token![,]>> {
+    parse_value::<LitStr>(input)?.parse_with(Punctuated::parse_terminated)
+}
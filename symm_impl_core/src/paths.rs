@@ -0,0 +1,84 @@
+//! Re-rooting of module-relative paths, for mirrors emitted in another module
+//! than the original implementation.
+
+use std::iter;
+
+use syn::{
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    ExprPath, Ident, ItemImpl, PatPath, Path, PathSegment, QSelf, Token, TypePath,
+};
+
+/// Rewrite the `self::` and `super::` paths of an implementation such that
+/// they resolve the same from a module nested `depth` levels below the one of
+/// the implementation. `crate::` and `::` paths resolve the same from
+/// anywhere in the crate and are left untouched.
+pub(crate) fn reroot(item: &mut ItemImpl, depth: usize) {
+    Reroot { depth }.visit_item_impl_mut(item);
+}
+
+struct Reroot {
+    depth: usize,
+}
+
+impl Reroot {
+    /// Re-root a path which may be qualified, e.g. `<A as self::Trait>::f`.
+    ///
+    /// The segments of the trait of a qualified path are counted in `qself`,
+    /// which needs to account for the added segments.
+    fn visit_qualified_path_mut(&mut self, qself: &mut Option<QSelf>, path: &mut Path) {
+        let before = path.segments.len();
+        self.visit_path_mut(path);
+        if let Some(qself) = qself {
+            self.visit_type_mut(&mut qself.ty);
+            if qself.position > 0 {
+                qself.position += path.segments.len() - before;
+            }
+        }
+    }
+}
+
+impl VisitMut for Reroot {
+    fn visit_expr_path_mut(&mut self, expr: &mut ExprPath) {
+        for attr in &mut expr.attrs {
+            self.visit_attribute_mut(attr);
+        }
+        self.visit_qualified_path_mut(&mut expr.qself, &mut expr.path);
+    }
+
+    fn visit_pat_path_mut(&mut self, pat: &mut PatPath) {
+        for attr in &mut pat.attrs {
+            self.visit_attribute_mut(attr);
+        }
+        self.visit_qualified_path_mut(&mut pat.qself, &mut pat.path);
+    }
+
+    fn visit_type_path_mut(&mut self, ty: &mut TypePath) {
+        self.visit_qualified_path_mut(&mut ty.qself, &mut ty.path);
+    }
+
+    fn visit_path_mut(&mut self, path: &mut Path) {
+        // a lone `self` is the receiver, not a module
+        if path.leading_colon.is_none() && path.segments.len() > 1 {
+            let first = &path.segments[0].ident;
+            // `self::` is replaced while `super::` is prefixed
+            let skipped = if first == "self" {
+                Some(1)
+            } else if first == "super" {
+                Some(0)
+            } else {
+                None
+            };
+            if let Some(skipped) = skipped {
+                let span = first.span();
+                let mut segments: Punctuated<PathSegment, Token![::]> =
+                    iter::repeat_with(|| PathSegment::from(Ident::new("super", span)))
+                        .take(self.depth)
+                        .collect();
+                segments.extend(path.segments.iter().skip(skipped).cloned());
+                path.segments = segments;
+            }
+        }
+        visit_mut::visit_path_mut(self, path);
+    }
+}
@@ -0,0 +1,260 @@
+//! Property tests checking that the operation is symmetric, requested by
+//! `#[symmetric(proptest)]` or `#[symmetric(proptest(strategy(...)))]`.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, Expr, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl, Result, Token, Type,
+};
+
+use crate::{attrs, options::MirrorOptions, render::render};
+
+/// A `proptest(...)` option.
+#[derive(Clone, Default)]
+pub(crate) struct Proptest {
+    /// Strategies generating the operands of the given types, instead of
+    /// `any::<T>()`.
+    strategies: Vec<(Type, Expr)>,
+}
+
+/// A `Type = strategy` pair of `strategy(...)`.
+struct Strategy {
+    ty: Type,
+    strategy: Expr,
+}
+
+impl Parse for Strategy {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let strategy = input.parse()?;
+        Ok(Strategy { ty, strategy })
+    }
+}
+
+impl Parse for Proptest {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut proptest = Proptest::default();
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            match name.to_string().as_str() {
+                "strategy" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let strategies: Punctuated<Strategy, Token![,]> =
+                        content.parse_terminated(Strategy::parse)?;
+                    for Strategy { ty, strategy } in strategies {
+                        if proptest
+                            .strategies
+                            .iter()
+                            .any(|(listed, _)| render(listed) == render(&ty))
+                        {
+                            return Err(Error::new_spanned(
+                                &ty,
+                                format!("duplicate strategy for `{}`", render(&ty)),
+                            ));
+                        }
+                        proptest.strategies.push((ty, strategy));
+                    }
+                }
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}` of `proptest`", name),
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(proptest)
+    }
+}
+
+impl Proptest {
+    /// The strategy generating operands of type `ty`.
+    pub(crate) fn strategy(&self, ty: &Type) -> TokenStream {
+        let rendered = render(ty);
+        match self
+            .strategies
+            .iter()
+            .find(|(listed, _)| render(listed) == rendered)
+        {
+            Some((_, strategy)) => quote!(#strategy),
+            None => quote!(::proptest::prelude::any::<#ty>()),
+        }
+    }
+}
+
+/// Generate the property tests of every mirrored method, only compiled for
+/// the tests of the crate, if requested by the options.
+pub(crate) fn generate(ast: &ItemImpl, options: &MirrorOptions) -> Result<TokenStream> {
+    let proptest = match &options.proptest {
+        Some(proptest) => proptest,
+        None => return Ok(TokenStream::new()),
+    };
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`proptest` does not support generic implementations",
+        ));
+    }
+    let (_, trait_path, _) = ast.trait_.as_ref().unwrap();
+    let self_type = ast.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, self_type.clone());
+    let (self_rendered, other_rendered) = (render(self_type), render(&other_type));
+    if let Some((ty, _)) = proptest.strategies.iter().find(|(ty, _)| {
+        let rendered = render(ty);
+        rendered != self_rendered && rendered != other_rendered
+    }) {
+        return Err(Error::new_spanned(
+            ty,
+            format!(
+                "`{}` is not an operand, expected `{}` or `{}`",
+                render(ty),
+                self_rendered,
+                other_rendered
+            ),
+        ));
+    }
+
+    let mut tests = Vec::new();
+    for item in &ast.items {
+        match item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                tests.push(test(ast, method, &other_type, proptest, options)?);
+            }
+            _ => (),
+        }
+    }
+    let cfg = options
+        .cfg
+        .as_ref()
+        .map(|predicate| quote!(#[cfg(#predicate)]));
+    Ok(module(ast, "symmetric", cfg, &tests))
+}
+
+/// Put the property tests of a law of an implementation into their own
+/// module, only compiled for the tests of the crate.
+pub(crate) fn module(
+    ast: &ItemImpl,
+    law: &str,
+    cfg: Option<TokenStream>,
+    tests: &[TokenStream],
+) -> TokenStream {
+    let (_, trait_path, _) = ast.trait_.as_ref().unwrap();
+    let self_type = &ast.self_ty;
+    // test functions must be nameable, so they cannot go into the anonymous
+    // scope of the mirror, the module is named after the implementation and
+    // the law
+    let mut hasher = DefaultHasher::new();
+    render(&quote!(impl #trait_path for #self_type)).hash(&mut hasher);
+    if law != "symmetric" {
+        law.hash(&mut hasher);
+    }
+    let module = format_ident!("__symm_proptest_{:016x}", hasher.finish());
+    let impl_attrs = ast.attrs.iter().filter(|attr| attrs::is_cfg_like(attr));
+    quote! {
+        #(#impl_attrs)*
+        #cfg
+        #[cfg(test)]
+        mod #module {
+            use super::*;
+
+            ::proptest::proptest! {
+                #(#tests)*
+            }
+        }
+    }
+}
+
+/// How a law passes a generated operand to the operation, which is used
+/// several times.
+pub(crate) fn operand(method: &ImplItemMethod, operand: &TokenStream) -> Result<TokenStream> {
+    match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => Err(Error::new(
+                method.sig.ident.span(),
+                "`proptest` does not support methods taking `&mut self`",
+            )),
+            (Some(_), None) => Ok(quote!(&#operand)),
+            (None, _) => Ok(quote!(::core::clone::Clone::clone(&#operand))),
+        },
+        FnArg::Typed(_) => Err(Error::new(
+            method.sig.ident.span(),
+            "`proptest` requires a method taking `self`",
+        )),
+    }
+}
+
+/// Generate the test asserting that `method` gives the same result with the
+/// operands in both orders, or the negated result for antisymmetric relations.
+fn test(
+    item: &ItemImpl,
+    method: &ImplItemMethod,
+    other_type: &Type,
+    proptest: &Proptest,
+    options: &MirrorOptions,
+) -> Result<TokenStream> {
+    if method.sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "`proptest` does not support async methods",
+        ));
+    }
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    crate::lift::replace_other(&mut other_path, self_type.clone());
+    let method_name = &method.sig.ident;
+    let (lhs, rhs) = (
+        operand(method, &quote!(lhs))?,
+        operand(method, &quote!(rhs))?,
+    );
+    // the mirror of a paired method delegates to its counterpart
+    let mirrored_name = options.counterpart(method_name).unwrap_or(method_name);
+    let mirrored_call = quote!(<#other_type as #other_path>::#mirrored_name(#rhs, #lhs));
+    // the mirror flips the results, which are flipped back for the comparison
+    let mirrored_call = if options.swap_output {
+        let runtime_path = &options.runtime_path;
+        quote!(#runtime_path::SwapOperands::swap_operands(#mirrored_call))
+    } else {
+        mirrored_call
+    };
+    let (mirrored, relation) = if options.antisymmetric {
+        (quote!(-#mirrored_call), "antisymmetric")
+    } else {
+        (quote!(#mirrored_call), "symmetric")
+    };
+    let message = format!(
+        "`{}` of `{}` is not {}",
+        method_name,
+        render(trait_path),
+        relation
+    );
+    let name = format_ident!("{}_is_{}", method_name, relation);
+    let (lhs_strategy, rhs_strategy) =
+        (proptest.strategy(self_type), proptest.strategy(other_type));
+    let fn_attrs = method.attrs.iter().filter(|attr| attrs::is_cfg_like(attr));
+    Ok(quote! {
+        #(#fn_attrs)*
+        #[test]
+        fn #name(lhs in #lhs_strategy, rhs in #rhs_strategy) {
+            ::proptest::prop_assert!(
+                <#self_type as #trait_path>::#method_name(#lhs, #rhs) == #mirrored,
+                #message,
+            );
+        }
+    })
+}
@@ -0,0 +1,101 @@
+//! Knowledge of well-known traits of the standard library, whose provided
+//! methods are mirrored without being written in the implementation.
+//!
+//! The comparison operators of `PartialOrd` are not symmetric: the mirror of
+//! `impl PartialOrd<Disk> for Point2D` answers `disk < point` with
+//! `point > disk`, and reverses the ordering given by `partial_cmp`.
+
+use proc_macro2::Ident;
+use syn::{parse_quote, ImplItem, ImplItemMethod, ItemImpl, Path, Type};
+
+/// A well-known trait of the standard library.
+struct Known {
+    /// Name of the trait.
+    name: &'static str,
+    /// Provided methods, along with the method of the original
+    /// implementation their mirror delegates to.
+    provided: &'static [(&'static str, &'static str)],
+    /// Methods whose result is reversed by the mirror.
+    reversed: &'static [&'static str],
+}
+
+const KNOWN: &[Known] = &[
+    Known {
+        name: "PartialEq",
+        provided: &[("ne", "ne")],
+        reversed: &[],
+    },
+    Known {
+        name: "PartialOrd",
+        provided: &[("lt", "gt"), ("le", "ge"), ("gt", "lt"), ("ge", "le")],
+        reversed: &["partial_cmp"],
+    },
+];
+
+/// The well-known trait named by the path, spelled either on its own or
+/// through `cmp`, `core::cmp` or `std::cmp`.
+fn known(trait_path: &Path) -> Option<&'static Known> {
+    let last = trait_path.segments.last()?;
+    // the leading segments, innermost first
+    let prefix: Vec<_> = trait_path
+        .segments
+        .iter()
+        .rev()
+        .skip(1)
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    match prefix.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] | ["cmp"] | ["cmp", "core"] | ["cmp", "std"] => (),
+        _ => return None,
+    }
+    KNOWN.iter().find(|known| last.ident == known.name)
+}
+
+/// How the mirror of a method delegates to the original implementation: the
+/// method it calls, and whether the result is reversed.
+pub(crate) fn delegation(trait_path: &Path, method_name: &Ident) -> (Ident, bool) {
+    let known = match known(trait_path) {
+        Some(known) => known,
+        None => return (method_name.clone(), false),
+    };
+    let delegated = known
+        .provided
+        .iter()
+        .find(|(provided, _)| method_name == provided)
+        .map_or_else(
+            || method_name.clone(),
+            |(_, delegated)| Ident::new(delegated, method_name.span()),
+        );
+    let reversed = known
+        .reversed
+        .iter()
+        .any(|reversed| method_name == reversed);
+    (delegated, reversed)
+}
+
+/// The provided methods of a well-known trait which are not written in the
+/// implementation, with the signatures the implementation would have.
+pub(crate) fn missing(ast: &ItemImpl, trait_path: &Path, other_type: &Type) -> Vec<ImplItemMethod> {
+    let known = match known(trait_path) {
+        Some(known) => known,
+        None => return Vec::new(),
+    };
+    known
+        .provided
+        .iter()
+        .filter(|(provided, _)| {
+            !ast.items.iter().any(|item| match item {
+                ImplItem::Method(method) => method.sig.ident == provided,
+                _ => false,
+            })
+        })
+        .map(|(provided, _)| {
+            let method_name = Ident::new(provided, proc_macro2::Span::call_site());
+            parse_quote! {
+                fn #method_name(&self, other: &#other_type) -> bool {
+                    ::core::unreachable!()
+                }
+            }
+        })
+        .collect()
+}
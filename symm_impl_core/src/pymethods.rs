@@ -0,0 +1,135 @@
+//! `#[pymethods]` exposing the implementations on both Python classes,
+//! requested by `#[symmetric(pymethods)]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Attribute, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl, LitStr, Result,
+    ReturnType,
+};
+
+use crate::{attrs, bounds, options::MirrorOptions};
+
+/// Generate the Python methods of every mirrored method on both classes, if
+/// requested by the options.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<TokenStream> {
+    if !options.pymethods {
+        return Ok(TokenStream::new());
+    }
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`pymethods` does not support generic implementations",
+        ));
+    }
+    let mut methods = Vec::new();
+    for item in &ast.items {
+        match item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                methods.push(method);
+            }
+            _ => (),
+        }
+    }
+    let original = class(ast, &methods, options)?;
+    let mirrored = class(mirrored_ast, &methods, options)?;
+    Ok(quote!(#original #mirrored))
+}
+
+/// Generate the `#[pymethods]` block of the self type of `item`, calling the
+/// `methods` of `item`.
+fn class(
+    item: &ItemImpl,
+    methods: &[&ImplItemMethod],
+    options: &MirrorOptions,
+) -> Result<TokenStream> {
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, parse_quote!(()));
+    let mut py_methods = Vec::new();
+    for method in methods {
+        if method.sig.asyncness.is_some() {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                "`pymethods` does not support async methods",
+            ));
+        }
+        let (receiver, other, rhs) = match &method.sig.inputs[0] {
+            FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+                (Some(_), Some(_)) => (
+                    quote!(&mut self),
+                    quote!(mut other: ::pyo3::PyRefMut<'_, #other_type>),
+                    quote!(&mut *other),
+                ),
+                (Some(_), None) => (
+                    quote!(&self),
+                    quote!(other: ::pyo3::PyRef<'_, #other_type>),
+                    quote!(&*other),
+                ),
+                (None, _) => {
+                    return Err(Error::new(
+                        method.sig.ident.span(),
+                        "`pymethods` requires methods taking the operands by reference",
+                    ));
+                }
+            },
+            FnArg::Typed(_) => unreachable!(),
+        };
+        let output = match &method.sig.output {
+            ReturnType::Type(arrow, ty) => {
+                let ty = bounds::resolve_self(ty, self_type, trait_path);
+                quote!(#arrow #ty)
+            }
+            ReturnType::Default => quote!(),
+        };
+        let method_name = &method.sig.ident;
+        // the Rust name is hidden, so that the method of the trait is not
+        // shadowed by an inherent method
+        let name = format_ident!("__symm_py_{}", method_name);
+        let py_name = LitStr::new(
+            method_name.to_string().trim_start_matches("r#"),
+            method_name.span(),
+        );
+        let summary = format!(
+            " Call `{}` of `{}` with `{}` as the other operand.",
+            method_name,
+            crate::render(self_type),
+            crate::render(&other_type),
+        );
+        let method_attrs: Vec<&Attribute> = method
+            .attrs
+            .iter()
+            .filter(|attr| attrs::is_cfg_like(attr))
+            .collect();
+        py_methods.push(quote! {
+            #[doc = #summary]
+            #(#method_attrs)*
+            #[pyo3(name = #py_name)]
+            fn #name(#receiver, #other) #output {
+                <#self_type as #trait_path>::#method_name(self, #rhs)
+            }
+        });
+    }
+    let mut impl_attrs: Vec<&Attribute> = item
+        .attrs
+        .iter()
+        .filter(|attr| attrs::is_cfg_like(attr))
+        .collect();
+    let cfg: Option<Attribute> = options
+        .cfg
+        .as_ref()
+        .map(|predicate| parse_quote!(#[cfg(#predicate)]));
+    impl_attrs.extend(&cfg);
+    Ok(quote! {
+        #(#impl_attrs)*
+        #[::pyo3::pymethods]
+        impl #self_type {
+            #(#py_methods)*
+        }
+    })
+}
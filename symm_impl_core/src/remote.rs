@@ -0,0 +1,122 @@
+//! Local newtype of a foreign operand, requested by
+//! `#[symmetric(remote = Wrapper)]`.
+//!
+//! A foreign trait cannot be implemented between two foreign types. The
+//! implementation is instead written for a `#[repr(transparent)]` wrapper of
+//! the self type declared by the crate, which dereferences to the wrapped
+//! operand such that the bodies read the same. The mirror then implements the
+//! trait for the other operand with the wrapper as the type for the symmetry,
+//! which the orphan rules accept since the wrapper is local.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, ItemImpl, Result, Type, Visibility,
+};
+
+use crate::render;
+
+/// The `remote = Wrapper` option, with an optional visibility of the wrapper,
+/// e.g. `remote = pub LocalPoint`.
+#[derive(Clone)]
+pub(crate) struct Remote {
+    vis: Visibility,
+    ident: Ident,
+}
+
+impl Parse for Remote {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Remote {
+            vis: input.parse()?,
+            ident: input.parse()?,
+        })
+    }
+}
+
+/// Declare the wrapper of the self type of `ast` along with its conversions,
+/// and return the implementation rewritten for the wrapper.
+pub(crate) fn localize(ast: ItemImpl, remote: &Remote) -> Result<(TokenStream, ItemImpl)> {
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`remote` does not support generic implementations",
+        ));
+    }
+    let Remote { vis, ident } = remote;
+    let wrapped = ast.self_ty.as_ref().clone();
+    let doc = format!(
+        " Local wrapper of [`{}`], implementing the symmetric trait on its behalf.",
+        render::render(&wrapped),
+    );
+    let declaration = quote! {
+        #[doc = #doc]
+        #[repr(transparent)]
+        #vis struct #ident(#vis #wrapped);
+
+        const _: () = {
+            #[automatically_derived]
+            impl ::core::convert::From<#wrapped> for #ident {
+                #[inline]
+                fn from(value: #wrapped) -> Self {
+                    #ident(value)
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::From<#ident> for #wrapped {
+                #[inline]
+                fn from(value: #ident) -> Self {
+                    value.0
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::AsRef<#wrapped> for #ident {
+                #[inline]
+                fn as_ref(&self) -> &#wrapped {
+                    &self.0
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::AsMut<#wrapped> for #ident {
+                #[inline]
+                fn as_mut(&mut self) -> &mut #wrapped {
+                    &mut self.0
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::ops::Deref for #ident {
+                type Target = #wrapped;
+
+                #[inline]
+                fn deref(&self) -> &#wrapped {
+                    &self.0
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::ops::DerefMut for #ident {
+                #[inline]
+                fn deref_mut(&mut self) -> &mut #wrapped {
+                    &mut self.0
+                }
+            }
+        };
+    };
+
+    let mut ast = ast;
+    *ast.self_ty = Type::Verbatim(quote!(#ident));
+    Ok((declaration, ast))
+}
+
+/// Error for `remote` outside of the attribute, where the original
+/// implementation cannot be rewritten.
+pub(crate) fn unsupported() -> Error {
+    Error::new(
+        Span::call_site(),
+        "`remote` rewrites the implementation and is only supported by the attribute",
+    )
+}
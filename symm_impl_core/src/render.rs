@@ -0,0 +1,189 @@
+//! Human readable rendering of syntax trees, for diagnostics and generated
+//! documentation.
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::Path;
+
+/// Render a syntax tree the way it would be written by hand, e.g.
+/// `impl Distance<Disk> for Point2D` rather than the spaced out
+/// `impl Distance < Disk > for Point2D` produced by `TokenStream`.
+pub fn render<T: ToTokens + ?Sized>(node: &T) -> String {
+    let mut rendered = String::new();
+    render_stream(node.to_token_stream(), &mut rendered, Context::Plain);
+    rendered
+}
+
+/// Where the tokens being rendered are, telling apart binary operators of
+/// const expressions, e.g. `Grid<{ N * 2 }>` or `[u8; N * 2]`, from the
+/// pointers and references of types.
+#[derive(Clone, Copy, PartialEq)]
+enum Context {
+    Plain,
+    /// Within brackets, whose tokens after a `;` are an array length.
+    Array,
+    /// Within a const expression.
+    Const,
+}
+
+/// Whether the rendered text ends with an operand, such that an operator
+/// placed after it is binary.
+fn ends_with_operand(rendered: &str) -> bool {
+    match rendered.chars().last() {
+        Some(ch) => ch.is_alphanumeric() || matches!(ch, '_' | ')' | ']' | '}' | '"'),
+        None => false,
+    }
+}
+
+/// Whether a word placed after the rendered text needs to be separated by a
+/// space.
+fn needs_space(rendered: &str) -> bool {
+    match rendered.chars().last() {
+        Some(ch) => ch.is_alphanumeric() || matches!(ch, '_' | ',' | ';' | '>' | ')' | ']' | '"'),
+        None => false,
+    }
+}
+
+/// Whether the rendered text ends with a keyword that is followed by a type,
+/// such that a parenthesized type after it needs to be separated by a space.
+fn ends_with_keyword(rendered: &str) -> bool {
+    let word = rendered
+        .rsplit(|ch: char| !ch.is_alphanumeric() && ch != '_')
+        .next()
+        .unwrap_or_default();
+    matches!(
+        word,
+        "as" | "dyn" | "for" | "impl" | "in" | "mut" | "where" | "const"
+    )
+}
+
+/// Whether a `<` placed after the rendered text opens a qualified path, e.g.
+/// `<Cfg as Config>::Shape` in `for <Cfg as Config>::Shape`, rather than
+/// generic arguments or parameters.
+///
+/// `next` is the token after the `<`, telling `for<'a>` apart.
+fn opens_qualified_path(rendered: &str, next: Option<&TokenTree>) -> bool {
+    let word = rendered
+        .rsplit(|ch: char| !ch.is_alphanumeric() && ch != '_')
+        .next()
+        .unwrap_or_default();
+    let after_lifetime =
+        !word.is_empty() && rendered[..rendered.len() - word.len()].ends_with('\'');
+    let before_lifetime = matches!(next, Some(TokenTree::Punct(punct)) if punct.as_char() == '\'');
+    after_lifetime
+        || rendered.ends_with(',')
+        || matches!(word, "as" | "dyn" | "in" | "mut" | "where" | "const")
+        || (word == "for" && !before_lifetime)
+}
+
+fn render_stream(stream: TokenStream, rendered: &mut String, mut context: Context) {
+    // the previous punctuation if it is joint with the current one
+    let mut joint = None;
+    let mut stream = stream.into_iter().peekable();
+    while let Some(token) = stream.next() {
+        match token {
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if needs_space(rendered) {
+                    rendered.push(' ');
+                }
+                rendered.push_str(&token.to_string());
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                let space = match group.delimiter() {
+                    Delimiter::Parenthesis => {
+                        rendered.ends_with(',')
+                            || rendered.ends_with(';')
+                            || ends_with_keyword(rendered)
+                    }
+                    _ => needs_space(rendered),
+                };
+                let nested = match group.delimiter() {
+                    // a const generic argument, e.g. `{ N * 2 }` in `Grid<{ N * 2 }>`
+                    Delimiter::Brace if rendered.ends_with('<') || rendered.ends_with(',') => {
+                        Context::Const
+                    }
+                    Delimiter::Bracket => Context::Array,
+                    _ if context == Context::Const => Context::Const,
+                    _ => Context::Plain,
+                };
+                if space {
+                    rendered.push(' ');
+                }
+                rendered.push_str(open);
+                render_stream(group.stream(), rendered, nested);
+                rendered.push_str(close);
+            }
+            TokenTree::Punct(ref punct) => {
+                let ch = punct.as_char();
+                let is_joint = punct.spacing() == Spacing::Joint;
+                match ch {
+                    ';' if context == Context::Array => {
+                        rendered.push(ch);
+                        context = Context::Const;
+                    }
+                    '*' | '/' | '%' | '^' | '|' | '&' | '<' | '>'
+                        if context == Context::Const
+                            && (joint.is_some() || ends_with_operand(rendered)) =>
+                    {
+                        // binary operator, possibly spelled with several
+                        // characters, e.g. `<<` or `&&`
+                        if joint.is_none() {
+                            rendered.push(' ');
+                        }
+                        rendered.push(ch);
+                        if !is_joint {
+                            rendered.push(' ');
+                        }
+                    }
+                    ':' if is_joint || joint == Some(':') => rendered.push(':'),
+                    ':' => rendered.push_str(": "),
+                    '>' if matches!(joint, Some('-') | Some('=')) => rendered.push_str("> "),
+                    '-' | '=' if is_joint => {
+                        rendered.push(' ');
+                        rendered.push(ch);
+                    }
+                    '=' | '+' | '-' => {
+                        rendered.push(' ');
+                        rendered.push(ch);
+                        rendered.push(' ');
+                    }
+                    '<' if joint.is_none() && opens_qualified_path(rendered, stream.peek()) => {
+                        rendered.push_str(" <");
+                    }
+                    '\'' | '&' | '*' | '!' | '#' => {
+                        if needs_space(rendered) {
+                            rendered.push(' ');
+                        }
+                        rendered.push(ch);
+                    }
+                    _ => rendered.push(ch),
+                }
+                joint = if is_joint { Some(ch) } else { None };
+                continue;
+            }
+        }
+        joint = None;
+    }
+}
+
+/// Render the target of an intra-doc link to a trait, which is the path to
+/// the trait without any generic arguments.
+pub(crate) fn doc_link(path: &Path) -> String {
+    let mut link = String::from("trait@");
+    if path.leading_colon.is_some() {
+        link.push_str("::");
+    }
+    let segments: Vec<_> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    link.push_str(&segments.join("::"));
+    link
+}
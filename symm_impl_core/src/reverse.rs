@@ -0,0 +1,346 @@
+//! Converse traits, requested by `#[with_reverse(Within, contains = within)]`
+//! on the definition of `trait Contains<Other>`, e.g. `trait Within<Other>`
+//! implemented for every type contained in the other operand.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    visit_mut::VisitMut,
+    Attribute, Error, FnArg, GenericParam, Ident, ItemTrait, Pat, Receiver, Result, Token,
+    TraitBound, TraitBoundModifier, TraitItem, TraitItemMethod, Type, TypeParam, TypeParamBound,
+};
+
+use crate::{render::render, stub::Swap};
+
+/// Arguments of `#[with_reverse(Converse, method = converse, ...)]`.
+struct ReverseArgs {
+    /// Name of the converse trait.
+    ident: Ident,
+    /// Names of the converse methods, if different from the original ones.
+    renames: Vec<(Ident, Ident)>,
+}
+
+impl Parse for ReverseArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse()?;
+        let mut renames = Vec::new();
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            renames = crate::complement::renames(input)?;
+        }
+        Ok(ReverseArgs { ident, renames })
+    }
+}
+
+/// Expand `#[with_reverse]`, emitting the trait along with its converse.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut expanded = item.clone();
+    let generated = syn::parse2::<ReverseArgs>(attr).and_then(|args| {
+        let ast = syn::parse2::<ItemTrait>(item)?;
+        converse(&ast, &args)
+    });
+    match generated {
+        Ok(generated) => expanded.extend(generated),
+        Err(error) => expanded.extend(error.to_compile_error()),
+    }
+    expanded
+}
+
+/// Generate the converse trait, and its implementation for every type whose
+/// other operand implements the trait.
+fn converse(ast: &ItemTrait, args: &ReverseArgs) -> Result<TokenStream> {
+    let other = ast
+        .generics
+        .params
+        .iter()
+        .find_map(|param| match param {
+            GenericParam::Type(TypeParam { ident, .. }) => Some(ident),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            Error::new_spanned(
+                &ast.ident,
+                "#[with_reverse] requires a trait with a type parameter for the other operand",
+            )
+        })?;
+    for (method, _) in &args.renames {
+        let declared = ast.items.iter().any(|item| match item {
+            TraitItem::Method(declared) => declared.sig.ident == *method,
+            _ => false,
+        });
+        if !declared {
+            return Err(Error::new(
+                method.span(),
+                format!("method `{}` is not in the trait", method),
+            ));
+        }
+    }
+
+    let ident = &ast.ident;
+    let converse_ident = &args.ident;
+    // the trait implemented by the other operand, with the self type of the
+    // converse as its other operand
+    let arguments = ast.generics.params.iter().map(|param| match param {
+        GenericParam::Type(TypeParam { ident, .. }) if ident == other => quote!(__SymmSelf),
+        GenericParam::Type(TypeParam { ident, .. }) => quote!(#ident),
+        GenericParam::Lifetime(lifetime) => {
+            let lifetime = &lifetime.lifetime;
+            quote!(#lifetime)
+        }
+        GenericParam::Const(constant) => {
+            let ident = &constant.ident;
+            quote!(#ident)
+        }
+    });
+    let original_trait = quote!(#ident<#(#arguments),*>);
+
+    let self_type: Type = parse_quote!(Self);
+    let other_type: Type = parse_quote!(#other);
+    let mut swap = Swap {
+        lhs: &self_type,
+        rhs: &other_type,
+    };
+    let mut declarations = Vec::with_capacity(ast.items.len());
+    let mut definitions = Vec::with_capacity(ast.items.len());
+    for item in &ast.items {
+        match item {
+            TraitItem::Method(method) => {
+                let name = args
+                    .renames
+                    .iter()
+                    .find(|(original, _)| *original == method.sig.ident)
+                    .map_or(&method.sig.ident, |(_, renamed)| renamed);
+                let (declaration, definition) =
+                    converse_method(method, name, ident, &original_trait, &other_type, &mut swap)?;
+                declarations.push(declaration);
+                definitions.push(definition);
+            }
+            TraitItem::Type(associated_type) => {
+                if associated_type.generics.lt_token.is_some() {
+                    return Err(Error::new_spanned(
+                        &associated_type.generics,
+                        "#[with_reverse] does not support generic associated types",
+                    ));
+                }
+                let type_ident = &associated_type.ident;
+                let attrs = cfg_attrs(&associated_type.attrs);
+                let doc = format!(" Converse of [`{}::{}`].", ident, type_ident);
+                let mut bounds = associated_type.bounds.clone();
+                for bound in bounds.iter_mut() {
+                    swap.visit_type_param_bound_mut(bound);
+                }
+                let colon = associated_type.colon_token;
+                declarations.push(quote! {
+                    #[doc = #doc]
+                    #(#attrs)*
+                    type #type_ident #colon #bounds;
+                });
+                definitions.push(quote! {
+                    #(#attrs)*
+                    type #type_ident = <#other as #original_trait>::#type_ident;
+                });
+            }
+            TraitItem::Const(constant) => {
+                let const_ident = &constant.ident;
+                let attrs = cfg_attrs(&constant.attrs);
+                let doc = format!(" Converse of [`{}::{}`].", ident, const_ident);
+                let mut ty = constant.ty.clone();
+                swap.visit_type_mut(&mut ty);
+                declarations.push(quote! {
+                    #[doc = #doc]
+                    #(#attrs)*
+                    const #const_ident: #ty;
+                });
+                definitions.push(quote! {
+                    #(#attrs)*
+                    const #const_ident: #ty = <#other as #original_trait>::#const_ident;
+                });
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    item,
+                    "#[with_reverse] only supports methods, associated types and constants",
+                ));
+            }
+        }
+    }
+
+    let vis = &ast.vis;
+    let unsafety = &ast.unsafety;
+    let attrs = cfg_attrs(&ast.attrs);
+    let doc = format!(
+        " Converse of [`{}`], implemented for every type whose other operand implements it.",
+        ident
+    );
+    // the bounds of the other operand are the bounds of the self type of the
+    // converse, and conversely
+    let mut generics = ast.generics.clone();
+    let mut unsized_self = false;
+    let mut bounds = Vec::new();
+    for param in generics.type_params_mut() {
+        if param.ident != *other {
+            continue;
+        }
+        param.colon_token = None;
+        for bound in std::mem::take(&mut param.bounds) {
+            match bound {
+                TypeParamBound::Trait(TraitBound {
+                    modifier: TraitBoundModifier::Maybe(_),
+                    ..
+                }) => unsized_self = true,
+                bound => bounds.push(bound),
+            }
+        }
+    }
+    if !bounds.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#other: #(#bounds)+*));
+    }
+    if let Some(where_clause) = &mut generics.where_clause {
+        swap.visit_where_clause_mut(where_clause);
+    }
+    let trait_where_clause = &generics.where_clause;
+    let (_, type_generics, _) = generics.split_for_impl();
+    let mut impl_generics = generics.clone();
+    let lifetimes = impl_generics.lifetimes().count();
+    let self_param: GenericParam = if unsized_self {
+        parse_quote!(__SymmSelf: ?Sized)
+    } else {
+        parse_quote!(__SymmSelf)
+    };
+    impl_generics.params.insert(lifetimes, self_param);
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#other: #original_trait));
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+    Ok(quote! {
+        #[doc = #doc]
+        #(#attrs)*
+        #vis #unsafety trait #converse_ident #generics #trait_where_clause {
+            #(#declarations)*
+        }
+
+        #(#attrs)*
+        #[automatically_derived]
+        #unsafety impl #impl_generics #converse_ident #type_generics for __SymmSelf #where_clause {
+            #(#definitions)*
+        }
+    })
+}
+
+/// Generate the declaration of the converse of `method` named `name`, and its
+/// definition calling `method` with the operands swapped.
+fn converse_method(
+    method: &TraitItemMethod,
+    name: &Ident,
+    trait_ident: &Ident,
+    original_trait: &TokenStream,
+    other_type: &Type,
+    swap: &mut Swap,
+) -> Result<(TokenStream, TokenStream)> {
+    let unsupported = || {
+        Error::new_spanned(
+            &method.sig,
+            "#[with_reverse] requires methods taking `self` and the other operand, \
+             each by value or by reference",
+        )
+    };
+    let mut sig = method.sig.clone();
+    let receiver = match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) => receiver.clone(),
+        _ => return Err(unsupported()),
+    };
+    let (other_pat, other_arg) = match sig.inputs.iter().nth(1) {
+        Some(FnArg::Typed(typed)) => (typed.pat.as_ref(), typed.ty.as_ref()),
+        _ => return Err(unsupported()),
+    };
+    // the other operand becomes the receiver, and the receiver the other
+    // operand
+    let reference = match other_arg {
+        Type::Reference(reference) if render(&reference.elem) == render(other_type) => {
+            Some(reference)
+        }
+        ty if render(ty) == render(other_type) => None,
+        _ => return Err(unsupported()),
+    };
+    let self_token = receiver.self_token;
+    let converse_receiver = Receiver {
+        attrs: Vec::new(),
+        reference: reference.map(|reference| (reference.and_token, reference.lifetime.clone())),
+        mutability: reference.and_then(|reference| reference.mutability),
+        self_token,
+    };
+    let other_ident = match other_pat {
+        Pat::Ident(pat) => pat.ident.clone(),
+        _ => format_ident!("other"),
+    };
+    let converse_other: Type = match &receiver.reference {
+        Some((and, lifetime)) => {
+            let mutability = &receiver.mutability;
+            parse_quote!(#and #lifetime #mutability #other_type)
+        }
+        None => other_type.clone(),
+    };
+
+    let mut arguments = Vec::with_capacity(sig.inputs.len());
+    let mut inputs = sig.inputs.clone().into_iter();
+    inputs.next();
+    inputs.next();
+    sig.inputs.clear();
+    sig.inputs.push(FnArg::Receiver(converse_receiver));
+    sig.inputs.push(parse_quote!(#other_ident: #converse_other));
+    for (i, input) in inputs.enumerate() {
+        let mut typed = match input {
+            FnArg::Typed(typed) => typed,
+            FnArg::Receiver(_) => unreachable!(),
+        };
+        let arg = match typed.pat.as_ref() {
+            Pat::Ident(pat) => pat.ident.clone(),
+            _ => format_ident!("arg{}", i),
+        };
+        *typed.pat = parse_quote!(#arg);
+        swap.visit_type_mut(&mut typed.ty);
+        sig.inputs.push(FnArg::Typed(typed));
+        arguments.push(arg);
+    }
+    swap.visit_generics_mut(&mut sig.generics);
+    swap.visit_return_type_mut(&mut sig.output);
+    sig.ident = name.clone();
+
+    let method_ident = &method.sig.ident;
+    let mut call = quote!(<#other_type as #original_trait>::#method_ident(#other_ident, self, #(#arguments),*));
+    if sig.asyncness.is_some() {
+        call = quote!(#call.await);
+    }
+    if sig.unsafety.is_some() {
+        call = quote!(unsafe { #call });
+    }
+    let attrs = cfg_attrs(&method.attrs);
+    let doc = format!(" Converse of [`{}::{}`].", trait_ident, method_ident);
+    let declaration = quote! {
+        #[doc = #doc]
+        #(#attrs)*
+        #sig;
+    };
+    let definition = quote! {
+        #(#attrs)*
+        #[inline]
+        #sig {
+            #call
+        }
+    };
+    Ok((declaration, definition))
+}
+
+/// The attributes of `attrs` applying to the converse items too.
+fn cfg_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| crate::attrs::is_cfg_like(attr))
+        .collect()
+}
@@ -0,0 +1,404 @@
+//! Mirrored bodies copied from the original methods, requested by
+//! `#[symmetric(rewrite_body)]`, rather than delegating to them.
+//!
+//! `self` and the other operand swap names in the expressions and bindings of
+//! the body, as well as in the arguments of the macros it invokes, and `Self`
+//! names the self type of the original implementation. Fields, methods and
+//! path segments named after the other operand keep their names, while the
+//! shorthand `Foo { other }` and the format strings of the formatting macros
+//! of `std` capturing the other operand are expanded first. A body binding a local of the same name as the
+//! other operand refers to the operand through an alias instead, since `self`
+//! cannot be bound. The bindings of a destructured other operand are made from
+//! `self` at the start of the body.
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::Parser,
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Block, Expr, ExprLit, ExprPath, FieldPat, FieldValue, ImplItem, Item, ItemImpl, Lit, LitStr,
+    Macro, Member, Pat, PatIdent, Path, Result, Token, Type,
+};
+
+/// The names of the operands, and the meaning of `Self` in the original body.
+pub(crate) struct Rewrite<'a> {
+    /// Pattern of the other operand in the original method.
+    pub(crate) original_other: &'a Pat,
+    /// Name of the other operand in the mirrored method.
+    pub(crate) mirrored_other: &'a Ident,
+    pub(crate) self_type: &'a Type,
+    pub(crate) original_trait: &'a Path,
+    /// The associated types of the original implementation.
+    pub(crate) associated_types: &'a [&'a Ident],
+}
+
+/// The associated types of `ast`, which `Self::Type` refers to through the
+/// trait.
+pub(crate) fn associated_types(ast: &ItemImpl) -> Vec<&Ident> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Type(associated_type) => Some(&associated_type.ident),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether `block` binds a local named `other`, e.g. `let other = ...`, such
+/// that the mirror refers to its receiver through an alias.
+pub(crate) fn shadows(block: &Block, other: &Ident) -> bool {
+    let mut rename = Rename {
+        other,
+        renamed: other.clone(),
+        mirrored_other: other,
+        shadowed: false,
+    };
+    rename.visit_block_mut(&mut block.clone());
+    rename.shadowed
+}
+
+impl Rewrite<'_> {
+    /// Rewrite the body of the original method into the body of the mirror.
+    pub(crate) fn body(&self, block: &Block) -> Result<Block> {
+        let mut block = block.clone();
+        match self.original_other {
+            Pat::Ident(original_other) => {
+                let other = &original_other.ident;
+                Shorthand { other }.visit_block_mut(&mut block);
+                let shadowed = shadows(&block, other);
+                let renamed = if shadowed {
+                    format_ident!("__symm_{}", other)
+                } else {
+                    Ident::new("self", other.span())
+                };
+                Rename {
+                    other,
+                    renamed: renamed.clone(),
+                    mirrored_other: self.mirrored_other,
+                    shadowed: false,
+                }
+                .visit_block_mut(&mut block);
+                if shadowed {
+                    let alias = Pat::Ident(PatIdent {
+                        ident: renamed,
+                        ..original_other.clone()
+                    });
+                    block.stmts.insert(0, parse_quote!(let #alias = self;));
+                } else if let Some((_, subpat)) = &original_other.subpat {
+                    block.stmts.insert(0, parse_quote!(let #subpat = self;));
+                }
+            }
+            pattern => {
+                let mut rename = Rename {
+                    other: self.mirrored_other,
+                    renamed: self.mirrored_other.clone(),
+                    mirrored_other: self.mirrored_other,
+                    shadowed: false,
+                };
+                rename.visit_pat_mut(&mut pattern.clone());
+                if rename.shadowed {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        format!(
+                            "the pattern binds `{}`, the other operand of the mirror, which can be renamed by `other_ident`",
+                            self.mirrored_other
+                        ),
+                    ));
+                }
+                rename.visit_block_mut(&mut block);
+                block.stmts.insert(0, parse_quote!(let #pattern = self;));
+            }
+        }
+        syn::parse2(self.self_types(block.to_token_stream()))
+    }
+
+    /// Replace `Self` by the self type of the original implementation.
+    fn self_types(&self, stream: TokenStream) -> TokenStream {
+        let tokens: Vec<TokenTree> = stream.into_iter().collect();
+        let mut rewritten = TokenStream::new();
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => {
+                    let mut nested = Group::new(group.delimiter(), self.self_types(group.stream()));
+                    nested.set_span(group.span());
+                    rewritten.extend(Some(TokenTree::Group(nested)));
+                }
+                // path segments keep their names
+                TokenTree::Ident(ident)
+                    if ident == "Self" && !after_path_separator(&tokens[..i]) =>
+                {
+                    rewritten.extend(self.self_type_at(ident, &tokens[i + 1..]));
+                }
+                token => rewritten.extend(Some(token.clone())),
+            }
+        }
+        rewritten
+    }
+
+    /// The self type of the original implementation replacing `Self`, which
+    /// is followed by `rest`.
+    fn self_type_at(&self, ident: &Ident, rest: &[TokenTree]) -> TokenStream {
+        let self_type = self.self_type;
+        let original_trait = self.original_trait;
+        match rest {
+            [TokenTree::Punct(first), TokenTree::Punct(second), TokenTree::Ident(next), ..]
+                if is_path_separator(first, second) =>
+            {
+                if self.associated_types.contains(&next) {
+                    quote_spanned!(ident.span()=> <#self_type as #original_trait>)
+                } else {
+                    quote_spanned!(ident.span()=> <#self_type>)
+                }
+            }
+            _ => quote!(#self_type),
+        }
+    }
+}
+
+/// Swap of the names of the operands: `self` becomes `mirrored_other`, and
+/// `other` becomes `renamed`, either `self` or its alias. Nested items cannot
+/// refer to either operand, so they are left alone.
+struct Rename<'a> {
+    other: &'a Ident,
+    renamed: Ident,
+    mirrored_other: &'a Ident,
+    /// Whether a local named `other` is bound.
+    shadowed: bool,
+}
+
+impl Rename<'_> {
+    /// The new name of `ident`, if it names an operand.
+    fn rename(&self, ident: &Ident) -> Option<Ident> {
+        let mut renamed = if ident == "self" {
+            self.mirrored_other.clone()
+        } else if ident == self.other {
+            self.renamed.clone()
+        } else {
+            return None;
+        };
+        renamed.set_span(ident.span());
+        Some(renamed)
+    }
+
+    /// Rename the operands in the arguments of a macro which are not
+    /// expressions, token by token. Fields, methods and path segments keep
+    /// their names, as do fields initialized in a struct expression.
+    fn tokens(&self, stream: TokenStream, delimiter: Delimiter) -> TokenStream {
+        let tokens: Vec<TokenTree> = stream.into_iter().collect();
+        let mut rewritten = TokenStream::new();
+        for (i, token) in tokens.iter().enumerate() {
+            let qualified = after_path_separator(&tokens[..i])
+                || match &tokens[..i] {
+                    // but a range starting at an operand is renamed, e.g. `0..other`
+                    [.., TokenTree::Punct(range), TokenTree::Punct(previous)]
+                        if range.as_char() == '.' && range.spacing() == Spacing::Joint =>
+                    {
+                        previous.as_char() != '.'
+                    }
+                    [.., TokenTree::Punct(previous)] => previous.as_char() == '.',
+                    _ => false,
+                };
+            let field = matches!(
+                tokens.get(i + 1),
+                Some(TokenTree::Punct(next))
+                    if next.as_char() == ':' && next.spacing() == Spacing::Alone
+            ) && match &tokens[..i] {
+                [] => delimiter == Delimiter::Brace,
+                [.., TokenTree::Punct(previous)] => previous.as_char() == ',',
+                _ => false,
+            };
+            match token {
+                TokenTree::Group(group) => {
+                    let stream = self.tokens(group.stream(), group.delimiter());
+                    let mut nested = Group::new(group.delimiter(), stream);
+                    nested.set_span(group.span());
+                    rewritten.extend(Some(TokenTree::Group(nested)));
+                }
+                TokenTree::Ident(ident) if !qualified && !field => {
+                    let renamed = self.rename(ident).unwrap_or_else(|| ident.clone());
+                    rewritten.extend(Some(TokenTree::Ident(renamed)));
+                }
+                token => rewritten.extend(Some(token.clone())),
+            }
+        }
+        rewritten
+    }
+}
+
+impl VisitMut for Rename<'_> {
+    fn visit_expr_path_mut(&mut self, expr: &mut ExprPath) {
+        if expr.qself.is_none() {
+            if let Some(ident) = expr.path.get_ident() {
+                if let Some(renamed) = self.rename(ident) {
+                    expr.path = renamed.into();
+                }
+            }
+        }
+        visit_mut::visit_expr_path_mut(self, expr);
+    }
+
+    fn visit_pat_ident_mut(&mut self, pat: &mut PatIdent) {
+        if pat.ident == *self.other {
+            self.shadowed = true;
+            pat.ident = self.renamed.clone();
+        }
+        visit_mut::visit_pat_ident_mut(self, pat);
+    }
+
+    fn visit_field_pat_mut(&mut self, field: &mut FieldPat) {
+        // the shorthand `Foo { other }` binds the field to a local
+        if matches!(&field.member, Member::Named(name) if name == self.other) {
+            field.colon_token.get_or_insert_with(Default::default);
+        }
+        visit_mut::visit_field_pat_mut(self, field);
+    }
+
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        // macros in statement position are parsed as items
+        if let Item::Macro(item) = item {
+            if item.ident.is_none() {
+                self.visit_macro_mut(&mut item.mac);
+            }
+        }
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+        mac.tokens = match parser.parse2(mac.tokens.clone()) {
+            Ok(mut args) => {
+                for arg in args.iter_mut() {
+                    self.visit_expr_mut(arg);
+                }
+                args.into_token_stream()
+            }
+            Err(_) => self.tokens(mac.tokens.clone(), Delimiter::None),
+        };
+    }
+}
+
+/// Expansion of the uses of the other operand which are not written as an
+/// expression, before it is renamed: struct fields initialized by the
+/// shorthand, e.g. `Foo { other }`, and arguments of format strings captured
+/// by name, e.g. `"{other:?}"`, since `self` cannot be captured.
+struct Shorthand<'a> {
+    other: &'a Ident,
+}
+
+impl Shorthand<'_> {
+    /// The name of the format argument standing for the other operand.
+    fn argument(&self) -> Ident {
+        format_ident!("__symm_{}", self.other)
+    }
+
+    /// Rename the arguments of a format string naming the other operand,
+    /// returning whether one is captured rather than given.
+    fn rename_in_format(&self, format: &mut LitStr, given: bool) -> bool {
+        let value = format.value();
+        let mut renamed = String::with_capacity(value.len());
+        let mut rest = value.as_str();
+        let mut captured = false;
+        while let Some(start) = rest.find('{') {
+            renamed.push_str(&rest[..=start]);
+            rest = &rest[start + 1..];
+            if let Some(escaped) = rest.strip_prefix('{') {
+                renamed.push('{');
+                rest = escaped;
+                continue;
+            }
+            let end = rest.find([':', '}']).unwrap_or(rest.len());
+            if self.other == rest[..end].trim() {
+                renamed.push_str(&self.argument().to_string());
+                captured = !given;
+                rest = &rest[end..];
+            }
+        }
+        renamed.push_str(rest);
+        if renamed != value {
+            *format = LitStr::new(&renamed, format.span());
+        }
+        captured
+    }
+
+    /// Rename the other operand in the format string at `position` among
+    /// `args` and in the argument naming it, giving it as an argument when
+    /// captured.
+    fn rename_format_args(&self, args: &mut Punctuated<Expr, Token![,]>, position: usize) {
+        let argument = self.argument();
+        let mut given = false;
+        for arg in args.iter_mut().skip(position + 1) {
+            if let Expr::Assign(assign) = arg {
+                if matches!(assign.left.as_ref(), Expr::Path(name) if name.path.is_ident(self.other))
+                {
+                    *assign.left = parse_quote!(#argument);
+                    given = true;
+                }
+            }
+        }
+        if let Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(format),
+            ..
+        })) = args.iter_mut().nth(position)
+        {
+            if self.rename_in_format(format, given) {
+                let other = self.other;
+                args.push(parse_quote!(#argument = #other));
+            }
+        }
+    }
+}
+
+impl VisitMut for Shorthand<'_> {
+    fn visit_field_value_mut(&mut self, field: &mut FieldValue) {
+        if matches!(&field.member, Member::Named(name) if name == self.other) {
+            field.colon_token.get_or_insert_with(Default::default);
+        }
+        visit_mut::visit_field_value_mut(self, field);
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        // the arguments of most macros are expressions, which are visited
+        // as such, e.g. `vec![Foo { other }]`
+        let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+        let mut args = match parser.parse2(mac.tokens.clone()) {
+            Ok(args) => args,
+            Err(_) => return,
+        };
+        let before = args.to_token_stream().to_string();
+        if let Some(position) = format_position(&mac.path) {
+            self.rename_format_args(&mut args, position);
+        }
+        for arg in args.iter_mut() {
+            self.visit_expr_mut(arg);
+        }
+        let after = args.to_token_stream();
+        // the tokens are kept as written unless they were expanded
+        if after.to_string() != before {
+            mac.tokens = after;
+        }
+    }
+}
+
+/// The position of the format string among the arguments of `path`, if it
+/// names one of the formatting macros of `std`.
+fn format_position(path: &Path) -> Option<usize> {
+    match path.segments.last()?.ident.to_string().as_str() {
+        "format" | "format_args" | "print" | "println" | "eprint" | "eprintln" | "panic" => Some(0),
+        "write" | "writeln" => Some(1),
+        _ => None,
+    }
+}
+
+/// Whether `tokens` end with `::`.
+fn after_path_separator(tokens: &[TokenTree]) -> bool {
+    matches!(
+        tokens,
+        [.., TokenTree::Punct(first), TokenTree::Punct(second)] if is_path_separator(first, second)
+    )
+}
+
+/// Whether two punctuations are `::`.
+fn is_path_separator(first: &Punct, second: &Punct) -> bool {
+    first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':'
+}
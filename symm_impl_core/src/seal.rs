@@ -0,0 +1,68 @@
+//! Implementations of a sealing trait, requested by
+//! `#[symmetric(seal = path::to::Seal)]`.
+//!
+//! A trait declared as `pub trait Distance<Other>: private::DistanceSeal<Other>`,
+//! with `DistanceSeal` unnameable outside of the crate, can only be
+//! implemented where `DistanceSeal` is, i.e. by the implementations of the
+//! crate with the `seal` option. Downstream crates thus cannot write a reverse
+//! implementation diverging from the mirror.
+
+use quote::quote;
+use syn::{parse_quote, ItemImpl, Path};
+
+use crate::{attrs, options::MirrorOptions, render};
+
+/// Generate the implementations of the sealing trait for the original
+/// implementation and every generated implementation of the same trait.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    generated: &[ItemImpl],
+    options: &MirrorOptions,
+) -> Vec<ItemImpl> {
+    let seal = match &options.seal {
+        Some(seal) => seal,
+        None => return Vec::new(),
+    };
+    let (_, original_trait, _) = ast.trait_.as_ref().unwrap();
+    let trait_ident = &original_trait.segments.last().unwrap().ident;
+    let self_type = &ast.self_ty;
+    let summary = format!(
+        "Seal of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #self_type }),
+        render::doc_link(original_trait),
+    );
+    std::iter::once(ast)
+        .chain(generated)
+        .filter_map(|item| match &item.trait_ {
+            // the mirror may spell the trait differently, as long as the name
+            // is the same
+            Some((_, trait_path, _))
+                if trait_path.segments.last().unwrap().ident == *trait_ident =>
+            {
+                Some(sealed(item, trait_path, seal, &summary, ast, options))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Generate the implementation of the sealing trait for an implementation of
+/// `trait_path`.
+fn sealed(
+    item: &ItemImpl,
+    trait_path: &Path,
+    seal: &Path,
+    summary: &str,
+    ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> ItemImpl {
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, item.self_ty.as_ref().clone());
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let self_type = &item.self_ty;
+    let attrs = attrs::generated_impl(summary, &ast.attrs, options);
+    parse_quote! {
+        #(#attrs)*
+        impl #impl_generics #seal<#other_type> for #self_type #where_clause {}
+    }
+}
@@ -0,0 +1,147 @@
+//! Snapshot testing of the code generated for an implementation, such that
+//! crates relying on `#[symmetric]` notice when an upgrade changes their
+//! mirrors.
+
+use std::{env, fs, path::Path};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::ItemImpl;
+
+use crate::render::render;
+
+/// Name of the environment variable which, when set, makes snapshots be
+/// written instead of compared.
+const BLESS_VAR: &str = "SYMM_IMPL_BLESS";
+
+/// Assert that the code generated by `#[symmetric(attr)]` for the
+/// implementation `item` matches the snapshot stored in the file at `path`.
+///
+/// The generated code is laid out one statement or item per line, such that
+/// differences read well in a diff. When the environment variable
+/// `SYMM_IMPL_BLESS` is set, the snapshot is written instead, to be reviewed
+/// and committed.
+///
+/// # Panics
+/// Panics if the code does not match the snapshot, if the snapshot is missing,
+/// or if the implementation is rejected by the attribute.
+///
+/// ```no_run
+/// symm_impl_core::assert_expansion_snapshot(
+///     "",
+///     "impl Distance<Disk> for Point2D {
+///         fn distance(&self, other: &Disk) -> f64 { 0.0 }
+///     }",
+///     "tests/snapshots/distance.rs",
+/// );
+/// ```
+pub fn assert_expansion_snapshot(attr: &str, item: &str, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let attr: TokenStream = attr.parse().expect("the options are not valid tokens");
+    let item: ItemImpl = syn::parse_str(item).expect("the item is not an implementation");
+    let generated = match crate::generate(attr, &item) {
+        Ok(generated) => generated,
+        Err(error) => panic!("the implementation is rejected: {}", error),
+    };
+    let actual = layout(generated);
+
+    if env::var_os(BLESS_VAR).is_some_and(|bless| !bless.is_empty()) {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(path, &actual)
+            .unwrap_or_else(|error| panic!("failed to write `{}`: {}", path.display(), error));
+        return;
+    }
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(error) => panic!(
+            "failed to read the snapshot `{}`: {}, set {} to write it",
+            path.display(),
+            error,
+            BLESS_VAR
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "the generated code does not match the snapshot `{}`, set {} to update it\n\
+             expected:\n{}\nactual:\n{}",
+            path.display(),
+            BLESS_VAR,
+            expected,
+            actual
+        );
+    }
+}
+
+/// Lay out generated code with one attribute, statement or item per line, and
+/// the contents of braces indented.
+fn layout(stream: TokenStream) -> String {
+    let mut laid_out = String::new();
+    layout_block(stream, 0, &mut laid_out);
+    laid_out
+}
+
+fn layout_block(stream: TokenStream, depth: usize, laid_out: &mut String) {
+    let indent = "    ".repeat(depth);
+    // tokens of the current line, and whether it continues a closing brace
+    let mut line = TokenStream::new();
+    let mut after_brace = false;
+    let flush = |line: &mut TokenStream, after_brace: &mut bool, laid_out: &mut String| {
+        if line.is_empty() {
+            return;
+        }
+        let rendered = render(line);
+        let rendered = rendered.trim();
+        if *after_brace {
+            // e.g. `};` or `} else {`
+            laid_out.pop();
+            if !rendered.starts_with([';', ',']) {
+                laid_out.push(' ');
+            }
+        } else {
+            laid_out.push_str(&indent);
+        }
+        laid_out.push_str(rendered);
+        *line = TokenStream::new();
+        *after_brace = false;
+    };
+    for token in stream {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                flush(&mut line, &mut after_brace, laid_out);
+                if laid_out.ends_with('\n') || laid_out.is_empty() {
+                    laid_out.push_str(&indent);
+                } else {
+                    laid_out.push(' ');
+                }
+                laid_out.push_str("{\n");
+                layout_block(group.stream(), depth + 1, laid_out);
+                laid_out.push_str(&indent);
+                laid_out.push_str("}\n");
+                after_brace = true;
+            }
+            // attributes go on their own line
+            TokenTree::Group(ref group)
+                if group.delimiter() == Delimiter::Bracket
+                    && matches!(
+                        line.clone().into_iter().last(),
+                        Some(TokenTree::Punct(punct)) if punct.as_char() == '#'
+                    ) =>
+            {
+                line.extend(Some(token));
+                flush(&mut line, &mut after_brace, laid_out);
+                laid_out.push('\n');
+            }
+            TokenTree::Punct(ref punct) if punct.as_char() == ';' => {
+                line.extend(Some(token));
+                flush(&mut line, &mut after_brace, laid_out);
+                laid_out.push('\n');
+            }
+            token => line.extend(Some(token)),
+        }
+    }
+    if !line.is_empty() {
+        flush(&mut line, &mut after_brace, laid_out);
+        laid_out.push('\n');
+    }
+}
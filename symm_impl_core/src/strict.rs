@@ -0,0 +1,155 @@
+//! Exhaustiveness check of `#[symmetric(strict)]`.
+//!
+//! A procedural macro only sees the item it is applied to, so the methods of a
+//! trait are recorded by `#[symmetric_trait]` on its definition, into a
+//! declarative macro named after the trait. Since macros and traits live in
+//! different namespaces, importing the trait also imports the macro, and the
+//! strict check finds it through the path of the trait in the implementation.
+//! The macro hands the methods over to `__check_strict`, which compares them
+//! with the methods written in the implementation.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, Ident, ImplItem, ItemImpl, ItemTrait, PathArguments, Result, Token, TraitItem,
+    Visibility,
+};
+
+use crate::options::MirrorOptions;
+
+/// Expand `#[symmetric_trait]`, recording the methods of a trait for
+/// `#[symmetric(strict)]`.
+pub fn expand_trait(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut expanded = item.clone();
+    if !attr.is_empty() {
+        expanded.extend(Error::new(Span::call_site(), "expected no options").to_compile_error());
+        return expanded;
+    }
+    let ast = match syn::parse2::<ItemTrait>(item) {
+        Ok(ast) => ast,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let ident = &ast.ident;
+    let module = format_ident!("__symm_impl_{}", ident);
+    let methods = ast.items.iter().filter_map(|item| match item {
+        TraitItem::Method(method) => Some(&method.sig.ident),
+        _ => None,
+    });
+    // a macro which is not exported cannot be visible outside of the crate
+    let vis = match &ast.vis {
+        Visibility::Public(_) => quote!(pub(crate)),
+        Visibility::Inherited => quote!(),
+        vis => quote!(#vis),
+    };
+    expanded.extend(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        mod #module {
+            macro_rules! #ident {
+                (($($callback:tt)*) $($args:tt)*) => {
+                    $($callback)*! { methods = [#(#methods),*] $($args)* }
+                };
+            }
+            pub(crate) use #ident;
+        }
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        #vis use self::#module::#ident;
+    });
+    expanded
+}
+
+/// Generate the check that an implementation writes every method of its
+/// trait, if requested by the options.
+pub(crate) fn check(ast: &ItemImpl, options: &MirrorOptions) -> TokenStream {
+    let trait_path = match &ast.trait_ {
+        Some((_, trait_path, _)) if options.strict => trait_path,
+        _ => return TokenStream::new(),
+    };
+    // macro paths take no generic arguments, on any segment, e.g. for
+    // `scalar::<f64>::Distance<Disk>`
+    let mut macro_path = trait_path.clone();
+    for segment in &mut macro_path.segments {
+        segment.arguments = PathArguments::None;
+    }
+    let at = macro_path.segments.last().unwrap().ident.clone();
+    let written = ast.items.iter().filter_map(|item| match item {
+        ImplItem::Method(method) => Some(&method.sig.ident),
+        _ => None,
+    });
+    let crate_path = &options.crate_path;
+    quote! {
+        #macro_path! {
+            (#crate_path::__check_strict) at = #at, written = [#(#written),*]
+        }
+    }
+}
+
+/// Input of `__check_strict`, e.g.
+/// `methods = [distance, distance_squared] at = Distance, written = [distance]`.
+struct CheckInput {
+    methods: Punctuated<Ident, Token![,]>,
+    at: Ident,
+    written: Punctuated<Ident, Token![,]>,
+}
+
+/// Parse the `key = [a, b, ...]` part of the input.
+fn parse_idents(input: ParseStream, key: &str) -> Result<Punctuated<Ident, Token![,]>> {
+    let name = input.parse::<Ident>()?;
+    if name != key {
+        return Err(Error::new(name.span(), format!("expected `{}`", key)));
+    }
+    input.parse::<Token![=]>()?;
+    let content;
+    bracketed!(content in input);
+    content.parse_terminated(Ident::parse)
+}
+
+impl Parse for CheckInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let methods = parse_idents(input, "methods")?;
+        let name = input.parse::<Ident>()?;
+        if name != "at" {
+            return Err(Error::new(name.span(), "expected `at`"));
+        }
+        input.parse::<Token![=]>()?;
+        let at = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let written = parse_idents(input, "written")?;
+        Ok(CheckInput {
+            methods,
+            at,
+            written,
+        })
+    }
+}
+
+/// Expand `__check_strict`, reporting the methods of the trait missing from
+/// the implementation.
+pub fn check_strict(input: TokenStream) -> TokenStream {
+    let input = match syn::parse2::<CheckInput>(input) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error(),
+    };
+    let missing: Vec<_> = input
+        .methods
+        .iter()
+        .filter(|method| !input.written.iter().any(|written| written == *method))
+        .map(|method| format!("`{}`", method))
+        .collect();
+    if missing.is_empty() {
+        return TokenStream::new();
+    }
+    Error::new(
+        input.at.span(),
+        format!(
+            "#[symmetric(strict)] requires every method to be implemented, missing {}",
+            missing.join(", ")
+        ),
+    )
+    .to_compile_error()
+}
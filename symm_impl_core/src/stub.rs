@@ -0,0 +1,129 @@
+//! Stub mirror emitted along with the error when an implementation cannot be
+//! mirrored, such that code relying on the mirror still type checks and the
+//! error is reported once, on the implementation.
+
+use syn::{
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    FnArg, GenericArgument, ImplItem, ItemImpl, PathArguments, Type,
+};
+
+use crate::{bounds, options::MirrorOptions, render::render};
+
+/// Generate a mirror of the implementation whose methods are unimplemented, if
+/// the operands can be told apart, for the trait and the other type given by
+/// `trait_path` and `other`.
+///
+/// The methods keep the signatures as written with the operands swapped,
+/// whether or not they have the shape of a symmetric method. The stub is only
+/// compiled where the mirror would be, as given by `cfg(...)`.
+pub(crate) fn generate(ast: &ItemImpl, options: &MirrorOptions) -> Option<ItemImpl> {
+    let (bang, trait_path, for_token) = ast.trait_.as_ref()?;
+    if bang.is_some() {
+        return None;
+    }
+    let self_type = ast.self_ty.as_ref();
+    // the trait and the other type as the mirror would take them
+    let trait_path = &crate::spelled_trait(trait_path, options);
+    let mut mirrored_trait = trait_path.clone();
+    let other_type = match &mut mirrored_trait.segments.last_mut()?.arguments {
+        PathArguments::AngleBracketed(generics) => {
+            let slot = crate::other_slot(generics, options).ok()?;
+            match &mut generics.args[slot] {
+                GenericArgument::Type(other_type) => other_type,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let mirrored_self_type = std::mem::replace(other_type, self_type.clone());
+    if render(self_type) == render(&mirrored_self_type) {
+        // the stub would conflict with the implementation
+        return None;
+    }
+    let mut swap = Swap {
+        lhs: self_type,
+        rhs: &mirrored_self_type,
+    };
+
+    let mut items = Vec::with_capacity(ast.items.len());
+    for item in &ast.items {
+        match item {
+            ImplItem::Method(method) => {
+                let mut sig = method.sig.clone();
+                for input in sig.inputs.iter_mut() {
+                    match input {
+                        // the receiver is unused
+                        FnArg::Receiver(receiver) if receiver.reference.is_none() => {
+                            receiver.mutability = None;
+                        }
+                        FnArg::Receiver(_) => (),
+                        FnArg::Typed(typed) => *typed.pat = parse_quote!(_),
+                    }
+                }
+                swap.visit_signature_mut(&mut sig);
+                let attrs: Vec<_> = method
+                    .attrs
+                    .iter()
+                    .filter(|attr| crate::attrs::is_cfg_like(attr))
+                    .cloned()
+                    .collect();
+                items.push(ImplItem::Method(parse_quote! {
+                    #(#attrs)*
+                    #sig {
+                        ::core::unimplemented!()
+                    }
+                }));
+            }
+            ImplItem::Type(associated_type) => {
+                let mut associated_type = associated_type.clone();
+                let type_ident = &associated_type.ident;
+                associated_type.ty = parse_quote!(<#self_type as #trait_path>::#type_ident);
+                items.push(ImplItem::Type(associated_type));
+            }
+            _ => (),
+        }
+    }
+
+    let mut generics = ast.generics.clone();
+    bounds::resolve_self_in(&mut generics, self_type, trait_path);
+    let mut attrs: Vec<_> = ast
+        .attrs
+        .iter()
+        .filter(|attr| crate::attrs::is_cfg_like(attr))
+        .cloned()
+        .collect();
+    if let Some(predicate) = &options.cfg {
+        attrs.push(parse_quote!(#[cfg(#predicate)]));
+    }
+    Some(ItemImpl {
+        attrs,
+        defaultness: None,
+        unsafety: ast.unsafety,
+        impl_token: ast.impl_token,
+        generics,
+        trait_: Some((None, mirrored_trait, *for_token)),
+        self_ty: Box::new(mirrored_self_type),
+        brace_token: ast.brace_token,
+        items,
+    })
+}
+
+/// Swap of the types of the operands wherever they appear.
+pub(crate) struct Swap<'a> {
+    pub(crate) lhs: &'a Type,
+    pub(crate) rhs: &'a Type,
+}
+
+impl VisitMut for Swap<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let rendered = render(ty);
+        if rendered == render(self.lhs) {
+            *ty = self.rhs.clone();
+        } else if rendered == render(self.rhs) {
+            *ty = self.lhs.clone();
+        } else {
+            visit_mut::visit_type_mut(self, ty);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Transitive relations, declared by `#[transitive]`, e.g.
+//! `impl Reaches<Node> for Node`, whose property tests requested by
+//! `#[transitive(proptest)]` check that `R(a, b)` and `R(b, c)` imply
+//! `R(a, c)`, and that `R(a, a)` holds for a relation declared `reflexive`.
+//! `#[transitive(markers)]` implements `TransitiveOp`, and `ReflexiveOp` for a
+//! reflexive relation.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_quote, token, Error, Ident, ItemImpl, Path, Result, ReturnType, Token, Type,
+};
+
+use crate::{fold, markers, proptest::Proptest, render::render};
+
+/// Arguments of `#[transitive(method = name, reflexive, markers, proptest)]`.
+struct TransitiveArgs {
+    /// The method of the relation, needed if the implementation has several
+    /// methods.
+    method: Option<Ident>,
+    /// Whether the relation is also reflexive.
+    reflexive: bool,
+    /// Whether the marker traits of `symm_impl_runtime` are implemented.
+    markers: bool,
+    /// Path to `symm_impl_runtime` in generated code.
+    runtime_path: Option<Path>,
+    /// Whether property tests check the transitivity of the relation.
+    proptest: Option<Proptest>,
+}
+
+impl Parse for TransitiveArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = TransitiveArgs {
+            method: None,
+            reflexive: false,
+            markers: false,
+            runtime_path: None,
+            proptest: None,
+        };
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            match name.to_string().as_str() {
+                "method" if args.method.is_none() => {
+                    input.parse::<Token![=]>()?;
+                    args.method = Some(input.parse()?);
+                }
+                "reflexive" if !args.reflexive => args.reflexive = true,
+                "markers" if !args.markers => args.markers = true,
+                "runtime" if args.runtime_path.is_none() => {
+                    input.parse::<Token![=]>()?;
+                    args.runtime_path = Some(input.parse()?);
+                }
+                "proptest" if args.proptest.is_none() => {
+                    args.proptest = Some(if input.peek(token::Paren) {
+                        let content;
+                        parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        Proptest::default()
+                    });
+                }
+                "method" | "reflexive" | "markers" | "runtime" | "proptest" => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("duplicate option `{}`", name),
+                    ));
+                }
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}`", name),
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(args)
+    }
+}
+
+/// Expand `#[transitive]`, emitting the implementation along with the checks
+/// of the law.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut expanded = item.clone();
+    let generated = syn::parse2::<TransitiveArgs>(attr).and_then(|args| {
+        let ast = syn::parse2::<ItemImpl>(item)?;
+        checks(&ast, &args)
+    });
+    match generated {
+        Ok(generated) => expanded.extend(generated),
+        Err(error) => expanded.extend(error.to_compile_error()),
+    }
+    expanded
+}
+
+/// Check that the implementation is a relation of a type with itself, and
+/// generate the marker implementations and the property tests of its laws if
+/// requested.
+fn checks(ast: &ItemImpl, args: &TransitiveArgs) -> Result<TokenStream> {
+    let trait_path = fold::homogeneous(ast, "#[transitive]")?;
+    let method = fold::select(ast, args.method.as_ref(), "#[transitive]")?;
+    let returns_bool = match &method.sig.output {
+        ReturnType::Type(_, output) => {
+            matches!(output.as_ref(), Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+        }
+        ReturnType::Default => false,
+    };
+    if !returns_bool {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "#[transitive] requires a relation returning `bool`",
+        ));
+    }
+    if args.runtime_path.is_some() && !args.markers {
+        return Err(Error::new(
+            Span::call_site(),
+            "`runtime` requires `markers`",
+        ));
+    }
+    let mut generated = TokenStream::new();
+    if args.markers {
+        let runtime_path = args
+            .runtime_path
+            .clone()
+            .unwrap_or_else(|| parse_quote!(::symm_impl_runtime));
+        let transitive_op = markers::property(ast, "TransitiveOp", &runtime_path);
+        generated.extend(quote!(#transitive_op));
+        if args.reflexive {
+            let reflexive_op = markers::property(ast, "ReflexiveOp", &runtime_path);
+            generated.extend(quote!(#reflexive_op));
+        }
+    }
+    let proptest = match &args.proptest {
+        Some(proptest) => proptest,
+        None => return Ok(generated),
+    };
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`proptest` does not support generic implementations",
+        ));
+    }
+
+    let self_type = ast.self_ty.as_ref();
+    let method_name = &method.sig.ident;
+    let a = crate::proptest::operand(method, &quote!(a))?;
+    let b = crate::proptest::operand(method, &quote!(b))?;
+    let c = crate::proptest::operand(method, &quote!(c))?;
+    let operation = quote!(<#self_type as #trait_path>::#method_name);
+    let relation = |lhs: &TokenStream, rhs: &TokenStream| quote!(#operation(#lhs, #rhs));
+    let (ab, bc, ac) = (relation(&a, &b), relation(&b, &c), relation(&a, &c));
+    let name = format_ident!("{}_is_transitive", method_name);
+    let strategy = proptest.strategy(self_type);
+    let message = format!(
+        "`{}` of `{}` is not transitive",
+        method_name,
+        render(trait_path)
+    );
+    let mut tests = vec![quote! {
+        #[test]
+        fn #name(a in #strategy, b in #strategy, c in #strategy) {
+            ::proptest::prop_assert!(!(#ab && #bc) || #ac, #message,);
+        }
+    }];
+    if args.reflexive {
+        let aa = relation(&a, &a);
+        let name = format_ident!("{}_is_reflexive", method_name);
+        let message = format!(
+            "`{}` of `{}` is not reflexive",
+            method_name,
+            render(trait_path)
+        );
+        tests.push(quote! {
+            #[test]
+            fn #name(a in #strategy) {
+                ::proptest::prop_assert!(#aa, #message,);
+            }
+        });
+    }
+    generated.extend(crate::proptest::module(ast, "transitive", None, &tests));
+    Ok(generated)
+}
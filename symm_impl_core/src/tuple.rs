@@ -0,0 +1,181 @@
+//! Mirrors of traits implemented for a pair of operands, e.g.
+//! `impl Collide for (Ball, Wall)`, implemented for the swapped pair
+//! `(Wall, Ball)` by swapping the components back.
+
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, spanned::Spanned, Error, FnArg, GenericArgument, ImplItem, ImplItemMethod,
+    ImplItemType, ItemImpl, Pat, PatIdent, Path, PathArguments, Result, Type,
+};
+
+use crate::{
+    attrs, bounds,
+    options::{CallStyle, MirrorOptions},
+    render,
+};
+
+/// Whether the implementation is of a trait without type arguments for a pair
+/// of operands.
+pub(crate) fn is_tuple_impl(ast: &ItemImpl, trait_path: &Path) -> bool {
+    let has_type_argument = match &trait_path.segments.last().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => generics
+            .args
+            .iter()
+            .any(|arg| matches!(arg, GenericArgument::Type(_))),
+        _ => false,
+    };
+    !has_type_argument
+        && matches!(ast.self_ty.as_ref(), Type::Tuple(tuple) if tuple.elems.len() == 2)
+}
+
+/// Generate the implementation for the swapped pair.
+pub(crate) fn mirror(
+    ast: &ItemImpl,
+    original_trait: &Path,
+    options: &MirrorOptions,
+) -> Result<ItemImpl> {
+    let unsupported = [
+        ("antisymmetric", options.antisymmetric),
+        ("swap_output", options.swap_output),
+        ("by_ref", options.by_ref),
+        ("fan_out", !options.fan_out.is_empty()),
+        ("lift", !options.lift.is_empty()),
+        ("wrap", !options.wrap.is_empty()),
+        ("optional", options.optional.is_some()),
+        ("operator", !options.operator.is_empty()),
+        ("ffi", options.ffi.is_some()),
+        ("wasm_bindgen", options.wasm_bindgen.is_some()),
+        ("pymethods", options.pymethods),
+        ("markers", options.markers),
+        ("seal", options.seal.is_some()),
+        ("kani", options.kani),
+        ("proptest", options.proptest.is_some()),
+        ("instrument", options.instrument),
+        ("other", options.other.is_some()),
+        ("rewrite_body", options.rewrite_body),
+        ("pairs", !options.pairs.is_empty()),
+        ("delegate_via", options.delegate_via.is_some()),
+        ("call", matches!(options.call, CallStyle::Method)),
+    ];
+    if let Some((name, _)) = unsupported.iter().find(|(_, given)| *given) {
+        return Err(Error::new_spanned(
+            &ast.self_ty,
+            format!("`{}` is not supported for implementations for a pair", name),
+        ));
+    }
+    let self_type = ast.self_ty.as_ref();
+    let swapped: Type = match self_type {
+        Type::Tuple(tuple) => {
+            let (first, second) = (&tuple.elems[0], &tuple.elems[1]);
+            parse_quote!((#second, #first))
+        }
+        _ => unreachable!(),
+    };
+
+    let mut items = Vec::with_capacity(ast.items.len());
+    for item in &ast.items {
+        let mirrored_item = match item {
+            ImplItem::Method(method) if !crate::is_mirrored(method, options) => continue,
+            ImplItem::Method(method) => {
+                ImplItem::Method(mirror_method(method, self_type, original_trait, options)?)
+            }
+            ImplItem::Type(associated_type) => {
+                let type_ident = &associated_type.ident;
+                ImplItem::Type(ImplItemType {
+                    attrs: attrs::forwarded(&associated_type.attrs, options),
+                    ty: parse_quote!(<#self_type as #original_trait>::#type_ident),
+                    ..associated_type.clone()
+                })
+            }
+            _ => item.clone(),
+        };
+        items.push(mirrored_item);
+    }
+
+    let summary = format!(
+        "Symmetric mirror of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #self_type }),
+        render::doc_link(original_trait),
+    );
+    let attrs = attrs::generated_impl(&summary, &ast.attrs, options);
+    let mut generics = ast.generics.clone();
+    bounds::resolve_self_in(&mut generics, self_type, original_trait);
+    if options.guarded {
+        bounds::require_original(&mut generics, self_type, original_trait);
+    }
+    bounds::adjust(&mut generics, options)?;
+    let (_, _, for_token) = ast.trait_.as_ref().unwrap();
+
+    Ok(ItemImpl {
+        attrs,
+        defaultness: ast.defaultness,
+        unsafety: ast.unsafety,
+        impl_token: ast.impl_token,
+        generics,
+        trait_: Some((None, original_trait.clone(), *for_token)),
+        self_ty: Box::new(swapped),
+        brace_token: ast.brace_token,
+        items,
+    })
+}
+
+/// Generate the method of the swapped pair, calling the original one with the
+/// components swapped back and the other arguments passed along.
+fn mirror_method(
+    method: &ImplItemMethod,
+    self_type: &Type,
+    original_trait: &Path,
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    let mut sig = method.sig.clone();
+    let pair = match sig.inputs.first_mut() {
+        Some(FnArg::Receiver(receiver)) => match (&receiver.reference, &receiver.mutability) {
+            (None, _) => {
+                // the pair is taken apart without being mutated
+                receiver.mutability = None;
+                quote!((self.1, self.0))
+            }
+            // the components are copied into a new pair, e.g. references
+            (Some(_), None) => quote!(&(self.1, self.0)),
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    receiver.span(),
+                    "methods of a pair taking `&mut self` cannot be mirrored, since the swapped pair is a copy",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new(sig.inputs.span(), "expected a receiver"));
+        }
+    };
+    let mut arguments = Vec::with_capacity(sig.inputs.len() - 1);
+    for (index, input) in sig.inputs.iter_mut().enumerate().skip(1) {
+        if let FnArg::Typed(typed) = input {
+            let ident = match typed.pat.as_ref() {
+                Pat::Ident(pat) if pat.subpat.is_none() => pat.ident.clone(),
+                _ => format_ident!("arg{}", index),
+            };
+            *typed.pat = Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: ident.clone(),
+                subpat: None,
+            });
+            arguments.push(ident);
+        }
+    }
+    let method_name = &sig.ident;
+    let call = quote!(<#self_type as #original_trait>::#method_name(#pair, #(#arguments),*));
+    let call = match sig.asyncness {
+        Some(_) => quote!(#call.await),
+        None => call,
+    };
+    Ok(ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: method.defaultness,
+        sig,
+        block: parse_quote!({ #call }),
+    })
+}
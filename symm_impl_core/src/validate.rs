@@ -0,0 +1,114 @@
+//! Validation of the symmetric shape of an implementation, without generating
+//! the mirror.
+
+use syn::{Error, GenericArgument, Ident, ImplItem, ItemImpl, Path, PathArguments, Result, Type};
+
+use crate::options::MirrorOptions;
+
+/// Shape of an implementation accepted by `#[symmetric]`, as returned by
+/// [`validate_symmetric_impl`].
+#[derive(Clone)]
+pub struct SymmetryInfo {
+    trait_path: Path,
+    self_type: Type,
+    other_type: Type,
+    slot: usize,
+    methods: Vec<Ident>,
+}
+
+impl SymmetryInfo {
+    /// Trait of the implementation, as written.
+    pub fn trait_path(&self) -> &Path {
+        &self.trait_path
+    }
+
+    /// Self type of the implementation.
+    pub fn self_type(&self) -> &Type {
+        &self.self_type
+    }
+
+    /// Other operand, i.e. the type argument of the trait swapped with the self
+    /// type in the mirror.
+    pub fn other_type(&self) -> &Type {
+        &self.other_type
+    }
+
+    /// Index of the other operand among the generic arguments of the trait,
+    /// counting lifetimes and constants, e.g. `1` for `Trait<'a, Other>`.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// Methods mirrored, in the order of the implementation.
+    pub fn methods(&self) -> &[Ident] {
+        &self.methods
+    }
+}
+
+/// Check that an implementation has the shape `#[symmetric]` requires with the
+/// given options, without generating code.
+///
+/// This is meant for other procedural macros and tools deciding whether an
+/// implementation can be mirrored. The errors are the ones `#[symmetric]`
+/// reports.
+///
+/// # Example
+/// ```
+/// use symm_impl_core::{render, validate_symmetric_impl, MirrorOptions};
+/// use syn::{parse_quote, ItemImpl};
+///
+/// let original: ItemImpl = parse_quote! {
+///     impl Distance<Disk> for Point2D {
+///         fn distance(&self, other: &Disk) -> f64 {
+///             self.distance(&other.center) - other.radius
+///         }
+///     }
+/// };
+/// let info = validate_symmetric_impl(&original, &MirrorOptions::default()).unwrap();
+/// assert_eq!(render(info.other_type()), "Disk");
+/// assert_eq!(info.slot(), 0);
+/// ```
+pub fn validate_symmetric_impl(item: &ItemImpl, options: &MirrorOptions) -> Result<SymmetryInfo> {
+    // the checks are the ones of the mirroring, whose output is dropped
+    crate::mirror_impl(item, options)?;
+    if let Some((_, trait_path, _)) = &item.trait_ {
+        if crate::tuple::is_tuple_impl(item, trait_path) {
+            return Err(Error::new_spanned(
+                &item.self_ty,
+                "expected an implementation of the form `impl Trait<Other> for Self`, not for a pair",
+            ));
+        }
+    }
+
+    // the mirroring succeeded, so the implementation has a generic trait with a
+    // type argument
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let (slot, other_type) = match &trait_path.segments.last().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => {
+            let slot = crate::other_slot(generics, options)?;
+            match &generics.args[slot] {
+                GenericArgument::Type(other_type) => (slot, other_type.clone()),
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    };
+    let methods = item
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                Some(method.sig.ident.clone())
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(SymmetryInfo {
+        trait_path: trait_path.clone(),
+        self_type: item.self_ty.as_ref().clone(),
+        other_type,
+        slot,
+        methods,
+    })
+}
@@ -0,0 +1,279 @@
+//! Double dispatch of symmetric traits through trait objects, with the visitor
+//! pattern.
+//!
+//! The operand types implement an object-safe trait whose methods take the
+//! other operand as a trait object. The other operand is asked to `accept` a
+//! visitor carrying the first one, which is then called back with the concrete
+//! type of the other operand and calls the implementation between both.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    Attribute, Error, FnArg, GenericArgument, Ident, Path, PathArguments, Result, ReturnType,
+    Signature, Token, Type, Visibility,
+};
+
+/// Method of the object-safe trait, e.g.
+/// `fn distance(&self, other: &dyn Shape) -> f64 = Distance;`.
+struct VisitorMethod {
+    attrs: Vec<Attribute>,
+    sig: Signature,
+    /// Symmetric trait called, with `_` for the other operand if it takes
+    /// several generic arguments.
+    trait_path: Path,
+}
+
+impl Parse for VisitorMethod {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let sig = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let trait_path = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(VisitorMethod {
+            attrs,
+            sig,
+            trait_path,
+        })
+    }
+}
+
+/// Input of `symmetric_visitor!`.
+struct VisitorInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    operands: Punctuated<Type, Token![,]>,
+    visitor: Ident,
+    methods: Vec<VisitorMethod>,
+}
+
+impl Parse for VisitorInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![trait]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![for]>()?;
+        let mut operands = Punctuated::new();
+        while !input.peek(syn::token::Brace) {
+            operands.push_value(input.parse()?);
+            if input.peek(syn::token::Brace) {
+                break;
+            }
+            operands.push_punct(input.parse()?);
+        }
+        let content;
+        braced!(content in input);
+        let keyword = content.parse::<Ident>()?;
+        if keyword != "visitor" {
+            return Err(Error::new(
+                keyword.span(),
+                "expected the name of the visitor trait, e.g. `visitor ShapeVisitor;`",
+            ));
+        }
+        let visitor = content.parse()?;
+        content.parse::<Token![;]>()?;
+        let mut methods = Vec::new();
+        while !content.is_empty() {
+            methods.push(content.parse()?);
+        }
+        Ok(VisitorInput {
+            attrs,
+            vis,
+            ident,
+            operands,
+            visitor,
+            methods,
+        })
+    }
+}
+
+/// Expand `symmetric_visitor!`, generating the object-safe trait, the visitor
+/// trait and their implementations for every operand type.
+pub fn expand(input: TokenStream) -> TokenStream {
+    match syn::parse2::<VisitorInput>(input).and_then(|input| generate(&input)) {
+        Ok(generated) => generated,
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// Name of the visitor method of an operand type, the snake case of the last
+/// segment of its path, e.g. `visit_point2d` for `geometry::Point2D`.
+fn visit_method(operand: &Type) -> Result<Ident> {
+    let ident = match operand {
+        Type::Path(path) if path.qself.is_none() => &path.path.segments.last().unwrap().ident,
+        _ => {
+            return Err(Error::new_spanned(
+                operand,
+                "expected the path of an operand type, e.g. `Point2D`",
+            ));
+        }
+    };
+    let mut name = String::from("visit_");
+    let mut previous_lowercase = false;
+    for c in ident.to_string().chars() {
+        if c.is_uppercase() && previous_lowercase {
+            name.push('_');
+        }
+        previous_lowercase = c.is_lowercase();
+        name.extend(c.to_lowercase());
+    }
+    Ok(Ident::new(&name, ident.span()))
+}
+
+/// Spell the symmetric trait with the given other operand, replacing `_` or
+/// appending the argument.
+fn with_other(trait_path: &Path, other_type: &Type) -> Path {
+    let mut trait_path = trait_path.clone();
+    let last_segment = trait_path.segments.last_mut().unwrap();
+    match &mut last_segment.arguments {
+        PathArguments::AngleBracketed(generics) => {
+            for arg in &mut generics.args {
+                if let GenericArgument::Type(Type::Infer(_)) = arg {
+                    *arg = GenericArgument::Type(other_type.clone());
+                }
+            }
+        }
+        arguments => *arguments = PathArguments::AngleBracketed(parse_quote!(<#other_type>)),
+    }
+    trait_path
+}
+
+/// Generate the traits and the glue.
+fn generate(input: &VisitorInput) -> Result<TokenStream> {
+    let VisitorInput {
+        attrs,
+        vis,
+        ident,
+        operands,
+        visitor,
+        methods,
+    } = input;
+    let visit_methods = operands
+        .iter()
+        .map(visit_method)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut signatures = Vec::with_capacity(methods.len());
+    let mut helpers = TokenStream::new();
+    let mut bodies = Vec::with_capacity(methods.len());
+    for method in methods {
+        let sig = &method.sig;
+        match sig.inputs.first() {
+            Some(FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+            _ => {
+                return Err(Error::new(
+                    sig.ident.span(),
+                    "expected a method taking `&self`",
+                ));
+            }
+        }
+        if sig.inputs.len() != 2 {
+            return Err(Error::new_spanned(&sig.inputs, "expected 2 arguments"));
+        }
+        let method_attrs = &method.attrs;
+        signatures.push(quote!(#(#method_attrs)* #sig;));
+
+        // visitor carrying the first operand, called back with the other one
+        let method_name = &sig.ident;
+        let helper = format_ident!("__{}Visitor_{}", ident, method_name);
+        let output = match &sig.output {
+            ReturnType::Type(_, output) => quote!(#output),
+            ReturnType::Default => quote!(()),
+        };
+        let mut bounds = Vec::with_capacity(operands.len());
+        let mut visits = Vec::with_capacity(operands.len());
+        for (operand, visit) in operands.iter().zip(&visit_methods) {
+            let pair_trait = with_other(&method.trait_path, operand);
+            bounds.push(quote!(__L: #pair_trait));
+            visits.push(quote! {
+                fn #visit(&mut self, operand: &#operand) {
+                    self.output = ::core::option::Option::Some(
+                        <__L as #pair_trait>::#method_name(self.lhs, operand),
+                    );
+                }
+            });
+        }
+        helpers.extend(quote! {
+            #[allow(non_camel_case_types)]
+            struct #helper<'__a, __L> {
+                lhs: &'__a __L,
+                output: ::core::option::Option<#output>,
+            }
+
+            impl<'__a, __L> #visitor for #helper<'__a, __L>
+            where
+                #(#bounds,)*
+            {
+                #(#visits)*
+            }
+        });
+
+        let mut sig = sig.clone();
+        if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+            *other_arg.pat = parse_quote!(other);
+        }
+        bodies.push((helper, sig));
+    }
+
+    let mut impls = TokenStream::new();
+    for (operand, visit) in operands.iter().zip(&visit_methods) {
+        let methods = bodies.iter().map(|(helper, sig)| {
+            quote! {
+                #[inline]
+                #sig {
+                    let mut visitor = #helper {
+                        lhs: self,
+                        output: ::core::option::Option::None,
+                    };
+                    #ident::accept(other, &mut visitor);
+                    // every operand type calls back the visitor
+                    visitor.output.unwrap()
+                }
+            }
+        });
+        impls.extend(quote! {
+            #[automatically_derived]
+            impl #ident for #operand {
+                #[inline]
+                fn accept(&self, visitor: &mut dyn #visitor) {
+                    visitor.#visit(self)
+                }
+
+                #(#methods)*
+            }
+        });
+    }
+
+    let operand_types = operands.iter().collect::<Vec<_>>();
+    let trait_doc = format!(" Visitor of the operand types implementing [`{}`].", ident);
+    let accept_doc = format!(
+        " Call the method of the [`{}`] for the concrete type of `self`.",
+        visitor
+    );
+    Ok(quote! {
+        #(#attrs)*
+        #vis trait #ident {
+            #[doc = #accept_doc]
+            fn accept(&self, visitor: &mut dyn #visitor);
+
+            #(#signatures)*
+        }
+
+        #[doc = #trait_doc]
+        #vis trait #visitor {
+            #(fn #visit_methods(&mut self, operand: &#operand_types);)*
+        }
+
+        const _: () = {
+            #helpers
+            #impls
+        };
+    })
+}
@@ -0,0 +1,119 @@
+//! `#[wasm_bindgen]` functions calling the implementations in both argument
+//! orders, requested by `#[symmetric(wasm_bindgen(point, disk))]`.
+
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Attribute, Error, FnArg, Ident, ImplItem, ImplItemMethod, ItemFn, ItemImpl,
+    Result, ReturnType, Type,
+};
+
+use crate::{attrs, bounds, options::MirrorOptions};
+
+/// Generate the exported functions of every mirrored method, named
+/// `<self_name>_<other_name>_<method>` and `<other_name>_<self_name>_<method>`,
+/// if requested by the options.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemFn>> {
+    let names = match &options.wasm_bindgen {
+        Some(names) => names,
+        None => return Ok(Vec::new()),
+    };
+    if ast.generics.lt_token.is_some() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`wasm_bindgen` does not support generic implementations",
+        ));
+    }
+    let mut functions = Vec::new();
+    for item in &ast.items {
+        match item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                functions.push(binding(
+                    ast,
+                    method,
+                    &names.self_name,
+                    &names.other_name,
+                    options,
+                ));
+                functions.push(binding(
+                    mirrored_ast,
+                    method,
+                    &names.other_name,
+                    &names.self_name,
+                    options,
+                ));
+            }
+            _ => (),
+        }
+    }
+    Ok(functions)
+}
+
+/// Generate the function calling `method` of `item`, taking the operands the
+/// way the method does.
+fn binding(
+    item: &ItemImpl,
+    method: &ImplItemMethod,
+    lhs_name: &Ident,
+    rhs_name: &Ident,
+    options: &MirrorOptions,
+) -> ItemFn {
+    let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let mut other_path = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut other_path, parse_quote!(()));
+    let (lhs_type, rhs_type): (Type, Type) = match &method.sig.inputs[0] {
+        FnArg::Receiver(receiver) => match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => (
+                parse_quote!(&mut #self_type),
+                parse_quote!(&mut #other_type),
+            ),
+            (Some(_), None) => (parse_quote!(&#self_type), parse_quote!(&#other_type)),
+            (None, _) => (self_type.clone(), other_type.clone()),
+        },
+        FnArg::Typed(_) => unreachable!(),
+    };
+    let output = match &method.sig.output {
+        ReturnType::Type(arrow, ty) => {
+            let ty = bounds::resolve_self(ty, self_type, trait_path);
+            quote!(#arrow #ty)
+        }
+        ReturnType::Default => quote!(),
+    };
+    let asyncness = &method.sig.asyncness;
+    let method_name = &method.sig.ident;
+    let call = quote!(<#self_type as #trait_path>::#method_name(lhs, rhs));
+    let call = match asyncness {
+        Some(_) => quote!(#call.await),
+        None => call,
+    };
+
+    let name = format_ident!("{}_{}_{}", lhs_name, rhs_name, method_name);
+    let summary = format!(
+        " Call `{}` of `{}` with `{}` as the other operand.",
+        method_name,
+        crate::render(self_type),
+        crate::render(&other_type),
+    );
+    let mut fn_attrs: Vec<Attribute> = method
+        .attrs
+        .iter()
+        .chain(&item.attrs)
+        .filter(|attr| attrs::is_cfg_like(attr))
+        .cloned()
+        .collect();
+    if let Some(predicate) = &options.cfg {
+        fn_attrs.push(parse_quote!(#[cfg(#predicate)]));
+    }
+    parse_quote! {
+        #[doc = #summary]
+        #(#fn_attrs)*
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        pub #asyncness fn #name(lhs: #lhs_type, rhs: #rhs_type) #output {
+            #call
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Implementations between wrappers of both operands, requested by
+//! `#[symmetric(wrap(Wrapper, unwrap = ...))]`, e.g.
+//! `impl Distance<Transformed<Disk>> for Transformed<Point2D>` delegating to
+//! `impl Distance<Disk> for Point2D` with the unwrapped operands.
+
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Error, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl, Path, PathArguments,
+    Result, Token, Type,
+};
+
+use crate::{attrs, options::MirrorOptions, render};
+
+/// A `wrap(Wrapper, unwrap = ...)` option.
+#[derive(Clone)]
+pub(crate) struct Wrap {
+    /// Wrapper of both operands, e.g. `Transformed`.
+    wrapper: Path,
+    /// Function from a reference to a wrapper to its operand, or to anything
+    /// borrowing as the operand. Defaults to `Deref::deref`.
+    unwrap: Option<Path>,
+}
+
+impl Parse for Wrap {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let wrapper: Path = input.parse()?;
+        if let Some(segment) = wrapper
+            .segments
+            .iter()
+            .find(|segment| !matches!(segment.arguments, PathArguments::None))
+        {
+            return Err(Error::new_spanned(
+                &segment.arguments,
+                "the wrapped types are the operands of the implementation",
+            ));
+        }
+        let mut unwrap = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            match name.to_string().as_str() {
+                "unwrap" if unwrap.is_none() => unwrap = Some(input.parse()?),
+                "unwrap" => {
+                    return Err(Error::new(name.span(), "duplicate option `unwrap`"));
+                }
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown option `{}` of `wrap`", name),
+                    ));
+                }
+            }
+        }
+        Ok(Wrap { wrapper, unwrap })
+    }
+}
+
+/// Generate the implementations between the wrappers of the operands listed in
+/// the options, in both directions.
+pub(crate) fn generate(
+    ast: &ItemImpl,
+    mirrored_ast: &ItemImpl,
+    options: &MirrorOptions,
+) -> Result<Vec<ItemImpl>> {
+    let mut generated = Vec::with_capacity(options.wrap.len() * 2);
+    for wrap in &options.wrap {
+        generated.push(wrapped(ast, ast, wrap, options)?);
+        generated.push(wrapped(mirrored_ast, ast, wrap, options)?);
+    }
+    Ok(generated)
+}
+
+/// Generate the implementation between the wrappers of the operands of `item`,
+/// either the original implementation or its mirror.
+fn wrapped(
+    item: &ItemImpl,
+    original: &ItemImpl,
+    wrap: &Wrap,
+    options: &MirrorOptions,
+) -> Result<ItemImpl> {
+    // the original and mirrored implementations were checked to be generic
+    // trait implementations
+    let (_, trait_path, for_token) = item.trait_.as_ref().unwrap();
+    let self_type = item.self_ty.as_ref();
+    let wrapper = &wrap.wrapper;
+    let mut wrapped_trait = trait_path.clone();
+    let other_type = crate::lift::replace_other(&mut wrapped_trait, self_type.clone());
+    crate::lift::replace_other(&mut wrapped_trait, parse_quote!(#wrapper<#other_type>));
+
+    let mut items = Vec::with_capacity(item.items.len());
+    for impl_item in &item.items {
+        match impl_item {
+            ImplItem::Method(method) if crate::is_mirrored(method, options) => {
+                items.push(ImplItem::Method(wrapped_method(
+                    method,
+                    self_type,
+                    &other_type,
+                    trait_path,
+                    wrap,
+                    options,
+                )?));
+            }
+            ImplItem::Type(associated_type) => {
+                let mut associated_type = associated_type.clone();
+                let type_ident = &associated_type.ident;
+                associated_type.attrs = attrs::forwarded(&associated_type.attrs, options);
+                associated_type.ty = parse_quote!(<#self_type as #trait_path>::#type_ident);
+                items.push(ImplItem::Type(associated_type));
+            }
+            ImplItem::Const(constant) => {
+                let mut constant = constant.clone();
+                let const_ident = &constant.ident;
+                constant.attrs = attrs::forwarded(&constant.attrs, options);
+                constant.expr = parse_quote!(<#self_type as #trait_path>::#const_ident);
+                items.push(ImplItem::Const(constant));
+            }
+            _ => (),
+        }
+    }
+
+    let (_, original_trait, _) = original.trait_.as_ref().unwrap();
+    let original_self = &original.self_ty;
+    let summary = format!(
+        "Wrapped counterpart of [`{}`]({}).",
+        render::render(&quote! { impl #original_trait for #original_self }),
+        render::doc_link(original_trait),
+    );
+    Ok(ItemImpl {
+        attrs: attrs::generated_impl(&summary, &original.attrs, options),
+        defaultness: None,
+        unsafety: item.unsafety,
+        impl_token: item.impl_token,
+        generics: item.generics.clone(),
+        trait_: Some((None, wrapped_trait, *for_token)),
+        self_ty: parse_quote!(#wrapper<#self_type>),
+        brace_token: item.brace_token,
+        items,
+    })
+}
+
+/// Generate a method unwrapping both operands and calling `method` with them.
+fn wrapped_method(
+    method: &ImplItemMethod,
+    self_type: &Type,
+    other_type: &Type,
+    trait_path: &Path,
+    wrap: &Wrap,
+    options: &MirrorOptions,
+) -> Result<ImplItemMethod> {
+    let mut sig = method.sig.clone();
+    match &sig.inputs[0] {
+        FnArg::Receiver(receiver)
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => {
+            return Err(Error::new(
+                method.sig.ident.span(),
+                "`wrap` requires methods taking their operands by shared reference",
+            ));
+        }
+    }
+    if sig.asyncness.is_some() {
+        return Err(Error::new(
+            method.sig.ident.span(),
+            "`wrap` does not support async methods",
+        ));
+    }
+    let wrapper = &wrap.wrapper;
+    if let FnArg::Typed(other_arg) = &mut sig.inputs[1] {
+        *other_arg.pat = parse_quote!(other);
+        if let Type::Reference(reference) = other_arg.ty.as_mut() {
+            *reference.elem = parse_quote!(#wrapper<#other_type>);
+        }
+    }
+
+    let unwrap = match &wrap.unwrap {
+        Some(unwrap) => quote!(#unwrap),
+        None => quote!(::core::ops::Deref::deref),
+    };
+    let method_name = &method.sig.ident;
+    Ok(ImplItemMethod {
+        attrs: attrs::generated_method(method, options),
+        vis: method.vis.clone(),
+        defaultness: None,
+        sig,
+        block: parse_quote! {
+            {
+                let lhs = #unwrap(self);
+                let rhs = #unwrap(other);
+                <#self_type as #trait_path>::#method_name(
+                    ::core::borrow::Borrow::<#self_type>::borrow(&lhs),
+                    ::core::borrow::Borrow::<#other_type>::borrow(&rhs),
+                )
+            }
+        },
+    })
+}
@@ -0,0 +1,113 @@
+use std::{env, fs};
+
+use symm_impl_core::{mirror, render, MirrorOptions};
+use syn::{
+    parse::{ParseStream, Parser},
+    parse_quote, ItemImpl,
+};
+
+fn original() -> ItemImpl {
+    parse_quote! {
+        impl Distance<Disk> for Point2D {
+            #[must_use]
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    }
+}
+
+/// The attributes of the mirror and of its method with the options of an
+/// attribute, on top of the configuration.
+fn attrs(attr: &str) -> (Vec<String>, Vec<String>) {
+    let options = configured(attr).unwrap();
+    let mirrored = mirror(original(), options).unwrap();
+    let method_attrs = match &mirrored.items[0] {
+        syn::ImplItem::Method(method) => method.attrs.iter().map(render).collect(),
+        _ => unreachable!(),
+    };
+    (mirrored.attrs.iter().map(render).collect(), method_attrs)
+}
+
+/// The options of an attribute, on top of the configuration read as the
+/// attribute does.
+fn configured(attr: &str) -> syn::Result<MirrorOptions> {
+    let defaults = MirrorOptions::from_config()?;
+    let parser = |input: ParseStream| MirrorOptions::parse_with(defaults, input);
+    parser.parse_str(attr)
+}
+
+fn error(attr: &str) -> String {
+    match configured(attr) {
+        Ok(_) => panic!("expected an error"),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[test]
+fn test_config() {
+    let dir = env::temp_dir().join(format!("symm_impl_config_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("symm_impl.toml");
+    env::set_var("SYMM_IMPL_CONFIG", &path);
+
+    fs::write(
+        &path,
+        "# defaults of the crate\n\
+         options = '''\n    doc_hidden, inline = \"never\",\n    skip_attrs(must_use),\n'''\n",
+    )
+    .unwrap();
+    let (impl_attrs, method_attrs) = attrs("");
+    assert!(impl_attrs.contains(&String::from("#[doc(hidden)]")));
+    assert!(method_attrs.contains(&String::from("#[inline(never)]")));
+    assert!(!method_attrs.contains(&String::from("#[must_use]")));
+    // parsing options alone does not read the configuration
+    let options: MirrorOptions = syn::parse_str("").unwrap();
+    let mirrored = mirror(original(), options).unwrap();
+    assert!(!mirrored
+        .attrs
+        .iter()
+        .any(|attr| render(attr) == "#[doc(hidden)]"));
+    // options of the attribute override the defaults, or add to them
+    let (_, method_attrs) = attrs("inline = \"always\", skip_attrs(track_caller)");
+    assert!(method_attrs.contains(&String::from("#[inline(always)]")));
+    assert!(!method_attrs.contains(&String::from("#[must_use]")));
+    // flags of the defaults are unset by the attribute
+    let (impl_attrs, _) = attrs("doc_hidden = false");
+    assert!(!impl_attrs.contains(&String::from("#[doc(hidden)]")));
+
+    fs::write(
+        &path,
+        "options = \"clippy_allow(float_cmp)\" # basic string\n",
+    )
+    .unwrap();
+    let (_, method_attrs) = attrs("");
+    assert!(method_attrs.contains(&String::from("#[allow(clippy::float_cmp)]")));
+
+    let display = path.display();
+    fs::write(&path, "defaults = 'doc_hidden'\n").unwrap();
+    assert_eq!(
+        error(""),
+        format!(
+            "invalid configuration `{}`: unknown key `defaults`, expected `options`",
+            display
+        )
+    );
+    fs::write(&path, "options = 'doc_hiden'\n").unwrap();
+    assert_eq!(
+        error(""),
+        format!(
+            "invalid configuration `{}`: unknown option `doc_hiden`",
+            display
+        )
+    );
+    fs::remove_file(&path).unwrap();
+    assert!(error("").starts_with(&format!("failed to read `{}`", display)));
+
+    // without a named configuration, `symm_impl.toml` is optional
+    env::remove_var("SYMM_IMPL_CONFIG");
+    env::set_var("CARGO_MANIFEST_DIR", &dir);
+    let (impl_attrs, _) = attrs("");
+    assert!(!impl_attrs.contains(&String::from("#[doc(hidden)]")));
+    fs::remove_dir_all(&dir).unwrap();
+}
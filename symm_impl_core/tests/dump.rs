@@ -0,0 +1,62 @@
+use std::{env, fs};
+
+use quote::quote;
+use symm_impl_core::{expand, mirror, render, MirrorOptions};
+use syn::{parse_quote, Expr, Item, ItemImpl, Stmt};
+
+#[test]
+fn test_dump() {
+    let dir = env::temp_dir().join(format!("symm_impl_dump_{}", std::process::id()));
+    env::set_var("SYMM_IMPL_DUMP", &dir);
+
+    let original: ItemImpl = parse_quote! {
+        impl<T: Copy> Distance<Vec<T>> for Point2D<T> {
+            fn distance(&self, other: &Vec<T>) -> f64 {
+                other.len() as f64
+            }
+        }
+    };
+    expand(quote!(), quote!(#original));
+
+    // separators are collapsed in the name of the file, after the mirror
+    let dumped = fs::read_to_string(dir.join("Distance_Point2D_T_for_Vec_T.rs")).unwrap();
+    let (header, code) = dumped.split_once('\n').unwrap();
+    assert_eq!(
+        header,
+        "// Generated by #[symmetric] for `impl Distance<Vec<T>> for Point2D<T>`"
+    );
+    // the generated items are in their own scope
+    let file: syn::File = syn::parse_str(code).unwrap();
+    let block = match file.items.as_slice() {
+        [Item::Const(scope)] => match scope.expr.as_ref() {
+            Expr::Block(block) => block.block.clone(),
+            _ => unreachable!(),
+        },
+        _ => panic!("expected a single scope"),
+    };
+    let mirrored = match block.stmts.as_slice() {
+        [Stmt::Item(Item::Impl(mirrored))] => mirrored,
+        _ => panic!("expected a single implementation"),
+    };
+    let expected = mirror(original, MirrorOptions::default()).unwrap();
+    assert_eq!(render(mirrored), render(&expected));
+
+    // every stacked attribute dumps its own mirror
+    let stacked: ItemImpl = parse_quote! {
+        impl Touch<Disk, Line> for Point2D {
+            fn touch_disk(&self, other: &Disk) -> bool {
+                true
+            }
+            fn touch_line(&self, other: &Line) -> bool {
+                false
+            }
+        }
+    };
+    expand(quote!(other = "Disk"), quote!(#stacked));
+    expand(quote!(other = "Line"), quote!(#stacked));
+    let disk = fs::read_to_string(dir.join("Touch_Point2D_Line_for_Disk.rs")).unwrap();
+    let line = fs::read_to_string(dir.join("Touch_Disk_Point2D_for_Line.rs")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(disk.contains("for Disk"));
+    assert!(line.contains("for Line"));
+}
@@ -0,0 +1,55 @@
+use std::{env, fs};
+
+use quote::quote;
+use symm_impl_core::expand;
+
+#[test]
+fn test_inventory() {
+    let out_dir = env::temp_dir().join(format!("symm_impl_inventory_{}", std::process::id()));
+    fs::create_dir_all(&out_dir).unwrap();
+    env::set_var("OUT_DIR", &out_dir);
+    env::set_var("SYMM_IMPL_INVENTORY", "1");
+
+    expand(
+        quote!(),
+        quote! {
+            impl Distance<Disk> for Point2D {
+                fn distance(&self, other: &Disk) -> f64 {
+                    self.distance(&other.center) - other.radius
+                }
+            }
+        },
+    );
+    expand(
+        quote!(),
+        quote! {
+            impl Collide for (Ball, Wall) {
+                type Output = bool;
+                fn collide(&self) -> bool { true }
+            }
+        },
+    );
+    // expanded again, e.g. by an IDE
+    expand(
+        quote!(),
+        quote! {
+            impl Distance<Disk> for Point2D {
+                fn distance(&self, other: &Disk) -> f64 {
+                    self.distance(&other.center) - other.radius
+                }
+            }
+        },
+    );
+
+    let inventory = fs::read_to_string(out_dir.join("symm_impl.json")).unwrap();
+    fs::remove_dir_all(&out_dir).unwrap();
+    assert_eq!(
+        inventory,
+        "[\n  \
+         {\"trait\": \"Collide\", \"self_type\": \"(Wall, Ball)\", \"other_type\": null, \
+         \"methods\": [\"collide\"], \"original\": \"impl Collide for (Ball, Wall)\"},\n  \
+         {\"trait\": \"Distance<Point2D>\", \"self_type\": \"Disk\", \"other_type\": \"Point2D\", \
+         \"methods\": [\"distance\"], \"original\": \"impl Distance<Disk> for Point2D\"}\n\
+         ]\n"
+    );
+}
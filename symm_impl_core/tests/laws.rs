@@ -0,0 +1,191 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use symm_impl_core::{expand_commutative, expand_transitive, generate};
+use syn::{parse_quote, ItemImpl};
+
+/// The content of the generated module of property tests, whose name is
+/// hashed.
+fn tests(generated: TokenStream) -> String {
+    let mut tokens = generated.into_iter().skip_while(|token| match token {
+        TokenTree::Ident(ident) => ident != "mod",
+        _ => true,
+    });
+    tokens.next().unwrap();
+    let module = tokens.next().unwrap().to_string();
+    assert!(module.starts_with("__symm_proptest_"));
+    match tokens.next() {
+        Some(TokenTree::Group(content)) => content.stream().to_string(),
+        _ => panic!("expected the content of the module"),
+    }
+}
+
+#[test]
+fn test_antisymmetric_proptest() {
+    let original: ItemImpl = parse_quote! {
+        impl Offset<Disk> for Point2D {
+            fn offset(&self, other: &Disk) -> f64 {
+                other.center.x - self.x
+            }
+        }
+    };
+    let generated = generate("antisymmetric, proptest".parse().unwrap(), &original).unwrap();
+    let expected = quote! {
+        ::proptest::prop_assert!(
+            <Point2D as Offset<Disk> >::offset(&lhs, &rhs)
+                == -<Disk as Offset<Point2D> >::offset(&rhs, &lhs),
+            "`offset` of `Offset<Disk>` is not antisymmetric",
+        );
+    };
+    let generated = tests(generated);
+    assert!(generated.contains("fn offset_is_antisymmetric"));
+    assert!(generated.contains(&expected.to_string()));
+}
+
+#[test]
+fn test_commutative_proptest() {
+    let item = quote! {
+        impl Merge<Count> for Count {
+            fn merge(self, other: Count) -> Count {
+                Count(self.0 + other.0)
+            }
+        }
+    };
+    let generated = expand_commutative(quote!(identity = Count(0), proptest), item);
+    let expected = quote! {
+        use super::*;
+
+        ::proptest::proptest! {
+            #[test]
+            fn merge_is_commutative(
+                a in ::proptest::prelude::any::<Count>(),
+                b in ::proptest::prelude::any::<Count>()
+            ) {
+                ::proptest::prop_assert!(
+                    <Count as Merge<Count> >::merge(
+                        ::core::clone::Clone::clone(&a),
+                        ::core::clone::Clone::clone(&b)
+                    ) == <Count as Merge<Count> >::merge(
+                        ::core::clone::Clone::clone(&b),
+                        ::core::clone::Clone::clone(&a)
+                    ),
+                    "`merge` of `Merge<Count>` is not commutative",
+                );
+            }
+            #[test]
+            fn merge_is_associative(
+                a in ::proptest::prelude::any::<Count>(),
+                b in ::proptest::prelude::any::<Count>(),
+                c in ::proptest::prelude::any::<Count>()
+            ) {
+                ::proptest::prop_assert!(
+                    <Count as Merge<Count> >::merge(
+                        <Count as Merge<Count> >::merge(
+                            ::core::clone::Clone::clone(&a),
+                            ::core::clone::Clone::clone(&b)
+                        ),
+                        ::core::clone::Clone::clone(&c)
+                    ) == <Count as Merge<Count> >::merge(
+                        ::core::clone::Clone::clone(&a),
+                        <Count as Merge<Count> >::merge(
+                            ::core::clone::Clone::clone(&b),
+                            ::core::clone::Clone::clone(&c)
+                        )
+                    ),
+                    "`merge` of `Merge<Count>` is not associative",
+                );
+            }
+            #[test]
+            fn merge_has_identity(a in ::proptest::prelude::any::<Count>()) {
+                ::proptest::prop_assert!(
+                    <Count as Merge<Count> >::merge(::core::clone::Clone::clone(&a), (Count(0))) == a,
+                    "`Count(0)` is not the identity element of `merge` of `Merge<Count>`",
+                );
+            }
+        }
+    };
+    assert_eq!(tests(generated), expected.to_string());
+}
+
+#[test]
+fn test_transitive_proptest() {
+    let item = quote! {
+        impl Reaches<Node> for Node {
+            fn reaches(&self, other: &Node) -> bool {
+                self.0 <= other.0
+            }
+        }
+    };
+    let generated = expand_transitive(quote!(proptest(strategy(Node = nodes()))), item);
+    let expected = quote! {
+        use super::*;
+
+        ::proptest::proptest! {
+            #[test]
+            fn reaches_is_transitive(a in nodes(), b in nodes(), c in nodes()) {
+                ::proptest::prop_assert!(
+                    !(<Node as Reaches<Node> >::reaches(&a, &b)
+                        && <Node as Reaches<Node> >::reaches(&b, &c))
+                        || <Node as Reaches<Node> >::reaches(&a, &c),
+                    "`reaches` of `Reaches<Node>` is not transitive",
+                );
+            }
+        }
+    };
+    assert_eq!(tests(generated), expected.to_string());
+}
+
+#[test]
+fn test_reflexive_proptest() {
+    let item = quote! {
+        impl Reaches<Node> for Node {
+            fn reaches(&self, other: &Node) -> bool {
+                self.0 <= other.0
+            }
+        }
+    };
+    let generated = expand_transitive(quote!(reflexive, proptest(strategy(Node = nodes()))), item);
+    let expected = quote! {
+        use super::*;
+
+        ::proptest::proptest! {
+            #[test]
+            fn reaches_is_transitive(a in nodes(), b in nodes(), c in nodes()) {
+                ::proptest::prop_assert!(
+                    !(<Node as Reaches<Node> >::reaches(&a, &b)
+                        && <Node as Reaches<Node> >::reaches(&b, &c))
+                        || <Node as Reaches<Node> >::reaches(&a, &c),
+                    "`reaches` of `Reaches<Node>` is not transitive",
+                );
+            }
+
+            #[test]
+            fn reaches_is_reflexive(a in nodes()) {
+                ::proptest::prop_assert!(
+                    <Node as Reaches<Node> >::reaches(&a, &a),
+                    "`reaches` of `Reaches<Node>` is not reflexive",
+                );
+            }
+        }
+    };
+    assert_eq!(tests(generated), expected.to_string());
+}
+
+#[test]
+fn test_transitive_markers() {
+    let item = quote! {
+        impl Reaches<Node> for Node {
+            fn reaches(&self, other: &Node) -> bool {
+                self.0 <= other.0
+            }
+        }
+    };
+    let generated = expand_transitive(quote!(reflexive, markers, runtime = ::rt), item.clone());
+    let expected = quote! {
+        #item
+        #[automatically_derived]
+        impl ::rt::TransitiveOp<dyn Reaches<Node> > for Node {}
+        #[automatically_derived]
+        impl ::rt::ReflexiveOp<dyn Reaches<Node> > for Node {}
+    };
+    assert_eq!(generated.to_string(), expected.to_string());
+}
@@ -0,0 +1,413 @@
+use symm_impl_core::{generate, mirror, render, validate_symmetric_impl, MirrorOptions};
+use syn::{parse_quote, ItemImpl};
+
+#[test]
+fn test_mirror() {
+    let original: ItemImpl = parse_quote! {
+        impl<'a> Overlap<Segment<'a>> for Point2D {
+            type Output = bool;
+            fn overlap(self, other: Segment<'a>) -> bool {
+                other.contains(self)
+            }
+        }
+    };
+    let mirrored = mirror(original, MirrorOptions::default()).unwrap();
+    assert_eq!(
+        render(&mirrored),
+        "#[doc = \"Symmetric mirror of [`impl Overlap<Segment<'a>> for Point2D`](trait@Overlap).\"] \
+         #[automatically_derived] \
+         impl<'a> Overlap<Point2D> for Segment<'a> { \
+         type Output = <Point2D as Overlap<Segment<'a>>>::Output; \
+         #[track_caller] #[inline] \
+         #[allow(clippy::mut_mut, clippy::needless_borrow, clippy::needless_lifetimes, clippy::use_self)] \
+         fn overlap(self, other: Point2D) -> bool { \
+         <Point2D as Overlap<Segment<'a>>>::overlap(other, self) } }"
+    );
+}
+
+#[test]
+fn test_mirror_with_options() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str(
+        "call = \"method\", inline = \"none\", clippy_allow(), other_ident = point, \
+         track_caller = false",
+    )
+    .unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "fn distance(&self, point: &Point2D) -> f64 { point.distance(self) }"
+    );
+}
+
+#[test]
+fn test_mirror_async_track_caller() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            async fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center).await - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("clippy_allow()").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "#[inline] async fn distance(&self, other: &Point2D) -> f64 { \
+         <Point2D as Distance<Disk>>::distance(other, self).await }"
+    );
+}
+
+#[test]
+fn test_mirror_target_feature() {
+    let original: ItemImpl = parse_quote! {
+        impl Dot<Vec4> for Vec2 {
+            #[target_feature(enable = "avx2")]
+            unsafe fn dot(&self, other: &Vec4) -> f32 {
+                dot_avx2(&self.0, &other.0[..2])
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("inline = \"always\", clippy_allow()").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "#[target_feature(enable = \"avx2\")] #[track_caller] #[inline] \
+         unsafe fn dot(&self, other: &Vec2) -> f32 { \
+         unsafe { <Vec2 as Dot<Vec4>>::dot(other, self) } }"
+    );
+}
+
+#[test]
+fn test_mirror_dyn_self() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for dyn Shape + Send {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.center().distance(&other.center) - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("clippy_allow()").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored),
+        "#[doc = \"Symmetric mirror of [`impl Distance<Disk> for dyn Shape + Send`](trait@Distance).\"] \
+         #[automatically_derived] \
+         impl Distance<dyn Shape + Send> for Disk { \
+         #[track_caller] #[inline] \
+         fn distance(&self, other: &(dyn Shape + Send + 'static)) -> f64 { \
+         <dyn Shape + Send as Distance<Disk>>::distance(other, self) } }"
+    );
+}
+
+#[test]
+fn test_mirror_coverage_off() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("coverage_off, clippy_allow()").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "#[track_caller] #[inline] #[cfg_attr(coverage_nightly, coverage(off))] \
+         fn distance(&self, other: &Point2D) -> f64 { \
+         <Point2D as Distance<Disk>>::distance(other, self) }"
+    );
+}
+
+#[test]
+fn test_mirror_rewrite_body() {
+    let original: ItemImpl = parse_quote! {
+        impl Merge<Disk> for Point2D {
+            type Output = Disk;
+            fn merge(mut self, rhs: Disk) -> Self::Output {
+                self.0 = self.0.min(rhs.center.0);
+                debug_assert!(rhs.radius >= 0.0, "{:?}", self);
+                Disk { center: Self::origin().offset(self), radius: rhs.radius }
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("rewrite_body").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored.items[1]),
+        "#[track_caller] #[inline] \
+         #[allow(clippy::mut_mut, clippy::needless_borrow, clippy::needless_lifetimes, clippy::use_self)] \
+         fn merge(self, mut rhs: Point2D) -> Self::Output { \
+         rhs.0 = rhs.0.min(self.center.0); \
+         debug_assert !(self.radius> = 0.0, \"{:?}\", rhs); \
+         Disk { center: <Point2D>::origin().offset(rhs), radius: self.radius } }"
+    );
+}
+
+#[test]
+fn test_mirror_rewrite_body_names() {
+    let original: ItemImpl = parse_quote! {
+        impl Describe<Disk> for Point2D {
+            fn describe(&self, rhs: &Disk) -> Labels {
+                let labels = vec![format!("{rhs:?} {:?}", self), format!("{rhs}", rhs = rhs.radius)];
+                Labels { lhs: self.0, rhs, labels }
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("rewrite_body, clippy_allow()").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    // fields keep their names, and format strings capture the renamed operand
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "#[track_caller] #[inline] \
+         fn describe(&self, rhs: &Point2D) -> Labels { \
+         let labels = vec ![\
+         format !(\"{__symm_rhs:?} {:?}\", rhs, __symm_rhs = self), \
+         format !(\"{__symm_rhs}\", __symm_rhs = self.radius)\
+         ]; \
+         Labels { lhs: rhs.0, rhs: self, labels } }"
+    );
+}
+
+#[test]
+fn test_mirror_rewrite_body_shadowed() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, rhs: &Disk) -> f64 {
+                let rhs: f64 = (0..rhs.len).map(|i| self.0 - rhs.at(i)).sum();
+                rhs
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("rewrite_body, clippy_allow()").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    // the shadowed operand is bound to an alias
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "#[track_caller] #[inline] \
+         fn distance(&self, rhs: &Point2D) -> f64 { \
+         let __symm_rhs = self; \
+         let __symm_rhs: f64 = (0..__symm_rhs.len).map(|i|rhs.0 - __symm_rhs.at(i)).sum(); \
+         __symm_rhs }"
+    );
+}
+
+#[test]
+fn test_mirror_copied_attrs_not_duplicated() {
+    let original: ItemImpl = parse_quote! {
+        #[automatically_derived]
+        #[doc(hidden)]
+        impl Distance<Disk> for Point2D {
+            #[inline(always)]
+            #[allow(unused_mut, clippy::use_self)]
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str(
+        "copy_attrs(automatically_derived, inline, allow), doc_hidden, track_caller = false",
+    )
+    .unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    assert_eq!(
+        render(&mirrored),
+        "#[doc = \"Symmetric mirror of [`impl Distance<Disk> for Point2D`](trait@Distance).\"] \
+         #[doc = \"\"] #[automatically_derived] #[doc(hidden)] \
+         impl Distance<Point2D> for Disk { \
+         #[inline(always)] #[allow(unused_mut, clippy::use_self)] \
+         #[allow(clippy::mut_mut, clippy::needless_borrow, clippy::needless_lifetimes)] \
+         fn distance(&self, other: &Point2D) -> f64 { \
+         <Point2D as Distance<Disk>>::distance(other, self) } }"
+    );
+}
+
+#[test]
+fn test_mirror_cfg_doc() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("cfg(feature = \"mirror-impls\")").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    let attrs: Vec<_> = mirrored.attrs.iter().map(render).collect();
+    assert_eq!(
+        attrs[1..],
+        [
+            "#[cfg(feature = \"mirror-impls\")]",
+            "#[cfg_attr(docsrs, doc(cfg(feature = \"mirror-impls\")))]",
+            "#[automatically_derived]",
+        ]
+    );
+}
+
+#[test]
+fn test_mirror_empty_warning() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn unit_distance(&self, other: &Disk) -> f64 {
+                1.0
+            }
+        }
+    };
+    let warns = |attr: &str| {
+        generate(attr.parse().unwrap(), &original)
+            .unwrap()
+            .to_string()
+            .contains("symmetric_mirror_is_empty")
+    };
+    assert!(warns("only()"));
+    assert!(!warns("only(unit_distance)"));
+    // marker traits are mirrored empty on purpose
+    let marker: ItemImpl = parse_quote!(impl Adjacent<Disk> for Point2D {});
+    let generated = generate(proc_macro2::TokenStream::new(), &marker).unwrap();
+    assert!(!generated.to_string().contains("symmetric_mirror_is_empty"));
+}
+
+#[test]
+fn test_mirror_error() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self) -> f64 {
+                0.0
+            }
+        }
+    };
+    let error = match mirror(original, MirrorOptions::default()) {
+        Ok(_) => panic!("expected an error"),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error.to_string(),
+        "expected 2 arguments, the expected signature is \
+         `fn distance(&self, other: &Disk) -> f64`"
+    );
+}
+
+#[test]
+fn test_mirror_pair() {
+    let original: ItemImpl = parse_quote! {
+        impl Collide for (Ball, Wall) {
+            fn collide(&self, (dx, dy): (f64, f64), mut dt: f64) -> bool {
+                dt *= 2.0;
+                self.0.reaches(&self.1, dx, dy, dt)
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("inline = \"none\", clippy_allow()").unwrap();
+    let mirrored = mirror(original.clone(), options).unwrap();
+    assert_eq!(render(&mirrored.self_ty), "(Wall, Ball)");
+    assert_eq!(
+        render(&mirrored.items[0]),
+        "#[track_caller] \
+         fn collide(&self, arg1: (f64, f64), dt: f64) -> bool { \
+         <(Ball, Wall) as Collide>::collide(&(self.1, self.0), arg1, dt) }"
+    );
+
+    let options: MirrorOptions = syn::parse_str("by_ref").unwrap();
+    let error = mirror(original, options).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "`by_ref` is not supported for implementations for a pair"
+    );
+}
+
+#[test]
+fn test_mirror_intermediate_generics() {
+    let original: ItemImpl = parse_quote! {
+        impl ops::scalar::<f64>::Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let mirrored = mirror(original.clone(), MirrorOptions::default()).unwrap();
+    let (_, mirrored_trait, _) = mirrored.trait_.as_ref().unwrap();
+    assert_eq!(
+        render(mirrored_trait),
+        "ops::scalar::<f64>::Distance<Point2D>"
+    );
+    assert_eq!(
+        render(&mirrored.attrs[0]),
+        "#[doc = \"Symmetric mirror of \
+         [`impl ops::scalar::<f64>::Distance<Disk> for Point2D`](trait@ops::scalar::Distance).\"]"
+    );
+
+    let generated = generate(quote::quote!(strict), &original).unwrap();
+    assert!(render(&generated).starts_with("ops::scalar::Distance !"));
+}
+
+#[test]
+fn test_mirror_const_binding() {
+    let original: ItemImpl = parse_quote! {
+        impl Grid<Disk, RESOLUTION = 3> for Point2D {
+            fn cells(&self, other: &Disk) -> usize {
+                other.cover(self, Self::RESOLUTION)
+            }
+        }
+    };
+    let info = validate_symmetric_impl(&original, &MirrorOptions::default()).unwrap();
+    assert_eq!(render(info.other_type()), "Disk");
+    assert_eq!(info.slot(), 0);
+
+    let options: MirrorOptions = syn::parse_str("guarded").unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    let (_, mirrored_trait, _) = mirrored.trait_.as_ref().unwrap();
+    assert_eq!(render(mirrored_trait), "Grid<Point2D, RESOLUTION = 3>");
+    assert_eq!(
+        render(&mirrored.generics.where_clause),
+        "where Point2D: Grid<Disk, RESOLUTION = 3>"
+    );
+    assert!(render(&mirrored.items[0])
+        .ends_with("<Point2D as Grid<Disk, RESOLUTION = 3>>::cells(other, self) }"));
+}
+
+#[test]
+fn test_mirror_projection() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<<Geometry as Config>::Shape> for Point2D {
+            fn distance(&self, other: &<Geometry as Config>::Shape) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("by_ref").unwrap();
+    let mirrored = mirror(original.clone(), options).unwrap();
+    assert_eq!(render(&mirrored.self_ty), "<Geometry as Config>::Shape");
+    let generated = render(&generate(quote::quote!(by_ref), &original).unwrap());
+    assert!(generated.contains("impl Distance<Point2D> for <Geometry as Config>::Shape { "));
+    assert!(generated
+        .contains("impl<'__symm> Distance<&'__symm <Geometry as Config>::Shape> for Point2D { "));
+}
+
+#[test]
+fn test_mirror_const_expr() {
+    let original: ItemImpl = parse_quote! {
+        impl<const N: usize> Fit<Grid<{ N * 2 }>> for Grid<N>
+        where
+            [(); N * 2]: Sized
+        {
+            fn fit(&self, other: &Grid<{ N * 2 }>) -> bool {
+                other.cells() == 2 * self.cells()
+            }
+        }
+    };
+    let mirrored = mirror(original, MirrorOptions::default()).unwrap();
+    let (_, mirrored_trait, _) = mirrored.trait_.as_ref().unwrap();
+    assert_eq!(render(mirrored_trait), "Fit<Grid<N>>");
+    assert_eq!(render(&mirrored.self_ty), "Grid<{ N * 2 }>");
+    assert_eq!(
+        render(&mirrored.generics.where_clause),
+        "where [(); N * 2]: Sized"
+    );
+    assert!(render(&mirrored.items[0]).contains("fn fit(&self, other: &Grid<N>) -> bool"));
+}
@@ -0,0 +1,113 @@
+use quote::quote;
+use symm_impl_core::{expand_symmetric_options, mirror, render, MirrorOptions};
+use syn::{ImplItem, Item, ItemMod};
+
+fn parse_error(options: &str) -> String {
+    syn::parse_str::<MirrorOptions>(options)
+        .err()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_options() {
+    assert!(syn::parse_str::<MirrorOptions>(
+        "strict, inline = \"never\", copy_attrs(must_use), copy_attrs(doc), only(a), only(b),"
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_options_flags() {
+    assert!(syn::parse_str::<MirrorOptions>("strict = true, by_ref = false").is_ok());
+}
+
+#[test]
+fn test_options_errors() {
+    assert_eq!(
+        parse_error("not_an_option"),
+        "unknown option `not_an_option`"
+    );
+    assert_eq!(
+        parse_error("inline = \"never\", inline = \"always\""),
+        "duplicate option `inline`"
+    );
+    assert_eq!(
+        parse_error("strict = 1"),
+        "`strict` is a flag and only takes `true` or `false`"
+    );
+    assert_eq!(
+        parse_error("markers()"),
+        "`markers` is a flag and only takes `true` or `false`"
+    );
+    assert_eq!(parse_error("in_mod(mirror)"), "expected `=`");
+    assert_eq!(parse_error("copy_attrs = doc"), "expected parentheses");
+    assert_eq!(parse_error("strict by_ref"), "expected `,`");
+    assert_eq!(
+        parse_error("inline = \"sometimes\""),
+        "expected one of \"always\", \"never\", \"hint\" or \"none\""
+    );
+}
+
+#[test]
+fn test_module_options() {
+    let expanded = expand_symmetric_options(
+        quote!(inline = "never", skip_attrs(must_use)),
+        quote! {
+            mod shapes {
+                #[symmetric(inline = "always")]
+                impl Distance<Disk> for Point2D {
+                    #[must_use]
+                    #[deprecated]
+                    fn distance(&self, other: &Disk) -> f64 {
+                        self.distance(&other.center) - other.radius
+                    }
+                }
+            }
+        },
+    );
+    let module: ItemMod = syn::parse2(expanded).unwrap();
+    let original = match &module.content.unwrap().1[0] {
+        Item::Impl(original) => original.clone(),
+        _ => unreachable!(),
+    };
+    let options: MirrorOptions = original.attrs[0].parse_args().unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    let method_attrs: Vec<_> = match &mirrored.items[0] {
+        ImplItem::Method(method) => method.attrs.iter().map(render).collect(),
+        _ => unreachable!(),
+    };
+    // the option of the implementation overrides the one of the module
+    assert!(method_attrs.contains(&String::from("#[inline(always)]")));
+    assert!(method_attrs.contains(&String::from("#[deprecated]")));
+    assert!(!method_attrs.contains(&String::from("#[must_use]")));
+}
+
+#[test]
+fn test_module_options_unset() {
+    let expanded = expand_symmetric_options(
+        quote!(doc_hidden),
+        quote! {
+            mod shapes {
+                #[symmetric(doc_hidden = false)]
+                impl Distance<Disk> for Point2D {
+                    fn distance(&self, other: &Disk) -> f64 {
+                        self.distance(&other.center) - other.radius
+                    }
+                }
+            }
+        },
+    );
+    let module: ItemMod = syn::parse2(expanded).unwrap();
+    let original = match &module.content.unwrap().1[0] {
+        Item::Impl(original) => original.clone(),
+        _ => unreachable!(),
+    };
+    let options: MirrorOptions = original.attrs[0].parse_args().unwrap();
+    let mirrored = mirror(original, options).unwrap();
+    // the flag of the module is unset by the implementation
+    assert!(!mirrored
+        .attrs
+        .iter()
+        .any(|attr| render(attr) == "#[doc(hidden)]"));
+}
@@ -0,0 +1,101 @@
+use proc_macro2::TokenTree;
+use quote::quote;
+use symm_impl_core::generate;
+use syn::{parse_quote, ItemImpl};
+
+fn original() -> ItemImpl {
+    parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    }
+}
+
+/// The content of the generated module of property tests, whose name is
+/// hashed.
+fn tests(attr: &str) -> String {
+    let generated = generate(attr.parse().unwrap(), &original()).unwrap();
+    let mut tokens = generated.into_iter().skip_while(|token| match token {
+        TokenTree::Ident(ident) => ident != "mod",
+        _ => true,
+    });
+    tokens.next().unwrap();
+    let module = tokens.next().unwrap().to_string();
+    assert!(module.starts_with("__symm_proptest_"));
+    match tokens.next() {
+        Some(TokenTree::Group(content)) => content.stream().to_string(),
+        _ => panic!("expected the content of the module"),
+    }
+}
+
+#[test]
+fn test_proptest() {
+    // `> >` as generated, which is the same as `>>` once parsed
+    let expected = quote! {
+        use super::*;
+
+        ::proptest::proptest! {
+            #[test]
+            fn distance_is_symmetric(
+                lhs in ::proptest::prelude::any::<Point2D>(),
+                rhs in ::proptest::prelude::any::<Disk>()
+            ) {
+                ::proptest::prop_assert!(
+                    <Point2D as Distance<Disk> >::distance(&lhs, &rhs)
+                        == <Disk as Distance<Point2D> >::distance(&rhs, &lhs),
+                    "`distance` of `Distance<Disk>` is not symmetric",
+                );
+            }
+        }
+    };
+    assert_eq!(tests("proptest"), expected.to_string());
+}
+
+#[test]
+fn test_proptest_strategy() {
+    let generated = tests(
+        "proptest(strategy(Point2D = finite_point(), Disk = (0.0..1.0).prop_map(Disk::unit)))",
+    );
+    let expected = quote! {
+        fn distance_is_symmetric(
+            lhs in finite_point(),
+            rhs in (0.0..1.0).prop_map(Disk::unit)
+        )
+    };
+    assert!(generated.contains(&expected.to_string()));
+}
+
+#[test]
+fn test_proptest_pairs() {
+    // the mirror of `distance` delegates to `clearance`
+    let generated = tests("proptest, pairs(distance = clearance)");
+    let expected = quote! {
+        <Point2D as Distance<Disk> >::distance(&lhs, &rhs)
+            == <Disk as Distance<Point2D> >::clearance(&rhs, &lhs)
+    };
+    assert!(generated.contains(&expected.to_string()));
+}
+
+#[test]
+fn test_proptest_errors() {
+    let error = |attr: &str| {
+        generate(attr.parse().unwrap(), &original())
+            .err()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(
+        error("proptest(strategy(Segment = any_segment()))"),
+        "`Segment` is not an operand, expected `Point2D` or `Disk`"
+    );
+    assert_eq!(
+        error("proptest(strategy(Disk = a(), Disk = b()))"),
+        "duplicate strategy for `Disk`"
+    );
+    assert_eq!(
+        error("proptest(cases = 10)"),
+        "unknown option `cases` of `proptest`"
+    );
+}
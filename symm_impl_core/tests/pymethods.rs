@@ -0,0 +1,177 @@
+use symm_impl_core::{generate, render};
+use syn::{
+    parse_quote, File, FnArg, GenericArgument, ImplItem, Item, ItemImpl, Lit, Meta, NestedMeta,
+    Path, PathArguments, Type,
+};
+
+/// The inherent implementations generated for `original`.
+fn items(attr: &str, original: &ItemImpl) -> Vec<ItemImpl> {
+    let generated = generate(attr.parse().unwrap(), original).unwrap();
+    let file: File = syn::parse2(generated).unwrap();
+    file.items
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Impl(item_impl) if item_impl.trait_.is_none() => Some(item_impl),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The inherent implementations generated for `original`, rendered.
+fn classes(attr: &str, original: &ItemImpl) -> Vec<String> {
+    items(attr, original).iter().map(render).collect()
+}
+
+/// The segments of a path, and whether it is absolute.
+fn segments(path: &Path) -> (bool, Vec<String>) {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    (path.leading_colon.is_some(), segments)
+}
+
+#[test]
+fn test_pymethods() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            type Output = f64;
+            fn distance(&self, other: &Disk) -> Self::Output {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    assert_eq!(
+        classes("pymethods", &original),
+        vec![
+            "#[::pyo3::pymethods] impl Point2D { \
+             #[doc = \" Call `distance` of `Point2D` with `Disk` as the other operand.\"] \
+             #[pyo3(name = \"distance\")] \
+             fn __symm_py_distance(&self, other: ::pyo3::PyRef<'_, Disk>) -> <Point2D as Distance<Disk>>::Output { \
+             <Point2D as Distance<Disk>>::distance(self, &*other) } }",
+            "#[::pyo3::pymethods] impl Disk { \
+             #[doc = \" Call `distance` of `Disk` with `Point2D` as the other operand.\"] \
+             #[pyo3(name = \"distance\")] \
+             fn __symm_py_distance(&self, other: ::pyo3::PyRef<'_, Point2D>) -> <Disk as Distance<Point2D>>::Output { \
+             <Disk as Distance<Point2D>>::distance(self, &*other) } }",
+        ]
+    );
+}
+
+#[test]
+fn test_pymethods_attributes() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let classes = items("pymethods", &original);
+    let self_types: Vec<_> = classes.iter().map(|class| render(&class.self_ty)).collect();
+    assert_eq!(self_types, ["Point2D", "Disk"]);
+    for (class, other_type) in classes.iter().zip(["Disk", "Point2D"]) {
+        assert_eq!(class.attrs.len(), 1);
+        assert_eq!(
+            segments(&class.attrs[0].path),
+            (true, vec![String::from("pyo3"), String::from("pymethods")])
+        );
+        assert!(class.attrs[0].tokens.is_empty());
+        assert_eq!(class.items.len(), 1);
+        let method = match &class.items[0] {
+            ImplItem::Method(method) => method,
+            _ => panic!("expected a method"),
+        };
+        // the method is exposed to Python under the name of the trait method
+        let names: Vec<_> = method
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("pyo3"))
+            .map(|attr| match attr.parse_meta().unwrap() {
+                Meta::List(list) => match list.nested.first() {
+                    Some(NestedMeta::Meta(Meta::NameValue(name)))
+                        if list.nested.len() == 1 && name.path.is_ident("name") =>
+                    {
+                        match &name.lit {
+                            Lit::Str(name) => name.value(),
+                            _ => panic!("expected a string"),
+                        }
+                    }
+                    _ => panic!("expected `name = ...`"),
+                },
+                _ => panic!("expected a list"),
+            })
+            .collect();
+        assert_eq!(names, ["distance"]);
+        assert!(matches!(method.sig.inputs[0], FnArg::Receiver(_)));
+        // the other operand is borrowed from its Python object
+        let other = match &method.sig.inputs[1] {
+            FnArg::Typed(other) => other,
+            FnArg::Receiver(_) => panic!("expected the other operand"),
+        };
+        let other_path = match other.ty.as_ref() {
+            Type::Path(other) if other.qself.is_none() => &other.path,
+            _ => panic!("expected a path"),
+        };
+        assert_eq!(
+            segments(other_path),
+            (true, vec![String::from("pyo3"), String::from("PyRef")])
+        );
+        let borrowed: Vec<_> = match &other_path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(generics) => generics
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(borrowed) => Some(render(borrowed)),
+                    _ => None,
+                })
+                .collect(),
+            _ => panic!("expected generic arguments"),
+        };
+        assert_eq!(borrowed, [other_type]);
+    }
+}
+
+#[test]
+fn test_pymethods_mut() {
+    let original: ItemImpl = parse_quote! {
+        #[cfg(feature = "python")]
+        impl Exchange<Wallet> for Account {
+            fn exchange(&mut self, other: &mut Wallet) {
+                std::mem::swap(&mut self.balance, &mut other.balance);
+            }
+        }
+    };
+    assert_eq!(
+        classes("pymethods", &original),
+        vec![
+            "#[cfg(feature = \"python\")] #[::pyo3::pymethods] impl Account { \
+             #[doc = \" Call `exchange` of `Account` with `Wallet` as the other operand.\"] \
+             #[pyo3(name = \"exchange\")] \
+             fn __symm_py_exchange(&mut self, mut other: ::pyo3::PyRefMut<'_, Wallet>) { \
+             <Account as Exchange<Wallet>>::exchange(self, &mut *other) } }",
+            "#[cfg(feature = \"python\")] #[::pyo3::pymethods] impl Wallet { \
+             #[doc = \" Call `exchange` of `Wallet` with `Account` as the other operand.\"] \
+             #[pyo3(name = \"exchange\")] \
+             fn __symm_py_exchange(&mut self, mut other: ::pyo3::PyRefMut<'_, Account>) { \
+             <Wallet as Exchange<Account>>::exchange(self, &mut *other) } }",
+        ]
+    );
+}
+
+#[test]
+fn test_pymethods_by_value() {
+    let original: ItemImpl = parse_quote! {
+        impl Overlap<Segment> for Point2D {
+            fn overlap(self, other: Segment) -> bool {
+                other.contains(self)
+            }
+        }
+    };
+    let error = generate("pymethods".parse().unwrap(), &original).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`pymethods` requires methods taking the operands by reference"
+    );
+}
@@ -0,0 +1,35 @@
+use symm_impl_core::assert_expansion_snapshot;
+
+const DISTANCE: &str = "
+    impl Distance<Disk> for Point2D {
+        fn distance(&self, other: &Disk) -> f64 {
+            self.distance(&other.center) - other.radius
+        }
+    }
+";
+
+#[test]
+fn test_snapshot() {
+    assert_expansion_snapshot("", DISTANCE, "tests/snapshots/distance.rs");
+}
+
+#[test]
+fn test_snapshot_with_options() {
+    assert_expansion_snapshot(
+        "call = \"method\", inline = \"none\"",
+        DISTANCE,
+        "tests/snapshots/distance_method.rs",
+    );
+}
+
+#[test]
+#[should_panic(expected = "does not match the snapshot")]
+fn test_snapshot_mismatch() {
+    assert_expansion_snapshot("antisymmetric", DISTANCE, "tests/snapshots/distance.rs");
+}
+
+#[test]
+#[should_panic(expected = "failed to read the snapshot")]
+fn test_snapshot_missing() {
+    assert_expansion_snapshot("", DISTANCE, "tests/snapshots/missing.rs");
+}
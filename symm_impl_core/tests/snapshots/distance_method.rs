@@ -0,0 +1,11 @@
+const _: () = {
+    #[doc = "Symmetric mirror of [`impl Distance<Disk> for Point2D`](trait@Distance)."]
+    #[automatically_derived]
+    impl Distance<Point2D> for Disk {
+        #[track_caller]
+        #[allow(clippy::mut_mut, clippy::needless_borrow, clippy::needless_lifetimes, clippy::use_self)]
+        fn distance(&self, other: &Point2D) -> f64 {
+            other.distance(self)
+        }
+    }
+};
@@ -0,0 +1,43 @@
+use symm_impl_core::{render, validate_symmetric_impl, MirrorOptions};
+use syn::{parse_quote, ItemImpl};
+
+#[test]
+fn test_validate() {
+    let original: ItemImpl = parse_quote! {
+        impl<'a> Overlap<'a, Segment<'a>, 3> for Point2D {
+            type Output = bool;
+            fn overlap(&self, other: &Segment<'a>) -> bool {
+                other.contains(self)
+            }
+            fn touches(&self, other: &Segment<'a>) -> bool {
+                other.ends_at(self)
+            }
+        }
+    };
+    let options: MirrorOptions = syn::parse_str("only(touches)").unwrap();
+    let info = validate_symmetric_impl(&original, &options).unwrap();
+    assert_eq!(render(info.trait_path()), "Overlap<'a, Segment<'a>, 3>");
+    assert_eq!(render(info.self_type()), "Point2D");
+    assert_eq!(render(info.other_type()), "Segment<'a>");
+    assert_eq!(info.slot(), 1);
+    assert_eq!(info.methods(), ["touches"]);
+}
+
+#[test]
+fn test_validate_error() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: Disk) -> f64 {
+                0.0
+            }
+        }
+    };
+    let error = validate_symmetric_impl(&original, &MirrorOptions::default())
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "expected a reference, the expected signature is \
+         `fn distance(&self, other: &Disk) -> f64`"
+    );
+}
@@ -0,0 +1,146 @@
+use symm_impl_core::{generate, render};
+use syn::{parse_quote, File, FnArg, Item, ItemFn, ItemImpl, Pat, Path, Visibility};
+
+/// The exported functions generated for `original`.
+fn items(attr: &str, original: &ItemImpl) -> Vec<ItemFn> {
+    let generated = generate(attr.parse().unwrap(), original).unwrap();
+    let file: File = syn::parse2(generated).unwrap();
+    file.items
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Fn(function) => Some(function),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The exported functions generated for `original`, rendered.
+fn functions(attr: &str, original: &ItemImpl) -> Vec<String> {
+    items(attr, original).iter().map(render).collect()
+}
+
+/// The segments of a path, and whether it is absolute.
+fn segments(path: &Path) -> (bool, Vec<String>) {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    (path.leading_colon.is_some(), segments)
+}
+
+#[test]
+fn test_wasm_bindgen() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            type Output = f64;
+            fn distance(&self, other: &Disk) -> Self::Output {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    assert_eq!(
+        functions("wasm_bindgen(point, disk)", &original),
+        vec![
+            "#[doc = \" Call `distance` of `Point2D` with `Disk` as the other operand.\"] \
+             #[::wasm_bindgen::prelude::wasm_bindgen] \
+             pub fn point_disk_distance(lhs: &Point2D, rhs: &Disk) -> <Point2D as Distance<Disk>>::Output { \
+             <Point2D as Distance<Disk>>::distance(lhs, rhs) }",
+            "#[doc = \" Call `distance` of `Disk` with `Point2D` as the other operand.\"] \
+             #[::wasm_bindgen::prelude::wasm_bindgen] \
+             pub fn disk_point_distance(lhs: &Disk, rhs: &Point2D) -> <Disk as Distance<Point2D>>::Output { \
+             <Disk as Distance<Point2D>>::distance(lhs, rhs) }",
+        ]
+    );
+}
+
+#[test]
+fn test_wasm_bindgen_exports() {
+    let original: ItemImpl = parse_quote! {
+        impl Distance<Disk> for Point2D {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let functions = items("wasm_bindgen(point, disk)", &original);
+    let names: Vec<_> = functions
+        .iter()
+        .map(|function| function.sig.ident.to_string())
+        .collect();
+    assert_eq!(names, ["point_disk_distance", "disk_point_distance"]);
+    for function in &functions {
+        // the functions are exported by the attribute of `wasm_bindgen`, as
+        // long as they are free functions which can be exported
+        let exports: Vec<_> = function
+            .attrs
+            .iter()
+            .filter(|attr| !attr.path.is_ident("doc"))
+            .collect();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(
+            segments(&exports[0].path),
+            (
+                true,
+                vec![
+                    String::from("wasm_bindgen"),
+                    String::from("prelude"),
+                    String::from("wasm_bindgen"),
+                ]
+            )
+        );
+        assert!(exports[0].tokens.is_empty());
+        assert!(matches!(function.vis, Visibility::Public(_)));
+        assert!(function.sig.generics.params.is_empty());
+        assert_eq!(function.sig.inputs.len(), 2);
+        for input in &function.sig.inputs {
+            match input {
+                FnArg::Typed(typed) => assert!(matches!(typed.pat.as_ref(), Pat::Ident(_))),
+                FnArg::Receiver(_) => panic!("expected a free function"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_wasm_bindgen_async() {
+    let original: ItemImpl = parse_quote! {
+        impl Meet<Guest> for Host {
+            #[cfg(feature = "meet")]
+            async fn meet(self, other: Guest) -> bool {
+                self.room == other.room
+            }
+        }
+    };
+    assert_eq!(
+        functions("wasm_bindgen(host, guest)", &original),
+        vec![
+            "#[doc = \" Call `meet` of `Host` with `Guest` as the other operand.\"] \
+             #[cfg(feature = \"meet\")] \
+             #[::wasm_bindgen::prelude::wasm_bindgen] \
+             pub async fn host_guest_meet(lhs: Host, rhs: Guest) -> bool { \
+             <Host as Meet<Guest>>::meet(lhs, rhs).await }",
+            "#[doc = \" Call `meet` of `Guest` with `Host` as the other operand.\"] \
+             #[cfg(feature = \"meet\")] \
+             #[::wasm_bindgen::prelude::wasm_bindgen] \
+             pub async fn guest_host_meet(lhs: Guest, rhs: Host) -> bool { \
+             <Guest as Meet<Host>>::meet(lhs, rhs).await }",
+        ]
+    );
+}
+
+#[test]
+fn test_wasm_bindgen_generic() {
+    let original: ItemImpl = parse_quote! {
+        impl<T> Distance<Disk> for Point<T> {
+            fn distance(&self, other: &Disk) -> f64 {
+                self.distance(&other.center) - other.radius
+            }
+        }
+    };
+    let error = generate("wasm_bindgen(point, disk)".parse().unwrap(), &original).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`wasm_bindgen` does not support generic implementations"
+    );
+}
@@ -0,0 +1,81 @@
+//! Uniform handle to a symmetric operation.
+
+use alloc::boxed::Box;
+use core::{fmt, marker::PhantomData};
+
+/// Symmetric operation between `A` and `B`, callable with the operands in
+/// either order.
+///
+/// The operation is usually a method of a symmetric trait, e.g.
+/// `<Point2D as Distance<Disk>>::distance`, but any closure taking the two
+/// operands by reference is accepted. [`SymmetricFn::boxed`] erases the type of
+/// the closure, such that operations over the same types can be stored
+/// together.
+///
+/// # Example
+/// ```
+/// use symm_impl_runtime::SymmetricFn;
+///
+/// let scale = SymmetricFn::new(|factor: &f64, text: &&str| text.len() as f64 * factor);
+/// assert_eq!(scale.call(&2.0, &"abc"), 6.0);
+/// assert_eq!(scale.call_rev(&"abc", &2.0), 6.0);
+/// ```
+pub struct SymmetricFn<A, B, R, F = Box<dyn Fn(&A, &B) -> R>> {
+    f: F,
+    operation: PhantomData<fn(&A, &B) -> R>,
+}
+
+impl<A, B, R, F: Fn(&A, &B) -> R> SymmetricFn<A, B, R, F> {
+    /// Wrap an operation taking `A` first.
+    pub fn new(f: F) -> Self {
+        SymmetricFn {
+            f,
+            operation: PhantomData,
+        }
+    }
+
+    /// Apply the operation with `A` first.
+    pub fn call(&self, a: &A, b: &B) -> R {
+        (self.f)(a, b)
+    }
+
+    /// Apply the operation with `B` first.
+    pub fn call_rev(&self, b: &B, a: &A) -> R {
+        (self.f)(a, b)
+    }
+
+    /// Unwrap the operation.
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+}
+
+impl<A, B, R, F> SymmetricFn<A, B, R, F>
+where
+    F: Fn(&A, &B) -> R + 'static,
+{
+    /// Erase the type of the operation.
+    pub fn boxed(self) -> SymmetricFn<A, B, R> {
+        SymmetricFn::new(Box::new(self.f))
+    }
+}
+
+impl<A, B, R, F: Clone> Clone for SymmetricFn<A, B, R, F> {
+    fn clone(&self) -> Self {
+        SymmetricFn {
+            f: self.f.clone(),
+            operation: PhantomData,
+        }
+    }
+}
+
+impl<A, B, R, F: Copy> Copy for SymmetricFn<A, B, R, F> {}
+
+impl<A, B, R, F> fmt::Debug for SymmetricFn<A, B, R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SymmetricFn")
+            .field("a", &core::any::type_name::<A>())
+            .field("b", &core::any::type_name::<B>())
+            .finish()
+    }
+}
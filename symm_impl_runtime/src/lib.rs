@@ -0,0 +1,43 @@
+//! Runtime companions of the `#[symmetric]` attribute of [`symm_impl`].
+//!
+//! `#[symmetric]` makes a binary operation between two types available in both
+//! directions. This crate provides the types commonly built on top of such
+//! operations, e.g. [`SymmMatrix`] storing the result of a symmetric operation
+//! for every pair of a set of operands, and [`SymmetricFn`] calling such an
+//! operation with the operands in either order. [`OpTable`] dispatches such
+//! operations on operands whose types are only known at runtime.
+//! [`SymmetricOp`] and the other marker traits state properties of operations
+//! which generic code can require. [`SwapOperands`] flips results referring to
+//! the operands in order.
+//!
+//! The crate is `#![no_std]`, and only relies on `alloc`.
+//!
+//! # Features
+//! * `rayon`: parallel versions of the helpers evaluating an operation over
+//!   every pair of operands, `SymmMatrix::par_from_operands` and
+//!   `par_cross`, using [rayon](https://docs.rs/rayon).
+//!
+//! [`symm_impl`]: https://docs.rs/symm_impl
+
+#![no_std]
+
+extern crate alloc;
+
+mod function;
+mod markers;
+mod matrix;
+mod pairwise;
+mod swap;
+mod table;
+
+pub use crate::{
+    function::SymmetricFn,
+    markers::{ReflexiveOp, SymmetricOp, TransitiveOp},
+    matrix::SymmMatrix,
+    pairwise::cross,
+    swap::SwapOperands,
+    table::{OpTable, OpTableBuilder},
+};
+
+#[cfg(feature = "rayon")]
+pub use crate::pairwise::par_cross;
@@ -0,0 +1,33 @@
+//! Marker traits stating algebraic properties of binary operations.
+//!
+//! The operation is named by the trait object of the trait implementing it,
+//! e.g. `dyn Distance<Disk>`, with its associated types bound if any, e.g.
+//! `dyn Merge<Disk, Output = Disk>`. The trait must therefore be dyn
+//! compatible to be named, which symmetric traits usually are.
+
+/// The operation `Op`, implemented by `Self` with `Other`, is also implemented
+/// by `Other` with `Self`, and both directions give the same result.
+///
+/// This is implemented by `#[symmetric(markers)]` for both the original
+/// implementation and its mirror, such that generic code can require the
+/// mirror to exist:
+/// ```ignore
+/// fn total<T, U>(a: &T, b: &U) -> f64
+/// where
+///     T: Distance<U> + SymmetricOp<U, dyn Distance<U>>,
+///     U: Distance<T>,
+/// ```
+pub trait SymmetricOp<Other, Op: ?Sized> {}
+
+/// The operation `Op`, implemented by `Self` with itself, relates every value
+/// to itself, e.g. `a.intersects(&a)` for every `a`.
+///
+/// Implemented by `#[transitive(reflexive, markers)]`, or by hand for a
+/// relation which is not transitive.
+pub trait ReflexiveOp<Op: ?Sized> {}
+
+/// The operation `Op`, implemented by `Self` with itself, is transitive, i.e.
+/// if it relates `a` to `b` and `b` to `c`, it relates `a` to `c`.
+///
+/// Implemented by `#[transitive(markers)]`.
+pub trait TransitiveOp<Op: ?Sized> {}
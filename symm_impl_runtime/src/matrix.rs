@@ -0,0 +1,158 @@
+//! Storage of one value per unordered pair of indices.
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// Square matrix whose entries at `(i, j)` and `(j, i)` are the same value,
+/// e.g. a distance matrix.
+///
+/// Only one value is stored per unordered pair of indices, including the
+/// diagonal, so an `n` by `n` matrix holds `n * (n + 1) / 2` values.
+///
+/// # Example
+/// ```
+/// use symm_impl_runtime::SymmMatrix;
+///
+/// let points = [1.0_f64, 4.0, 6.0];
+/// let distances = SymmMatrix::from_operands(&points, |a, b| (a - b).abs());
+/// assert_eq!(distances[(0, 2)], 5.0);
+/// assert_eq!(distances[(2, 0)], 5.0);
+/// assert_eq!(distances.get(1, 3), None);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SymmMatrix<T> {
+    size: usize,
+    values: Vec<T>,
+}
+
+/// Position of `(i, j)` in the row-major storage of the lower triangle.
+fn offset(i: usize, j: usize) -> usize {
+    let (row, column) = if i < j { (j, i) } else { (i, j) };
+    row * (row + 1) / 2 + column
+}
+
+impl<T> SymmMatrix<T> {
+    /// Create a `size` by `size` matrix, computing the value of every
+    /// unordered pair `(i, j)` with `i <= j` once.
+    pub fn from_fn(size: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut values = Vec::with_capacity(size * (size + 1) / 2);
+        for row in 0..size {
+            for column in 0..=row {
+                values.push(f(column, row));
+            }
+        }
+        SymmMatrix::from_values(size, values)
+    }
+
+    /// Create a matrix from the values of the lower triangle in row-major
+    /// order.
+    pub(crate) fn from_values(size: usize, values: Vec<T>) -> Self {
+        debug_assert_eq!(values.len(), size * (size + 1) / 2);
+        SymmMatrix { size, values }
+    }
+
+    /// Create the matrix of a symmetric operation over every pair of
+    /// `operands`, e.g. `|a, b| a.distance(b)`. The operation is evaluated once
+    /// per unordered pair, with the operand of the lower index first.
+    pub fn from_operands<A>(operands: &[A], mut f: impl FnMut(&A, &A) -> T) -> Self {
+        SymmMatrix::from_fn(operands.len(), |i, j| f(&operands[i], &operands[j]))
+    }
+
+    /// Number of rows, which is also the number of columns.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the matrix has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Value at `(i, j)`, which is the value at `(j, i)`, or `None` if out of
+    /// bounds.
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i < self.size && j < self.size {
+            Some(&self.values[offset(i, j)])
+        } else {
+            None
+        }
+    }
+
+    /// Mutable value at `(i, j)`, which is the value at `(j, i)`, or `None` if
+    /// out of bounds.
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i < self.size && j < self.size {
+            Some(&mut self.values[offset(i, j)])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the unordered pairs as `(i, j, value)` with `i <= j`.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        (0..self.size)
+            .flat_map(|row| (0..=row).map(move |column| (column, row)))
+            .zip(self.values.iter())
+            .map(|((i, j), value)| (i, j, value))
+    }
+
+    /// Iterate over the row `i`, which is also the column `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(i < self.size, "row {} out of bounds", i);
+        (0..self.size).map(move |j| &self.values[offset(i, j)])
+    }
+}
+
+impl<T: Clone> SymmMatrix<T> {
+    /// Create a `size` by `size` matrix with every entry set to `value`.
+    pub fn filled(size: usize, value: T) -> Self {
+        SymmMatrix {
+            size,
+            values: alloc::vec![value; size * (size + 1) / 2],
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for SymmMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        match self.get(i, j) {
+            Some(value) => value,
+            None => panic!("index ({}, {}) out of bounds of size {}", i, j, self.size),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for SymmMatrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        let size = self.size;
+        match self.get_mut(i, j) {
+            Some(value) => value,
+            None => panic!("index ({}, {}) out of bounds of size {}", i, j, size),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SymmMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.size).map(|i| RowDebug(self, i)))
+            .finish()
+    }
+}
+
+/// Full row of a matrix, for the debug representation.
+struct RowDebug<'a, T>(&'a SymmMatrix<T>, usize);
+
+impl<T: fmt::Debug> fmt::Debug for RowDebug<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.row(self.1)).finish()
+    }
+}
@@ -0,0 +1,68 @@
+//! Evaluation of an operation over every pair of operands.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "rayon")]
+use crate::matrix::SymmMatrix;
+
+/// Evaluate `f(a, b)` for every `a` of `lhs` and `b` of `rhs`, in row-major
+/// order, i.e. the result for `(lhs[i], rhs[j])` is at `i * rhs.len() + j`.
+///
+/// For a symmetric operation, this is also the result for every pair of `rhs`
+/// and `lhs`, transposed, so each unordered pair is only evaluated once.
+///
+/// # Example
+/// ```
+/// let products = symm_impl_runtime::cross(&[1, 2], &[3, 4, 5], |a, b| a * b);
+/// assert_eq!(products, [3, 4, 5, 6, 8, 10]);
+/// ```
+pub fn cross<A, B, T>(lhs: &[A], rhs: &[B], mut f: impl FnMut(&A, &B) -> T) -> Vec<T> {
+    let mut values = Vec::with_capacity(lhs.len() * rhs.len());
+    for a in lhs {
+        for b in rhs {
+            values.push(f(a, b));
+        }
+    }
+    values
+}
+
+/// Parallel version of [`cross`].
+#[cfg(feature = "rayon")]
+pub fn par_cross<A, B, T, F>(lhs: &[A], rhs: &[B], f: F) -> Vec<T>
+where
+    A: Sync,
+    B: Sync,
+    T: Send,
+    F: Fn(&A, &B) -> T + Sync,
+{
+    lhs.par_iter()
+        .flat_map_iter(|a| rhs.iter().map(|b| f(a, b)).collect::<Vec<_>>())
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Send> SymmMatrix<T> {
+    /// Parallel version of [`SymmMatrix::from_fn`].
+    pub fn par_from_fn<F>(size: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> T + Sync,
+    {
+        let values = (0..size)
+            .into_par_iter()
+            .flat_map_iter(|row| (0..=row).map(|column| f(column, row)).collect::<Vec<_>>())
+            .collect();
+        SymmMatrix::from_values(size, values)
+    }
+
+    /// Parallel version of [`SymmMatrix::from_operands`].
+    pub fn par_from_operands<A, F>(operands: &[A], f: F) -> Self
+    where
+        A: Sync,
+        F: Fn(&A, &A) -> T + Sync,
+    {
+        SymmMatrix::par_from_fn(operands.len(), |i, j| f(&operands[i], &operands[j]))
+    }
+}
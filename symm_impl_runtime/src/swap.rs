@@ -0,0 +1,53 @@
+//! Results of operations which refer to their operands in order.
+
+use alloc::vec::Vec;
+
+/// A result referring to the operands of the operation in order, e.g. the
+/// closest points of two shapes, which is flipped when the operands are.
+///
+/// `#[symmetric(swap_output)]` calls `swap_operands` on the results of the
+/// original implementation, such that the mirror gives them in its own order:
+/// ```ignore
+/// #[symmetric(swap_output)]
+/// impl ClosestPoints<Disk> for Segment {
+///     fn closest_points(&self, other: &Disk) -> (Point2D, Point2D) {
+///         // (point on the segment, point on the disk)
+///     }
+/// }
+/// // disk.closest_points(&segment) is (point on the disk, point on the segment)
+/// ```
+pub trait SwapOperands {
+    /// Flip the result as if the operation was called with the operands in
+    /// the reversed order.
+    fn swap_operands(self) -> Self;
+}
+
+/// A pair of values, one for each operand.
+impl<T> SwapOperands for (T, T) {
+    fn swap_operands(self) -> Self {
+        (self.1, self.0)
+    }
+}
+
+/// A pair of values, one for each operand.
+impl<T> SwapOperands for [T; 2] {
+    fn swap_operands(self) -> Self {
+        let [first, second] = self;
+        [second, first]
+    }
+}
+
+/// A result which may be missing, e.g. the contact of shapes which do not
+/// touch.
+impl<T: SwapOperands> SwapOperands for Option<T> {
+    fn swap_operands(self) -> Self {
+        self.map(SwapOperands::swap_operands)
+    }
+}
+
+/// Several results, e.g. the contact points of a manifold.
+impl<T: SwapOperands> SwapOperands for Vec<T> {
+    fn swap_operands(self) -> Self {
+        self.into_iter().map(SwapOperands::swap_operands).collect()
+    }
+}
@@ -0,0 +1,130 @@
+//! Dispatch of symmetric operations on operands whose types are only known at
+//! runtime.
+
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+/// Operation on type-erased operands, in the order of registration.
+type Erased<R> = Box<dyn Fn(&dyn Any, &dyn Any) -> R>;
+
+/// Table of symmetric operations over pairs of types, dispatching on the
+/// runtime types of the operands.
+///
+/// Each operation is registered for one order of the types, and is called for
+/// operands in either order. Operands of unregistered pairs go to the fallback
+/// handler, if any.
+///
+/// # Example
+/// ```
+/// use std::any::Any;
+///
+/// use symm_impl_runtime::OpTable;
+///
+/// let table = OpTable::builder()
+///     .register(|a: &i32, b: &f64| *a as f64 + b)
+///     .register(|a: &i32, b: &i32| f64::from(a + b))
+///     .fallback(|_, _| f64::NAN)
+///     .build();
+/// let operands: [&dyn Any; 3] = [&1_i32, &2.5_f64, &"text"];
+/// assert_eq!(table.call(operands[0], operands[1]), Some(3.5));
+/// assert_eq!(table.call(operands[1], operands[0]), Some(3.5));
+/// assert!(table.call(operands[0], operands[2]).unwrap().is_nan());
+/// ```
+pub struct OpTable<R> {
+    operations: BTreeMap<(TypeId, TypeId), Erased<R>>,
+    fallback: Option<Erased<R>>,
+}
+
+impl<R> OpTable<R> {
+    /// Start building a table.
+    pub fn builder() -> OpTableBuilder<R> {
+        OpTableBuilder {
+            table: OpTable {
+                operations: BTreeMap::new(),
+                fallback: None,
+            },
+        }
+    }
+
+    /// Whether an operation is registered for the types of `a` and `b`, in
+    /// either order.
+    pub fn contains(&self, a: &dyn Any, b: &dyn Any) -> bool {
+        let key = (a.type_id(), b.type_id());
+        self.operations.contains_key(&key) || self.operations.contains_key(&(key.1, key.0))
+    }
+
+    /// Apply the operation registered for the types of `a` and `b`, swapping
+    /// the operands if it was registered for the other order.
+    ///
+    /// Returns the result of the fallback handler for unregistered pairs, or
+    /// `None` without one.
+    pub fn call(&self, a: &dyn Any, b: &dyn Any) -> Option<R> {
+        let key = (a.type_id(), b.type_id());
+        if let Some(operation) = self.operations.get(&key) {
+            Some(operation(a, b))
+        } else if let Some(operation) = self.operations.get(&(key.1, key.0)) {
+            Some(operation(b, a))
+        } else {
+            self.fallback.as_ref().map(|fallback| fallback(a, b))
+        }
+    }
+}
+
+impl<R> fmt::Debug for OpTable<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpTable")
+            .field("operations", &self.operations.len())
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+/// Builder of an [`OpTable`].
+pub struct OpTableBuilder<R> {
+    table: OpTable<R>,
+}
+
+impl<R> OpTableBuilder<R> {
+    /// Register the operation between `A` and `B`, e.g.
+    /// `<Point2D as Distance<Disk>>::distance`. It is also used for operands
+    /// of types `B` and `A`, unless an operation is registered for that order.
+    ///
+    /// Registering an operation for the same order of types again replaces the
+    /// previous one.
+    pub fn register<A, B, F>(mut self, f: F) -> Self
+    where
+        A: Any,
+        B: Any,
+        F: Fn(&A, &B) -> R + 'static,
+    {
+        let operation: Erased<R> = Box::new(move |a, b| {
+            // the table only calls it with operands of the registered types
+            f(a.downcast_ref().unwrap(), b.downcast_ref().unwrap())
+        });
+        self.table
+            .operations
+            .insert((TypeId::of::<A>(), TypeId::of::<B>()), operation);
+        self
+    }
+
+    /// Set the handler called with the operands of unregistered pairs, in the
+    /// order they are given.
+    pub fn fallback(mut self, f: impl Fn(&dyn Any, &dyn Any) -> R + 'static) -> Self {
+        self.table.fallback = Some(Box::new(f));
+        self
+    }
+
+    /// Finish building the table.
+    pub fn build(self) -> OpTable<R> {
+        self.table
+    }
+}
+
+impl<R> fmt::Debug for OpTableBuilder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OpTableBuilder").field(&self.table).finish()
+    }
+}
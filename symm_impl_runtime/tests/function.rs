@@ -0,0 +1,58 @@
+use symm_impl::symmetric;
+use symm_impl_runtime::SymmetricFn;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[test]
+fn test_method() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    let distance = SymmetricFn::new(<Point2D as Distance<Disk>>::distance);
+    let copied = distance;
+    assert_eq!(distance.call(&p, &d), 3.0);
+    assert_eq!(copied.call_rev(&d, &p), d.distance(&p));
+}
+
+#[test]
+fn test_boxed() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    let offset = 1.0;
+    let operations: Vec<SymmetricFn<Point2D, Disk, f64>> = vec![
+        SymmetricFn::new(<Point2D as Distance<Disk>>::distance).boxed(),
+        SymmetricFn::new(move |p: &Point2D, d: &Disk| p.distance(d) + offset).boxed(),
+    ];
+    let results: Vec<_> = operations
+        .iter()
+        .map(|operation| operation.call_rev(&d, &p))
+        .collect();
+    assert_eq!(results, [3.0, 4.0]);
+    assert!(format!("{:?}", operations[0]).starts_with("SymmetricFn"));
+}
@@ -0,0 +1,132 @@
+use std::ops::Add;
+
+use symm_impl::{symmetric, transitive};
+use symm_impl_runtime::{ReflexiveOp, SymmetricOp, TransitiveOp};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Clone, Copy)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Clone, Copy)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+trait Reaches<Other> {
+    fn reaches(&self, other: &Other) -> bool;
+}
+
+trait Precedes<Other> {
+    fn precedes(&self, other: &Other) -> bool;
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl ReflexiveOp<dyn Distance<Point2D>> for Point2D {}
+
+#[derive(Clone, Copy)]
+struct Level(u8);
+
+#[transitive(reflexive, markers)]
+impl Reaches<Level> for Level {
+    fn reaches(&self, other: &Level) -> bool {
+        self.0 <= other.0
+    }
+}
+
+#[transitive(markers)]
+impl Precedes<Level> for Level {
+    fn precedes(&self, other: &Level) -> bool {
+        self.0 < other.0
+    }
+}
+
+#[symmetric(markers)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[symmetric(markers, in_mod = sum_mirror)]
+impl Add<Meters> for f64 {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self + other.0)
+    }
+}
+
+/// Sum of the distances in both directions, only accepting symmetric
+/// distances.
+fn round_trip<T, U>(a: &T, b: &U) -> f64
+where
+    T: Distance<U> + SymmetricOp<U, dyn Distance<U>>,
+    U: Distance<T> + SymmetricOp<T, dyn Distance<T>>,
+{
+    a.distance(b) + b.distance(a)
+}
+
+fn add_both<T, U>(a: T, b: U) -> (T::Output, U::Output)
+where
+    T: Add<U> + SymmetricOp<U, dyn Add<U, Output = <T as Add<U>>::Output>> + Copy,
+    U: Add<T> + SymmetricOp<T, dyn Add<T, Output = <U as Add<T>>::Output>> + Copy,
+{
+    (a + b, b + a)
+}
+
+fn reflexive<T: Distance<T> + ReflexiveOp<dyn Distance<T>>>(a: &T) -> f64 {
+    a.distance(a)
+}
+
+/// Whether `a` reaches `c` through `b`, only accepting transitive relations.
+fn reaches_through<T: Reaches<T> + TransitiveOp<dyn Reaches<T>>>(a: &T, b: &T, c: &T) -> bool {
+    a.reaches(b) && b.reaches(c)
+}
+
+fn reaches_itself<T: Reaches<T> + ReflexiveOp<dyn Reaches<T>>>(a: &T) -> bool {
+    a.reaches(a)
+}
+
+fn precedes_through<T: Precedes<T> + TransitiveOp<dyn Precedes<T>>>(a: &T, b: &T, c: &T) -> bool {
+    a.precedes(b) && b.precedes(c)
+}
+
+#[test]
+fn test_markers() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    assert_eq!(round_trip(&p, &d), 6.0);
+    assert_eq!(round_trip(&d, &p), 6.0);
+    assert_eq!(add_both(1.0, Meters(2.0)), (Meters(3.0), Meters(3.0)));
+    assert_eq!(reflexive(&p), 0.0);
+}
+
+#[test]
+fn test_transitive_markers() {
+    let (a, b, c) = (Level(1), Level(2), Level(3));
+    assert!(reaches_through(&a, &b, &c));
+    assert!(!reaches_through(&c, &b, &a));
+    assert!(reaches_itself(&a));
+    assert!(precedes_through(&a, &b, &c));
+    assert!(!precedes_through(&a, &a, &c));
+}
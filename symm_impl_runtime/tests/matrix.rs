@@ -0,0 +1,118 @@
+use symm_impl::symmetric;
+use symm_impl_runtime::SymmMatrix;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl Distance<Disk> for Disk {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.center.distance(&other.center) - self.radius - other.radius).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+}
+
+enum Shape {
+    Point(Point2D),
+    Disk(Disk),
+}
+
+impl Distance<Shape> for Shape {
+    fn distance(&self, other: &Shape) -> f64 {
+        match (self, other) {
+            (Shape::Point(a), Shape::Point(b)) => a.distance(b),
+            (Shape::Point(a), Shape::Disk(b)) => a.distance(b),
+            (Shape::Disk(a), Shape::Point(b)) => a.distance(b),
+            (Shape::Disk(a), Shape::Disk(b)) => a.distance(b),
+        }
+    }
+}
+
+#[test]
+fn test_from_operands() {
+    let shapes = [
+        Shape::Point(Point2D { x: 0.0, y: 0.0 }),
+        Shape::Disk(Disk {
+            center: Point2D { x: 3.0, y: 4.0 },
+            radius: 1.0,
+        }),
+        Shape::Point(Point2D { x: 6.0, y: 8.0 }),
+    ];
+    let mut evaluated = 0;
+    let distances = SymmMatrix::from_operands(&shapes, |a, b| {
+        evaluated += 1;
+        a.distance(b)
+    });
+    assert_eq!(evaluated, 6);
+    assert_eq!(distances.size(), 3);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(distances[(i, j)], shapes[i].distance(&shapes[j]));
+            assert_eq!(distances.get(i, j), distances.get(j, i));
+        }
+    }
+    assert_eq!(distances[(0, 1)], 4.0);
+    assert_eq!(distances[(2, 0)], 10.0);
+}
+
+#[test]
+fn test_access() {
+    let mut matrix = SymmMatrix::filled(3, 0);
+    assert!(!matrix.is_empty());
+    matrix[(2, 1)] = 5;
+    *matrix.get_mut(0, 2).unwrap() = 7;
+    assert_eq!(matrix[(1, 2)], 5);
+    assert_eq!(matrix.get(2, 0), Some(&7));
+    assert_eq!(matrix.get(3, 0), None);
+    assert_eq!(matrix.get_mut(0, 3), None);
+    assert_eq!(matrix.row(2).copied().collect::<Vec<_>>(), [7, 5, 0]);
+    assert_eq!(
+        matrix
+            .iter()
+            .map(|(i, j, &value)| (i, j, value))
+            .collect::<Vec<_>>(),
+        [
+            (0, 0, 0),
+            (0, 1, 0),
+            (1, 1, 0),
+            (0, 2, 7),
+            (1, 2, 5),
+            (2, 2, 0)
+        ]
+    );
+    assert_eq!(format!("{:?}", matrix), "[[0, 0, 7], [0, 0, 5], [7, 5, 0]]");
+    assert!(SymmMatrix::from_fn(0, |_, _| ()).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "index (0, 4) out of bounds of size 2")]
+fn test_out_of_bounds() {
+    let matrix = SymmMatrix::from_fn(2, |i, j| i + j);
+    let _ = matrix[(0, 4)];
+}
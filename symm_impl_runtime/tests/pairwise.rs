@@ -0,0 +1,92 @@
+use symm_impl::symmetric;
+use symm_impl_runtime::{cross, SymmMatrix};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+}
+
+fn points() -> Vec<Point2D> {
+    (0..50)
+        .map(|i| Point2D {
+            x: f64::from(i % 7),
+            y: f64::from(i / 7),
+        })
+        .collect()
+}
+
+fn disks() -> Vec<Disk> {
+    (0..20)
+        .map(|i| Disk {
+            center: Point2D {
+                x: f64::from(i),
+                y: -f64::from(i),
+            },
+            radius: 1.5,
+        })
+        .collect()
+}
+
+#[test]
+fn test_cross() {
+    let (points, disks) = (points(), disks());
+    let distances = cross(&points, &disks, |p, d| p.distance(d));
+    assert_eq!(distances.len(), points.len() * disks.len());
+    for (i, p) in points.iter().enumerate() {
+        for (j, d) in disks.iter().enumerate() {
+            assert_eq!(distances[i * disks.len() + j], d.distance(p));
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_cross() {
+    let (points, disks) = (points(), disks());
+    assert_eq!(
+        symm_impl_runtime::par_cross(&points, &disks, |p, d| p.distance(d)),
+        cross(&points, &disks, |p, d| p.distance(d))
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_from_operands() {
+    let points = points();
+    assert_eq!(
+        SymmMatrix::par_from_operands(&points, |a, b| a.distance(b)),
+        SymmMatrix::from_operands(&points, |a, b| a.distance(b))
+    );
+    assert!(SymmMatrix::par_from_fn(0, |_, _| ()).is_empty());
+}
+
+#[test]
+fn test_from_operands() {
+    let points = points();
+    let distances = SymmMatrix::from_operands(&points, |a, b| a.distance(b));
+    assert_eq!(distances[(3, 10)], points[10].distance(&points[3]));
+}
@@ -0,0 +1,86 @@
+use symm_impl::symmetric;
+use symm_impl_runtime::SwapOperands;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D(f64, f64);
+
+struct Segment(Point2D, Point2D);
+
+struct Disk(Point2D, f64);
+
+#[derive(Debug, PartialEq)]
+struct Contact {
+    normal: (f64, f64),
+    depth: f64,
+}
+
+impl SwapOperands for Contact {
+    fn swap_operands(self) -> Self {
+        Contact {
+            normal: (-self.normal.0, -self.normal.1),
+            depth: self.depth,
+        }
+    }
+}
+
+trait Closest<Other> {
+    type Points;
+
+    fn closest(&self, other: &Other) -> Self::Points;
+
+    fn contact(&self, other: &Other) -> Option<Contact>;
+}
+
+#[symmetric(swap_output)]
+impl Closest<Disk> for Segment {
+    type Points = [Point2D; 2];
+
+    // only horizontal segments below the disk, for the sake of the example
+    fn closest(&self, other: &Disk) -> [Point2D; 2] {
+        let Disk(center, radius) = other;
+        let x = center.0.max(self.0 .0).min(self.1 .0);
+        [Point2D(x, self.0 .1), Point2D(x, center.1 - radius)]
+    }
+
+    fn contact(&self, other: &Disk) -> Option<Contact> {
+        let [on_segment, on_disk] = self.closest(other);
+        if on_disk.1 <= on_segment.1 {
+            Some(Contact {
+                normal: (0.0, 1.0),
+                depth: on_segment.1 - on_disk.1,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_swap_output() {
+    let segment = Segment(Point2D(0.0, 0.0), Point2D(4.0, 0.0));
+    let disk = Disk(Point2D(1.0, 2.0), 1.0);
+    assert_eq!(
+        segment.closest(&disk),
+        [Point2D(1.0, 0.0), Point2D(1.0, 1.0)]
+    );
+    assert_eq!(
+        disk.closest(&segment),
+        [Point2D(1.0, 1.0), Point2D(1.0, 0.0)]
+    );
+    let touching = Disk(Point2D(5.0, 0.5), 1.0);
+    assert_eq!(
+        touching.contact(&segment),
+        Some(Contact {
+            normal: (-0.0, -1.0),
+            depth: 0.5,
+        })
+    );
+    assert_eq!(disk.contact(&segment), None);
+}
+
+#[test]
+fn test_swap_operands() {
+    assert_eq!((1, 2).swap_operands(), (2, 1));
+    assert_eq!(Some([1, 2]).swap_operands(), Some([2, 1]));
+    assert_eq!(vec![(1, 2), (3, 4)].swap_operands(), vec![(2, 1), (4, 3)]);
+}
@@ -0,0 +1,87 @@
+use std::any::Any;
+
+use symm_impl::symmetric;
+use symm_impl_runtime::OpTable;
+
+trait Intersects<Other> {
+    fn intersects(&self, other: &Other) -> bool;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+/// Shape without any registered operation.
+struct Segment;
+
+#[symmetric]
+impl Intersects<Disk> for Point2D {
+    fn intersects(&self, other: &Disk) -> bool {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        dx * dx + dy * dy <= other.radius * other.radius
+    }
+}
+
+impl Intersects<Point2D> for Point2D {
+    fn intersects(&self, other: &Point2D) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+fn shapes() -> Vec<Box<dyn Any>> {
+    vec![
+        Box::new(Point2D { x: 1.0, y: 1.0 }),
+        Box::new(Disk {
+            center: Point2D { x: 0.0, y: 0.0 },
+            radius: 2.0,
+        }),
+        Box::new(Segment),
+    ]
+}
+
+#[test]
+fn test_dispatch() {
+    let table = OpTable::builder()
+        .register(<Point2D as Intersects<Disk>>::intersects)
+        .register(<Point2D as Intersects<Point2D>>::intersects)
+        .build();
+    let shapes = shapes();
+    let (point, disk, segment) = (&*shapes[0], &*shapes[1], &*shapes[2]);
+    assert_eq!(table.call(point, disk), Some(true));
+    assert_eq!(table.call(disk, point), Some(true));
+    assert_eq!(table.call(point, point), Some(true));
+    assert!(table.contains(disk, point));
+    assert!(!table.contains(point, segment));
+    assert_eq!(table.call(point, segment), None);
+    assert_eq!(table.call(disk, disk), None);
+}
+
+#[test]
+fn test_fallback() {
+    let table = OpTable::builder()
+        .register(<Point2D as Intersects<Disk>>::intersects)
+        .fallback(|a, b| a.is::<Segment>() && !b.is::<Segment>())
+        .build();
+    let shapes = shapes();
+    let (point, disk, segment) = (&*shapes[0], &*shapes[1], &*shapes[2]);
+    assert_eq!(table.call(disk, point), Some(true));
+    assert_eq!(table.call(segment, point), Some(true));
+    assert_eq!(table.call(point, segment), Some(false));
+}
+
+#[test]
+fn test_registered_order_first() {
+    let table = OpTable::builder()
+        .register(|_: &i32, _: &u8| "i32 first")
+        .register(|_: &u8, _: &i32| "u8 first")
+        .build();
+    assert_eq!(table.call(&1_i32, &1_u8), Some("i32 first"));
+    assert_eq!(table.call(&1_u8, &1_i32), Some("u8 first"));
+}
@@ -0,0 +1,17 @@
+#!/bin/sh
+# Compile the macros to WebAssembly into symm_impl_watt/symm_impl.wasm, which
+# must be run after changing symm_impl_core. CI rebuilds the module and checks
+# that it is unchanged.
+#
+# The toolchain of rust-toolchain.toml is installed by rustup on the first run.
+set -e
+
+cd "$(dirname "$0")"
+# keep the paths of the build machine out of the module
+RUSTFLAGS="-C target-cpu=mvp \
+--remap-path-prefix=$(cd .. && pwd)=/symm_impl \
+--remap-path-prefix=${CARGO_HOME:-$HOME/.cargo}=/cargo \
+--remap-path-prefix=$(rustc --print sysroot)=/rust" \
+    cargo build --locked --release --target wasm32-unknown-unknown \
+    -Z build-std=std,panic_abort
+cp target/wasm32-unknown-unknown/release/symm_impl_wasm.wasm ../symm_impl_watt/symm_impl.wasm
@@ -0,0 +1,275 @@
+//! The token exchange with the watt runtime.
+//!
+//! The runtime hands the tokens over as handles, which are serialized by the
+//! host and rebuilt here as the tokens of the fallback `proc-macro2`. The
+//! spans of the host cannot be carried into the module, so each handle is
+//! stood for by the span of a marker identifier, parsed once per stream, whose
+//! text gives the handle back when the output is serialized. Spans the macros
+//! create themselves resolve to the call site.
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Once;
+
+#[link(wasm_import_module = "watt-0.5")]
+extern "C" {
+    fn token_stream_serialize(stream: u32) -> u32;
+    fn token_stream_deserialize(ptr: *const u8, len: usize) -> u32;
+    fn literal_to_string(literal: u32) -> u32;
+    fn string_new(ptr: *const u8, len: usize) -> u32;
+    fn string_len(string: u32) -> u32;
+    fn string_read(string: u32, ptr: *mut u8);
+    fn bytes_len(bytes: u32) -> u32;
+    fn bytes_read(bytes: u32, ptr: *mut u8);
+    fn print_panic(string: u32);
+}
+
+const CALL_SITE: u32 = u32::MAX;
+const MARKER: &str = "__symm_span_";
+
+#[no_mangle]
+pub extern "C" fn raw_to_token_stream(raw: u32) -> Box<TokenStream> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        std::panic::set_hook(Box::new(|info| {
+            let message = info.to_string();
+            unsafe { print_panic(string_new(message.as_ptr(), message.len())) }
+        }))
+    });
+
+    let bytes = unsafe {
+        let handle = token_stream_serialize(raw);
+        let mut bytes = vec![0; bytes_len(handle) as usize];
+        bytes_read(handle, bytes.as_mut_ptr());
+        bytes
+    };
+    let mut raw = Raw::default();
+    let stream = raw.stream(&mut &bytes[..]);
+    Box::new(raw.spans().tokens(stream))
+}
+
+#[no_mangle]
+pub extern "C" fn token_stream_into_raw(stream: Box<TokenStream>) -> u32 {
+    let mut bytes = Vec::new();
+    encode(*stream, &mut bytes);
+    unsafe { token_stream_deserialize(bytes.as_ptr(), bytes.len()) }
+}
+
+/// A token as serialized by the host, before the spans are resolved.
+enum Token {
+    Group(Delimiter, u32, Vec<Token>),
+    Ident(u32, String),
+    Punct(char, Spacing, u32),
+    Literal(u32, u32),
+}
+
+#[derive(Default)]
+struct Raw {
+    spans: Vec<u32>,
+}
+
+impl Raw {
+    fn stream(&mut self, bytes: &mut &[u8]) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = match byte(bytes) {
+                0 => break tokens,
+                1 => {
+                    let delimiter = match byte(bytes) {
+                        0 => Delimiter::Parenthesis,
+                        1 => Delimiter::Brace,
+                        2 => Delimiter::Bracket,
+                        _ => Delimiter::None,
+                    };
+                    let span = self.span(bytes);
+                    // the spans of the delimiters are not rebuilt by the host
+                    self.span(bytes);
+                    self.span(bytes);
+                    Token::Group(delimiter, span, self.stream(bytes))
+                }
+                2 => {
+                    let span = self.span(bytes);
+                    Token::Ident(span, string(bytes))
+                }
+                3 => {
+                    let ch = std::char::from_u32(word(bytes)).unwrap();
+                    let spacing = match byte(bytes) {
+                        0 => Spacing::Alone,
+                        _ => Spacing::Joint,
+                    };
+                    Token::Punct(ch, spacing, self.span(bytes))
+                }
+                _ => {
+                    let span = self.span(bytes);
+                    assert_eq!(byte(bytes), 1, "literals come from the host as handles");
+                    Token::Literal(span, word(bytes))
+                }
+            };
+            tokens.push(token);
+        }
+    }
+
+    fn span(&mut self, bytes: &mut &[u8]) -> u32 {
+        let span = word(bytes);
+        if span != CALL_SITE {
+            self.spans.push(span);
+        }
+        span
+    }
+
+    /// Parses a marker for each span of the host.
+    fn spans(self) -> Spans {
+        let source = self
+            .spans
+            .iter()
+            .map(|span| format!("{}{}", MARKER, span))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let markers = TokenStream::from_str(&source).unwrap();
+        Spans(
+            self.spans
+                .into_iter()
+                .zip(markers)
+                .map(|(handle, marker)| (handle, marker.span()))
+                .collect(),
+        )
+    }
+}
+
+struct Spans(HashMap<u32, Span>);
+
+impl Spans {
+    fn span(&self, handle: u32) -> Span {
+        self.0.get(&handle).copied().unwrap_or_else(Span::call_site)
+    }
+
+    fn tokens(&self, tokens: Vec<Token>) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|token| -> TokenTree {
+                match token {
+                    Token::Group(delimiter, span, stream) => {
+                        let mut group = Group::new(delimiter, self.tokens(stream));
+                        group.set_span(self.span(span));
+                        group.into()
+                    }
+                    Token::Ident(span, name) => match name.strip_prefix("r#") {
+                        Some(name) => Ident::new_raw(name, self.span(span)).into(),
+                        None => Ident::new(&name, self.span(span)).into(),
+                    },
+                    Token::Punct(ch, spacing, span) => {
+                        let mut punct = Punct::new(ch, spacing);
+                        punct.set_span(self.span(span));
+                        punct.into()
+                    }
+                    Token::Literal(span, handle) => {
+                        let text = unsafe {
+                            let string = literal_to_string(handle);
+                            let mut bytes = vec![0; string_len(string) as usize];
+                            string_read(string, bytes.as_mut_ptr());
+                            String::from_utf8(bytes).unwrap()
+                        };
+                        let mut literal = Literal::from_str(&text).unwrap();
+                        literal.set_span(self.span(span));
+                        literal.into()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn byte(bytes: &mut &[u8]) -> u8 {
+    let byte = bytes[0];
+    *bytes = &bytes[1..];
+    byte
+}
+
+fn word(bytes: &mut &[u8]) -> u32 {
+    let mut word = [0; 4];
+    word.copy_from_slice(&bytes[..4]);
+    *bytes = &bytes[4..];
+    u32::from_le_bytes(word)
+}
+
+fn string(bytes: &mut &[u8]) -> String {
+    let len = word(bytes) as usize;
+    let string = String::from_utf8(bytes[..len].to_vec()).unwrap();
+    *bytes = &bytes[len..];
+    string
+}
+
+/// Serializes the tokens for the host, which parses the literals from their
+/// text, giving back the handle of each span from its marker.
+fn encode(stream: TokenStream, bytes: &mut Vec<u8>) {
+    for token in stream {
+        match token {
+            TokenTree::Group(group) => {
+                bytes.push(1);
+                bytes.push(match group.delimiter() {
+                    Delimiter::Parenthesis => 0,
+                    Delimiter::Brace => 1,
+                    Delimiter::Bracket => 2,
+                    Delimiter::None => 3,
+                });
+                encode_span(group.span(), bytes);
+                encode(group.stream(), bytes);
+            }
+            TokenTree::Ident(ident) => {
+                bytes.push(2);
+                encode_span(ident.span(), bytes);
+                encode_str(&ident.to_string(), bytes);
+            }
+            TokenTree::Punct(punct) => {
+                encode_punct(punct.as_char(), punct.spacing(), punct.span(), bytes);
+            }
+            TokenTree::Literal(literal) => {
+                let text = literal.to_string();
+                // a negative number is two tokens to the host
+                let text = match text.strip_prefix('-') {
+                    Some(text) => {
+                        encode_punct('-', Spacing::Alone, literal.span(), bytes);
+                        text.to_owned()
+                    }
+                    None => text,
+                };
+                bytes.push(4);
+                encode_span(literal.span(), bytes);
+                bytes.push(0);
+                encode_str(&text, bytes);
+            }
+        }
+    }
+    bytes.push(0);
+}
+
+fn encode_punct(ch: char, spacing: Spacing, span: Span, bytes: &mut Vec<u8>) {
+    bytes.push(3);
+    bytes.extend_from_slice(&(ch as u32).to_le_bytes());
+    bytes.push(match spacing {
+        Spacing::Alone => 0,
+        Spacing::Joint => 1,
+    });
+    encode_span(span, bytes);
+}
+
+fn encode_span(span: Span, bytes: &mut Vec<u8>) {
+    let handle = span
+        .source_text()
+        .and_then(|text| {
+            // a joined span starts with the marker of its first token
+            let handle = text.strip_prefix(MARKER)?;
+            let end = handle
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(handle.len());
+            handle[..end].parse().ok()
+        })
+        .unwrap_or(CALL_SITE);
+    bytes.extend_from_slice(&u32::to_le_bytes(handle));
+}
+
+fn encode_str(string: &str, bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(string.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(string.as_bytes());
+}
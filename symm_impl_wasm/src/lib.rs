@@ -0,0 +1,95 @@
+//! The macros of [`symm_impl`] compiled to WebAssembly, executed by
+//! [watt](https://github.com/dtolnay/watt) in `symm_impl_watt`.
+//!
+//! Every macro is exported under the name of its function in `symm_impl`, and
+//! expands through `symm_impl_core` the same way. Run `build.sh` to compile
+//! the module into `symm_impl_watt/symm_impl.wasm`.
+//!
+//! The module implements the interface of the runtime itself in [`abi`], over
+//! the `proc-macro2` of crates.io, so it is built from the published crates
+//! alone.
+//!
+//! [`symm_impl`]: https://docs.rs/symm_impl
+
+mod abi;
+
+use proc_macro2::TokenStream;
+
+#[no_mangle]
+pub extern "C" fn symmetric(attr: Box<TokenStream>, item: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetric_trait(
+    attr: Box<TokenStream>,
+    item: Box<TokenStream>,
+) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_trait(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn __check_strict(input: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::check_strict(*input))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetric_approx(input: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_approx(*input))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetric_dispatch(input: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_dispatch_derive(*input))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetrize_enum(input: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_symmetrize_enum(*input))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetric_visitor(input: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_visitor(*input))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetric_bench(input: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_bench(*input))
+}
+
+#[no_mangle]
+pub extern "C" fn check_symmetric(
+    attr: Box<TokenStream>,
+    item: Box<TokenStream>,
+) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_check(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn complement(attr: Box<TokenStream>, item: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_complement(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn commutative(attr: Box<TokenStream>, item: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_commutative(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn transitive(attr: Box<TokenStream>, item: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_transitive(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn with_reverse(attr: Box<TokenStream>, item: Box<TokenStream>) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_reverse(*attr, *item))
+}
+
+#[no_mangle]
+pub extern "C" fn symmetric_options(
+    attr: Box<TokenStream>,
+    item: Box<TokenStream>,
+) -> Box<TokenStream> {
+    Box::new(symm_impl_core::expand_symmetric_options(*attr, *item))
+}
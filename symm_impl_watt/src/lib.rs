@@ -0,0 +1,119 @@
+//! The macros of [`symm_impl`], precompiled to WebAssembly and executed by
+//! [watt](https://github.com/dtolnay/watt).
+//!
+//! The macros expand exactly as those of `symm_impl`, but building this crate
+//! only compiles the small watt runtime instead of `syn`, `quote` and
+//! `proc-macro2`, which cuts the time of clean builds, e.g. in CI. The
+//! generated code refers to `::symm_impl`, so the crate is depended on under
+//! that name:
+//! ```toml
+//! [dependencies]
+//! symm_impl = { package = "symm_impl_watt", version = "0.1.2" }
+//! ```
+//!
+//! The WebAssembly module runs without access to the environment or the file
+//! system, so the features configured through environment variables are not
+//! available: the crate-wide configuration file, `SYMM_IMPL_DUMP` and
+//! `SYMM_IMPL_INVENTORY`. Neither is `#[symmetric(instrument)]`, which
+//! requires the `tracing` feature of `symm_impl`.
+//!
+//! The module `symm_impl.wasm` is built by `symm_impl_wasm/build.sh` in the
+//! repository whenever `symm_impl_core` changes, which CI checks.
+//!
+//! [`symm_impl`]: https://docs.rs/symm_impl
+
+use proc_macro::TokenStream;
+use watt::WasmMacro;
+
+static MACRO: WasmMacro = WasmMacro::new(WASM);
+static WASM: &[u8] = include_bytes!("../symm_impl.wasm");
+
+/// See [`symm_impl::symmetric`](https://docs.rs/symm_impl/latest/symm_impl/attr.symmetric.html).
+#[proc_macro_attribute]
+pub fn symmetric(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("symmetric", attr, item)
+}
+
+/// Shorthand for [`macro@symmetric`], taking the same options.
+#[proc_macro_attribute]
+pub fn symm(attr: TokenStream, item: TokenStream) -> TokenStream {
+    symmetric(attr, item)
+}
+
+/// See [`symm_impl::symmetric_trait`](https://docs.rs/symm_impl/latest/symm_impl/attr.symmetric_trait.html).
+#[proc_macro_attribute]
+pub fn symmetric_trait(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("symmetric_trait", attr, item)
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __check_strict(input: TokenStream) -> TokenStream {
+    MACRO.proc_macro("__check_strict", input)
+}
+
+/// See [`symm_impl::symmetric_approx`](https://docs.rs/symm_impl/latest/symm_impl/macro.symmetric_approx.html).
+#[proc_macro]
+pub fn symmetric_approx(input: TokenStream) -> TokenStream {
+    MACRO.proc_macro("symmetric_approx", input)
+}
+
+/// See [`symm_impl::SymmetricDispatch`](https://docs.rs/symm_impl/latest/symm_impl/derive.SymmetricDispatch.html).
+#[proc_macro_derive(SymmetricDispatch, attributes(symm))]
+pub fn symmetric_dispatch(input: TokenStream) -> TokenStream {
+    MACRO.proc_macro_derive("symmetric_dispatch", input)
+}
+
+/// See [`symm_impl::symmetrize_enum`](https://docs.rs/symm_impl/latest/symm_impl/macro.symmetrize_enum.html).
+#[proc_macro]
+pub fn symmetrize_enum(input: TokenStream) -> TokenStream {
+    MACRO.proc_macro("symmetrize_enum", input)
+}
+
+/// See [`symm_impl::symmetric_visitor`](https://docs.rs/symm_impl/latest/symm_impl/macro.symmetric_visitor.html).
+#[proc_macro]
+pub fn symmetric_visitor(input: TokenStream) -> TokenStream {
+    MACRO.proc_macro("symmetric_visitor", input)
+}
+
+/// See [`symm_impl::symmetric_bench`](https://docs.rs/symm_impl/latest/symm_impl/macro.symmetric_bench.html).
+#[proc_macro]
+pub fn symmetric_bench(input: TokenStream) -> TokenStream {
+    MACRO.proc_macro("symmetric_bench", input)
+}
+
+/// See [`symm_impl::check_symmetric`](https://docs.rs/symm_impl/latest/symm_impl/attr.check_symmetric.html).
+#[proc_macro_attribute]
+pub fn check_symmetric(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("check_symmetric", attr, item)
+}
+
+/// See [`symm_impl::complement`](https://docs.rs/symm_impl/latest/symm_impl/attr.complement.html).
+#[proc_macro_attribute]
+pub fn complement(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("complement", attr, item)
+}
+
+/// See [`symm_impl::commutative`](https://docs.rs/symm_impl/latest/symm_impl/attr.commutative.html).
+#[proc_macro_attribute]
+pub fn commutative(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("commutative", attr, item)
+}
+
+/// See [`symm_impl::transitive`](https://docs.rs/symm_impl/latest/symm_impl/attr.transitive.html).
+#[proc_macro_attribute]
+pub fn transitive(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("transitive", attr, item)
+}
+
+/// See [`symm_impl::with_reverse`](https://docs.rs/symm_impl/latest/symm_impl/attr.with_reverse.html).
+#[proc_macro_attribute]
+pub fn with_reverse(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("with_reverse", attr, item)
+}
+
+/// See [`symm_impl::symmetric_options`](https://docs.rs/symm_impl/latest/symm_impl/attr.symmetric_options.html).
+#[proc_macro_attribute]
+pub fn symmetric_options(attr: TokenStream, item: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute("symmetric_options", attr, item)
+}
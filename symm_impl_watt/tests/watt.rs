@@ -0,0 +1,73 @@
+extern crate symm_impl_watt as symm_impl;
+
+use symm_impl::{commutative, symmetric};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Touch<B, C> {
+    fn touches_first(&self, _other: &B) -> bool {
+        false
+    }
+    fn touches_second(&self, _other: &C) -> bool {
+        false
+    }
+}
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> Self;
+}
+
+#[derive(Debug, PartialEq)]
+struct Point1D(f64);
+struct Disk(f64, f64);
+struct Line(f64);
+#[derive(Debug, PartialEq)]
+struct Count(i64);
+
+#[symmetric]
+impl Distance<Disk> for Point1D {
+    fn distance(&self, r#other: &Disk) -> f64 {
+        ((self.0 - r#other.0).abs() - r#other.1).max(0.0)
+    }
+}
+
+#[symmetric(other = "Disk")]
+#[symmetric(other = "Line")]
+impl Touch<Disk, Line> for Point1D {
+    fn touches_first(&self, other: &Disk) -> bool {
+        (self.0 - other.0).abs() <= other.1
+    }
+    fn touches_second(&self, other: &Line) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[commutative(fold = total, identity = Count(-2))]
+impl Merge<Count> for Count {
+    fn merge(self, other: Count) -> Count {
+        Count(self.0 + other.0 + 1)
+    }
+}
+
+#[test]
+fn test_watt_symmetric() {
+    let p = Point1D(5.0);
+    let d = Disk(1.0, 2.0);
+    assert_eq!(p.distance(&d), 2.0);
+    assert_eq!(d.distance(&p), 2.0);
+}
+
+#[test]
+fn test_watt_stacked() {
+    let p = Point1D(1.0);
+    assert!(Disk(0.0, 1.0).touches_first(&p));
+    assert!(Line(1.0).touches_second(&p));
+}
+
+#[test]
+fn test_watt_fold() {
+    assert_eq!(total(vec![Count(1), Count(2)]), Count(3));
+    assert_eq!(total(Vec::new()), Count(-2));
+}
@@ -0,0 +1,70 @@
+//! Attribute macros used to test how `#[symmetric]` stacks with other
+//! attribute macros.
+
+use quote::quote;
+use syn::{
+    parse_macro_input,
+    visit_mut::{self, VisitMut},
+    GenericArgument, ItemImpl, PathArguments, Type,
+};
+
+/// Replace every occurrence of a type by another one.
+struct ReplaceType<'a> {
+    from: &'a Type,
+    to: &'a Type,
+}
+
+impl VisitMut for ReplaceType<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let from = self.from;
+        if quote!(#ty).to_string() == quote!(#from).to_string() {
+            *ty = self.to.clone();
+        } else {
+            visit_mut::visit_type_mut(self, ty);
+        }
+    }
+}
+
+/// `#[also_for(Square)]` on `impl Trait<Disk> for Point2D` emits the
+/// implementation, along with a copy where `Disk` is replaced by `Square`.
+///
+/// Attributes of the implementation are kept on both copies.
+#[proc_macro_attribute]
+pub fn also_for(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let to = parse_macro_input!(attr as Type);
+    let original = parse_macro_input!(item as ItemImpl);
+    let (_, trait_path, _) = original.trait_.as_ref().expect("expected a trait impl");
+    let from = match &trait_path.segments.last().unwrap().arguments {
+        PathArguments::AngleBracketed(generics) => generics
+            .args
+            .iter()
+            .find_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .expect("expected a type argument"),
+        _ => panic!("expected a generic trait"),
+    };
+    let mut copy = original.clone();
+    ReplaceType {
+        from: &from,
+        to: &to,
+    }
+    .visit_item_impl_mut(&mut copy);
+    proc_macro::TokenStream::from(quote! {
+        #original
+        #copy
+    })
+}
+
+/// Does nothing, standing for an arbitrary attribute macro.
+#[proc_macro_attribute]
+pub fn identity(
+    _attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    item
+}
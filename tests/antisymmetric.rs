@@ -0,0 +1,65 @@
+use std::ops::{Neg, Sub};
+
+use symm_impl::symmetric;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Offset<T> {
+    dx: T,
+    dy: T,
+}
+
+impl<T: Neg<Output = T>> Neg for Offset<T> {
+    type Output = Offset<T>;
+
+    fn neg(self) -> Offset<T> {
+        Offset {
+            dx: -self.dx,
+            dy: -self.dy,
+        }
+    }
+}
+
+#[symmetric(antisymmetric)]
+impl<T: Sub<Output = T>> Sub<Offset<T>> for Point<T> {
+    type Output = Offset<T>;
+
+    fn sub(self, other: Offset<T>) -> Self::Output {
+        Offset {
+            dx: self.x - other.dx,
+            dy: self.y - other.dy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Feet(f64);
+
+#[symmetric(antisymmetric)]
+impl Sub<Feet> for Meters {
+    type Output = f64;
+
+    fn sub(self, other: Feet) -> f64 {
+        self.0 - other.0 * 0.3048
+    }
+}
+
+#[test]
+fn test_antisymmetric() {
+    let p = Point { x: 5, y: 4 };
+    let o = Offset { dx: 1, dy: 7 };
+    assert_eq!(o - p, -(p - o));
+    assert_eq!(o - p, Offset { dx: -4, dy: 3 });
+
+    let m = Meters(3.0);
+    let f = Feet(10.0);
+    assert_eq!(f - m, -(m - f));
+}
@@ -0,0 +1,125 @@
+use approx::{
+    abs_diff_eq, assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq,
+    AbsDiffEq, RelativeEq, UlpsEq,
+};
+use symm_impl::{symmetric, symmetric_approx};
+
+#[derive(Debug)]
+struct Point2D {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Debug)]
+struct Vector2D<T> {
+    x: T,
+    y: T,
+}
+
+#[symmetric]
+impl PartialEq<Vector2D<f32>> for Point2D {
+    fn eq(&self, other: &Vector2D<f32>) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl AbsDiffEq<Vector2D<f32>> for Point2D {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> f32 {
+        1e-3
+    }
+
+    fn abs_diff_eq(&self, other: &Vector2D<f32>, epsilon: f32) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon) && self.y.abs_diff_eq(&other.y, epsilon)
+    }
+}
+
+impl RelativeEq<Vector2D<f32>> for Point2D {
+    fn default_max_relative() -> f32 {
+        1e-3
+    }
+
+    fn relative_eq(&self, other: &Vector2D<f32>, epsilon: f32, max_relative: f32) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative)
+            && self.y.relative_eq(&other.y, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq<Vector2D<f32>> for Point2D {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Vector2D<f32>, epsilon: f32, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon, max_ulps) && self.y.ulps_eq(&other.y, epsilon, max_ulps)
+    }
+}
+
+symmetric_approx!(Point2D, Vector2D<f32>);
+
+#[derive(Debug)]
+struct Wrapper<T>(T);
+
+impl<T: PartialEq> PartialEq<Vector2D<T>> for Wrapper<T> {
+    fn eq(&self, other: &Vector2D<T>) -> bool {
+        self.0 == other.x && self.0 == other.y
+    }
+}
+
+impl<T: PartialEq> PartialEq<Wrapper<T>> for Vector2D<T> {
+    fn eq(&self, other: &Wrapper<T>) -> bool {
+        other == self
+    }
+}
+
+impl<T: AbsDiffEq> AbsDiffEq<Vector2D<T>> for Wrapper<T>
+where
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Vector2D<T>, epsilon: T::Epsilon) -> bool {
+        self.0.abs_diff_eq(&other.x, epsilon) && self.0.abs_diff_eq(&other.y, epsilon)
+    }
+}
+
+symmetric_approx!(impl<T: AbsDiffEq> Wrapper<T>, Vector2D<T> where T::Epsilon: Copy; AbsDiffEq);
+
+#[test]
+fn test_approx_preset() {
+    let p = Point2D { x: 1.0, y: 2.0 };
+    let v = Vector2D {
+        x: 1.0005,
+        y: 1.9995,
+    };
+    assert_eq!(
+        <Vector2D<f32> as AbsDiffEq<Point2D>>::default_epsilon(),
+        1e-3
+    );
+    assert_eq!(
+        <Vector2D<f32> as RelativeEq<Point2D>>::default_max_relative(),
+        1e-3
+    );
+    assert_eq!(<Vector2D<f32> as UlpsEq<Point2D>>::default_max_ulps(), 4);
+    assert_abs_diff_eq!(p, v);
+    assert_abs_diff_eq!(v, p);
+    assert_relative_eq!(v, p);
+    assert_relative_ne!(v, p, max_relative = 0.0, epsilon = 0.0);
+    assert_ulps_eq!(v, p);
+}
+
+#[test]
+fn test_approx_preset_generic() {
+    let w = Wrapper(1.0_f64);
+    let v = Vector2D {
+        x: 1.0,
+        y: 1.0 + 1e-12,
+    };
+    assert!(abs_diff_eq!(v, w, epsilon = 1e-9));
+    assert!(!abs_diff_eq!(v, w, epsilon = 1e-15));
+}
@@ -0,0 +1,65 @@
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use async_trait::async_trait;
+use symm_impl::symmetric;
+
+#[async_trait]
+trait Distance<Other> {
+    async fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[async_trait]
+impl Distance<Point2D> for Point2D {
+    async fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[symmetric]
+#[async_trait]
+impl Distance<Disk> for Point2D {
+    async fn distance(&self, other: &Disk) -> f64 {
+        let p_diff = self.distance(&other.center).await;
+        if p_diff.le(&other.radius) {
+            0.0_f64
+        } else {
+            p_diff - other.radius
+        }
+    }
+}
+
+/// Poll a future which never waits to completion.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is not ready"),
+    }
+}
+
+#[test]
+fn test_async_trait() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(block_on(p.distance(&c)), block_on(c.distance(&p)));
+}
@@ -0,0 +1,29 @@
+use std::panic::Location;
+
+use symm_impl::symmetric;
+
+trait Locate<Other> {
+    fn locate(&self, other: &Other) -> u32;
+}
+
+struct A {}
+
+struct B {}
+
+#[symmetric(copy_attrs(allow), skip_attrs(track_caller))]
+impl Locate<B> for A {
+    #[track_caller]
+    #[allow(unused_variables)]
+    fn locate(&self, _other: &B) -> u32 {
+        Location::caller().line()
+    }
+}
+
+#[test]
+fn test_skip_attrs() {
+    let a = A {};
+    let b = B {};
+    let line = line!();
+    assert_eq!(a.locate(&b), line + 1);
+    assert_ne!(b.locate(&a), line + 2);
+}
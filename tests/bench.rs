@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use criterion::Criterion;
+use symm_impl::{symmetric, symmetric_bench};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> f64;
+}
+
+#[derive(Clone)]
+struct Point(f64);
+
+#[derive(Clone)]
+struct Disk(f64, f64);
+
+#[symmetric]
+impl Distance<Disk> for Point {
+    fn distance(&self, other: &Disk) -> f64 {
+        ((self.0 - other.0).abs() - other.1).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Merge<Disk> for Point {
+    fn merge(self, other: Disk) -> f64 {
+        self.0.max(other.0 + other.1)
+    }
+}
+
+symmetric_bench! {
+    fn bench_distance = Distance::distance(&Point(3.0), &Disk(0.0, 1.0));
+    fn bench_merge = Merge::merge(Point(3.0), Disk(0.0, 1.0));
+}
+
+#[test]
+fn test_bench() {
+    let mut criterion = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(1))
+        .measurement_time(Duration::from_millis(10))
+        .without_plots();
+    bench_distance(&mut criterion);
+    bench_merge(&mut criterion);
+}
@@ -0,0 +1,46 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A<T> {
+    a: T,
+}
+
+struct B {
+    b: i32,
+}
+
+#[symmetric(bound = "T: Clone", bound = "T: Default")]
+impl<T: Into<i32> + Copy> Op<B> for A<T> {
+    fn op(&self, other: &B) -> i32 {
+        self.a.into() + other.b
+    }
+}
+
+#[derive(Clone, Copy)]
+struct NoDefault(i32);
+
+impl From<NoDefault> for i32 {
+    fn from(value: NoDefault) -> i32 {
+        value.0
+    }
+}
+
+// would conflict with the mirror if the additional bound was not added
+impl Op<A<NoDefault>> for B {
+    fn op(&self, other: &A<NoDefault>) -> i32 {
+        other.a.0 - self.b
+    }
+}
+
+#[test]
+fn test_bound() {
+    let a = A { a: 1 };
+    let b = B { b: 2 };
+    assert_eq!(a.op(&b), b.op(&a));
+    let a = A { a: NoDefault(1) };
+    assert_eq!(a.op(&b), 3);
+    assert_eq!(b.op(&a), -1);
+}
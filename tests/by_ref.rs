@@ -0,0 +1,109 @@
+use std::ops::Add;
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric(by_ref)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Feet(f64);
+
+#[symmetric(by_ref)]
+impl Add<Feet> for Meters {
+    type Output = Meters;
+
+    fn add(self, mut other: Feet) -> Meters {
+        other.0 *= 0.3048;
+        Meters(self.0 + other.0)
+    }
+}
+
+trait Absorb<Other> {
+    fn absorb(&mut self, other: &mut Other) -> f64;
+    fn gap(&self, other: &Other) -> f64;
+    fn pair(self, other: Other) -> (f64, f64);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tank(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cup(f64);
+
+// the references to the other operand are mutable
+#[symmetric(by_ref)]
+impl Absorb<Cup> for Tank {
+    fn absorb(&mut self, other: &mut Cup) -> f64 {
+        self.0 += other.0;
+        other.0 = 0.0;
+        self.0
+    }
+
+    fn gap(&self, other: &Cup) -> f64 {
+        self.0 - other.0
+    }
+
+    fn pair(self, other: Cup) -> (f64, f64) {
+        (self.0, other.0)
+    }
+}
+
+fn total<T: Distance<U>, U>(from: &T, to: U) -> f64 {
+    from.distance(&to)
+}
+
+#[test]
+fn test_by_ref() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    assert_eq!(total(&p, &d), 3.0);
+    assert_eq!(total(&d, &p), 3.0);
+    assert_eq!(total(&p, d), 3.0);
+
+    let f = Feet(10.0);
+    assert_eq!(Meters(1.0) + &f, Meters(1.0) + f.clone());
+    assert_eq!(Add::add(f.clone(), &Meters(1.0)), Meters(1.0) + f);
+}
+
+#[test]
+fn test_by_ref_mutable() {
+    let mut tank = Tank(5.0);
+    let mut cup = Cup(2.0);
+    assert_eq!(tank.absorb(&mut &mut cup), 7.0);
+    assert_eq!(cup, Cup(0.0));
+
+    cup = Cup(1.0);
+    assert_eq!(cup.absorb(&mut &mut tank), 8.0);
+    assert_eq!((tank, cup), (Tank(8.0), Cup(0.0)));
+
+    assert_eq!(tank.gap(&&mut cup), 8.0);
+    assert_eq!(cup.gap(&&mut tank), 8.0);
+    assert_eq!(tank.pair(&mut cup), (8.0, 0.0));
+    assert_eq!(cup.pair(&mut tank), (8.0, 0.0));
+}
@@ -0,0 +1,23 @@
+use symm_impl::symmetric;
+
+trait Overlap<Other> {
+    fn overlap(&self, other: &Other) -> usize;
+}
+
+#[symmetric(call = "method")]
+impl Overlap<&str> for String {
+    fn overlap(&self, other: &&str) -> usize {
+        self.chars()
+            .zip(other.chars())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+}
+
+#[test]
+fn test_method_call_style() {
+    let a = String::from("symmetric");
+    let b = "symmetry";
+    assert_eq!(a.overlap(&b), 7);
+    assert_eq!(b.overlap(&a), 7);
+}
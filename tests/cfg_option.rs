@@ -0,0 +1,39 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {}
+
+struct B {}
+
+struct C {}
+
+#[symmetric(cfg(all()))]
+impl Op<B> for A {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+#[symmetric(cfg(any()))]
+impl Op<C> for A {
+    fn op(&self, _other: &C) -> i32 {
+        2
+    }
+}
+
+// would conflict with the mirror if it was not compiled out
+impl Op<A> for C {
+    fn op(&self, _other: &A) -> i32 {
+        3
+    }
+}
+
+#[test]
+fn test_cfg_option() {
+    assert_eq!(A {}.op(&B {}), B {}.op(&A {}));
+    assert_eq!(A {}.op(&C {}), 2);
+    assert_eq!(C {}.op(&A {}), 3);
+}
@@ -0,0 +1,29 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D(f64);
+
+struct Disk(f64, f64);
+
+#[symmetric(check_only)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - other.1
+    }
+}
+
+// would conflict with the mirror if it was emitted
+impl Distance<Point2D> for Disk {
+    fn distance(&self, other: &Point2D) -> f64 {
+        (self.0 - other.0).abs() - self.1
+    }
+}
+
+#[test]
+fn test_check_only() {
+    assert_eq!(Point2D(5.0).distance(&Disk(1.0, 2.0)), 2.0);
+    assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+}
@@ -0,0 +1,265 @@
+use std::{panic, thread};
+
+use symm_impl::check_symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Label<Other> {
+    fn label(&self, other: &Other) -> &str;
+}
+
+trait Ratio<Other> {
+    fn ratio(&self, other: &Other) -> Option<f64>;
+}
+
+trait Cover<Other> {
+    fn cover(&self, other: &Other) -> bool;
+}
+
+trait Overlap<Other> {
+    fn overlap(&self, other: &Other) -> bool;
+    fn touches(&self, other: &Other) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Square {
+    center: Point2D,
+    side: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
+
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let p_diff = self.distance(&other.center);
+        if p_diff.le(&other.radius) {
+            0.0_f64
+        } else {
+            p_diff - other.radius
+        }
+    }
+}
+
+fn close(lhs: &f64, rhs: &f64) -> bool {
+    (lhs - rhs).abs() < 1e-9
+}
+
+#[check_symmetric(eq = close)]
+impl Distance<Point2D> for Disk {
+    fn distance(&self, point: &Point2D) -> f64 {
+        let squared = (self.center.x - point.x).powi(2) + (self.center.y - point.y).powi(2);
+        if squared <= self.radius * self.radius {
+            return 0.0;
+        }
+        squared.sqrt() - self.radius
+    }
+}
+
+impl Overlap<Square> for Point2D {
+    fn overlap(&self, other: &Square) -> bool {
+        (self.x - other.center.x).abs() <= other.side / 2.0
+            && (self.y - other.center.y).abs() <= other.side / 2.0
+    }
+    fn touches(&self, other: &Square) -> bool {
+        (self.x - other.center.x).abs() == other.side / 2.0
+            || (self.y - other.center.y).abs() == other.side / 2.0
+    }
+}
+
+// drifted from `Overlap<Square> for Point2D`: the boundary is not part of the
+// square
+#[check_symmetric]
+impl Overlap<Point2D> for Square {
+    fn overlap(&self, other: &Point2D) -> bool {
+        (other.x - self.center.x).abs() < self.side / 2.0
+            && (other.y - self.center.y).abs() < self.side / 2.0
+    }
+    fn touches(&self, other: &Point2D) -> bool {
+        other.touches(self)
+    }
+}
+
+impl Cover<Square> for Point2D {
+    fn cover(&self, other: &Square) -> bool {
+        (self.x - other.center.x).abs() <= other.side / 2.0
+            && (self.y - other.center.y).abs() <= other.side / 2.0
+    }
+}
+
+// drifted from `Cover<Square> for Point2D`: the boundary is not covered
+#[check_symmetric]
+impl Cover<Point2D> for Square {
+    fn cover(&self, other: &Point2D) -> bool {
+        (other.x - self.center.x).abs() < self.side / 2.0
+            && (other.y - self.center.y).abs() < self.side / 2.0
+    }
+}
+
+// both directions are checked against each other, and return a reference
+// with an elided lifetime
+#[check_symmetric]
+impl Label<Disk> for Point2D {
+    fn label(&self, other: &Disk) -> &str {
+        if self.distance(other) == 0.0 {
+            return "inside";
+        }
+        "outside"
+    }
+}
+
+#[check_symmetric]
+impl Label<Point2D> for Disk {
+    fn label(&self, other: &Point2D) -> &str {
+        for _ in 0..2 {
+            if other.distance(self) == 0.0 {
+                return "inside";
+            }
+        }
+        "outside"
+    }
+}
+
+fn positive(value: f64) -> Option<f64> {
+    Some(value).filter(|value| *value > 0.0)
+}
+
+#[check_symmetric]
+impl Ratio<Disk> for Point2D {
+    fn ratio(&self, other: &Disk) -> Option<f64> {
+        Some(self.distance(&other.center) / positive(other.radius)?)
+    }
+}
+
+#[check_symmetric]
+impl Ratio<Point2D> for Disk {
+    fn ratio(&self, other: &Point2D) -> Option<f64> {
+        let radius = positive(self.radius)?;
+        Some(other.distance(&self.center) / radius)
+    }
+}
+
+trait Inside<Other> {
+    fn inside(&self, other: &Other) -> bool;
+}
+
+impl Inside<Square> for Point2D {
+    fn inside(&self, other: &Square) -> bool {
+        assert!(other.side >= 0.0, "negative side");
+        (self.x - other.center.x).abs() <= other.side / 2.0
+            && (self.y - other.center.y).abs() <= other.side / 2.0
+    }
+}
+
+// drifted from `Inside<Square> for Point2D`: the boundary is not inside
+#[check_symmetric]
+impl Inside<Point2D> for Square {
+    fn inside(&self, other: &Point2D) -> bool {
+        (other.x - self.center.x).abs() < self.side / 2.0
+            && (other.y - self.center.y).abs() < self.side / 2.0
+    }
+}
+
+#[test]
+fn test_check_symmetric() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: 1.0 },
+        radius: 3.0,
+    };
+    assert_eq!(c.distance(&p), 2.0);
+    assert_eq!(c.distance(&c.center), 0.0);
+
+    let s = Square {
+        center: Point2D { x: 0.0, y: 0.0 },
+        side: 2.0,
+    };
+    assert!(s.touches(&Point2D { x: 1.0, y: 0.5 }));
+    assert!(s.overlap(&Point2D { x: 0.5, y: 0.5 }));
+}
+
+#[test]
+fn test_check_symmetric_early_exit() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: 1.0 },
+        radius: 3.0,
+    };
+    assert_eq!(c.label(&p), "outside");
+    assert_eq!(p.label(&c), "outside");
+    assert_eq!(c.label(&c.center), "inside");
+    assert_eq!(c.center.label(&c), "inside");
+
+    assert_eq!(c.ratio(&p), Some(5.0 / 3.0));
+    assert_eq!(p.ratio(&c), Some(5.0 / 3.0));
+    let point = Disk { radius: 0.0, ..c };
+    assert_eq!(point.ratio(&p), None);
+    assert_eq!(p.ratio(&point), None);
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(
+        expected = "`overlap` of `impl Overlap<Point2D> for Square` disagrees with \
+                    `impl Overlap<Square> for Point2D`"
+    )
+)]
+fn test_check_symmetric_drift() {
+    let s = Square {
+        center: Point2D { x: 0.0, y: 0.0 },
+        side: 2.0,
+    };
+    // the boundary is where the implementations disagree
+    assert!(!s.overlap(&Point2D { x: 1.0, y: 0.5 }));
+}
+
+#[test]
+fn test_check_symmetric_threads() {
+    let s = Square {
+        center: Point2D { x: 0.0, y: 0.0 },
+        side: 2.0,
+    };
+    assert!(s.cover(&Point2D { x: 0.5, y: 0.5 }));
+    // checks are not limited to the thread of the first one
+    let boundary = Point2D { x: 1.0, y: 0.5 };
+    let caught = thread::spawn(move || s.cover(&boundary)).join().is_err();
+    assert_eq!(caught, cfg!(debug_assertions));
+}
+
+#[test]
+fn test_check_symmetric_panic() {
+    let negative = Square {
+        center: Point2D { x: 0.0, y: 0.0 },
+        side: -2.0,
+    };
+    let origin = Point2D { x: 0.0, y: 0.0 };
+    let panicked = panic::catch_unwind(|| negative.inside(&origin)).is_err();
+    assert_eq!(panicked, cfg!(debug_assertions));
+
+    // the check is not disabled by the panic of the other direction
+    let s = Square {
+        center: Point2D { x: 0.0, y: 0.0 },
+        side: 2.0,
+    };
+    let boundary = Point2D { x: 1.0, y: 0.5 };
+    let caught = panic::catch_unwind(|| s.inside(&boundary)).is_err();
+    assert_eq!(caught, cfg!(debug_assertions));
+}
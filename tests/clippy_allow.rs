@@ -0,0 +1,36 @@
+#![deny(clippy::pedantic)]
+
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(self, other: Other) -> i32;
+}
+
+#[derive(Clone, Copy)]
+struct A {}
+
+#[derive(Clone, Copy)]
+struct B {}
+
+struct C {}
+
+#[symmetric]
+impl Op<B> for A {
+    fn op(self, _other: B) -> i32 {
+        1
+    }
+}
+
+#[symmetric(clippy_allow(needless_pass_by_value))]
+impl Op<C> for A {
+    #[allow(clippy::needless_pass_by_value)]
+    fn op(self, _other: C) -> i32 {
+        2
+    }
+}
+
+#[test]
+fn test_clippy_allow() {
+    assert_eq!(A {}.op(B {}), B {}.op(A {}));
+    assert_eq!(A {}.op(C {}), C {}.op(A {}));
+}
@@ -0,0 +1,84 @@
+use std::collections::{BTreeSet, HashSet};
+
+use symm_impl::commutative;
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> Self;
+}
+
+trait Union<Other> {
+    fn union(&self, other: &Other) -> Self;
+    fn is_subset(&self, other: &Other) -> bool;
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Bounds(i32, i32);
+
+#[derive(Debug, PartialEq)]
+struct Set<T>(BTreeSet<T>);
+
+#[commutative(fold = pub combine_all)]
+impl Merge<Bounds> for Bounds {
+    fn merge(self, other: Bounds) -> Bounds {
+        Bounds(self.0.min(other.0), self.1.max(other.1))
+    }
+}
+
+#[commutative(fold = union_all, method = union)]
+impl<T: Ord + Clone> Union<Set<T>> for Set<T> {
+    fn union(&self, other: &Set<T>) -> Self {
+        Set(self.0.union(&other.0).cloned().collect())
+    }
+
+    fn is_subset(&self, other: &Set<T>) -> bool {
+        self.0.is_subset(&other.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Count(u32);
+
+#[derive(Debug, Default, PartialEq)]
+struct Product(u32);
+
+#[commutative(fold = total, identity = Count(0), default)]
+impl Merge<Count> for Count {
+    fn merge(self, other: Count) -> Count {
+        Count(self.0 + other.0)
+    }
+}
+
+#[commutative(fold = product, identity = Product(1))]
+impl Merge<Product> for Product {
+    fn merge(self, other: Product) -> Product {
+        Product(self.0 * other.0)
+    }
+}
+
+#[test]
+fn test_fold() {
+    let bounds = vec![Bounds(1, 2), Bounds(-1, 0), Bounds(3, 4)];
+    assert_eq!(combine_all(bounds), Some(Bounds(-1, 4)));
+    assert_eq!(combine_all(None), None);
+    let unordered: HashSet<_> = vec![Bounds(1, 2), Bounds(-1, 0)].into_iter().collect();
+    assert_eq!(combine_all(unordered), Some(Bounds(-1, 2)));
+}
+
+#[test]
+fn test_fold_generic() {
+    let sets = [Set(BTreeSet::from([1, 2])), Set(BTreeSet::from([2, 3]))];
+    let union = union_all(sets).unwrap();
+    assert_eq!(union, Set(BTreeSet::from([1, 2, 3])));
+    assert!(Set(BTreeSet::from([1])).is_subset(&union));
+}
+
+#[test]
+fn test_fold_identity() {
+    assert_eq!(total(vec![Count(1), Count(2)]), Count(3));
+    assert_eq!(total(None), Count(0));
+    assert_eq!(Count::default(), Count(0));
+    assert_eq!(product(vec![Product(2), Product(3)]), Product(6));
+    assert_eq!(product(None), Product(1));
+    // `Default` is only implemented if requested
+    assert_eq!(Product::default(), Product(0));
+}
@@ -0,0 +1,68 @@
+use symm_impl::{complement, symmetric};
+
+trait Intersects<Other> {
+    fn intersects(&self, other: &Other) -> bool;
+}
+
+trait Disjoint<Other> {
+    fn disjoint(&self, other: &Other) -> bool;
+}
+
+trait Contains<Other> {
+    fn intersects(&self, other: &Other) -> bool;
+}
+
+struct Point2D(f64);
+
+struct Disk(f64, f64);
+
+struct Interval<T>(T, T);
+
+#[complement(Disjoint, intersects = disjoint)]
+#[symmetric]
+impl Intersects<Disk> for Point2D {
+    fn intersects(&self, other: &Disk) -> bool {
+        (self.0 - other.0).abs() <= other.1
+    }
+}
+
+// the complementary trait may be spelled with its arguments, and have the
+// same method names
+#[complement(Contains<Interval<T>>)]
+#[symmetric]
+impl<T: Copy + Into<f64>> Intersects<Interval<T>> for Point2D {
+    fn intersects(&self, other: &Interval<T>) -> bool {
+        other.0.into() <= self.0 && self.0 <= other.1.into()
+    }
+}
+
+// both orders are the same implementation
+#[complement(Disjoint, intersects = disjoint)]
+impl Intersects<Disk> for Disk {
+    fn intersects(&self, other: &Disk) -> bool {
+        (self.0 - other.0).abs() <= self.1 + other.1
+    }
+}
+
+#[test]
+fn test_complement() {
+    assert!(Intersects::intersects(&Point2D(2.0), &Disk(1.0, 2.0)));
+    assert!(!Point2D(2.0).disjoint(&Disk(1.0, 2.0)));
+    assert!(Point2D(5.0).disjoint(&Disk(1.0, 2.0)));
+    assert!(Disk(1.0, 2.0).disjoint(&Point2D(5.0)));
+    assert!(!Disk(1.0, 2.0).disjoint(&Point2D(2.0)));
+}
+
+#[test]
+fn test_complement_generic() {
+    let interval = Interval(1_u8, 2);
+    assert!(!Contains::intersects(&Point2D(1.5), &interval));
+    assert!(Contains::intersects(&Point2D(3.0), &interval));
+    assert!(Contains::intersects(&interval, &Point2D(3.0)));
+}
+
+#[test]
+fn test_complement_same_type() {
+    assert!(!Disk(1.0, 2.0).disjoint(&Disk(4.0, 2.0)));
+    assert!(Disk(1.0, 2.0).disjoint(&Disk(6.0, 2.0)));
+}
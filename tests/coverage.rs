@@ -0,0 +1,22 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D(f64);
+struct Disk(f64, f64);
+
+#[symmetric(coverage_off)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - other.1
+    }
+}
+
+#[test]
+fn test_coverage_off() {
+    assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+}
@@ -0,0 +1,25 @@
+mod reexport {
+    pub use symm_impl::*;
+}
+
+use reexport::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {}
+
+struct B {}
+
+#[symmetric(crate = crate::reexport)]
+impl Op<B> for A {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+#[test]
+fn test_crate_path() {
+    assert_eq!(A {}.op(&B {}), B {}.op(&A {}));
+}
@@ -0,0 +1,91 @@
+use symm_impl::symmetric;
+use symm_impl_test_macros::{also_for, identity};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+struct Square {
+    center: Point2D,
+    radius: f64,
+}
+
+struct Ring {
+    center: Point2D,
+    radius: f64,
+}
+
+struct Ellipse {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+// mirrored after `also_for` duplicated the implementation, so both copies are
+// mirrored
+#[symmetric(defer, inline = "always")]
+#[also_for(Square)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+}
+
+// attribute macros above `#[symmetric]` expand first, so both copies are
+// mirrored too
+#[also_for(Ring)]
+#[symmetric]
+#[identity]
+#[cfg_attr(any(), identity)]
+impl Distance<Ellipse> for Point2D {
+    fn distance(&self, other: &Ellipse) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+}
+
+#[test]
+fn test_defer() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), c.distance(&p));
+    let s = Square {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&s), s.distance(&p));
+}
+
+#[test]
+fn test_stacked_above() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let r = Ring {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&r), r.distance(&p));
+    let e = Ellipse {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&e), e.distance(&p));
+}
@@ -0,0 +1,54 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Overlap<Other> {
+    fn overlap(&self, other: &Other) -> bool;
+    fn touch(&self, other: &Other) -> bool {
+        self.overlap(other)
+    }
+}
+
+struct Point2D(f64);
+struct Disk(f64, f64);
+
+mod kernels {
+    use super::{Disk, Point2D};
+
+    pub fn point_disk_distance(point: &Point2D, disk: &Disk) -> f64 {
+        (point.0 - disk.0).abs() - disk.1
+    }
+
+    pub fn point_disk_overlap(point: &Point2D, disk: &Disk) -> bool {
+        point_disk_distance(point, disk) <= 0.0
+    }
+}
+
+#[symmetric(delegate_via = kernels::point_disk_distance)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        kernels::point_disk_distance(self, other)
+    }
+}
+
+#[symmetric(delegate_via = kernels::point_disk_overlap, only(overlap))]
+impl Overlap<Disk> for Point2D {
+    fn overlap(&self, other: &Disk) -> bool {
+        kernels::point_disk_overlap(self, other)
+    }
+    fn touch(&self, other: &Disk) -> bool {
+        kernels::point_disk_distance(self, other) == 0.0
+    }
+}
+
+#[test]
+fn test_delegate_via() {
+    assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+    assert!(Disk(1.0, 2.0).overlap(&Point2D(2.0)));
+    assert!(!Disk(1.0, 2.0).overlap(&Point2D(4.0)));
+    // left to the default of the trait
+    assert!(Disk(1.0, 2.0).touch(&Point2D(2.0)));
+    assert!(Point2D(3.0).touch(&Disk(1.0, 2.0)));
+}
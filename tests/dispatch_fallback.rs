@@ -0,0 +1,125 @@
+use symm_impl::{symmetric, symmetrize_enum, SymmetricDispatch};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment(f64, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Polygon(f64, f64);
+
+impl Distance<Point> for Point {
+    fn distance(&self, other: &Point) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+impl Distance<Segment> for Segment {
+    fn distance(&self, other: &Segment) -> f64 {
+        (other.0 - self.1).max(self.0 - other.1).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Distance<Segment> for Point {
+    fn distance(&self, other: &Segment) -> f64 {
+        (other.0 - self.0).max(self.0 - other.1).max(0.0)
+    }
+}
+
+// the implementations with `Polygon` are not written yet
+mod approximate {
+    use super::Shape;
+
+    fn bounds(shape: &Shape) -> (f64, f64) {
+        match shape {
+            Shape::Point(point) => (point.0, point.0),
+            Shape::Segment(segment) => (segment.0, segment.1),
+            Shape::Polygon(polygon) => (polygon.0, polygon.1),
+            Shape::Empty => (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    }
+
+    pub fn distance(lhs: &Shape, rhs: &Shape) -> f64 {
+        let (lhs, rhs) = (bounds(lhs), bounds(rhs));
+        (rhs.0 - lhs.1).max(lhs.0 - rhs.1).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, SymmetricDispatch)]
+#[symm(fallback = approximate)]
+#[symm(impl Distance<Shape> {
+    fn distance(&self, other: &Shape) -> f64;
+})]
+enum Shape {
+    Point(Point),
+    Segment(Segment),
+    Polygon(Polygon),
+    #[symm(skip)]
+    Empty,
+}
+
+#[test]
+fn test_fallback() {
+    let point = Shape::Point(Point(5.0));
+    let segment = Shape::Segment(Segment(0.0, 2.0));
+    let polygon = Shape::Polygon(Polygon(8.0, 9.0));
+    assert_eq!(point.distance(&segment), 3.0);
+    assert_eq!(segment.distance(&point), 3.0);
+    assert_eq!(point.distance(&polygon), 3.0);
+    assert_eq!(polygon.distance(&segment), 6.0);
+    assert_eq!(polygon.distance(&polygon), 0.0);
+    assert_eq!(Shape::Empty.distance(&point), 0.0);
+}
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> (f64, f64);
+}
+
+impl Merge<Point> for Point {
+    fn merge(self, other: Point) -> (f64, f64) {
+        (self.0.min(other.0), self.0.max(other.0))
+    }
+}
+
+mod hull {
+    use super::Figure;
+
+    pub fn merge(lhs: Figure, rhs: Figure) -> (f64, f64) {
+        match (lhs, rhs) {
+            (Figure::Point(point), Figure::Segment(segment))
+            | (Figure::Segment(segment), Figure::Point(point)) => {
+                (point.0.min(segment.0), point.0.max(segment.1))
+            }
+            _ => (0.0, 0.0),
+        }
+    }
+}
+
+symmetrize_enum! {
+    #[symm(fallback = hull)]
+    #[derive(Debug, Clone, Copy)]
+    enum Figure {
+        Point(Point),
+        Segment(Segment),
+    }
+
+    impl Merge<Figure> {
+        fn merge(self, other: Figure) -> (f64, f64);
+    }
+}
+
+#[test]
+fn test_fallback_by_value() {
+    let point = Figure::from(Point(5.0));
+    let segment = Figure::from(Segment(0.0, 2.0));
+    assert_eq!(point.merge(Figure::from(Point(1.0))), (1.0, 5.0));
+    assert_eq!(point.merge(segment), (0.0, 5.0));
+    assert_eq!(segment.merge(point), (0.0, 5.0));
+    assert_eq!(segment.merge(segment), (0.0, 0.0));
+}
@@ -0,0 +1,96 @@
+use symm_impl::{symmetric, symmetrize_enum, SymmetricDispatch};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment(f64, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Label(&'static str);
+
+impl Distance<Point> for Point {
+    fn distance(&self, other: &Point) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+impl Distance<Segment> for Segment {
+    fn distance(&self, other: &Segment) -> f64 {
+        (other.0 - self.1).max(self.0 - other.1).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Distance<Segment> for Point {
+    fn distance(&self, other: &Segment) -> f64 {
+        (other.0 - self.0).max(self.0 - other.1).max(0.0)
+    }
+}
+
+symmetrize_enum! {
+    #[derive(Debug, Clone, Copy)]
+    enum Shape {
+        Point(Point),
+        Segment(Segment),
+        #[symm(skip)]
+        Label(Label),
+        #[symm(skip)]
+        Empty,
+    }
+
+    impl Distance<Shape> {
+        fn distance(&self, other: &Shape) -> f64 {
+            f64::INFINITY
+        }
+    }
+}
+
+#[test]
+fn test_skip() {
+    let point = Shape::from(Point(5.0));
+    let segment = Shape::from(Segment(0.0, 2.0));
+    let label = Shape::from(Label("origin"));
+    assert_eq!(point.distance(&segment), 3.0);
+    assert_eq!(segment.distance(&point), 3.0);
+    assert_eq!(point.distance(&label), f64::INFINITY);
+    assert_eq!(label.distance(&segment), f64::INFINITY);
+    assert_eq!(Shape::Empty.distance(&Shape::Empty), f64::INFINITY);
+    assert!(matches!(label, Shape::Label(Label("origin"))));
+}
+
+mod sentinel {
+    use super::Figure;
+
+    pub fn distance(lhs: &Figure, rhs: &Figure) -> f64 {
+        match (lhs, rhs) {
+            (Figure::Origin, Figure::Point(point)) | (Figure::Point(point), Figure::Origin) => {
+                point.0.abs()
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, SymmetricDispatch)]
+#[symm(impl Distance<Figure> {
+    fn distance(&self, other: &Figure) -> f64;
+})]
+enum Figure {
+    Point(Point),
+    #[symm(with = sentinel)]
+    Origin,
+}
+
+#[test]
+fn test_with() {
+    let point = Figure::Point(Point(-3.0));
+    assert_eq!(point.distance(&Figure::Point(Point(1.0))), 4.0);
+    assert_eq!(point.distance(&Figure::Origin), 3.0);
+    assert_eq!(Figure::Origin.distance(&point), 3.0);
+    assert_eq!(Figure::Origin.distance(&Figure::Origin), 0.0);
+}
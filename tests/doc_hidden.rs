@@ -0,0 +1,21 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {}
+
+struct B {}
+
+#[symmetric(doc_hidden)]
+impl Op<B> for A {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+#[test]
+fn test_doc_hidden() {
+    assert_eq!(A {}.op(&B {}), B {}.op(&A {}));
+}
@@ -0,0 +1,58 @@
+use symm_impl::symmetric;
+
+trait Distance<Other: ?Sized> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Shape {
+    fn center(&self) -> f64;
+}
+
+struct Disk(f64, f64);
+
+impl Shape for Disk {
+    fn center(&self) -> f64 {
+        self.0
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for dyn Shape {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.center() - other.0).abs() - other.1
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for dyn Shape + Send {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.center() - other.0).abs()
+    }
+}
+
+trait Overlap<Other: ?Sized> {
+    fn overlap(&self, other: &Other) -> bool;
+}
+
+#[symmetric(call = "method")]
+impl<'a> Overlap<Disk> for dyn Shape + 'a {
+    fn overlap(&self, other: &Disk) -> bool {
+        (self.center() - other.0).abs() <= other.1
+    }
+}
+
+#[test]
+fn test_dyn_self() {
+    let shape: &dyn Shape = &Disk(5.0, 1.0);
+    assert_eq!(Disk(1.0, 2.0).distance(shape), 2.0);
+    let shape: &(dyn Shape + Send) = &Disk(5.0, 1.0);
+    assert_eq!(Disk(1.0, 2.0).distance(shape), 4.0);
+}
+
+#[test]
+fn test_dyn_self_lifetime() {
+    let disk = Disk(2.0, 1.0);
+    let shape: &dyn Shape = &disk;
+    assert!(Disk(1.0, 2.0).overlap(shape));
+    assert!(!Disk(5.0, 0.5).overlap(shape));
+}
@@ -0,0 +1,43 @@
+use symm_impl::symmetric;
+
+pub trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+pub trait Shape {
+    fn center(&self) -> f64;
+}
+
+pub struct Point2D(f64);
+
+#[symmetric(export = mirror_distance)]
+impl<T: Shape> crate::Distance<T> for Point2D {
+    fn distance(&self, other: &T) -> f64 {
+        (self.0 - other.center()).abs()
+    }
+}
+
+struct Square(f64, f64);
+
+impl Shape for Square {
+    fn center(&self) -> f64 {
+        (self.0 + self.1) / 2.0
+    }
+}
+
+struct Triangle(f64);
+
+impl Shape for Triangle {
+    fn center(&self) -> f64 {
+        self.0
+    }
+}
+
+mirror_distance!(Square, Triangle);
+
+#[test]
+fn test_export() {
+    assert_eq!(Point2D(1.0).distance(&Square(3.0, 5.0)), 3.0);
+    assert_eq!(Square(3.0, 5.0).distance(&Point2D(1.0)), 3.0);
+    assert_eq!(Triangle(-1.0).distance(&Point2D(1.0)), 2.0);
+}
@@ -0,0 +1,22 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {}
+
+struct Disk {}
+
+struct Square {}
+
+type Shape = Square;
+
+#[symmetric]
+impl Distance<Shape> for Point2D {
+    fn distance(&self, _other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+fn main() {}
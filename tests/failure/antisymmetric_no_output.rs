@@ -0,0 +1,16 @@
+use symm_impl::symmetric;
+
+trait Annotate<Other> {
+    fn annotate(&mut self, other: &mut Other);
+}
+
+struct Point2D {}
+
+struct Disk {}
+
+#[symmetric(antisymmetric)]
+impl Annotate<Disk> for Point2D {
+    fn annotate(&mut self, _other: &mut Disk) {}
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use symm_impl::symmetric;
+
+#[async_trait]
+trait Distance<Other> {
+    async fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {}
+
+struct Disk {}
+
+#[async_trait]
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    async fn distance(&self, _other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+fn main() {}
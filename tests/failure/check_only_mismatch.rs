@@ -0,0 +1,25 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other, scale: f64) -> f64;
+}
+
+struct Point2D(f64);
+
+struct Disk(f64, f64);
+
+#[symmetric(check_only)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk, scale: f64) -> f64 {
+        ((self.0 - other.0).abs() - other.1) * scale
+    }
+}
+
+// the hand-written mirror does not conflict with a stub
+impl Distance<Point2D> for Disk {
+    fn distance(&self, other: &Point2D, scale: f64) -> f64 {
+        ((self.0 - other.0).abs() - self.1) * scale
+    }
+}
+
+fn main() {}
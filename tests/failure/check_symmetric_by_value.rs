@@ -0,0 +1,23 @@
+use symm_impl::check_symmetric;
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> Vec<u32>;
+}
+
+struct A(u32);
+struct B(u32);
+
+impl Merge<B> for A {
+    fn merge(self, other: B) -> Vec<u32> {
+        vec![self.0, other.0]
+    }
+}
+
+#[check_symmetric]
+impl Merge<A> for B {
+    fn merge(self, other: A) -> Vec<u32> {
+        vec![other.0, self.0]
+    }
+}
+
+fn main() {}
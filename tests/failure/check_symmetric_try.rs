@@ -0,0 +1,35 @@
+use symm_impl::check_symmetric;
+
+type Fallible<T> = Result<T, String>;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> Fallible<f64>;
+}
+
+struct A(f64);
+struct B(f64);
+
+fn finite(value: f64) -> Fallible<f64> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(format!("{} is not finite", value))
+    }
+}
+
+impl Distance<B> for A {
+    fn distance(&self, other: &B) -> Fallible<f64> {
+        finite((self.0 - other.0).abs())
+    }
+}
+
+// the kind of `?` cannot be told from an alias of `Result`
+#[check_symmetric]
+impl Distance<A> for B {
+    fn distance(&self, other: &A) -> Fallible<f64> {
+        let distance = finite(other.0 - self.0)?;
+        Ok(distance.abs())
+    }
+}
+
+fn main() {}
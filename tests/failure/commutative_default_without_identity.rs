@@ -0,0 +1,16 @@
+use symm_impl::commutative;
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> Self;
+}
+
+struct Count(u32);
+
+#[commutative(fold = total, default)]
+impl Merge<Count> for Count {
+    fn merge(self, other: Count) -> Count {
+        Count(self.0 + other.0)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use symm_impl::commutative;
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> Self;
+}
+
+struct A(u32);
+struct B(u32);
+
+#[commutative(fold = combine_all)]
+impl Merge<B> for A {
+    fn merge(self, other: B) -> A {
+        A(self.0 + other.0)
+    }
+}
+
+fn main() {}
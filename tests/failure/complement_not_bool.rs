@@ -0,0 +1,21 @@
+use symm_impl::complement;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Far<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct A(f64);
+struct B(f64);
+
+#[complement(Far)]
+impl Distance<B> for A {
+    fn distance(&self, other: &B) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+fn main() {}
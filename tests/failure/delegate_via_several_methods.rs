@@ -0,0 +1,25 @@
+use symm_impl::symmetric;
+
+trait Overlap<Other> {
+    fn overlap(&self, other: &Other) -> bool;
+    fn touch(&self, other: &Other) -> bool;
+}
+
+struct Point2D(f64);
+struct Disk(f64, f64);
+
+fn overlap(point: &Point2D, disk: &Disk) -> bool {
+    (point.0 - disk.0).abs() <= disk.1
+}
+
+#[symmetric(delegate_via = overlap)]
+impl Overlap<Disk> for Point2D {
+    fn overlap(&self, other: &Disk) -> bool {
+        overlap(self, other)
+    }
+    fn touch(&self, other: &Disk) -> bool {
+        (self.0 - other.0).abs() == other.1
+    }
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+use symm_impl::{symmetric, SymmetricDispatch};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D(f64);
+
+struct Disk(f64, f64);
+
+struct Segment(f64, f64);
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+impl Distance<Disk> for Disk {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - self.1 - other.1
+    }
+}
+
+impl Distance<Segment> for Segment {
+    fn distance(&self, _other: &Segment) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - other.1
+    }
+}
+
+impl Distance<Segment> for Point2D {
+    fn distance(&self, _other: &Segment) -> f64 {
+        0.0
+    }
+}
+
+#[derive(SymmetricDispatch)]
+#[symm(impl Distance<Shape> {
+    fn distance(&self, other: &Shape) -> f64;
+})]
+enum Shape {
+    Point(Point2D),
+    Disk(Disk),
+    Segment(Segment),
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use symm_impl::SymmetricDispatch;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point(f64);
+
+impl Distance<Point> for Point {
+    fn distance(&self, other: &Point) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+#[derive(SymmetricDispatch)]
+#[symm(impl Distance<Shape> {
+    fn distance(&self, other: &Shape) -> f64;
+})]
+enum Shape {
+    Point(Point),
+    #[symm(skip)]
+    Empty,
+}
+
+fn main() {}
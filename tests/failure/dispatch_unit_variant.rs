@@ -0,0 +1,26 @@
+use symm_impl::symmetrize_enum;
+
+trait T<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {}
+
+impl T<A> for A {
+    fn op(&self, _other: &A) -> i32 {
+        0
+    }
+}
+
+symmetrize_enum! {
+    enum E {
+        A(A),
+        Empty,
+    }
+
+    impl T<E> {
+        fn op(&self, other: &E) -> i32;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use symm_impl::SymmetricDispatch;
+
+struct A {}
+
+#[derive(SymmetricDispatch)]
+enum E {
+    A(A),
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#![deny(deprecated)]
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64 {
+        let _ = other;
+        0.0
+    }
+}
+
+struct A(f64);
+struct B(f64);
+
+#[symmetric(only())]
+impl Distance<B> for A {
+    fn distance(&self, other: &B) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D(f64);
+
+struct Disk(f64);
+
+#[symmetric(export = mirror_distance)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+fn main() {}
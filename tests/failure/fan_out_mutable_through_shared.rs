@@ -0,0 +1,18 @@
+use symm_impl::symmetric;
+
+trait Absorb<Other> {
+    fn absorb(&mut self, other: &mut Other) -> f64;
+}
+
+struct Point2D(f64);
+struct Disk(f64);
+
+#[symmetric(fan_out(&))]
+impl Absorb<Disk> for Point2D {
+    fn absorb(&mut self, other: &mut Disk) -> f64 {
+        self.0 += other.0;
+        self.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use symm_impl::symmetric;
+
+trait T<Other> {
+    fn op(self, other: Other) -> i32;
+}
+
+struct A {}
+
+struct B {}
+
+#[symmetric(lift(Vec, reduce = sum))]
+impl T<B> for A {
+    fn op(self, _other: B) -> i32 {
+        1
+    }
+}
+
+fn main() {}
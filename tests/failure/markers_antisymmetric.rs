@@ -0,0 +1,18 @@
+use std::ops::Sub;
+
+use symm_impl::symmetric;
+
+struct A(i32);
+
+struct B(i32);
+
+#[symmetric(markers, antisymmetric)]
+impl Sub<B> for A {
+    type Output = i32;
+
+    fn sub(self, other: B) -> i32 {
+        self.0 - other.0
+    }
+}
+
+fn main() {}
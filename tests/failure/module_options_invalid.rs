@@ -0,0 +1,6 @@
+use symm_impl::symmetric_options;
+
+#[symmetric_options(by_reference)]
+mod shapes {}
+
+fn main() {}
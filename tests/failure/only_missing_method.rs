@@ -0,0 +1,21 @@
+use symm_impl::symmetric;
+
+trait T<Other> {
+    fn op(&self, other: &Other) -> i32;
+    fn other_op(&self, _other: &Other) -> i32 {
+        0
+    }
+}
+
+struct A {}
+
+struct B {}
+
+#[symmetric(only(op, other_op))]
+impl T<B> for A {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+fn main() {}
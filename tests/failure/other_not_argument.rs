@@ -0,0 +1,18 @@
+use symm_impl::symmetric;
+
+trait Touch<B, C> {
+    fn touches(&self, other: &B) -> bool;
+}
+
+struct Point2D;
+struct Disk;
+struct Line;
+
+#[symmetric(other = "Segment")]
+impl Touch<Disk, Line> for Point2D {
+    fn touches(&self, _other: &Disk) -> bool {
+        true
+    }
+}
+
+fn main() {}
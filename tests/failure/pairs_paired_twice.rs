@@ -0,0 +1,25 @@
+use symm_impl::symmetric;
+
+trait Shadow<Other> {
+    fn casts_shadow_on(&self, other: &Other) -> bool;
+    fn shadowed_by(&self, other: &Other) -> bool;
+    fn hidden_by(&self, other: &Other) -> bool;
+}
+
+struct Pole(f64);
+struct Wall(f64);
+
+#[symmetric(pairs(casts_shadow_on = shadowed_by, casts_shadow_on = hidden_by))]
+impl Shadow<Wall> for Pole {
+    fn casts_shadow_on(&self, other: &Wall) -> bool {
+        self.0 > other.0
+    }
+    fn shadowed_by(&self, other: &Wall) -> bool {
+        self.0 < other.0
+    }
+    fn hidden_by(&self, other: &Wall) -> bool {
+        self.0 < other.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use symm_impl::symmetric;
+
+trait T<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A<U> {
+    a: U,
+}
+
+struct B {}
+
+#[symmetric(remove_bound = "U: Clone")]
+impl<U: Copy> T<B> for A<U> {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+fn main() {}
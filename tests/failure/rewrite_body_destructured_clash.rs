@@ -0,0 +1,17 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D(f64);
+struct Offset(f64);
+
+#[symmetric(rewrite_body)]
+impl Distance<Offset> for Point2D {
+    fn distance(&self, Offset(other): &Offset) -> f64 {
+        self.0 - other
+    }
+}
+
+fn main() {}
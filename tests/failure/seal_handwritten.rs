@@ -0,0 +1,29 @@
+use symm_impl::symmetric;
+
+mod private {
+    pub trait DistanceSeal<Other: ?Sized> {}
+}
+
+pub trait Distance<Other>: private::DistanceSeal<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+pub struct Point2D(f64);
+
+pub struct Disk(f64, f64);
+
+#[symmetric(seal = private::DistanceSeal)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - other.1
+    }
+}
+
+// the implementations not generated with the seal are rejected
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+fn main() {}
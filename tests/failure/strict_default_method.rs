@@ -0,0 +1,22 @@
+use symm_impl::{symmetric, symmetric_trait};
+
+#[symmetric_trait]
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+    fn distance_squared(&self, other: &Other) -> f64 {
+        self.distance(other).powi(2)
+    }
+}
+
+struct Point2D {}
+
+struct Disk {}
+
+#[symmetric(strict)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, _other: &Disk) -> f64 {
+        0.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: Other) -> i32;
+}
+
+struct A {
+    a: i32,
+}
+
+struct B {
+    b: i32,
+}
+
+// rejected, but the uses of the mirror below are not reported
+#[symmetric]
+impl Op<B> for A {
+    fn op(&self, other: B) -> i32 {
+        self.a + other.b
+    }
+}
+
+fn main() {
+    let a = A { a: 1 };
+    let b = B { b: 2 };
+    a.op(B { b: 3 });
+    b.op(a);
+}
@@ -0,0 +1,31 @@
+#![deny(warnings)]
+
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(self, other: Other) -> i32;
+}
+
+struct A {
+    a: i32,
+}
+
+struct B {
+    b: i32,
+}
+
+// the options are rejected, and the stub taking `self` by value is not
+// reported either
+#[symmetric(unknown_option)]
+impl Op<B> for A {
+    fn op(mut self, other: B) -> i32 {
+        self.a += 1;
+        self.a + other.b
+    }
+}
+
+fn main() {
+    let a = A { a: 1 };
+    let b = B { b: 2 };
+    b.op(a);
+}
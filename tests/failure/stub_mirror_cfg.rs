@@ -0,0 +1,36 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: Other) -> i32;
+}
+
+struct A {
+    a: i32,
+}
+
+struct B {
+    b: i32,
+}
+
+// rejected, and the stub is only compiled along with the mirror, so it does
+// not conflict with the one written for the other configurations
+#[symmetric(cfg(miri))]
+impl Op<B> for A {
+    fn op(&self, other: B) -> i32 {
+        self.a + other.b
+    }
+}
+
+#[cfg(not(miri))]
+impl Op<A> for B {
+    fn op(&self, other: A) -> i32 {
+        self.b + other.a
+    }
+}
+
+fn main() {
+    let a = A { a: 1 };
+    let b = B { b: 2 };
+    a.op(B { b: 3 });
+    b.op(a);
+}
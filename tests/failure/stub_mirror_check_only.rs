@@ -0,0 +1,30 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {
+    a: i32,
+}
+
+struct B {
+    b: i32,
+}
+
+// the options are rejected, and no stub conflicts with the hand-written
+// mirror
+#[symmetric(check_only, unknown_option)]
+impl Op<B> for A {
+    fn op(&self, other: &B) -> i32 {
+        self.a + other.b
+    }
+}
+
+impl Op<A> for B {
+    fn op(&self, other: &A) -> i32 {
+        other.op(self)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use symm_impl::symmetric;
+
+trait Shape {
+    fn size(&self) -> i32;
+}
+
+struct A {
+    a: i32,
+}
+
+// rejected, and no stub is emitted since the mirrors are instantiated by
+// downstream crates, while a blanket stub breaks the orphan rules
+#[symmetric(export = mirror_eq, only(ne))]
+impl<T: Shape> PartialEq<T> for A {
+    fn eq(&self, other: &T) -> bool {
+        self.a == other.size()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use symm_impl::symmetric;
+
+trait Op<First, Second> {
+    fn op(&self, other: Second) -> i32;
+}
+
+struct A {
+    a: i32,
+}
+
+struct B {
+    b: i32,
+}
+
+struct C;
+
+// rejected, but the uses of the mirror of `B` below are not reported
+#[symmetric(other = "B")]
+impl Op<C, B> for A {
+    fn op(&self, other: B) -> i32 {
+        self.a + other.b
+    }
+}
+
+fn main() {
+    let a = A { a: 1 };
+    let b = B { b: 2 };
+    a.op(B { b: 3 });
+    b.op(a);
+}
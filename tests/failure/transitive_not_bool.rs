@@ -0,0 +1,16 @@
+use symm_impl::transitive;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point(f64);
+
+#[transitive]
+impl Distance<Point> for Point {
+    fn distance(&self, other: &Point) -> f64 {
+        (self.0 - other.0).abs()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use symm_impl::symmetric;
+
+trait Touching {
+    fn touching(&self) -> bool;
+}
+
+struct Ball(f64);
+struct Wall(f64);
+
+// the mirror of a pair always calls the original method by its qualified path
+#[symmetric(call = "method")]
+impl Touching for (Ball, Wall) {
+    fn touching(&self) -> bool {
+        self.0 .0 == self.1 .0
+    }
+}
+
+fn main() {}
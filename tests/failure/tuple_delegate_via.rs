@@ -0,0 +1,22 @@
+use symm_impl::symmetric;
+
+trait Touching {
+    fn touching(&self) -> bool;
+}
+
+struct Ball(f64);
+struct Wall(f64);
+
+fn touching(ball: &Ball, wall: &Wall) -> bool {
+    ball.0 == wall.0
+}
+
+// a pair has no other operand to pass to the helper
+#[symmetric(delegate_via = touching)]
+impl Touching for (Ball, Wall) {
+    fn touching(&self) -> bool {
+        touching(&self.0, &self.1)
+    }
+}
+
+fn main() {}
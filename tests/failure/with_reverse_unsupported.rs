@@ -0,0 +1,8 @@
+use symm_impl::with_reverse;
+
+#[with_reverse(Within)]
+trait Contains<Other> {
+    fn contains(&self, other: Box<Other>) -> bool;
+}
+
+fn main() {}
@@ -0,0 +1,124 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    type Output;
+    fn distance(&self, other: &Other) -> Self::Output;
+}
+
+trait Absorb<Other> {
+    fn absorb(&mut self, other: &mut Other) -> f64;
+}
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    type Output = f64;
+    fn distance(&self, other: &Point2D) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
+
+#[symmetric(fan_out(&, &mut, Box))]
+impl Distance<Disk> for Point2D {
+    type Output = f64;
+    fn distance(&self, other: &Disk) -> f64 {
+        let p_diff = self.distance(&other.center);
+        if p_diff.le(&other.radius) {
+            0.0_f64
+        } else {
+            p_diff - other.radius
+        }
+    }
+}
+
+#[symmetric(fan_out(&mut, Box))]
+impl Absorb<Disk> for Point2D {
+    fn absorb(&mut self, other: &mut Disk) -> f64 {
+        other.radius += 1.0;
+        self.x += 1.0;
+        other.radius
+    }
+}
+
+#[symmetric(fan_out(Box))]
+impl Merge<Disk> for Point2D {
+    fn merge(self, other: Disk) -> f64 {
+        self.x + other.radius
+    }
+}
+
+fn total<A, B>(lhs: &[A], rhs: &B) -> f64
+where
+    for<'a> &'a A: Distance<B, Output = f64>,
+{
+    lhs.iter().map(|a| a.distance(rhs)).sum()
+}
+
+fn measure<A: Distance<B, Output = f64>, B>(lhs: A, rhs: &B) -> f64 {
+    lhs.distance(rhs)
+}
+
+#[test]
+fn test_fan_out() {
+    let mut p = Point2D { x: 5.0, y: 4.0 };
+    let mut c = Disk {
+        center: Point2D { x: 1.0, y: 1.0 },
+        radius: 3.0,
+    };
+    assert_eq!(<&Point2D as Distance<Disk>>::distance(&&p, &c), 2.0);
+    assert_eq!(<&Disk as Distance<Point2D>>::distance(&&c, &p), 2.0);
+    assert_eq!(measure(c, &&p), 2.0);
+    assert_eq!(measure(p, &&c), 2.0);
+    assert_eq!(measure(Box::new(p), &c), 2.0);
+    assert_eq!(measure(Box::new(c), &p), 2.0);
+    assert_eq!(measure(c, &Box::new(p)), 2.0);
+    assert_eq!(measure(p, &Box::new(c)), 2.0);
+    assert_eq!(<&mut Point2D as Distance<Disk>>::distance(&&mut p, &c), 2.0);
+    assert_eq!(<&mut Disk as Distance<Point2D>>::distance(&&mut c, &p), 2.0);
+    assert_eq!(measure(c, &&mut p), 2.0);
+
+    assert_eq!(total(&[p, p], &c), 4.0);
+    assert_eq!(total(&[c], &p), 2.0);
+}
+
+#[test]
+fn test_fan_out_mutable() {
+    let mut p = Point2D { x: 5.0, y: 4.0 };
+    let mut c = Disk {
+        center: Point2D { x: 1.0, y: 1.0 },
+        radius: 3.0,
+    };
+    assert_eq!(Absorb::<Disk>::absorb(&mut &mut p, &mut c), 4.0);
+    assert_eq!(Absorb::<Point2D>::absorb(&mut &mut c, &mut p), 5.0);
+    let mut boxed = Box::new(c);
+    assert_eq!(Absorb::<Point2D>::absorb(&mut boxed, &mut p), 6.0);
+    assert_eq!(Absorb::<Box<Disk>>::absorb(&mut p, &mut boxed), 7.0);
+    assert_eq!(p.x, 9.0);
+}
+
+#[test]
+fn test_fan_out_owned() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: 1.0 },
+        radius: 3.0,
+    };
+    assert_eq!(Box::new(p).merge(c), 8.0);
+    assert_eq!(Box::new(c).merge(p), 8.0);
+    assert_eq!(c.merge(Box::new(p)), 8.0);
+    assert_eq!(p.merge(Box::new(c)), 8.0);
+}
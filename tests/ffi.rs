@@ -0,0 +1,54 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Contains<Other> {
+    fn contains(self, other: Other) -> bool;
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric(ffi(point, disk))]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[symmetric(ffi(point, disk))]
+impl Contains<Disk> for Point2D {
+    fn contains(self, other: Disk) -> bool {
+        self.distance(&other) == 0.0
+    }
+}
+
+#[test]
+fn test_ffi() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    unsafe {
+        assert_eq!(point_disk_distance(&p, &d), 3.0);
+        assert_eq!(disk_point_distance(&d, &p), 3.0);
+        assert!(!point_disk_contains(p, d));
+        assert!(!disk_point_contains(d, p));
+    }
+}
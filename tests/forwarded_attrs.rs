@@ -0,0 +1,29 @@
+use std::panic::Location;
+
+use symm_impl::symmetric;
+
+trait Locate<Other> {
+    fn locate(&self, other: &Other) -> u32;
+}
+
+struct A {}
+
+struct B {}
+
+#[symmetric]
+impl Locate<B> for A {
+    /// Report the line of the call site
+    #[track_caller]
+    fn locate(&self, _other: &B) -> u32 {
+        Location::caller().line()
+    }
+}
+
+#[test]
+fn test_track_caller() {
+    let a = A {};
+    let b = B {};
+    let (line, location) = (line!(), b.locate(&a));
+    assert_eq!(location, line);
+    assert_eq!(a.locate(&b), line!());
+}
@@ -0,0 +1,42 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A<T> {
+    a: T,
+}
+
+struct B {
+    b: i32,
+}
+
+// without the guard, the mirror would lose the bounds the delegation needs
+#[symmetric(guarded, remove_bound = "T: Into<i32> + Copy")]
+impl<T: Into<i32> + Copy> Op<B> for A<T> {
+    fn op(&self, other: &B) -> i32 {
+        self.a.into() - other.b
+    }
+}
+
+struct C {
+    c: i32,
+}
+
+#[symmetric(guarded)]
+impl Op<C> for B {
+    fn op(&self, other: &C) -> i32 {
+        self.b * other.c
+    }
+}
+
+#[test]
+fn test_guarded() {
+    let a = A { a: 5_u8 };
+    let b = B { b: 2 };
+    assert_eq!(a.op(&b), 3);
+    assert_eq!(b.op(&a), 3);
+    let c = C { c: 4 };
+    assert_eq!(c.op(&b), 8);
+}
@@ -0,0 +1,31 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {}
+
+struct B {}
+
+struct C {}
+
+#[symmetric(in_mod = generated_b)]
+impl Op<B> for A {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+#[symmetric(in_mod = generated_c)]
+impl Op<C> for A {
+    fn op(&self, _other: &C) -> i32 {
+        2
+    }
+}
+
+#[test]
+fn test_in_mod() {
+    assert_eq!(A {}.op(&B {}), B {}.op(&A {}));
+    assert_eq!(A {}.op(&C {}), C {}.op(&A {}));
+}
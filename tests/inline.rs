@@ -0,0 +1,41 @@
+use symm_impl::symmetric;
+
+trait Op<Other> {
+    fn op(&self, other: &Other) -> i32;
+}
+
+struct A {}
+
+struct B {}
+
+struct C {}
+
+struct D {}
+
+#[symmetric(inline = "always")]
+impl Op<B> for A {
+    fn op(&self, _other: &B) -> i32 {
+        1
+    }
+}
+
+#[symmetric(inline = "never")]
+impl Op<C> for A {
+    fn op(&self, _other: &C) -> i32 {
+        2
+    }
+}
+
+#[symmetric(inline = "none")]
+impl Op<D> for A {
+    fn op(&self, _other: &D) -> i32 {
+        3
+    }
+}
+
+#[test]
+fn test_inline() {
+    assert_eq!(A {}.op(&B {}), B {}.op(&A {}));
+    assert_eq!(A {}.op(&C {}), C {}.op(&A {}));
+    assert_eq!(A {}.op(&D {}), D {}.op(&A {}));
+}
@@ -0,0 +1,109 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: (f64, f64),
+    radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D<T> {
+    x: T,
+    y: T,
+}
+
+// the inline bounds stay the same in the mirror, which delegates under the
+// same conditions
+#[symmetric]
+impl<T: Into<f64> + Copy> Distance<Disk> for Point2D<T> {
+    fn distance(&self, other: &Disk) -> f64 {
+        let (x, y) = (self.x.into(), self.y.into());
+        ((x - other.center.0).powi(2) + (y - other.center.1).powi(2)).sqrt() - other.radius
+    }
+}
+
+/// A disk given by anything convertible into one.
+#[derive(Debug, Clone, Copy)]
+struct Anchor<T>(T);
+
+impl From<(f64, f64)> for Disk {
+    fn from(center: (f64, f64)) -> Disk {
+        Disk {
+            center,
+            radius: 1.0,
+        }
+    }
+}
+
+// the inline bound names the other operand, and the mirror of `Anchor<T>`
+// keeps it since it delegates to the original
+#[symmetric]
+impl<T: Into<Disk> + Copy> Distance<Disk> for Anchor<T> {
+    fn distance(&self, other: &Disk) -> f64 {
+        let anchor: Disk = self.0.into();
+        let (dx, dy) = (
+            anchor.center.0 - other.center.0,
+            anchor.center.1 - other.center.1,
+        );
+        (dx * dx + dy * dy).sqrt() - anchor.radius - other.radius
+    }
+}
+
+struct Origin;
+
+// `Self` in an inline bound is spelled as the self type in the mirror
+#[symmetric]
+impl<U: PartialEq<Self>> Distance<Point2D<U>> for Origin
+where
+    U: Copy + Into<f64>,
+{
+    fn distance(&self, other: &Point2D<U>) -> f64 {
+        let (x, y): (f64, f64) = (other.x.into(), other.y.into());
+        (x * x + y * y).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Unit(f64);
+
+impl From<Unit> for f64 {
+    fn from(unit: Unit) -> f64 {
+        unit.0
+    }
+}
+
+impl PartialEq<Origin> for Unit {
+    fn eq(&self, _other: &Origin) -> bool {
+        self.0 == 0.0
+    }
+}
+
+#[test]
+fn test_inline_bound() {
+    let p = Point2D { x: 4.0_f32, y: 4.0 };
+    let c = Disk {
+        center: (1.0, 0.0),
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), 2.0);
+    assert_eq!(c.distance(&p), 2.0);
+
+    let p = Point2D {
+        x: Unit(3.0),
+        y: Unit(4.0),
+    };
+    assert_eq!(Origin.distance(&p), 5.0);
+    assert_eq!(p.distance(&Origin), 5.0);
+
+    let a = Anchor((4.0, 4.0));
+    let c = Disk {
+        center: (1.0, 0.0),
+        radius: 2.0,
+    };
+    assert_eq!(a.distance(&c), 2.0);
+    assert_eq!(c.distance(&a), 2.0);
+}
@@ -0,0 +1,98 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use symm_impl::symmetric;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric(instrument)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+/// Subscriber recording the fields of every span created.
+#[derive(Default)]
+struct Recorder {
+    next_id: AtomicU64,
+    spans: Mutex<Vec<String>>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(span.metadata().name().to_string());
+        span.record(&mut fields);
+        self.spans.lock().unwrap().push(fields.0);
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_instrument() {
+    let recorder = std::sync::Arc::new(Recorder::default());
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: 4.0 },
+        radius: 3.0,
+    };
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!(p.distance(&c), 1.0);
+        assert_eq!(c.distance(&p), 1.0);
+    });
+    assert_eq!(
+        *recorder.spans.lock().unwrap(),
+        [
+            "symmetric operation=\"<Point2D as Distance<Disk>>::distance\" \
+             self_type=\"Disk\" other_type=\"Point2D\" direction=\"mirrored\""
+        ]
+    );
+}
@@ -0,0 +1,47 @@
+use std::ops::Sub;
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> u32;
+}
+
+struct Point(u16);
+
+struct Interval(u16, u16);
+
+#[symmetric(kani)]
+impl Distance<Interval> for Point {
+    fn distance(&self, other: &Interval) -> u32 {
+        let (start, end) = (
+            u32::from(other.0.min(other.1)),
+            u32::from(other.0.max(other.1)),
+        );
+        let point = u32::from(self.0);
+        start.saturating_sub(point) + point.saturating_sub(end)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Offset(i16);
+
+#[derive(Clone, Copy)]
+struct Position(i16);
+
+#[symmetric(kani, antisymmetric)]
+impl Sub<Position> for Offset {
+    type Output = i32;
+
+    fn sub(self, other: Position) -> i32 {
+        i32::from(self.0) - i32::from(other.0)
+    }
+}
+
+// the harnesses are only compiled by `cargo kani`
+#[test]
+fn test_kani() {
+    assert_eq!(Point(3).distance(&Interval(5, 8)), 2);
+    assert_eq!(Interval(5, 8).distance(&Point(10)), 2);
+    assert_eq!(Offset(3) - Position(5), -2);
+    assert_eq!(Position(5) - Offset(3), 2);
+}
@@ -26,7 +26,7 @@ impl<'a> Distance<'a, Point2D<'a>> for Point2D<'a> {
 impl<'a> Distance<'a, Disk<'a>> for Point2D<'a> {
     fn distance(&'a self, other: &'a Disk<'a>) -> f64 {
         let p_diff = self.distance(&other.center);
-        if p_diff.le(&other.radius) {
+        if p_diff.le(other.radius) {
             0.0_f64
         } else {
             p_diff - other.radius
@@ -0,0 +1,96 @@
+use std::collections::VecDeque;
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Intersects<Other> {
+    fn intersects(&self, other: &Other) -> bool;
+}
+
+trait Touch<Other> {
+    fn touch(&mut self, other: &mut Other) -> u32;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+    touched: u32,
+}
+
+#[symmetric(
+    lift(Vec, reduce = min, empty = f64::INFINITY),
+    lift(VecDeque, reduce = max)
+)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[symmetric(lift(Vec, reduce = any), lift(VecDeque, reduce = all))]
+impl Intersects<Disk> for Point2D {
+    fn intersects(&self, other: &Disk) -> bool {
+        self.distance(other) == 0.0
+    }
+}
+
+#[symmetric(lift(Vec, reduce = sum))]
+impl Touch<Disk> for Point2D {
+    fn touch(&mut self, other: &mut Disk) -> u32 {
+        other.touched += 1;
+        self.x += 1.0;
+        other.touched
+    }
+}
+
+fn disk(x: f64, radius: f64) -> Disk {
+    Disk {
+        center: Point2D { x, y: 0.0 },
+        radius,
+        touched: 0,
+    }
+}
+
+#[test]
+fn test_lift() {
+    let p = Point2D { x: 0.0, y: 0.0 };
+    let disks = vec![disk(10.0, 1.0), disk(5.0, 2.0), disk(-20.0, 1.0)];
+    assert_eq!(p.distance(&disks), 3.0);
+    assert_eq!(disks.distance(&p), 3.0);
+    assert!(!p.intersects(&disks));
+    assert!(disks.intersects(&Point2D { x: 5.0, y: 0.0 }));
+    assert_eq!(p.distance(&Vec::<Disk>::new()), f64::INFINITY);
+
+    let disks: VecDeque<_> = disks.into_iter().collect();
+    assert_eq!(p.distance(&disks), 19.0);
+    assert!(!disks.intersects(&Point2D { x: 5.0, y: 0.0 }));
+    assert!(VecDeque::new().intersects(&p));
+}
+
+#[test]
+#[should_panic(expected = "`distance` of an empty container has no value")]
+fn test_lift_empty() {
+    Point2D { x: 0.0, y: 0.0 }.distance(&VecDeque::<Disk>::new());
+}
+
+#[test]
+fn test_lift_mut() {
+    let mut p = Point2D { x: 0.0, y: 0.0 };
+    let mut disks = vec![disk(1.0, 1.0), disk(2.0, 1.0)];
+    assert_eq!(p.touch(&mut disks), 2);
+    assert_eq!(disks.touch(&mut p), 4);
+    assert_eq!(p.x, 4.0);
+    assert_eq!(Vec::<Disk>::new().touch(&mut p), 0);
+}
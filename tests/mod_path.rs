@@ -1,6 +1,7 @@
 use symm_impl::symmetric;
 
 mod inner {
+    #[allow(clippy::module_inception)]
     pub(crate) mod inner {
         pub(crate) trait Distance<Other> {
             fn distance(&self, other: &Other) -> f64;
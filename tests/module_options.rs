@@ -0,0 +1,93 @@
+use symm_impl::symmetric_options;
+
+pub trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+pub trait Signed<Other> {
+    fn signed_distance(&self, other: &Other) -> f64;
+}
+
+pub trait Area<Other> {
+    fn overlap(&self, other: &Other) -> f64;
+}
+
+pub struct Point2D(f64);
+
+pub struct Disk(f64, f64);
+
+pub struct Segment(f64, f64);
+
+#[symmetric_options(by_ref)]
+mod shapes {
+    use super::*;
+    use symm_impl::symmetric;
+
+    #[symmetric]
+    impl Distance<Disk> for Point2D {
+        fn distance(&self, other: &Disk) -> f64 {
+            (self.0 - other.0).abs() - other.1
+        }
+    }
+
+    // options of the attribute add to the ones of the module
+    #[symm_impl::symmetric(antisymmetric)]
+    impl Signed<Disk> for Point2D {
+        fn signed_distance(&self, other: &Disk) -> f64 {
+            self.0 - other.0
+        }
+    }
+
+    mod segments {
+        use super::*;
+
+        // modules without options inherit too
+        #[symm_impl::symm]
+        impl Distance<Segment> for Point2D {
+            fn distance(&self, other: &Segment) -> f64 {
+                (other.0 - self.0).max(self.0 - other.1).max(0.0)
+            }
+        }
+    }
+
+    // the implementation unsets the flag of the module, so the references are
+    // implemented by hand
+    #[symmetric(by_ref = false)]
+    impl Area<Disk> for Point2D {
+        fn overlap(&self, other: &Disk) -> f64 {
+            other.1 - (self.0 - other.0).abs()
+        }
+    }
+
+    impl<'a> Area<&'a Disk> for Point2D {
+        fn overlap(&self, _other: &&'a Disk) -> f64 {
+            0.0
+        }
+    }
+
+    #[symmetric_options(antisymmetric)]
+    mod signed {
+        use super::*;
+
+        // options of the nested module add to the ones of the module
+        #[symmetric]
+        impl Signed<Segment> for Point2D {
+            fn signed_distance(&self, other: &Segment) -> f64 {
+                self.0 - (other.0 + other.1) / 2.0
+            }
+        }
+    }
+}
+
+#[test]
+fn test_module_options() {
+    let (point, disk, segment) = (Point2D(5.0), Disk(1.0, 2.0), Segment(1.0, 2.0));
+    assert_eq!(disk.distance(&&point), 2.0);
+    assert_eq!(point.distance(&&disk), 2.0);
+    assert_eq!(disk.signed_distance(&point), -4.0);
+    assert_eq!(disk.signed_distance(&&point), -4.0);
+    assert_eq!(segment.distance(&&point), 3.0);
+    assert_eq!(segment.signed_distance(&&point), -3.5);
+    assert_eq!(disk.overlap(&point), -2.0);
+    assert_eq!(point.overlap(&&disk), 0.0);
+}
@@ -0,0 +1,121 @@
+use symm_impl::{symmetric, symmetrize_enum, SymmetricDispatch};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment(f64, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Ray(f64);
+
+fn gap(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.1).max(a.0 - b.1).max(0.0)
+}
+
+impl Distance<Point> for Point {
+    fn distance(&self, other: &Point) -> f64 {
+        gap((self.0, self.0), (other.0, other.0))
+    }
+}
+
+impl Distance<Segment> for Segment {
+    fn distance(&self, other: &Segment) -> f64 {
+        gap((self.0, self.1), (other.0, other.1))
+    }
+}
+
+impl Distance<Ray> for Ray {
+    fn distance(&self, _other: &Ray) -> f64 {
+        0.0
+    }
+}
+
+#[symmetric]
+impl Distance<Segment> for Point {
+    fn distance(&self, other: &Segment) -> f64 {
+        gap((self.0, self.0), (other.0, other.1))
+    }
+}
+
+#[symmetric]
+impl Distance<Ray> for Point {
+    fn distance(&self, other: &Ray) -> f64 {
+        (other.0 - self.0).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Distance<Ray> for Segment {
+    fn distance(&self, other: &Ray) -> f64 {
+        (other.0 - self.1).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, SymmetricDispatch)]
+#[symm(impl Distance<Curve> {
+    fn distance(&self, other: &Curve) -> f64;
+})]
+enum Curve {
+    Segment(Segment),
+    Ray(Ray),
+}
+
+symmetrize_enum! {
+    #[derive(Debug, Clone, Copy)]
+    enum Shape {
+        Point(Point),
+        #[symm(nested)]
+        Curve(Curve),
+    }
+
+    impl Distance<Shape> {
+        fn distance(&self, other: &Shape) -> f64;
+    }
+}
+
+#[test]
+fn test_nested_with_flat() {
+    let point = Shape::from(Point(5.0));
+    let segment = Shape::Curve(Curve::Segment(Segment(0.0, 2.0)));
+    let ray = Shape::Curve(Curve::Ray(Ray(7.0)));
+    assert_eq!(point.distance(&segment), 3.0);
+    assert_eq!(segment.distance(&point), 3.0);
+    assert_eq!(point.distance(&ray), 2.0);
+    assert_eq!(ray.distance(&point), 2.0);
+}
+
+#[test]
+fn test_nested_with_nested() {
+    let segment = Shape::Curve(Curve::Segment(Segment(0.0, 2.0)));
+    let other_segment = Shape::Curve(Curve::Segment(Segment(4.0, 6.0)));
+    let ray = Shape::Curve(Curve::Ray(Ray(7.0)));
+    assert_eq!(segment.distance(&other_segment), 2.0);
+    assert_eq!(segment.distance(&ray), 5.0);
+    assert_eq!(ray.distance(&segment), 5.0);
+}
+
+#[derive(Debug, Clone, Copy, SymmetricDispatch)]
+#[symm(impl Distance<Figure<T>> {
+    fn distance(&self, other: &Figure<T>) -> f64;
+})]
+enum Figure<T> {
+    Point(Point),
+    #[symm(nested)]
+    Curve(Curve),
+    Other(T),
+}
+
+#[test]
+fn test_nested_generic() {
+    let point: Figure<Point> = Figure::Point(Point(1.0));
+    let segment: Figure<Point> = Figure::Curve(Curve::Segment(Segment(3.0, 4.0)));
+    let other: Figure<Point> = Figure::Other(Point(10.0));
+    assert_eq!(point.distance(&segment), 2.0);
+    assert_eq!(segment.distance(&other), 6.0);
+    assert_eq!(other.distance(&segment), 6.0);
+}
@@ -0,0 +1,82 @@
+#![no_std]
+
+use symm_impl::{check_symmetric, symmetric};
+
+trait Distance<Other> {
+    type Output;
+    fn distance(&self, other: &Other) -> Self::Output;
+}
+
+struct Point1D {
+    x: i32,
+}
+
+struct Interval {
+    low: i32,
+    high: i32,
+}
+
+#[symmetric]
+impl Distance<Interval> for Point1D {
+    type Output = i32;
+    fn distance(&self, other: &Interval) -> i32 {
+        if self.x < other.low {
+            other.low - self.x
+        } else if self.x > other.high {
+            self.x - other.high
+        } else {
+            0
+        }
+    }
+}
+
+trait Gap<Other> {
+    fn gap(&self, other: &Other) -> i32;
+}
+
+impl Gap<Interval> for Point1D {
+    fn gap(&self, other: &Interval) -> i32 {
+        self.distance(other)
+    }
+}
+
+#[check_symmetric]
+impl Gap<Point1D> for Interval {
+    fn gap(&self, other: &Point1D) -> i32 {
+        (self.low - other.x).max(other.x - self.high).max(0)
+    }
+}
+
+#[no_implicit_prelude]
+mod no_prelude {
+    use ::symm_impl::symmetric;
+
+    trait Op<Other> {
+        fn op(self, other: Other) -> i32;
+    }
+
+    pub(crate) struct A {}
+
+    pub(crate) struct B {}
+
+    #[symmetric(in_mod = generated)]
+    impl Op<B> for A {
+        fn op(self, _other: B) -> i32 {
+            1
+        }
+    }
+
+    pub(crate) fn op_both() -> (i32, i32) {
+        (A {}.op(B {}), B {}.op(A {}))
+    }
+}
+
+#[test]
+fn test_no_std() {
+    let p = Point1D { x: 5 };
+    let i = Interval { low: 1, high: 3 };
+    assert_eq!(p.distance(&i), i.distance(&p));
+    assert_eq!(i.gap(&p), 2);
+    let (forward, backward) = no_prelude::op_both();
+    assert_eq!(forward, backward);
+}
@@ -0,0 +1,58 @@
+use symm_impl::symmetric;
+
+trait Shape<Other> {
+    fn distance(&self, other: &Other) -> f64;
+    fn intersects(&self, other: &Other) -> bool;
+    fn contains(&self, _other: &Other) -> bool {
+        false
+    }
+    fn describe(&self, _other: &Other, verbose: bool) -> String {
+        String::from(if verbose { "shape" } else { "" })
+    }
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric(only(distance, intersects))]
+impl Shape<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+
+    fn intersects(&self, other: &Disk) -> bool {
+        self.distance(other) == 0.0
+    }
+
+    // not symmetric, and not of the symmetric shape
+    fn contains(&self, _other: &Disk) -> bool {
+        false
+    }
+
+    fn describe(&self, _other: &Disk, verbose: bool) -> String {
+        String::from(if verbose { "point" } else { "" })
+    }
+}
+
+#[test]
+fn test_only() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 1.0,
+    };
+    assert_eq!(d.distance(&p), 4.0);
+    assert!(!d.intersects(&p));
+    assert!(!d.contains(&p));
+    assert_eq!(d.describe(&p, true), "shape");
+    assert_eq!(p.describe(&d, true), "point");
+}
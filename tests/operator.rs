@@ -0,0 +1,64 @@
+use symm_impl::symmetric;
+
+trait Intersect<Other> {
+    fn intersects(&self, other: &Other) -> bool;
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Merge<Other> {
+    type Output;
+    fn merge(self, other: Other) -> Self::Output;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric(operator(BitAnd = intersects, Sub = distance))]
+impl Intersect<Disk> for Point2D {
+    fn intersects(&self, other: &Disk) -> bool {
+        self.distance(other) == 0.0
+    }
+
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[symmetric(operator(BitOr = merge))]
+impl Merge<Disk> for Point2D {
+    type Output = Disk;
+
+    fn merge(self, other: Disk) -> Self::Output {
+        let radius = Intersect::distance(&self, &other) + other.radius;
+        Disk {
+            center: other.center,
+            radius,
+        }
+    }
+}
+
+#[test]
+fn test_operator() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    assert!(!(p & d));
+    assert!(!(d & p));
+    assert_eq!(p - d, 3.0);
+    assert_eq!(d - p, 3.0);
+    assert_eq!((p | d).radius, 5.0);
+    assert_eq!(d | p, p | d);
+}
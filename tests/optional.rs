@@ -0,0 +1,75 @@
+use std::ops::Add;
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+#[symmetric(optional = "default")]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+trait Overlap<Other> {
+    fn overlap(&self, other: &Other) -> bool;
+}
+
+#[symmetric(optional = "panic")]
+impl Overlap<Disk> for Point2D {
+    fn overlap(&self, other: &Disk) -> bool {
+        self.distance(other) == 0.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Feet(f64);
+
+#[symmetric(optional = "none")]
+impl Add<Feet> for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Feet) -> Self::Output {
+        Meters(self.0 + other.0 * 0.3048)
+    }
+}
+
+#[test]
+fn test_optional() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Some(Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    });
+    assert_eq!(p.distance(&d), 3.0);
+    assert_eq!(d.distance(&p), 3.0);
+    assert_eq!(p.distance(&None::<Disk>), 0.0);
+    assert!(!d.overlap(&p));
+
+    assert_eq!(Meters(1.0) + Some(Feet(10.0)), Some(Meters(4.048)));
+    assert_eq!(Some(Feet(10.0)) + Meters(1.0), Some(Meters(4.048)));
+    assert_eq!(None::<Feet> + Meters(1.0), None);
+}
+
+#[test]
+#[should_panic(expected = "`overlap` of `None`")]
+fn test_optional_panic() {
+    Point2D { x: 0.0, y: 0.0 }.overlap(&None::<Disk>);
+}
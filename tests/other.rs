@@ -0,0 +1,58 @@
+use symm_impl::symmetric;
+
+trait Touch<B, C> {
+    fn touches_first(&self, _other: &B) -> bool {
+        false
+    }
+    fn touches_second(&self, _other: &C) -> bool {
+        false
+    }
+}
+
+struct Point2D(f64);
+struct Disk(f64, f64);
+struct Line(f64);
+
+#[symmetric(other = "Disk")]
+#[symmetric(other = "Line")]
+impl Touch<Disk, Line> for Point2D {
+    fn touches_first(&self, other: &Disk) -> bool {
+        (self.0 - other.0).abs() <= other.1
+    }
+    fn touches_second(&self, other: &Line) -> bool {
+        self.0 == other.0
+    }
+}
+
+trait Combine<L, R> {
+    type Output;
+    fn combine(self, other: R) -> Self::Output;
+}
+
+#[symmetric(other = "Line")]
+impl<T> Combine<T, Line> for Disk {
+    type Output = f64;
+    fn combine(self, other: Line) -> f64 {
+        self.0 + other.0
+    }
+}
+
+#[test]
+fn test_other() {
+    let (point, disk, line) = (Point2D(2.0), Disk(1.0, 2.0), Line(2.0));
+    assert!(<Disk as Touch<Point2D, Line>>::touches_first(&disk, &point));
+    assert!(<Line as Touch<Disk, Point2D>>::touches_second(
+        &line, &point
+    ));
+    // the methods of the other slot are left to the defaults of the trait
+    assert!(!<Disk as Touch<Point2D, Line>>::touches_second(
+        &disk, &line
+    ));
+    assert!(!<Line as Touch<Disk, Point2D>>::touches_first(&line, &disk));
+}
+
+#[test]
+fn test_other_generic() {
+    let output: f64 = <Line as Combine<u8, Disk>>::combine(Line(1.0), Disk(2.0, 0.0));
+    assert_eq!(output, 3.0);
+}
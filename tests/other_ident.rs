@@ -0,0 +1,37 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point1D {
+    x: f64,
+}
+
+struct Interval {
+    low: f64,
+    high: f64,
+}
+
+#[symmetric(other_ident = rhs)]
+impl Distance<Interval> for Point1D {
+    fn distance(&self, Interval { low, high }: &Interval) -> f64 {
+        if self.x < *low {
+            low - self.x
+        } else if self.x > *high {
+            self.x - high
+        } else {
+            0.0
+        }
+    }
+}
+
+#[test]
+fn test_other_ident() {
+    let p = Point1D { x: 5.0 };
+    let i = Interval {
+        low: 1.0,
+        high: 3.0,
+    };
+    assert_eq!(p.distance(&i), i.distance(&p));
+}
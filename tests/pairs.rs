@@ -0,0 +1,58 @@
+use symm_impl::symmetric;
+
+trait Shadow<Other> {
+    fn casts_shadow_on(&self, other: &Other) -> bool;
+    fn shadowed_by(&self, other: &Other) -> bool;
+}
+
+trait Order<Other> {
+    fn precedes(&self, other: &Other) -> bool;
+    fn follows(&self, other: &Other) -> bool {
+        !self.precedes(other)
+    }
+    fn rank(&self, other: &Other) -> i32;
+}
+
+struct Pole(f64);
+struct Wall(f64);
+
+#[symmetric(pairs(casts_shadow_on = shadowed_by))]
+impl Shadow<Wall> for Pole {
+    fn casts_shadow_on(&self, other: &Wall) -> bool {
+        self.0 > other.0
+    }
+    fn shadowed_by(&self, other: &Wall) -> bool {
+        self.0 < other.0
+    }
+}
+
+#[symmetric(pairs(follows = precedes), call = "method")]
+impl Order<Wall> for Pole {
+    fn precedes(&self, other: &Wall) -> bool {
+        self.0 < other.0
+    }
+    fn rank(&self, other: &Wall) -> i32 {
+        (self.0 - other.0) as i32
+    }
+}
+
+#[test]
+fn test_pairs() {
+    let (pole, wall) = (Pole(3.0), Wall(2.0));
+    assert!(pole.casts_shadow_on(&wall));
+    assert!(wall.shadowed_by(&pole));
+    assert!(!wall.casts_shadow_on(&pole));
+    assert!(!pole.shadowed_by(&wall));
+}
+
+#[test]
+fn test_pairs_with_default() {
+    let (pole, wall) = (Pole(3.0), Wall(2.0));
+    assert!(!pole.precedes(&wall));
+    assert!(wall.precedes(&pole));
+    // the counterpart is left to the default of the trait
+    assert!(pole.follows(&wall));
+    assert!(!wall.follows(&pole));
+    // unpaired methods keep their name
+    assert_eq!(wall.rank(&pole), 1);
+}
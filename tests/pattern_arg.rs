@@ -26,7 +26,7 @@ impl Distance<Point2D> for Point2D {
 impl Distance<Disk> for Point2D {
     fn distance(&self, Disk { center, radius }: &Disk) -> f64 {
         let p_diff = self.distance(center);
-        if p_diff.le(&radius) {
+        if p_diff.le(radius) {
             0.0_f64
         } else {
             p_diff - radius
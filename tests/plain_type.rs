@@ -42,4 +42,4 @@ fn test_plain_type() {
         radius: 3.0,
     };
     assert_eq!(p.distance(&c), c.distance(&p));
-}
\ No newline at end of file
+}
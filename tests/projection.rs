@@ -0,0 +1,47 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Config {
+    type Shape;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+struct Geometry;
+
+impl Config for Geometry {
+    type Shape = Disk;
+}
+
+#[symmetric]
+impl Distance<<Geometry as Config>::Shape> for Point2D {
+    fn distance(&self, other: &<Geometry as Config>::Shape) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[test]
+fn test_projection() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: 4.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), 1.0);
+    assert_eq!(c.distance(&p), 1.0);
+}
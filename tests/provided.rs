@@ -0,0 +1,86 @@
+use std::cmp::Ordering;
+
+use symm_impl::symmetric;
+
+#[derive(Debug, Clone, Copy)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy)]
+struct Feet(f64);
+
+impl Meters {
+    fn feet(self) -> f64 {
+        self.0 / 0.3048
+    }
+}
+
+#[symmetric]
+impl PartialEq<Feet> for Meters {
+    fn eq(&self, other: &Feet) -> bool {
+        (self.feet() - other.0).abs() < 1e-9
+    }
+    // deliberately inconsistent, to tell whether the mirror delegates to it
+    #[allow(clippy::partialeq_ne_impl)]
+    fn ne(&self, _other: &Feet) -> bool {
+        true
+    }
+}
+
+#[symmetric]
+impl PartialOrd<Feet> for Meters {
+    fn partial_cmp(&self, other: &Feet) -> Option<Ordering> {
+        self.feet().partial_cmp(&other.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Seconds(u64);
+
+#[derive(Debug, Clone, Copy)]
+struct Minutes(u64);
+
+#[symmetric]
+impl std::cmp::PartialEq<Minutes> for Seconds {
+    fn eq(&self, other: &Minutes) -> bool {
+        self.0 == other.0 * 60
+    }
+}
+
+#[symmetric]
+impl std::cmp::PartialOrd<Minutes> for Seconds {
+    fn partial_cmp(&self, other: &Minutes) -> Option<Ordering> {
+        self.0.partial_cmp(&(other.0 * 60))
+    }
+    fn lt(&self, other: &Minutes) -> bool {
+        self.0 < other.0 * 60
+    }
+}
+
+#[test]
+fn test_provided_ne() {
+    assert!(Feet(10.0) == Meters(3.048));
+    // delegated to the written `ne` rather than negating `eq`
+    assert!(Feet(10.0) != Meters(3.048));
+    assert!(Minutes(1) == Seconds(60));
+    assert!(Minutes(1) != Seconds(61));
+    assert!(!(Minutes(1) != Seconds(60)));
+}
+
+#[test]
+fn test_provided_ordering() {
+    assert_eq!(
+        Feet(10.0).partial_cmp(&Meters(1.0)),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(Meters(1.0).partial_cmp(&Feet(10.0)), Some(Ordering::Less));
+    assert!(Feet(10.0) > Meters(1.0));
+    assert!(Feet(10.0) >= Meters(1.0));
+    assert!(!(Feet(10.0) < Meters(1.0)));
+    assert!(!(Feet(10.0) <= Meters(1.0)));
+
+    assert!(Minutes(1) > Seconds(59));
+    assert!(Minutes(1) >= Seconds(60));
+    assert!(Minutes(1) < Seconds(61));
+    assert!(Minutes(1) <= Seconds(60));
+    assert_eq!(Minutes(1).partial_cmp(&Seconds(61)), Some(Ordering::Less));
+}
@@ -0,0 +1,75 @@
+use symm_impl::symmetric;
+
+mod shapes {
+    pub trait Distance<Other> {
+        fn distance(&self, other: &Other) -> f64;
+    }
+
+    pub mod planar {
+        use super::super::symmetric;
+
+        pub struct Point2D {
+            pub x: f64,
+            pub y: f64,
+        }
+
+        pub struct Disk {
+            pub center: Point2D,
+            pub radius: f64,
+        }
+
+        impl super::Distance<Point2D> for Point2D {
+            fn distance(&self, other: &Point2D) -> f64 {
+                let dx = self.x - other.x;
+                let dy = self.y - other.y;
+                (dx * dx + dy * dy).sqrt()
+            }
+        }
+
+        #[symmetric(in_mod = disk_point)]
+        impl super::Distance<self::Disk> for self::Point2D {
+            fn distance(&self, other: &self::Disk) -> f64 {
+                let p_diff = super::Distance::distance(self, &other.center);
+                if p_diff.le(&other.radius) {
+                    0.0_f64
+                } else {
+                    p_diff - other.radius
+                }
+            }
+        }
+
+        pub struct Square {
+            pub center: Point2D,
+            pub side: f64,
+        }
+
+        #[symmetric(in_mod = square_point)]
+        impl crate::shapes::Distance<Square> for ::std::boxed::Box<Point2D> {
+            fn distance(&self, other: &Square) -> f64 {
+                super::Distance::distance(self.as_ref(), &other.center) - other.side / 2.0
+            }
+        }
+    }
+}
+
+use shapes::{
+    planar::{Disk, Point2D, Square},
+    Distance,
+};
+
+#[test]
+fn test_relative_paths() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), c.distance(&p));
+
+    let p = Box::new(Point2D { x: 5.0, y: 4.0 });
+    let s = Square {
+        center: Point2D { x: 1.0, y: 1.0 },
+        side: 2.0,
+    };
+    assert_eq!(p.distance(&s), s.distance(&p));
+}
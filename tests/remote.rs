@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use symm_impl::symmetric;
+
+#[symmetric(remote = Seconds)]
+impl PartialEq<u64> for Duration {
+    fn eq(&self, other: &u64) -> bool {
+        self.as_secs() == *other && self.subsec_nanos() == 0
+    }
+}
+
+#[test]
+fn test_remote() {
+    let seconds = Seconds::from(Duration::from_secs(3));
+    assert!(seconds == 3);
+    assert!(3 == seconds);
+    assert!(4 != seconds);
+    assert!(Seconds(Duration::from_millis(3500)) != 3);
+    assert_eq!(seconds.as_secs(), 3);
+    assert_eq!(Duration::from(seconds), Duration::from_secs(3));
+}
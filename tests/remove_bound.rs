@@ -0,0 +1,32 @@
+use std::fmt::Debug;
+
+use symm_impl::symmetric;
+
+trait Describe<Other> {
+    fn describe(&self, other: &Other) -> String;
+}
+
+struct A<T> {
+    a: T,
+}
+
+struct B {}
+
+// the mirror only needs the forward implementation to exist, instead of
+// repeating all of its requirements
+#[symmetric(remove_bound = "T: Debug + Default", bound = "A<T>: Describe<B>")]
+impl<T: Clone + Debug> Describe<B> for A<T>
+where
+    T: Default,
+{
+    fn describe(&self, _other: &B) -> String {
+        format!("{:?}", self.a)
+    }
+}
+
+#[test]
+fn test_remove_bound() {
+    let a = A { a: 1 };
+    let b = B {};
+    assert_eq!(a.describe(&b), b.describe(&a));
+}
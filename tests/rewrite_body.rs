@@ -0,0 +1,254 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    type Output;
+    fn distance(&self, other: &Other) -> Self::Output;
+}
+
+trait Merge<Other> {
+    fn merge(self, other: Other) -> (f64, f64);
+}
+
+trait Describe<Other> {
+    fn describe(&self, other: &Other) -> String;
+}
+
+trait Wrap<Other> {
+    type Output;
+    fn wrap(self, other: Other) -> Self::Output;
+}
+
+trait Count<Other> {
+    fn count(&self, other: &Other) -> usize;
+}
+
+trait Scale<Other> {
+    fn scale(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, PartialEq)]
+struct Wrapped<T, U> {
+    first: T,
+    other: U,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Point2D(f64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Point2D {
+    fn origin() -> Self {
+        Point2D(0.0)
+    }
+}
+
+#[symmetric(rewrite_body)]
+impl Distance<Disk> for Point2D {
+    type Output = f64;
+    fn distance(&self, disk: &Disk) -> Self::Output {
+        let offset: Self::Output = (self.0 - disk.center.0).abs();
+        assert!(disk.radius >= 0.0, "{:?} has a negative radius", disk);
+        (offset - disk.radius).max(Self::origin().0)
+    }
+}
+
+#[symmetric(rewrite_body)]
+impl Merge<Disk> for Point2D {
+    fn merge(mut self, other: Disk) -> (f64, f64) {
+        self.0 += other.center.0;
+        (self.0, other.radius)
+    }
+}
+
+// the other operand is captured by the format string
+#[symmetric(rewrite_body)]
+impl Describe<Disk> for Point2D {
+    fn describe(&self, other: &Disk) -> String {
+        let first = format!("{:?}", self);
+        format!("{first} vs {other:?}, radius {:.1}", other.radius)
+    }
+}
+
+trait Label<Other> {
+    fn label(&self, other: &Other) -> String;
+}
+
+macro_rules! pair {
+    ($text:expr, $value:expr) => {
+        format!("{} = {:?}", $text, $value)
+    };
+}
+
+// only the format strings of the formatting macros capture the other operand
+#[symmetric(rewrite_body)]
+impl Label<Disk> for Point2D {
+    fn label(&self, other: &Disk) -> String {
+        use std::fmt::Write;
+        let mut label = pair!("{other}", self);
+        write!(label, ", {other:?}").unwrap();
+        label
+    }
+}
+
+// the other operand initializes the field named after it
+#[symmetric(rewrite_body)]
+impl Wrap<Disk> for Point2D {
+    type Output = Wrapped<Point2D, Disk>;
+    fn wrap(self, other: Disk) -> Self::Output {
+        Wrapped { first: self, other }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Steps {
+    start: usize,
+    end: usize,
+}
+
+// the range starts at the other operand
+#[symmetric(rewrite_body)]
+impl Count<Steps> for usize {
+    fn count(&self, other: &Steps) -> usize {
+        let mut count = *self;
+        for _ in other.start..other.end {
+            count += 1;
+        }
+        for _ in 0..other.start {
+            count += 100;
+        }
+        count
+    }
+}
+
+// a typed local shadows the other operand
+#[symmetric(rewrite_body)]
+impl Scale<Disk> for Point2D {
+    fn scale(&self, other: &Disk) -> f64 {
+        let radius = other.radius;
+        let other: f64 = other.center.0 * radius;
+        let scaled = |other: f64| other * self.0;
+        scaled(other)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Offset(f64);
+
+// the other operand is destructured
+#[symmetric(rewrite_body)]
+impl Scale<Offset> for Point2D {
+    fn scale(&self, Offset(offset): &Offset) -> f64 {
+        self.0 - offset
+    }
+}
+
+// the other operand is both bound and destructured
+#[symmetric(rewrite_body)]
+impl Count<Offset> for Disk {
+    fn count(&self, other @ Offset(offset): &Offset) -> usize {
+        (self.radius * offset) as usize + other.0 as usize
+    }
+}
+
+#[test]
+fn test_rewrite_body() {
+    let disk = Disk {
+        center: Point2D(1.0),
+        radius: 2.0,
+    };
+    assert_eq!(disk.distance(&Point2D(5.0)), 2.0);
+    assert_eq!(disk.distance(&Point2D(2.0)), 0.0);
+}
+
+#[test]
+fn test_rewrite_body_by_value() {
+    let disk = Disk {
+        center: Point2D(1.0),
+        radius: 2.0,
+    };
+    assert_eq!(disk.merge(Point2D(5.0)), (6.0, 2.0));
+}
+
+#[test]
+#[should_panic(expected = "has a negative radius")]
+fn test_rewrite_body_macro() {
+    let disk = Disk {
+        center: Point2D(1.0),
+        radius: -2.0,
+    };
+    disk.distance(&Point2D(5.0));
+}
+
+#[test]
+fn test_rewrite_body_captures() {
+    let disk = Disk {
+        center: Point2D(1.0),
+        radius: 2.0,
+    };
+    assert_eq!(
+        disk.describe(&Point2D(5.0)),
+        "Point2D(5.0) vs Disk { center: Point2D(1.0), radius: 2.0 }, radius 2.0"
+    );
+}
+
+#[test]
+fn test_rewrite_body_format_macros() {
+    let disk = Disk {
+        center: Point2D(1.0),
+        radius: 2.0,
+    };
+    assert_eq!(
+        Point2D(5.0).label(&disk),
+        "{other} = Point2D(5.0), Disk { center: Point2D(1.0), radius: 2.0 }"
+    );
+    assert_eq!(disk.label(&Point2D(5.0)), Point2D(5.0).label(&disk));
+}
+
+#[test]
+fn test_rewrite_body_shorthand() {
+    let disk = Disk {
+        center: Point2D(1.0),
+        radius: 2.0,
+    };
+    assert_eq!(
+        disk.wrap(Point2D(5.0)),
+        Wrapped {
+            first: Point2D(5.0),
+            other: disk,
+        }
+    );
+}
+
+#[test]
+fn test_rewrite_body_range() {
+    let steps = Steps { start: 1, end: 5 };
+    assert_eq!(5.count(&steps), 109);
+    assert_eq!(steps.count(&5), 109);
+}
+
+#[test]
+fn test_rewrite_body_shadowed() {
+    let disk = Disk {
+        center: Point2D(3.0),
+        radius: 2.0,
+    };
+    assert_eq!(Point2D(5.0).scale(&disk), 30.0);
+    assert_eq!(disk.scale(&Point2D(5.0)), 30.0);
+}
+
+#[test]
+fn test_rewrite_body_destructured() {
+    assert_eq!(Point2D(5.0).scale(&Offset(2.0)), 3.0);
+    assert_eq!(Offset(2.0).scale(&Point2D(5.0)), 3.0);
+    let disk = Disk {
+        center: Point2D(3.0),
+        radius: 2.0,
+    };
+    assert_eq!(disk.count(&Offset(4.0)), 12);
+    assert_eq!(Offset(4.0).count(&disk), 12);
+}
@@ -0,0 +1,41 @@
+use symm_impl::symmetric;
+
+mod private {
+    pub trait DistanceSeal<Other: ?Sized> {}
+}
+
+pub trait Distance<Other>: private::DistanceSeal<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+pub struct Point2D(f64);
+
+pub struct Disk(f64, f64);
+
+pub struct Segment<T>(T, T);
+
+#[symmetric(seal = private::DistanceSeal)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - other.1
+    }
+}
+
+// the generated implementations of the trait are sealed too
+#[symmetric(seal = self::private::DistanceSeal, by_ref, in_mod = segment)]
+impl<T: Copy + Into<f64>> Distance<Segment<T>> for Point2D {
+    fn distance(&self, other: &Segment<T>) -> f64 {
+        let (start, end) = (other.0.into(), other.1.into());
+        (start - self.0).max(self.0 - end).max(0.0)
+    }
+}
+
+#[test]
+fn test_seal() {
+    assert_eq!(Point2D(5.0).distance(&Disk(1.0, 2.0)), 2.0);
+    assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+    let segment = Segment(1_u8, 2);
+    assert_eq!(segment.distance(&Point2D(5.0)), 3.0);
+    assert_eq!(Point2D(5.0).distance(&&segment), 3.0);
+    assert_eq!(segment.distance(&&Point2D(5.0)), 3.0);
+}
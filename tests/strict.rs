@@ -0,0 +1,90 @@
+use symm_impl::{symmetric, symmetric_trait};
+
+mod shapes {
+    use symm_impl::symmetric_trait;
+
+    #[symmetric_trait]
+    pub trait Distance<Other> {
+        fn distance(&self, other: &Other) -> f64;
+        fn distance_squared(&self, other: &Other) -> f64 {
+            self.distance(other).powi(2)
+        }
+    }
+}
+
+#[symmetric_trait]
+trait Overlap<Other> {
+    type Output;
+    fn overlap(&self, other: &Other) -> Self::Output;
+}
+
+use shapes::Distance;
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[symmetric(strict)]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+    fn distance_squared(&self, other: &Disk) -> f64 {
+        self.distance(other) * self.distance(other)
+    }
+}
+
+struct Square {
+    center: Point2D,
+    side: f64,
+}
+
+#[symmetric(strict)]
+impl shapes::Distance<Square> for Disk {
+    fn distance(&self, other: &Square) -> f64 {
+        (self.center.distance(&other.center) - self.radius - other.side / 2.0).max(0.0)
+    }
+    fn distance_squared(&self, other: &Square) -> f64 {
+        self.distance(other).powi(2)
+    }
+}
+
+#[symmetric(strict)]
+impl Overlap<Disk> for Point2D {
+    type Output = bool;
+    fn overlap(&self, other: &Disk) -> bool {
+        self.distance(other) == 0.0
+    }
+}
+
+#[test]
+fn test_strict() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), c.distance(&p));
+    assert_eq!(p.distance_squared(&c), c.distance_squared(&p));
+    assert_eq!(p.overlap(&c), c.overlap(&p));
+
+    let s = Square {
+        center: Point2D { x: 10.0, y: -2.0 },
+        side: 2.0,
+    };
+    assert_eq!(c.distance(&s), s.distance(&c));
+}
@@ -0,0 +1,32 @@
+use symm_impl::symm;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+struct Point2D(f64);
+
+struct Disk(f64, f64);
+
+struct Segment(f64, f64);
+
+#[symm]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.0 - other.0).abs() - other.1
+    }
+}
+
+#[symm(by_ref, only(distance))]
+impl Distance<Segment> for Point2D {
+    fn distance(&self, other: &Segment) -> f64 {
+        (other.0 - self.0).max(self.0 - other.1).max(0.0)
+    }
+}
+
+#[test]
+fn test_symm_alias() {
+    assert_eq!(Disk(1.0, 2.0).distance(&Point2D(5.0)), 2.0);
+    assert_eq!(Segment(1.0, 2.0).distance(&Point2D(5.0)), 3.0);
+    assert_eq!(Point2D(5.0).distance(&&Segment(1.0, 2.0)), 3.0);
+}
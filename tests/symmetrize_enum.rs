@@ -0,0 +1,136 @@
+use std::ops::Add;
+
+use symm_impl::{symmetric, symmetrize_enum, SymmetricDispatch};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl Distance<Disk> for Disk {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.center.distance(&other.center) - self.radius - other.radius).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (self.distance(&other.center) - other.radius).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Feet(f64);
+
+impl Add<Meters> for Meters {
+    type Output = f64;
+
+    fn add(self, other: Meters) -> f64 {
+        self.0 + other.0
+    }
+}
+
+impl Add<Feet> for Feet {
+    type Output = f64;
+
+    fn add(self, other: Feet) -> f64 {
+        (self.0 + other.0) * 0.3048
+    }
+}
+
+#[symmetric]
+impl Add<Feet> for Meters {
+    type Output = f64;
+
+    fn add(self, other: Feet) -> f64 {
+        self.0 + other.0 * 0.3048
+    }
+}
+
+symmetrize_enum! {
+    #[derive(Debug, Clone, Copy)]
+    enum Shape {
+        Point(Point2D),
+        Disk(Disk),
+    }
+
+    impl Distance<Shape> {
+        fn distance(&self, other: &Shape) -> f64;
+    }
+}
+
+symmetrize_enum! {
+    #[derive(Debug, Clone, Copy)]
+    enum Length {
+        Meters(Meters),
+        Feet(Feet),
+    }
+
+    impl Add<Length> {
+        type Output = f64;
+        fn add(self, other: Length) -> f64;
+    }
+}
+
+#[derive(SymmetricDispatch)]
+#[symm(impl Distance<Solid> {
+    /// Distance between the shapes
+    fn distance(&self, other: &Solid) -> f64;
+})]
+enum Solid {
+    Disk(Disk),
+    Point(Point2D),
+}
+
+#[test]
+fn test_symmetrize_enum() {
+    let p = Point2D { x: 3.0, y: 4.0 };
+    let d = Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    };
+    let shapes: [Shape; 2] = [p.into(), d.into()];
+    assert!(matches!(shapes[0], Shape::Point(point) if point == p));
+    assert_eq!(shapes[0].distance(&shapes[1]), p.distance(&d));
+    assert_eq!(shapes[1].distance(&shapes[0]), d.distance(&p));
+    assert_eq!(shapes[0].distance(&shapes[0]), 0.0);
+    assert_eq!(shapes[1].distance(&shapes[1]), 0.0);
+
+    let lengths: [Length; 2] = [Meters(1.0).into(), Feet(10.0).into()];
+    assert_eq!(lengths[0] + lengths[1], lengths[1] + lengths[0]);
+    assert_eq!(lengths[1] + lengths[1], 6.096);
+}
+
+#[test]
+fn test_derive() {
+    let p = Solid::Point(Point2D { x: 3.0, y: 4.0 });
+    let d = Solid::Disk(Disk {
+        center: Point2D { x: 0.0, y: 0.0 },
+        radius: 2.0,
+    });
+    assert_eq!(p.distance(&d), 3.0);
+    assert_eq!(d.distance(&p), 3.0);
+}
@@ -0,0 +1,32 @@
+#![cfg(target_arch = "x86_64")]
+
+use symm_impl::symmetric;
+
+trait Dot<Other> {
+    /// # Safety
+    ///
+    /// The CPU must support SSE2.
+    unsafe fn dot(&self, other: &Other) -> f32;
+}
+
+struct Vec2([f32; 2]);
+
+struct Vec4([f32; 4]);
+
+#[symmetric(inline = "always")]
+impl Dot<Vec4> for Vec2 {
+    #[target_feature(enable = "sse2")]
+    unsafe fn dot(&self, other: &Vec4) -> f32 {
+        self.0[0] * other.0[0] + self.0[1] * other.0[1]
+    }
+}
+
+#[test]
+fn test_target_feature() {
+    let (lhs, rhs) = (Vec2([1.0, 2.0]), Vec4([3.0, 4.0, 5.0, 6.0]));
+    // SSE2 is part of the baseline of x86_64
+    unsafe {
+        assert_eq!(lhs.dot(&rhs), 11.0);
+        assert_eq!(rhs.dot(&lhs), 11.0);
+    }
+}
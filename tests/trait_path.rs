@@ -0,0 +1,47 @@
+use symm_impl::symmetric;
+
+mod shapes {
+    pub trait Distance<Other> {
+        type Output;
+        fn distance(&self, other: &Other) -> Self::Output;
+    }
+
+    pub struct Point1D {
+        pub x: f64,
+    }
+
+    pub struct Interval {
+        pub low: f64,
+        pub high: f64,
+    }
+}
+
+mod prelude {
+    pub use super::shapes::Distance as Metric;
+}
+
+use shapes::{Distance, Interval, Point1D};
+
+#[symmetric(trait_path = prelude::Metric)]
+impl Distance<Interval> for Point1D {
+    type Output = f64;
+    fn distance(&self, other: &Interval) -> f64 {
+        if self.x < other.low {
+            other.low - self.x
+        } else if self.x > other.high {
+            self.x - other.high
+        } else {
+            0.0
+        }
+    }
+}
+
+#[test]
+fn test_trait_path() {
+    let p = Point1D { x: 5.0 };
+    let i = Interval {
+        low: 1.0,
+        high: 3.0,
+    };
+    assert_eq!(p.distance(&i), i.distance(&p));
+}
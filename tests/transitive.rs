@@ -0,0 +1,42 @@
+use symm_impl::transitive;
+
+trait Reaches<Other> {
+    fn reaches(&self, other: &Other) -> bool;
+}
+
+trait Precedes<Other> {
+    fn precedes(self, other: Other) -> bool;
+    fn gap(self, other: Other) -> u32;
+}
+
+#[derive(Clone, Copy)]
+struct Level(u8);
+
+#[derive(Clone, Copy)]
+struct Natural(u32);
+
+#[transitive]
+impl Reaches<Level> for Level {
+    fn reaches(&self, other: &Level) -> bool {
+        self.0 <= other.0
+    }
+}
+
+#[transitive(method = precedes)]
+impl Precedes<Natural> for Natural {
+    fn precedes(self, other: Natural) -> bool {
+        self.0 < other.0
+    }
+
+    fn gap(self, other: Natural) -> u32 {
+        self.0.abs_diff(other.0)
+    }
+}
+
+#[test]
+fn test_transitive() {
+    assert!(Level(1).reaches(&Level(2)));
+    assert!(!Level(2).reaches(&Level(1)));
+    assert!(Natural(2).precedes(Natural(4)));
+    assert_eq!(Natural(2).gap(Natural(4)), 2);
+}
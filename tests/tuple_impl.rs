@@ -0,0 +1,70 @@
+use symm_impl::symmetric;
+
+trait Collide {
+    type Contact;
+
+    fn collide(self, speed: f64) -> Option<Self::Contact>;
+}
+
+trait Touching {
+    fn touching(&self) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Ball {
+    center: f64,
+    radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Wall {
+    position: f64,
+}
+
+#[symmetric]
+impl Collide for (Ball, Wall) {
+    type Contact = f64;
+
+    fn collide(self, speed: f64) -> Option<f64> {
+        let (ball, wall) = self;
+        let gap = (wall.position - ball.center).abs() - ball.radius;
+        if gap <= speed {
+            Some(wall.position)
+        } else {
+            None
+        }
+    }
+}
+
+#[symmetric]
+impl<'a> Touching for (&'a Ball, &'a Wall) {
+    fn touching(&self) -> bool {
+        (self.1.position - self.0.center).abs() <= self.0.radius
+    }
+}
+
+#[test]
+fn test_tuple_impl() {
+    let ball = Ball {
+        center: 0.0,
+        radius: 1.0,
+    };
+    let wall = Wall { position: 3.0 };
+    assert_eq!((ball, wall).collide(2.5), Some(3.0));
+    assert_eq!((wall, ball).collide(2.5), Some(3.0));
+    assert_eq!((wall, ball).collide(1.0), None);
+    let contact: <(Wall, Ball) as Collide>::Contact = 3.0;
+    assert_eq!((wall, ball).collide(2.0), Some(contact));
+}
+
+#[test]
+fn test_tuple_impl_by_ref() {
+    let ball = Ball {
+        center: 0.0,
+        radius: 1.0,
+    };
+    let (near, far) = (Wall { position: 1.0 }, Wall { position: 2.0 });
+    assert!((&ball, &near).touching());
+    assert!((&near, &ball).touching());
+    assert!(!(&far, &ball).touching());
+}
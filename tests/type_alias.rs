@@ -0,0 +1,98 @@
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait HasCenter {
+    type Center;
+}
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+struct Segment<T> {
+    start: T,
+    end: T,
+}
+
+type DiskAlias = Disk;
+type PointSegment = Segment<Point2D>;
+
+impl HasCenter for Point2D {
+    type Center = Disk;
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[symmetric]
+impl Distance<DiskAlias> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let p_diff = self.distance(&other.center);
+        if p_diff.le(&other.radius) {
+            0.0_f64
+        } else {
+            p_diff - other.radius
+        }
+    }
+}
+
+#[symmetric]
+impl Distance<Segment<Point2D>> for Disk {
+    fn distance(&self, other: &PointSegment) -> f64 {
+        let start = self.center.distance(&other.start);
+        let end = self.center.distance(&other.end);
+        start.min(end) - self.radius
+    }
+}
+
+struct Ring {
+    center: Point2D,
+}
+
+// `Self` refers to the other type in the mirror, so the equivalence cannot be
+// checked there
+#[symmetric(same_type(<Self as HasCenter>::Center = Disk))]
+impl Distance<Disk> for Ring {
+    fn distance(&self, other: &<Self as HasCenter>::Center) -> f64 {
+        self.center.distance(&other.center)
+    }
+}
+
+impl HasCenter for Ring {
+    type Center = Disk;
+}
+
+#[test]
+fn test_type_alias() {
+    let p = Point2D { x: 5.0, y: 4.0 };
+    let c = Disk {
+        center: Point2D { x: 1.0, y: -2.0 },
+        radius: 3.0,
+    };
+    assert_eq!(p.distance(&c), c.distance(&p));
+
+    let s = Segment {
+        start: Point2D { x: 10.0, y: -2.0 },
+        end: Point2D { x: 1.0, y: 10.0 },
+    };
+    assert_eq!(c.distance(&s), s.distance(&c));
+
+    let r = Ring {
+        center: Point2D { x: 0.0, y: 0.0 },
+    };
+    assert_eq!(r.distance(&c), c.distance(&r));
+}
@@ -1,3 +1,5 @@
+#![deny(unused_mut)]
+
 use symm_impl::symmetric;
 
 trait Distance<Other> {
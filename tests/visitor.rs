@@ -0,0 +1,95 @@
+use symm_impl::{symmetric, symmetric_visitor};
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Overlap<Other, Boundary> {
+    fn overlaps(&self, other: &Other) -> bool;
+}
+
+struct Closed;
+
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Distance<Point2D> for Point2D {
+    fn distance(&self, other: &Point2D) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+impl Distance<Disk> for Disk {
+    fn distance(&self, other: &Disk) -> f64 {
+        (Distance::distance(&self.center, &other.center) - self.radius - other.radius).max(0.0)
+    }
+}
+
+#[symmetric]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        (Distance::distance(self, &other.center) - other.radius).max(0.0)
+    }
+}
+
+impl<T: Distance<U>, U> Overlap<U, Closed> for T {
+    fn overlaps(&self, other: &U) -> bool {
+        Distance::distance(self, other) == 0.0
+    }
+}
+
+symmetric_visitor! {
+    /// Any shape.
+    trait Shape for Point2D, Disk {
+        visitor ShapeVisitor;
+
+        fn distance(&self, other: &dyn Shape) -> f64 = Distance;
+        /// Whether both shapes overlap.
+        fn overlaps(&self, other: &dyn Shape) -> bool = Overlap<_, Closed>;
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Point2D { x: 3.0, y: 4.0 }),
+        Box::new(Disk {
+            center: Point2D { x: 0.0, y: 0.0 },
+            radius: 2.0,
+        }),
+        Box::new(Point2D { x: 0.0, y: 1.0 }),
+    ];
+    assert_eq!(shapes[0].distance(&*shapes[1]), 3.0);
+    assert_eq!(shapes[1].distance(&*shapes[0]), 3.0);
+    assert_eq!(shapes[0].distance(&*shapes[2]), 4.242640687119285);
+    assert!(shapes[1].overlaps(&*shapes[2]));
+    assert!(shapes[2].overlaps(&*shapes[1]));
+    assert!(!shapes[0].overlaps(&*shapes[2]));
+}
+
+#[test]
+fn test_visitor_accept() {
+    struct Name(&'static str);
+
+    impl ShapeVisitor for Name {
+        fn visit_point2d(&mut self, _operand: &Point2D) {
+            self.0 = "point";
+        }
+
+        fn visit_disk(&mut self, _operand: &Disk) {
+            self.0 = "disk";
+        }
+    }
+
+    let shape: &dyn Shape = &Point2D { x: 0.0, y: 0.0 };
+    let mut name = Name("");
+    shape.accept(&mut name);
+    assert_eq!(name.0, "point");
+}
@@ -0,0 +1,36 @@
+use symm_impl::symmetric;
+
+trait Describe<Other> {
+    fn describe(&self, other: &Other) -> String;
+}
+
+#[derive(Clone)]
+struct Label<'a, T> {
+    name: &'a str,
+    value: T,
+}
+
+// not `Clone`, the bounds on `Self` are about `Label`
+struct Plain;
+
+#[symmetric]
+impl<'a, T: std::fmt::Debug> Describe<Plain> for Label<'a, T>
+where
+    Self: Clone + 'a,
+{
+    fn describe(&self, _other: &Plain) -> String {
+        let copy = self.clone();
+        format!("{} = {:?}", copy.name, copy.value)
+    }
+}
+
+#[test]
+fn test_where_self() {
+    let name = String::from("answer");
+    let label = Label {
+        name: &name,
+        value: 42,
+    };
+    assert_eq!(label.describe(&Plain), "answer = 42");
+    assert_eq!(Plain.describe(&label), "answer = 42");
+}
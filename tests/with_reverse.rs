@@ -0,0 +1,112 @@
+use symm_impl::{symmetric, with_reverse};
+
+#[with_reverse(Within, contains = within)]
+trait Contains<Other> {
+    fn contains(&self, other: &Other) -> bool;
+}
+
+#[with_reverse(Absorbed)]
+trait Absorb<Other> {
+    type Output;
+    const COST: u32;
+    fn absorb(self, other: &mut Other) -> Self::Output;
+}
+
+#[with_reverse(Scaled, scale = scaled)]
+pub trait Scale<'a, Other, const N: usize>
+where
+    Other: Clone,
+{
+    fn scale(&self, other: &'a Other, factor: [f64; N]) -> Other;
+}
+
+#[with_reverse(Intersected)]
+trait Intersects<Other> {
+    fn intersects(&self, other: &Other) -> bool;
+}
+
+#[with_reverse(Matched, matches = matched)]
+trait Matches<Other: ?Sized> {
+    fn matches(&self, other: &Other) -> bool;
+}
+
+struct Prefix(&'static str);
+
+impl Matches<str> for Prefix {
+    fn matches(&self, other: &str) -> bool {
+        other.starts_with(self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Point2D(f64);
+struct Disk(f64, f64);
+
+impl Contains<Point2D> for Disk {
+    fn contains(&self, other: &Point2D) -> bool {
+        (self.0 - other.0).abs() <= self.1
+    }
+}
+
+impl Absorb<Disk> for Point2D {
+    type Output = f64;
+    const COST: u32 = 3;
+    fn absorb(self, other: &mut Disk) -> f64 {
+        other.1 += 1.0;
+        other.1 - self.0
+    }
+}
+
+impl<'a> Scale<'a, Point2D, 2> for Disk {
+    fn scale(&self, other: &'a Point2D, factor: [f64; 2]) -> Point2D {
+        Point2D(other.0 * factor[0] * factor[1] * self.1)
+    }
+}
+
+#[symmetric]
+impl Intersects<Disk> for Point2D {
+    fn intersects(&self, other: &Disk) -> bool {
+        (self.0 - other.0).abs() <= other.1
+    }
+}
+
+#[test]
+fn test_with_reverse() {
+    assert!(Point2D(2.0).within(&Disk(1.0, 2.0)));
+    assert!(!Point2D(5.0).within(&Disk(1.0, 2.0)));
+}
+
+#[test]
+fn test_with_reverse_receiver() {
+    let mut disk = Disk(0.0, 1.0);
+    let output: f64 = disk.absorb(Point2D(0.5));
+    assert_eq!(output, 1.5);
+    assert_eq!(disk.1, 2.0);
+    assert_eq!(<Disk as Absorbed<Point2D>>::COST, 3);
+}
+
+#[test]
+fn test_with_reverse_generics() {
+    let point = Point2D(1.0);
+    assert_eq!(point.scaled(&Disk(0.0, 2.0), [2.0, 3.0]), Point2D(12.0));
+}
+
+#[test]
+fn test_with_reverse_unsized() {
+    assert!("symmetric".matched(&Prefix("symm")));
+    assert!(!"impl".matched(&Prefix("symm")));
+}
+
+#[test]
+fn test_with_reverse_symmetric() {
+    // the converse of a symmetric relation holds in both orders too
+    // the converse method keeps the name of the method
+    assert!(<Point2D as Intersected<Disk>>::intersects(
+        &Point2D(2.0),
+        &Disk(1.0, 2.0)
+    ));
+    assert!(<Disk as Intersected<Point2D>>::intersects(
+        &Disk(1.0, 2.0),
+        &Point2D(2.0)
+    ));
+}
@@ -0,0 +1,91 @@
+use std::ops::Deref;
+
+use symm_impl::symmetric;
+
+trait Distance<Other> {
+    fn distance(&self, other: &Other) -> f64;
+}
+
+trait Translate {
+    fn translate(&self, dx: f64) -> Self;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Disk {
+    center: Point2D,
+    radius: f64,
+}
+
+impl Translate for Point2D {
+    fn translate(&self, dx: f64) -> Self {
+        Point2D {
+            x: self.x + dx,
+            y: self.y,
+        }
+    }
+}
+
+impl Translate for Disk {
+    fn translate(&self, dx: f64) -> Self {
+        Disk {
+            center: self.center.translate(dx),
+            radius: self.radius,
+        }
+    }
+}
+
+/// Shape moved along the x axis.
+struct Transformed<T> {
+    shape: T,
+    dx: f64,
+}
+
+fn apply<T: Translate>(transformed: &Transformed<T>) -> T {
+    transformed.shape.translate(transformed.dx)
+}
+
+/// Shape with a name, unwrapped by `Deref`.
+struct Named<T> {
+    shape: T,
+}
+
+impl<T> Deref for Named<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.shape
+    }
+}
+
+#[symmetric(wrap(Transformed, unwrap = apply), wrap(Named))]
+impl Distance<Disk> for Point2D {
+    fn distance(&self, other: &Disk) -> f64 {
+        let dx = self.x - other.center.x;
+        let dy = self.y - other.center.y;
+        ((dx * dx + dy * dy).sqrt() - other.radius).max(0.0)
+    }
+}
+
+#[test]
+fn test_wrap() {
+    let p = Point2D { x: 0.0, y: 0.0 };
+    let c = Disk {
+        center: Point2D { x: 5.0, y: 0.0 },
+        radius: 1.0,
+    };
+    let moved_p = Transformed { shape: p, dx: 2.0 };
+    let moved_c = Transformed { shape: c, dx: 1.0 };
+    assert_eq!(moved_p.distance(&moved_c), 3.0);
+    assert_eq!(moved_c.distance(&moved_p), 3.0);
+
+    let named_p = Named { shape: p };
+    let named_c = Named { shape: c };
+    assert_eq!(named_p.distance(&named_c), 4.0);
+    assert_eq!(named_c.distance(&named_p), 4.0);
+}